@@ -0,0 +1,203 @@
+//! Components built on top of decoded F1 2020 packets.
+//!
+//! Components start over with the first packet of a new `session_uid`, unless
+//! they say what they keep across sessions. Those about one car follow the
+//! player's unless made `for_car` another.
+
+use crate::f1_2020::header::PacketHeader;
+
+pub mod ers;
+pub mod frame;
+pub mod fuel;
+pub mod laps;
+pub mod state;
+pub mod stints;
+pub mod timing;
+pub mod tyres;
+
+/// Where a packet stands against the packets before it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Progress {
+    /// First packet of a session
+    NewSession,
+    /// Packet of an earlier frame, after a flashback
+    Flashback,
+    Onward,
+}
+
+/// Session and car a component follows.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct Tracking {
+    /// Car to follow, the player's when `None`
+    car: Option<usize>,
+    session_uid: Option<u64>,
+    frame_identifier: u32,
+}
+
+impl Tracking {
+    /// Tracking of the car at `index`.
+    pub(crate) fn car(index: usize) -> Self {
+        Tracking {
+            car: Some(index),
+            ..Tracking::default()
+        }
+    }
+
+    /// `None` until the first packet.
+    pub(crate) fn session_uid(&self) -> Option<u64> {
+        self.session_uid
+    }
+
+    /// Frame of the latest packet.
+    pub(crate) fn frame_identifier(&self) -> u32 {
+        self.frame_identifier
+    }
+}
+
+/// Component kept up to date from the packets of one session at a time.
+pub(crate) trait Tracked: Default {
+    fn tracking(&mut self) -> &mut Tracking;
+
+    /// Forgets the session that ended, all but the tracking by default.
+    fn start_over(&mut self) {
+        let tracking = *self.tracking();
+        *self = Self::default();
+        *self.tracking() = tracking;
+    }
+
+    /// Takes in the header of a packet, starting over on a new session, and
+    /// returns where the packet stands and the index of the car followed.
+    fn follow(&mut self, header: &PacketHeader) -> (Progress, usize) {
+        let tracking = self.tracking();
+        let progress = if tracking.session_uid != Some(header.session_uid) {
+            Progress::NewSession
+        } else if header.frame_identifier < tracking.frame_identifier {
+            Progress::Flashback
+        } else {
+            Progress::Onward
+        };
+        tracking.session_uid = Some(header.session_uid);
+        tracking.frame_identifier = header.frame_identifier;
+        let car = tracking.car.unwrap_or(header.player_car_index as usize);
+
+        if progress == Progress::NewSession {
+            self.start_over();
+        }
+        (progress, car)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod fixtures {
+    use crate::decoder::{DecodeMode, Decoder};
+    use crate::f1_2020::car::CarStatusData;
+    use crate::f1_2020::lap::LapData;
+    use crate::f1_2020::packet::{parse_f12020, Packet2020};
+    use byteorder::{LittleEndian, WriteBytesExt};
+    use std::io::Cursor;
+
+    pub(crate) const SESSION_UID: u64 = 1;
+
+    /// Decodes a zero filled packet of `packet_id` for the player in car 0,
+    /// `frame_identifier` 50ms frames into the session.
+    pub(crate) fn packet(packet_id: u8, frame_identifier: u32) -> Packet2020 {
+        let size = match packet_id {
+            0 => 1464,
+            1 => 251,
+            2 => 1190,
+            3 => 35,
+            4 => 1213,
+            5 => 1102,
+            6 => 1307,
+            7 => 1344,
+            8 => 839,
+            _ => 1169,
+        };
+
+        let mut bytes = Vec::with_capacity(size);
+        bytes.write_u16::<LittleEndian>(2020).unwrap();
+        bytes.extend_from_slice(&[1, 0, 1, packet_id]);
+        bytes.write_u64::<LittleEndian>(SESSION_UID).unwrap();
+        bytes
+            .write_f32::<LittleEndian>(frame_identifier as f32 * 0.05)
+            .unwrap();
+        bytes.write_u32::<LittleEndian>(frame_identifier).unwrap();
+        bytes.extend_from_slice(&[0, 255]);
+        bytes.resize(size, 0);
+
+        let mut decoder = Decoder::new(DecodeMode::Lenient);
+        parse_f12020(&mut Cursor::new(&bytes[..]), size, &mut decoder).unwrap()
+    }
+
+    /// A Lap packet with changes to the cars' lap data.
+    pub(crate) fn lap<F: FnOnce(&mut [LapData])>(frame_identifier: u32, change: F) -> Packet2020 {
+        let mut packet = packet(2, frame_identifier);
+        if let Packet2020::Lap(lap) = &mut packet {
+            change(&mut lap.lap_data);
+        }
+        packet
+    }
+
+    /// A Car Status packet with changes to the cars' status.
+    pub(crate) fn status<F: FnOnce(&mut [CarStatusData])>(
+        frame_identifier: u32,
+        change: F,
+    ) -> Packet2020 {
+        let mut packet = packet(7, frame_identifier);
+        if let Packet2020::CarStatus(status) = &mut packet {
+            change(&mut status.car_status_data);
+        }
+        packet
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::analysis::fixtures::packet;
+    use crate::analysis::{Progress, Tracked, Tracking};
+    use crate::f1_2020::packet::Packet2020;
+
+    #[derive(Debug, Default)]
+    struct Counter {
+        tracking: Tracking,
+        packets: usize,
+    }
+
+    impl Tracked for Counter {
+        fn tracking(&mut self) -> &mut Tracking {
+            &mut self.tracking
+        }
+    }
+
+    #[test]
+    fn follows_sessions_flashbacks_and_cars() {
+        let mut counter = Counter {
+            tracking: Tracking::car(5),
+            packets: 0,
+        };
+        let progress = |counter: &mut Counter, frame: u32, session_uid: u64| {
+            let mut packet = packet(3, frame);
+            if let Packet2020::Event(event) = &mut packet {
+                event.header.session_uid = session_uid;
+            }
+            let (progress, car) = counter.follow(packet.header());
+            counter.packets += 1;
+            assert_eq!(5, car);
+            progress
+        };
+
+        assert_eq!(Progress::NewSession, progress(&mut counter, 10, 1));
+        assert_eq!(Progress::Onward, progress(&mut counter, 10, 1));
+        assert_eq!(Progress::Onward, progress(&mut counter, 11, 1));
+        assert_eq!(Progress::Flashback, progress(&mut counter, 4, 1));
+        assert_eq!(4, counter.packets);
+
+        assert_eq!(Progress::NewSession, progress(&mut counter, 20, 2));
+        assert_eq!(1, counter.packets);
+        assert_eq!(Some(2), counter.tracking.session_uid());
+
+        // The player's car without one to follow
+        let (_, car) = Counter::default().follow(packet(3, 1).header());
+        assert_eq!(0, car);
+    }
+}
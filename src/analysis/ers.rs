@@ -0,0 +1,531 @@
+use crate::analysis::laps::LapHistory;
+use crate::analysis::{Progress, Tracked, Tracking};
+use crate::f1_2020::car::CarStatusData;
+use crate::f1_2020::lap::LapData;
+use crate::f1_2020::packet::Packet2020;
+use std::cmp::Ordering;
+use std::fmt;
+
+/// Energy the ERS store holds when full, in joules.
+pub const ERS_STORE_CAPACITY: f32 = 4_000_000.0;
+
+/// Store energy below which the battery counts as flat, 1% of its capacity.
+pub const FLAT_STORE_ENERGY: f32 = ERS_STORE_CAPACITY / 100.0;
+
+/// Length of the lap segments deployment is broken down by, in metres.
+pub const SEGMENT_LENGTH: f32 = 100.0;
+
+/// Energy deployed and harvested over part of a lap, in joules.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Energy {
+    pub deployed: f32,
+    pub harvested_mguk: f32,
+    pub harvested_mguh: f32,
+}
+
+impl Energy {
+    pub fn harvested(&self) -> f32 {
+        self.harvested_mguk + self.harvested_mguh
+    }
+
+    /// Energy harvested less energy deployed.
+    pub fn balance(&self) -> f32 {
+        self.harvested() - self.deployed
+    }
+
+    fn add(&mut self, other: Energy) {
+        self.deployed += other.deployed;
+        self.harvested_mguk += other.harvested_mguk;
+        self.harvested_mguh += other.harvested_mguh;
+    }
+}
+
+/// Energy flow of one lap.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LapEnergy {
+    pub lap_number: u8,
+    pub total: Energy,
+    pub sectors: [Energy; 3],
+    /// Energy deployed in each `SEGMENT_LENGTH` of the lap
+    pub deployed_by_distance: Vec<f32>,
+    pub store_start: f32,
+    pub store_end: f32,
+    /// Lap distance the store first ran flat at
+    pub ran_flat_at: Option<f32>,
+    /// Energy in each `SEGMENT_LENGTH` of the lap, per sector
+    segments: Vec<[Energy; 3]>,
+}
+
+impl LapEnergy {
+    fn new(lap_number: u8, store: f32) -> Self {
+        LapEnergy {
+            lap_number,
+            total: Energy::default(),
+            sectors: [Energy::default(); 3],
+            deployed_by_distance: Vec::new(),
+            store_start: store,
+            store_end: store,
+            ran_flat_at: None,
+            segments: Vec::new(),
+        }
+    }
+
+    fn add(&mut self, lap: &LapData, energy: Energy) {
+        // Lap and Car Status packets can straddle the line, so the car may be
+        // placed a lap off
+        let (sector, segment) = match lap.current_lap_num.cmp(&self.lap_number) {
+            Ordering::Equal => (
+                (lap.sector as usize).min(2),
+                (lap.lap_distance.max(0.0) / SEGMENT_LENGTH) as usize,
+            ),
+            Ordering::Less => (0, 0),
+            Ordering::Greater => (2, self.deployed_by_distance.len().saturating_sub(1)),
+        };
+        self.total.add(energy);
+        self.sectors[sector].add(energy);
+
+        if self.segments.len() <= segment {
+            self.segments.resize(segment + 1, [Energy::default(); 3]);
+            self.deployed_by_distance.resize(segment + 1, 0.0);
+        }
+        self.segments[segment][sector].add(energy);
+        self.deployed_by_distance[segment] += energy.deployed;
+    }
+
+    /// Takes back the energy booked past the segment a flashback went back
+    /// to `lap_distance` in.
+    fn rewind(&mut self, lap_distance: f32) {
+        let lap_distance = lap_distance.max(0.0);
+        let kept = (lap_distance / SEGMENT_LENGTH) as usize + 1;
+        self.segments.truncate(kept);
+        self.deployed_by_distance.truncate(kept);
+
+        self.sectors = [Energy::default(); 3];
+        for segment in &self.segments {
+            for (sector, energy) in self.sectors.iter_mut().zip(segment) {
+                sector.add(*energy);
+            }
+        }
+        self.total = Energy::default();
+        for sector in &self.sectors {
+            self.total.add(*sector);
+        }
+        if self.ran_flat_at.map_or(false, |at| at > lap_distance) {
+            self.ran_flat_at = None;
+        }
+    }
+}
+
+impl fmt::Display for LapEnergy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mj = |joules: f32| joules / 1_000_000.0;
+        write!(
+            f,
+            "Lap {}: deployed {:.2}MJ, harvested {:.2}MJ (MGU-K {:.2}MJ, MGU-H {:.2}MJ), \
+             balance {:+.2}MJ, store {:.2}MJ -> {:.2}MJ",
+            self.lap_number,
+            mj(self.total.deployed),
+            mj(self.total.harvested()),
+            mj(self.total.harvested_mguk),
+            mj(self.total.harvested_mguh),
+            mj(self.total.balance()),
+            mj(self.store_start),
+            mj(self.store_end),
+        )?;
+        if let Some(distance) = self.ran_flat_at {
+            write!(f, ", flat at {:.0}m", distance)?;
+        }
+        Ok(())
+    }
+}
+
+/// Per lap counters of the latest Car Status packet.
+#[derive(Debug, Clone, Copy)]
+struct Counters {
+    deployed: f32,
+    harvested_mguk: f32,
+    harvested_mguh: f32,
+}
+
+impl Counters {
+    fn new(status: &CarStatusData) -> Self {
+        Counters {
+            deployed: status.ers_deployed_this_lap,
+            harvested_mguk: status.ers_harvested_this_lap_mguk,
+            harvested_mguh: status.ers_harvested_this_lap_mguh,
+        }
+    }
+
+    /// Energy since the start of the lap.
+    fn energy(&self) -> Energy {
+        Energy {
+            deployed: self.deployed,
+            harvested_mguk: self.harvested_mguk,
+            harvested_mguh: self.harvested_mguh,
+        }
+    }
+
+    /// Energy since `last`, on the same lap.
+    fn since(&self, last: &Counters) -> Energy {
+        Energy {
+            deployed: (self.deployed - last.deployed).max(0.0),
+            harvested_mguk: (self.harvested_mguk - last.harvested_mguk).max(0.0),
+            harvested_mguh: (self.harvested_mguh - last.harvested_mguh).max(0.0),
+        }
+    }
+
+    /// Whether a counter went down, which they only do at the line.
+    fn reset_since(&self, last: &Counters) -> bool {
+        self.deployed < last.deployed
+            || self.harvested_mguk < last.harvested_mguk
+            || self.harvested_mguh < last.harvested_mguh
+    }
+
+    fn is_zero(&self) -> bool {
+        self.deployed == 0.0 && self.harvested_mguk == 0.0 && self.harvested_mguh == 0.0
+    }
+}
+
+/// Accounts for the ERS energy one car deploys and harvests, per lap, sector
+/// and lap distance.
+///
+/// Energy between Car Status packets is put down to where the latest Lap
+/// packet placed the car. The game's per lap counters going down mark the
+/// line, so a lap ends with the counters of its last Car Status packet even
+/// when the Lap packets cross the line earlier or later. Energy used between
+/// that packet and the line goes unseen. Laps are kept as `LapHistory`
+/// completes them. After a flashback the lap the car went back to keeps the
+/// energy booked up to the segment it went back into.
+#[derive(Debug, Clone, Default)]
+pub struct ERSAnalyser {
+    tracking: Tracking,
+    history: LapHistory,
+    lap: Option<LapData>,
+    counters: Option<Counters>,
+    /// Lap the per lap counters count
+    current: Option<LapEnergy>,
+    /// Laps the counters ended that `history` did not complete yet
+    ended: Vec<LapEnergy>,
+    laps: Vec<LapEnergy>,
+}
+
+impl ERSAnalyser {
+    /// Analyser of the player's car.
+    pub fn new() -> Self {
+        ERSAnalyser::default()
+    }
+
+    /// Analyser of the car at `index`.
+    pub fn for_car(index: usize) -> Self {
+        ERSAnalyser {
+            tracking: Tracking::car(index),
+            ..ERSAnalyser::default()
+        }
+    }
+
+    pub fn update(&mut self, packet: &Packet2020) {
+        let (progress, car) = self.follow(packet.header());
+        if progress == Progress::Flashback {
+            self.lap = None;
+            self.counters = None;
+        }
+        self.history.update(packet);
+
+        match packet {
+            Packet2020::Lap(lap) => self.lap = lap.lap_data.get(car).copied(),
+            Packet2020::CarStatus(status) => {
+                if let Some(status) = status.car_status_data.get(car) {
+                    self.account(status);
+                }
+            }
+            _ => return,
+        }
+        self.keep_completed(car);
+    }
+
+    fn account(&mut self, status: &CarStatusData) {
+        let lap = match self.lap {
+            Some(lap) => lap,
+            None => return,
+        };
+        let store = status.ers_store_energy;
+        let counters = Counters::new(status);
+
+        let mut current = match (self.current.take(), self.counters) {
+            (Some(mut current), Some(last)) => {
+                let crossed = counters.reset_since(&last)
+                    || (lap.current_lap_num > current.lap_number && last.is_zero())
+                    || lap.current_lap_num > current.lap_number + 1;
+                if crossed {
+                    // Close the lap with what the game counted on it
+                    current.total = last.energy();
+                    let lap_number = lap.current_lap_num.max(current.lap_number + 1);
+                    self.ended.push(current);
+                    let mut next = LapEnergy::new(lap_number, store);
+                    next.add(&lap, counters.energy());
+                    next
+                } else {
+                    current.add(&lap, counters.since(&last));
+                    current
+                }
+            }
+            (current, _) => {
+                // The counters went back with a flashback or tracking just
+                // started, go on with the lap if it was accounted before
+                let earlier = match self
+                    .ended
+                    .iter()
+                    .position(|energy| energy.lap_number == lap.current_lap_num)
+                {
+                    Some(position) => Some(self.ended.remove(position)),
+                    None => current.filter(|current| current.lap_number == lap.current_lap_num),
+                };
+                self.ended
+                    .retain(|energy| energy.lap_number < lap.current_lap_num);
+                match earlier {
+                    Some(mut earlier) => {
+                        earlier.rewind(lap.lap_distance);
+                        earlier
+                    }
+                    None => LapEnergy::new(lap.current_lap_num, store),
+                }
+            }
+        };
+
+        current.store_end = store;
+        if store < FLAT_STORE_ENERGY && current.ran_flat_at.is_none() {
+            current.ran_flat_at = Some(lap.lap_distance.max(0.0));
+        }
+        self.current = Some(current);
+        self.counters = Some(counters);
+    }
+
+    /// Moves the laps both the counters and `history` ended to `laps`, back
+    /// to `ended` those `history` forgot after a flashback, and drops those
+    /// `history` left out.
+    fn keep_completed(&mut self, car: usize) {
+        let completed = self.history.laps(car);
+        let is_completed = |energy: &LapEnergy| {
+            completed
+                .iter()
+                .any(|lap| lap.lap_number == energy.lap_number)
+        };
+        let last_completed = completed.last().map(|lap| lap.lap_number);
+        let (kept, forgotten): (Vec<LapEnergy>, Vec<LapEnergy>) =
+            self.laps.drain(..).partition(is_completed);
+        self.laps = kept;
+        let mut ended = Vec::new();
+        for energy in forgotten.into_iter().chain(self.ended.drain(..)) {
+            if is_completed(&energy) {
+                self.laps.push(energy);
+            } else if last_completed.map_or(true, |last| last < energy.lap_number) {
+                ended.push(energy);
+            }
+        }
+        self.ended = ended;
+    }
+
+    /// Completed laps, oldest first.
+    pub fn laps(&self) -> &[LapEnergy] {
+        &self.laps
+    }
+
+    /// Lap in progress.
+    pub fn current(&self) -> Option<&LapEnergy> {
+        self.current.as_ref()
+    }
+
+    /// Energy balance of the completed laps, a line per lap.
+    pub fn report(&self) -> String {
+        self.laps.iter().map(|lap| format!("{}\n", lap)).collect()
+    }
+}
+
+impl Tracked for ERSAnalyser {
+    fn tracking(&mut self) -> &mut Tracking {
+        &mut self.tracking
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::analysis::ers::ERSAnalyser;
+    use crate::analysis::fixtures::{lap, status};
+    use std::ops::RangeInclusive;
+
+    /// Steps of 500m on a 3km lap, from the start of the race: the car
+    /// deploys 100kJ a step over the first 1000m and harvests 50kJ a step
+    /// through the MGU-K after.
+    fn drive(analyser: &mut ERSAnalyser, steps: RangeInclusive<u32>, store: impl Fn(u32) -> f32) {
+        for step in steps {
+            let frame = step * 2 + 1;
+            let lap_distance = (step % 6) as f32 * 500.0;
+            let lap_number = 1 + (step / 6) as u8;
+            let sector = (lap_distance / 1000.0) as u8;
+            analyser.update(&lap(frame, |laps| {
+                laps[0].current_lap_num = lap_number;
+                laps[0].lap_distance = lap_distance;
+                laps[0].sector = sector;
+            }));
+            // Steps into the lap, counting the one crossing the line
+            let done = step % 6;
+            analyser.update(&status(frame + 1, |cars| {
+                cars[0].ers_store_energy = store(step);
+                cars[0].ers_deployed_this_lap = 100_000.0 * done.min(2) as f32;
+                cars[0].ers_harvested_this_lap_mguk = 50_000.0 * done.saturating_sub(2) as f32;
+            }));
+        }
+    }
+
+    #[test]
+    fn accounts_energy_per_lap_and_sector() {
+        let mut analyser = ERSAnalyser::new();
+        // Flat from 2000m into lap 2
+        drive(&mut analyser, 0..=13, |step| {
+            if step < 10 {
+                2_000_000.0
+            } else {
+                0.0
+            }
+        });
+
+        let laps = analyser.laps();
+        assert_eq!(2, laps.len());
+        let lap = &laps[1];
+        assert_eq!(2, lap.lap_number);
+        assert_eq!(200_000.0, lap.total.deployed);
+        assert_eq!(150_000.0, lap.total.harvested());
+        assert_eq!(-50_000.0, lap.total.balance());
+        // Sectors are 1000m long
+        assert_eq!(100_000.0, lap.sectors[0].deployed);
+        assert_eq!(0.0, lap.sectors[0].harvested());
+        assert_eq!(100_000.0, lap.sectors[1].deployed);
+        assert_eq!(50_000.0, lap.sectors[1].harvested_mguk);
+        assert_eq!(100_000.0, lap.sectors[2].harvested_mguk);
+
+        // Deployment shows up where the car was when it was reported
+        assert_eq!(100_000.0, lap.deployed_by_distance[5]);
+        assert_eq!(100_000.0, lap.deployed_by_distance[10]);
+        assert_eq!(0.0, lap.deployed_by_distance[15]);
+        assert_eq!(None, laps[0].ran_flat_at);
+        assert_eq!(Some(2000.0), lap.ran_flat_at);
+        assert_eq!(0.0, lap.store_end);
+        assert_eq!(3, analyser.current().unwrap().lap_number);
+
+        let report = analyser.report();
+        assert_eq!(2, report.lines().count());
+        let line = report.lines().nth(1).unwrap();
+        assert!(line.contains("balance -0.05MJ"), "{}", line);
+        assert!(line.ends_with("flat at 2000m"), "{}", line);
+    }
+
+    #[test]
+    fn leaves_out_the_lap_tracking_started_in() {
+        let mut analyser = ERSAnalyser::new();
+        drive(&mut analyser, 8..=19, |_| 2_000_000.0);
+
+        let laps = analyser.laps();
+        assert_eq!(1, laps.len());
+        assert_eq!(3, laps[0].lap_number);
+        assert_eq!(200_000.0, laps[0].total.deployed);
+    }
+
+    #[test]
+    fn drives_laps_again_after_a_flashback() {
+        let mut analyser = ERSAnalyser::new();
+        drive(&mut analyser, 0..=9, |_| 2_000_000.0);
+        assert_eq!(1, analyser.laps().len());
+
+        // Back to 1500m into lap 1, whose laps count once
+        drive(&mut analyser, 3..=13, |_| 2_000_000.0);
+        let laps = analyser.laps();
+        assert_eq!(
+            vec![1, 2],
+            laps.iter().map(|lap| lap.lap_number).collect::<Vec<_>>()
+        );
+        for lap in laps {
+            assert_eq!(200_000.0, lap.total.deployed);
+            assert_eq!(150_000.0, lap.total.harvested());
+        }
+
+        let mut straight = ERSAnalyser::new();
+        drive(&mut straight, 0..=13, |_| 2_000_000.0);
+        assert_eq!(straight.laps(), laps);
+    }
+
+    #[test]
+    fn counts_sectors_and_distances_once_after_a_flashback_within_a_lap() {
+        let mut analyser = ERSAnalyser::new();
+        // Flat from 2000m into lap 1, then back to 1000m before it
+        drive(&mut analyser, 0..=4, |step| {
+            if step < 4 {
+                2_000_000.0
+            } else {
+                0.0
+            }
+        });
+        assert_eq!(Some(2000.0), analyser.current().unwrap().ran_flat_at);
+        drive(&mut analyser, 2..=7, |_| 2_000_000.0);
+
+        let lap = &analyser.laps()[0];
+        assert_eq!(100_000.0, lap.sectors[0].deployed);
+        assert_eq!(100_000.0, lap.sectors[1].deployed);
+        assert_eq!(50_000.0, lap.sectors[1].harvested_mguk);
+        assert_eq!(100_000.0, lap.sectors[2].harvested_mguk);
+        assert_eq!(100_000.0, lap.deployed_by_distance[10]);
+        assert_eq!(None, lap.ran_flat_at);
+
+        let mut straight = ERSAnalyser::new();
+        drive(&mut straight, 0..=7, |_| 2_000_000.0);
+        assert_eq!(straight.laps(), analyser.laps());
+    }
+
+    #[test]
+    fn books_energy_to_the_lap_the_counters_were_on() {
+        let mut analyser = ERSAnalyser::new();
+        let on_lap = |frame: u32, lap_number: u8, lap_distance: f32| {
+            lap(frame, |laps| {
+                laps[0].current_lap_num = lap_number;
+                laps[0].lap_distance = lap_distance;
+                laps[0].sector = (lap_distance / 1000.0) as u8;
+            })
+        };
+        let deployed = |frame: u32, deployed: f32| {
+            status(frame, |cars| cars[0].ers_deployed_this_lap = deployed)
+        };
+        let packets = vec![
+            on_lap(1, 1, 0.0),
+            deployed(2, 0.0),
+            on_lap(3, 1, 1500.0),
+            deployed(4, 100_000.0),
+            // Car Status sampled before the line after a Lap packet past it
+            on_lap(5, 2, 50.0),
+            deployed(6, 150_000.0),
+            deployed(7, 20_000.0),
+            on_lap(8, 2, 2900.0),
+            deployed(9, 60_000.0),
+            // Car Status sampled past the line before the Lap packet
+            deployed(10, 10_000.0),
+        ];
+        for packet in &packets {
+            analyser.update(packet);
+        }
+
+        let laps = analyser.laps();
+        assert_eq!(1, laps.len());
+        assert_eq!(150_000.0, laps[0].total.deployed);
+        assert_eq!(100_000.0, laps[0].sectors[1].deployed);
+        assert_eq!(50_000.0, laps[0].sectors[2].deployed);
+        let current = analyser.current().unwrap();
+        assert_eq!(3, current.lap_number);
+        assert_eq!(10_000.0, current.deployed_by_distance[0]);
+
+        analyser.update(&on_lap(11, 3, 100.0));
+        let laps = analyser.laps();
+        assert_eq!(2, laps.len());
+        assert_eq!(2, laps[1].lap_number);
+        assert_eq!(60_000.0, laps[1].total.deployed);
+        assert_eq!(20_000.0, laps[1].sectors[0].deployed);
+        assert_eq!(40_000.0, laps[1].sectors[2].deployed);
+    }
+}
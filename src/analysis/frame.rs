@@ -0,0 +1,271 @@
+use crate::f1_2020::car::{
+    CarStatusData, CarTelemetryData, PacketCarStatusData, PacketCarTelemetryData,
+};
+use crate::f1_2020::lap::{LapData, PacketLapData};
+use crate::f1_2020::motion::{CarMotionData, PacketMotionData};
+use crate::f1_2020::packet::Packet2020;
+use std::time::{Duration, Instant};
+
+/// Motion, lap, telemetry and status of all cars at one frame of a session.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrameSnapshot {
+    pub session_uid: u64,
+    pub frame_identifier: u32,
+    pub session_time: Duration,
+    pub player_car_index: u8,
+    pub motion: Option<PacketMotionData>,
+    pub lap: Option<PacketLapData>,
+    pub telemetry: Option<PacketCarTelemetryData>,
+    pub status: Option<PacketCarStatusData>,
+}
+
+/// One car's share of a `FrameSnapshot`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CarFrame<'a> {
+    pub motion: Option<&'a CarMotionData>,
+    pub lap: Option<&'a LapData>,
+    pub telemetry: Option<&'a CarTelemetryData>,
+    pub status: Option<&'a CarStatusData>,
+}
+
+impl FrameSnapshot {
+    /// Whether all four packet types of the frame arrived.
+    pub fn is_complete(&self) -> bool {
+        self.motion.is_some()
+            && self.lap.is_some()
+            && self.telemetry.is_some()
+            && self.status.is_some()
+    }
+
+    pub fn car(&self, index: usize) -> CarFrame<'_> {
+        CarFrame {
+            motion: self
+                .motion
+                .as_ref()
+                .and_then(|packet| packet.motion_data.get(index)),
+            lap: self
+                .lap
+                .as_ref()
+                .and_then(|packet| packet.lap_data.get(index)),
+            telemetry: self
+                .telemetry
+                .as_ref()
+                .and_then(|packet| packet.car_telemetry_data.get(index)),
+            status: self
+                .status
+                .as_ref()
+                .and_then(|packet| packet.car_status_data.get(index)),
+        }
+    }
+
+    pub fn player(&self) -> CarFrame<'_> {
+        self.car(self.player_car_index as usize)
+    }
+}
+
+/// Groups the Motion, Lap, Telemetry and Status packets sharing a
+/// `frame_identifier` into `FrameSnapshot`s.
+///
+/// A frame is emitted once all four packets arrived or, incomplete, once
+/// `timeout` passed since its first packet. Frames are emitted in the order
+/// they started, so a completed frame also emits the older ones still
+/// pending.
+///
+/// Packets of frames up to the latest one emitted are dropped as late until
+/// `timeout` passed since, after that they start frames again, as after a
+/// flashback.
+#[derive(Debug)]
+pub struct FrameAssembler {
+    timeout: Duration,
+    pending: Vec<(Instant, FrameSnapshot)>,
+    /// When the latest frame was emitted, and its session and frame
+    emitted: Option<(Instant, u64, u32)>,
+}
+
+impl FrameAssembler {
+    pub fn new(timeout: Duration) -> Self {
+        FrameAssembler {
+            timeout,
+            pending: Vec::new(),
+            emitted: None,
+        }
+    }
+
+    /// Adds a packet, returning the frames ready to be emitted. Packets of
+    /// other types are ignored.
+    pub fn push(&mut self, packet: Packet2020) -> Vec<FrameSnapshot> {
+        self.push_at(packet, Instant::now())
+    }
+
+    /// Adds a packet received at `now`, as `push` does.
+    pub fn push_at(&mut self, packet: Packet2020, now: Instant) -> Vec<FrameSnapshot> {
+        match packet {
+            Packet2020::Motion(_)
+            | Packet2020::Lap(_)
+            | Packet2020::CarTelemetry(_)
+            | Packet2020::CarStatus(_) => {}
+            _ => return self.expire_at(now),
+        }
+
+        let header = *packet.header();
+        let late = self
+            .emitted
+            .map_or(false, |(emitted, session_uid, frame_identifier)| {
+                session_uid == header.session_uid
+                    && header.frame_identifier <= frame_identifier
+                    && now.duration_since(emitted) < self.timeout
+            });
+        if late {
+            return self.expire_at(now);
+        }
+
+        let position = match self.pending.iter().position(|(_, frame)| {
+            frame.session_uid == header.session_uid
+                && frame.frame_identifier == header.frame_identifier
+        }) {
+            Some(position) => position,
+            None => {
+                let frame = FrameSnapshot {
+                    session_uid: header.session_uid,
+                    frame_identifier: header.frame_identifier,
+                    session_time: header.session_time,
+                    player_car_index: header.player_car_index,
+                    motion: None,
+                    lap: None,
+                    telemetry: None,
+                    status: None,
+                };
+                self.pending.push((now, frame));
+                self.pending.len() - 1
+            }
+        };
+
+        let frame = &mut self.pending[position].1;
+        match packet {
+            Packet2020::Motion(motion) => frame.motion = Some(motion),
+            Packet2020::Lap(lap) => frame.lap = Some(lap),
+            Packet2020::CarTelemetry(telemetry) => frame.telemetry = Some(telemetry),
+            Packet2020::CarStatus(status) => frame.status = Some(status),
+            _ => {}
+        }
+
+        if frame.is_complete() {
+            return self.emit(position, now);
+        }
+
+        self.expire_at(now)
+    }
+
+    /// Returns the frames whose timeout passed, to be called when no packets
+    /// arrive.
+    pub fn expire(&mut self) -> Vec<FrameSnapshot> {
+        self.expire_at(Instant::now())
+    }
+
+    /// Returns the frames whose timeout passed at `now`, as `expire` does.
+    pub fn expire_at(&mut self, now: Instant) -> Vec<FrameSnapshot> {
+        let expired = self
+            .pending
+            .iter()
+            .rposition(|(started, _)| now.duration_since(*started) >= self.timeout);
+
+        match expired {
+            Some(position) => self.emit(position, now),
+            None => Vec::new(),
+        }
+    }
+
+    /// Emits the pending frames up to `position`.
+    fn emit(&mut self, position: usize, now: Instant) -> Vec<FrameSnapshot> {
+        let frames: Vec<FrameSnapshot> = self
+            .pending
+            .drain(..=position)
+            .map(|(_, frame)| frame)
+            .collect();
+        if let Some(frame) = frames.last() {
+            self.emitted = Some((now, frame.session_uid, frame.frame_identifier));
+        }
+        frames
+    }
+
+    /// Returns all pending frames, e.g. at the end of a session.
+    pub fn flush(&mut self) -> Vec<FrameSnapshot> {
+        self.pending.drain(..).map(|(_, frame)| frame).collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::analysis::fixtures::packet;
+    use crate::analysis::frame::FrameAssembler;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn emits_complete_frames() {
+        let mut assembler = FrameAssembler::new(Duration::from_secs(60));
+
+        assert!(assembler.push(packet(0, 1)).is_empty());
+        assert!(assembler.push(packet(2, 1)).is_empty());
+        // Session packets are not part of a frame
+        assert!(assembler.push(packet(1, 1)).is_empty());
+        assert!(assembler.push(packet(6, 1)).is_empty());
+        assert!(assembler.push(packet(0, 2)).is_empty());
+
+        let frames = assembler.push(packet(7, 1));
+        assert_eq!(1, frames.len());
+        assert_eq!(1, frames[0].frame_identifier);
+        assert!(frames[0].is_complete());
+        assert!(frames[0].player().status.is_some());
+        assert!(frames[0].car(22).lap.is_none());
+
+        let frames = assembler.flush();
+        assert_eq!(1, frames.len());
+        assert!(!frames[0].is_complete());
+    }
+
+    #[test]
+    fn emits_older_and_timed_out_frames() {
+        let mut assembler = FrameAssembler::new(Duration::from_millis(20));
+        let start = Instant::now();
+
+        assembler.push_at(packet(0, 1), start);
+        for id in &[0, 2, 6] {
+            assert!(assembler.push_at(packet(*id, 2), start).is_empty());
+        }
+        let frames: Vec<u32> = assembler
+            .push_at(packet(7, 2), start)
+            .iter()
+            .map(|frame| frame.frame_identifier)
+            .collect();
+        assert_eq!(vec![1, 2], frames);
+
+        assembler.push_at(packet(2, 3), start + Duration::from_millis(10));
+        assert!(assembler
+            .expire_at(start + Duration::from_millis(29))
+            .is_empty());
+        let frames = assembler.expire_at(start + Duration::from_millis(30));
+        assert_eq!(1, frames.len());
+        assert!(frames[0].lap.is_some() && frames[0].motion.is_none());
+    }
+
+    #[test]
+    fn drops_late_packets_of_emitted_frames() {
+        let mut assembler = FrameAssembler::new(Duration::from_millis(20));
+        let start = Instant::now();
+
+        for id in &[0, 2, 6, 7] {
+            assembler.push_at(packet(*id, 5), start);
+        }
+        let late = start + Duration::from_millis(19);
+        assert!(assembler.push_at(packet(2, 5), late).is_empty());
+        assert!(assembler.push_at(packet(0, 4), late).is_empty());
+        assert!(assembler.flush().is_empty());
+
+        // Earlier frames start again after a flashback
+        let flashback = start + Duration::from_millis(20);
+        assert!(assembler.push_at(packet(0, 3), flashback).is_empty());
+        let frames = assembler.flush();
+        assert_eq!(1, frames.len());
+        assert_eq!(3, frames[0].frame_identifier);
+    }
+}
@@ -0,0 +1,357 @@
+use crate::analysis::{Tracked, Tracking};
+use crate::f1_2020::car::{CarStatusData, FuelMix};
+use crate::f1_2020::lap::LapData;
+use crate::f1_2020::packet::Packet2020;
+use crate::f1_2020::session::Track;
+
+/// Distance to drive on a fuel mix before its burn rate is trusted, in metres.
+const MIN_DISTANCE: f64 = 1000.0;
+
+/// Where the race is headed with the fuel on board.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FuelProjection {
+    pub fuel_in_tank: f32,
+    pub fuel_mix: FuelMix,
+    /// Fuel burnt per lap on `fuel_mix`
+    pub burn_per_lap: f32,
+    /// Laps to the flag, counting what is left of the current lap
+    pub laps_remaining: f32,
+    /// Fuel left at the flag on `fuel_mix`, negative when short
+    pub fuel_at_flag: f32,
+    /// Richest measured mix that reaches the flag
+    pub recommended_fuel_mix: Option<FuelMix>,
+    /// Fuel to save each lap by lifting and coasting to reach the flag on
+    /// `fuel_mix`, zero when not short
+    pub lift_and_coast_per_lap: f32,
+}
+
+/// Fuel burnt over the distance driven on a fuel mix at a track.
+#[derive(Debug, Clone)]
+struct Usage {
+    track: Track,
+    track_length: u16,
+    fuel_mix: FuelMix,
+    fuel: f64,
+    distance: f64,
+}
+
+impl Usage {
+    fn burn_per_lap(&self) -> Option<f32> {
+        if self.distance < MIN_DISTANCE {
+            return None;
+        }
+        Some((self.fuel / self.distance * self.track_length as f64) as f32)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Circuit {
+    track: Track,
+    track_length: u16,
+    total_laps: u8,
+}
+
+/// Fuel on board at some total distance.
+#[derive(Debug, Clone, Copy)]
+struct Sample {
+    total_distance: f32,
+    fuel_in_tank: f32,
+    fuel_mix: FuelMix,
+}
+
+/// Measures the fuel one car burns per lap on each fuel mix, per track, and
+/// projects it to the flag.
+///
+/// Fuel burnt between Car Status packets is put down to the mix in use and
+/// the distance driven meanwhile, leaving out flashbacks. Measurements are
+/// kept across sessions, to suggest the fuel load of the next race at a track.
+#[derive(Debug, Clone, Default)]
+pub struct FuelModel {
+    tracking: Tracking,
+    circuit: Option<Circuit>,
+    lap: Option<LapData>,
+    status: Option<CarStatusData>,
+    last: Option<Sample>,
+    usage: Vec<Usage>,
+}
+
+impl FuelModel {
+    /// Model of the player's car.
+    pub fn new() -> Self {
+        FuelModel::default()
+    }
+
+    /// Model of the car at `index`.
+    pub fn for_car(index: usize) -> Self {
+        FuelModel {
+            tracking: Tracking::car(index),
+            ..FuelModel::default()
+        }
+    }
+
+    pub fn update(&mut self, packet: &Packet2020) {
+        let (_, car) = self.follow(packet.header());
+
+        match packet {
+            Packet2020::Session(session) => {
+                self.circuit = Some(Circuit {
+                    track: session.track_id.clone(),
+                    track_length: session.track_length,
+                    total_laps: session.total_laps,
+                });
+            }
+            Packet2020::Lap(lap) => self.lap = lap.lap_data.get(car).copied(),
+            Packet2020::CarStatus(status) => {
+                if let Some(status) = status.car_status_data.get(car) {
+                    self.measure(status);
+                    self.status = Some(status.clone());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn measure(&mut self, status: &CarStatusData) {
+        let lap = match &self.lap {
+            Some(lap) => lap,
+            None => return,
+        };
+        let sample = Sample {
+            total_distance: lap.total_distance,
+            fuel_in_tank: status.fuel_in_tank,
+            fuel_mix: status.fuel_mix,
+        };
+
+        if let (Some(last), Some(circuit)) = (self.last, &self.circuit) {
+            let distance = (sample.total_distance - last.total_distance) as f64;
+            let fuel = (last.fuel_in_tank - sample.fuel_in_tank) as f64;
+            // Keep burning from the last sample until the car moved
+            if distance == 0.0 {
+                return;
+            }
+            // Flashbacks and refuelling say nothing about the burn
+            if distance > 0.0 && fuel >= 0.0 {
+                let usage = match self.usage.iter().position(|usage| {
+                    usage.track == circuit.track && usage.fuel_mix == last.fuel_mix
+                }) {
+                    Some(position) => &mut self.usage[position],
+                    None => {
+                        self.usage.push(Usage {
+                            track: circuit.track.clone(),
+                            track_length: circuit.track_length,
+                            fuel_mix: last.fuel_mix,
+                            fuel: 0.0,
+                            distance: 0.0,
+                        });
+                        self.usage.last_mut().unwrap()
+                    }
+                };
+                usage.fuel += fuel;
+                usage.distance += distance;
+            }
+        }
+        self.last = Some(sample);
+    }
+
+    /// Fuel burnt per lap of a track on `fuel_mix`, `None` until a kilometre
+    /// was driven on it there.
+    pub fn track_burn_per_lap(&self, track: &Track, fuel_mix: FuelMix) -> Option<f32> {
+        self.usage
+            .iter()
+            .find(|usage| &usage.track == track && usage.fuel_mix == fuel_mix)
+            .and_then(Usage::burn_per_lap)
+    }
+
+    /// Fuel burnt per lap of the current track on `fuel_mix`.
+    pub fn burn_per_lap(&self, fuel_mix: FuelMix) -> Option<f32> {
+        self.track_burn_per_lap(&self.circuit.as_ref()?.track, fuel_mix)
+    }
+
+    /// Fuel at the flag on the current mix, `None` until its burn is known.
+    pub fn projection(&self) -> Option<FuelProjection> {
+        let circuit = self.circuit.as_ref()?;
+        let lap = self.lap.as_ref()?;
+        let status = self.status.as_ref()?;
+        if circuit.track_length == 0 {
+            return None;
+        }
+
+        let burn_per_lap = self.burn_per_lap(status.fuel_mix)?;
+        let lap_done = (lap.lap_distance.max(0.0) / circuit.track_length as f32).min(1.0);
+        let laps_remaining =
+            (circuit.total_laps as f32 - lap.current_lap_num as f32 + 1.0 - lap_done).max(0.0);
+        let fuel_at_flag = status.fuel_in_tank - burn_per_lap * laps_remaining;
+
+        let recommended_fuel_mix = [
+            FuelMix::Max,
+            FuelMix::Rich,
+            FuelMix::Standard,
+            FuelMix::Lean,
+        ]
+        .iter()
+        .copied()
+        .find(|fuel_mix| {
+            self.burn_per_lap(*fuel_mix)
+                .map_or(false, |burn| burn * laps_remaining <= status.fuel_in_tank)
+        });
+        let lift_and_coast_per_lap = if fuel_at_flag < 0.0 && laps_remaining > 0.0 {
+            -fuel_at_flag / laps_remaining
+        } else {
+            0.0
+        };
+
+        Some(FuelProjection {
+            fuel_in_tank: status.fuel_in_tank,
+            fuel_mix: status.fuel_mix,
+            burn_per_lap,
+            laps_remaining,
+            fuel_at_flag,
+            recommended_fuel_mix,
+            lift_and_coast_per_lap,
+        })
+    }
+
+    /// Fuel to start a race of `total_laps` at a track with, running
+    /// `fuel_mix` throughout and `margin_laps` to spare.
+    pub fn suggested_fuel_load(
+        &self,
+        track: &Track,
+        total_laps: u8,
+        fuel_mix: FuelMix,
+        margin_laps: f32,
+    ) -> Option<f32> {
+        let burn_per_lap = self.track_burn_per_lap(track, fuel_mix)?;
+        Some(burn_per_lap * (total_laps as f32 + margin_laps))
+    }
+}
+
+impl Tracked for FuelModel {
+    fn tracking(&mut self) -> &mut Tracking {
+        &mut self.tracking
+    }
+
+    fn start_over(&mut self) {
+        *self = FuelModel {
+            tracking: self.tracking,
+            usage: std::mem::take(&mut self.usage),
+            ..FuelModel::default()
+        };
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::analysis::fixtures::{lap, packet, status};
+    use crate::analysis::fuel::FuelModel;
+    use crate::f1_2020::car::FuelMix;
+    use crate::f1_2020::packet::Packet2020;
+    use crate::f1_2020::session::Track;
+
+    /// A kilometre a step on a 5km, 10 lap Monza: 2kg a lap on Standard for
+    /// a lap, then 1.5kg a lap on Lean for a lap.
+    fn race(model: &mut FuelModel) {
+        let mut session = packet(1, 0);
+        if let Packet2020::Session(session) = &mut session {
+            session.track_id = Track::Monza;
+            session.track_length = 5000;
+            session.total_laps = 10;
+        }
+        model.update(&session);
+
+        for step in 0..=10u32 {
+            let frame = step * 2 + 1;
+            let distance = step as f32 * 1000.0;
+            model.update(&lap(frame, |laps| {
+                laps[0].total_distance = distance;
+                laps[0].lap_distance = distance % 5000.0;
+                laps[0].current_lap_num = 1 + step as u8 / 5;
+            }));
+            model.update(&status(frame + 1, |cars| {
+                let standard = step.min(5) as f32;
+                let lean = step.saturating_sub(5) as f32;
+                cars[0].fuel_in_tank = 20.0 - 0.4 * standard - 0.3 * lean;
+                cars[0].fuel_mix = if step < 5 {
+                    FuelMix::Standard
+                } else {
+                    FuelMix::Lean
+                };
+            }));
+        }
+    }
+
+    fn assert_close(expected: f32, actual: f32) {
+        assert!(
+            (expected - actual).abs() < 0.01,
+            "{} != {}",
+            expected,
+            actual
+        );
+    }
+
+    #[test]
+    fn projects_fuel_at_flag() {
+        let mut model = FuelModel::new();
+        race(&mut model);
+
+        assert_close(2.0, model.burn_per_lap(FuelMix::Standard).unwrap());
+        assert_close(1.5, model.burn_per_lap(FuelMix::Lean).unwrap());
+        assert_eq!(None, model.burn_per_lap(FuelMix::Rich));
+
+        // Laps 3 to 10 to go with 16.5kg on board
+        let projection = model.projection().unwrap();
+        assert_eq!(FuelMix::Lean, projection.fuel_mix);
+        assert_close(8.0, projection.laps_remaining);
+        assert_close(4.5, projection.fuel_at_flag);
+        assert_eq!(Some(FuelMix::Standard), projection.recommended_fuel_mix);
+        assert_close(0.0, projection.lift_and_coast_per_lap);
+
+        model.update(&status(100, |cars| {
+            cars[0].fuel_in_tank = 10.0;
+            cars[0].fuel_mix = FuelMix::Lean;
+        }));
+        let projection = model.projection().unwrap();
+        assert_close(-2.0, projection.fuel_at_flag);
+        assert_eq!(None, projection.recommended_fuel_mix);
+        assert_close(0.25, projection.lift_and_coast_per_lap);
+    }
+
+    #[test]
+    fn suggests_fuel_load_per_track() {
+        let mut model = FuelModel::new();
+        race(&mut model);
+
+        let mut next = packet(1, 200);
+        if let Packet2020::Session(session) = &mut next {
+            session.header.session_uid = 2;
+        }
+        model.update(&next);
+
+        assert!(model.projection().is_none());
+        let load = model
+            .suggested_fuel_load(&Track::Monza, 50, FuelMix::Standard, 1.0)
+            .unwrap();
+        assert_close(102.0, load);
+        assert_eq!(
+            None,
+            model.suggested_fuel_load(&Track::Silverstone, 50, FuelMix::Standard, 1.0)
+        );
+    }
+
+    #[test]
+    fn leaves_flashbacks_out_of_the_burn() {
+        let mut model = FuelModel::new();
+        race(&mut model);
+
+        // Back to 2km on Standard, driving on for another kilometre
+        for (frame, distance, fuel) in [(5, 2000.0, 19.2), (7, 3000.0, 18.8)] {
+            model.update(&lap(frame, |laps| laps[0].total_distance = distance));
+            model.update(&status(frame + 1, |cars| {
+                cars[0].fuel_in_tank = fuel;
+                cars[0].fuel_mix = FuelMix::Standard;
+            }));
+        }
+
+        assert_close(2.0, model.burn_per_lap(FuelMix::Standard).unwrap());
+        assert_close(1.5, model.burn_per_lap(FuelMix::Lean).unwrap());
+    }
+}
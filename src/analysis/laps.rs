@@ -0,0 +1,355 @@
+use crate::analysis::{Progress, Tracked, Tracking};
+use crate::f1_2020::car::{ActualTyreCompound, CarStatusData, VisualTyreCompound, TOTAL_CARS};
+use crate::f1_2020::lap::{LapData, PitStatus};
+use crate::f1_2020::packet::Packet2020;
+use std::time::Duration;
+
+/// A lap a car completed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CompletedLap {
+    pub lap_number: u8,
+    pub lap_time: Duration,
+    pub sector_1_time: Duration,
+    pub sector_2_time: Duration,
+    /// Lap time less sectors 1 and 2
+    pub sector_3_time: Duration,
+    /// Whether the lap was never flagged invalid
+    pub valid: bool,
+    /// Compound the lap was finished on, `None` without Car Status packets
+    pub actual_tyre_compound: Option<ActualTyreCompound>,
+    pub visual_tyre_compound: Option<VisualTyreCompound>,
+    pub tyres_age_laps: Option<u8>,
+    /// Whether the car entered the pit lane during the lap
+    pub pit_in: bool,
+    /// Whether the lap started in the pit lane
+    pub pit_out: bool,
+}
+
+/// Lap in progress.
+#[derive(Debug, Clone)]
+struct CurrentLap {
+    lap_number: u8,
+    /// Whether the lap was seen from its start
+    observed_start: bool,
+    sector_1_time: Duration,
+    sector_2_time: Duration,
+    invalid: bool,
+    pit_in: bool,
+    pit_out: bool,
+    in_pit_lane: bool,
+}
+
+impl CurrentLap {
+    fn new(lap: &LapData, observed_start: bool) -> Self {
+        let in_pit_lane = lap.pit_status != PitStatus::None;
+        CurrentLap {
+            lap_number: lap.current_lap_num,
+            observed_start,
+            sector_1_time: Duration::ZERO,
+            sector_2_time: Duration::ZERO,
+            invalid: false,
+            pit_in: false,
+            pit_out: in_pit_lane,
+            in_pit_lane,
+        }
+    }
+
+    fn update(&mut self, lap: &LapData) {
+        if lap.sector_1_time > Duration::ZERO {
+            self.sector_1_time = lap.sector_1_time;
+        }
+        if lap.sector_2_time > Duration::ZERO {
+            self.sector_2_time = lap.sector_2_time;
+        }
+        self.invalid |= lap.current_lap_invalid;
+
+        let in_pit_lane = lap.pit_status != PitStatus::None;
+        self.pit_in |= in_pit_lane && !self.in_pit_lane;
+        self.in_pit_lane = in_pit_lane;
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct CarLaps {
+    completed: Vec<CompletedLap>,
+    current: Option<CurrentLap>,
+    status: Option<CarStatusData>,
+}
+
+impl CarLaps {
+    fn update(&mut self, lap: &LapData, rewound: bool) {
+        if rewound {
+            return self.rewind(lap);
+        }
+        match self.current.take() {
+            Some(mut current) if current.lap_number == lap.current_lap_num => {
+                current.update(lap);
+                self.current = Some(current);
+            }
+            Some(current) => {
+                if current.observed_start && lap.current_lap_num > current.lap_number {
+                    self.complete(current, lap.last_lap_time);
+                }
+                self.start(lap, true);
+            }
+            // Only a race's first lap is known to be seen from its start
+            None => self.start(lap, lap.current_lap_num == 1),
+        }
+    }
+
+    /// Goes back to a lap after a flashback, forgetting the laps driven again.
+    fn rewind(&mut self, lap: &LapData) {
+        let lap_number = lap.current_lap_num;
+        let earlier = match self.current.take() {
+            Some(current) if current.lap_number == lap_number => {
+                Some((current.observed_start, current.pit_out))
+            }
+            _ => self
+                .completed
+                .iter()
+                .find(|done| done.lap_number == lap_number)
+                .map(|done| (true, done.pit_out)),
+        };
+        let (observed_start, pit_out) = earlier.unwrap_or((lap_number == 1, false));
+
+        self.completed.retain(|done| done.lap_number < lap_number);
+        self.start(lap, observed_start);
+        if let Some(current) = self.current.as_mut() {
+            current.pit_out |= pit_out;
+        }
+    }
+
+    fn start(&mut self, lap: &LapData, observed_start: bool) {
+        let mut current = CurrentLap::new(lap, observed_start);
+        current.update(lap);
+        self.current = Some(current);
+    }
+
+    fn complete(&mut self, lap: CurrentLap, lap_time: Duration) {
+        let status = self.status.as_ref();
+        self.completed.push(CompletedLap {
+            lap_number: lap.lap_number,
+            lap_time,
+            sector_1_time: lap.sector_1_time,
+            sector_2_time: lap.sector_2_time,
+            sector_3_time: lap_time
+                .saturating_sub(lap.sector_1_time)
+                .saturating_sub(lap.sector_2_time),
+            valid: !lap.invalid,
+            actual_tyre_compound: status.map(|status| status.actual_tyre_compound),
+            visual_tyre_compound: status.map(|status| status.visual_tyre_compound),
+            tyres_age_laps: status.map(|status| status.tyres_age_laps),
+            pit_in: lap.pit_in,
+            pit_out: lap.pit_out,
+        });
+    }
+}
+
+/// Records every lap each car completes, from the lap number changing in
+/// Lap packets and the latest Car Status packet.
+///
+/// Laps already under way when tracking started are left out, apart from
+/// lap 1. A flashback forgets the laps it takes the car back into.
+#[derive(Debug, Clone)]
+pub struct LapHistory {
+    tracking: Tracking,
+    /// Whether a flashback happened since the latest Lap packet
+    rewound: bool,
+    cars: Vec<CarLaps>,
+}
+
+impl Default for LapHistory {
+    fn default() -> Self {
+        LapHistory {
+            tracking: Tracking::default(),
+            rewound: false,
+            cars: vec![CarLaps::default(); TOTAL_CARS],
+        }
+    }
+}
+
+impl LapHistory {
+    pub fn new() -> Self {
+        LapHistory::default()
+    }
+
+    pub fn update(&mut self, packet: &Packet2020) {
+        let (progress, _) = self.follow(packet.header());
+        self.rewound |= progress == Progress::Flashback;
+
+        match packet {
+            Packet2020::Lap(lap) => {
+                for (car, data) in self.cars.iter_mut().zip(&lap.lap_data) {
+                    car.update(data, self.rewound);
+                }
+                self.rewound = false;
+            }
+            Packet2020::CarStatus(status) => {
+                for (car, data) in self.cars.iter_mut().zip(&status.car_status_data) {
+                    car.status = Some(data.clone());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Session the history is for, `None` until the first packet.
+    pub fn session_uid(&self) -> Option<u64> {
+        self.tracking.session_uid()
+    }
+
+    /// Completed laps of a car, oldest first.
+    pub fn laps(&self, car: usize) -> &[CompletedLap] {
+        self.cars
+            .get(car)
+            .map(|car| car.completed.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// Fastest valid lap of a car.
+    pub fn best_lap(&self, car: usize) -> Option<&CompletedLap> {
+        self.laps(car)
+            .iter()
+            .filter(|lap| lap.valid)
+            .min_by_key(|lap| lap.lap_time)
+    }
+}
+
+impl Tracked for LapHistory {
+    fn tracking(&mut self) -> &mut Tracking {
+        &mut self.tracking
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::analysis::fixtures::{lap, status};
+    use crate::analysis::laps::LapHistory;
+    use crate::f1_2020::car::ActualTyreCompound;
+    use crate::f1_2020::lap::PitStatus;
+    use crate::f1_2020::packet::Packet2020;
+    use std::time::Duration;
+
+    #[test]
+    fn records_completed_laps() {
+        let mut history = LapHistory::new();
+        let packets = vec![
+            lap(1, |laps| laps[0].current_lap_num = 1),
+            lap(2, |laps| {
+                let lap = &mut laps[0];
+                lap.current_lap_num = 1;
+                lap.sector = 1;
+                lap.sector_1_time = Duration::from_millis(30_000);
+            }),
+            status(2, |cars| {
+                let status = &mut cars[0];
+                status.actual_tyre_compound = ActualTyreCompound::C3;
+                status.tyres_age_laps = 4;
+            }),
+            lap(3, |laps| {
+                let lap = &mut laps[0];
+                lap.current_lap_num = 1;
+                lap.sector = 2;
+                lap.sector_1_time = Duration::from_millis(30_000);
+                lap.sector_2_time = Duration::from_millis(31_000);
+                lap.current_lap_invalid = true;
+                lap.pit_status = PitStatus::Pitting;
+            }),
+            lap(4, |laps| {
+                let lap = &mut laps[0];
+                lap.current_lap_num = 2;
+                lap.last_lap_time = Duration::from_millis(90_000);
+                lap.pit_status = PitStatus::PitArea;
+            }),
+            lap(5, |laps| {
+                let lap = &mut laps[0];
+                lap.current_lap_num = 3;
+                lap.last_lap_time = Duration::from_millis(95_000);
+            }),
+        ];
+        for packet in &packets {
+            history.update(packet);
+        }
+
+        let laps = history.laps(0);
+        assert_eq!(2, laps.len());
+        assert_eq!(1, laps[0].lap_number);
+        assert_eq!(Duration::from_millis(29_000), laps[0].sector_3_time);
+        assert!(!laps[0].valid);
+        assert!(laps[0].pit_in && !laps[0].pit_out);
+        assert_eq!(Some(ActualTyreCompound::C3), laps[0].actual_tyre_compound);
+        assert_eq!(Some(4), laps[0].tyres_age_laps);
+
+        assert_eq!(2, laps[1].lap_number);
+        assert!(laps[1].valid);
+        assert!(laps[1].pit_out && !laps[1].pit_in);
+        assert_eq!(Some(&laps[1]), history.best_lap(0));
+        assert!(history.laps(22).is_empty());
+    }
+
+    #[test]
+    fn skips_laps_joined_midway() {
+        let mut history = LapHistory::new();
+        history.update(&lap(1, |laps| laps[0].current_lap_num = 5));
+        history.update(&lap(2, |laps| laps[0].current_lap_num = 6));
+        history.update(&lap(3, |laps| laps[0].current_lap_num = 7));
+
+        let laps: Vec<u8> = history.laps(0).iter().map(|lap| lap.lap_number).collect();
+        assert_eq!(vec![6], laps);
+    }
+
+    #[test]
+    fn drives_laps_again_after_a_flashback() {
+        let mut history = LapHistory::new();
+        let packets = vec![
+            lap(1, |laps| laps[0].current_lap_num = 1),
+            lap(2, |laps| {
+                laps[0].current_lap_num = 1;
+                laps[0].current_lap_invalid = true;
+            }),
+            lap(3, |laps| {
+                laps[0].current_lap_num = 2;
+                laps[0].last_lap_time = Duration::from_secs(90);
+            }),
+            // Back to before the lap was invalidated, first seen in a Car
+            // Status packet
+            status(2, |_| {}),
+            lap(2, |laps| laps[0].current_lap_num = 1),
+        ];
+        for packet in &packets {
+            history.update(packet);
+        }
+        assert!(history.laps(0).is_empty());
+
+        history.update(&lap(3, |laps| {
+            laps[0].current_lap_num = 2;
+            laps[0].last_lap_time = Duration::from_secs(91);
+        }));
+        let laps = history.laps(0);
+        assert_eq!(1, laps.len());
+        assert_eq!(Duration::from_secs(91), laps[0].lap_time);
+        assert!(laps[0].valid);
+    }
+
+    #[test]
+    fn starts_over_on_a_new_session_mid_lap() {
+        let mut history = LapHistory::new();
+        history.update(&lap(1, |laps| laps[0].current_lap_num = 1));
+        history.update(&lap(2, |laps| laps[0].current_lap_num = 2));
+        assert_eq!(1, history.laps(0).len());
+
+        let in_session = |mut packet: Packet2020| {
+            if let Packet2020::Lap(lap) = &mut packet {
+                lap.header.session_uid = 2;
+            }
+            packet
+        };
+        history.update(&in_session(lap(1, |laps| laps[0].current_lap_num = 3)));
+        assert_eq!(Some(2), history.session_uid());
+        assert!(history.laps(0).is_empty());
+
+        // The lap under way when the session changed was not seen whole
+        history.update(&in_session(lap(2, |laps| laps[0].current_lap_num = 4)));
+        assert!(history.laps(0).is_empty());
+    }
+}
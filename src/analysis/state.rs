@@ -0,0 +1,320 @@
+use crate::analysis::{Progress, Tracked, Tracking};
+use crate::f1_2020::car::{CarSetupData, CarStatusData, CarTelemetryData, TOTAL_CARS};
+use crate::f1_2020::event::PacketEventData;
+use crate::f1_2020::final_classification::FinalClassificationData;
+use crate::f1_2020::lap::LapData;
+use crate::f1_2020::motion::CarMotionData;
+use crate::f1_2020::packet::Packet2020;
+use crate::f1_2020::participants::ParticipantData;
+use crate::f1_2020::session::{PacketSessionData, SafetyCar, Track, Weather};
+use crate::f1_2020::team::Team;
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// Number of events kept, speed traps and penalties alone run to thousands
+/// over a long race.
+pub const MAX_EVENTS: usize = 256;
+
+/// Latest data received for one car.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CarState {
+    pub participant: Option<ParticipantData>,
+    pub motion: Option<CarMotionData>,
+    pub lap: Option<LapData>,
+    pub telemetry: Option<CarTelemetryData>,
+    pub status: Option<CarStatusData>,
+    pub setup: Option<CarSetupData>,
+    pub final_classification: Option<FinalClassificationData>,
+}
+
+impl CarState {
+    pub fn name(&self) -> Option<&str> {
+        self.participant
+            .as_ref()
+            .map(|participant| participant.name.as_str())
+    }
+
+    pub fn team(&self) -> Option<Team> {
+        self.participant
+            .as_ref()
+            .map(|participant| participant.team)
+    }
+}
+
+/// Current view of a session, kept up to date from its packets.
+///
+/// Only the latest `MAX_EVENTS` events are kept, older ones are dropped
+/// first, and a flashback drops the events it undid.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SessionState {
+    tracking: Tracking,
+    session_time: Duration,
+    player_car_index: u8,
+    session: Option<PacketSessionData>,
+    num_active_cars: u8,
+    cars: Vec<CarState>,
+    events: VecDeque<PacketEventData>,
+}
+
+impl Default for SessionState {
+    fn default() -> Self {
+        SessionState {
+            tracking: Tracking::default(),
+            session_time: Duration::ZERO,
+            player_car_index: 0,
+            session: None,
+            num_active_cars: 0,
+            cars: vec![CarState::default(); TOTAL_CARS],
+            events: VecDeque::new(),
+        }
+    }
+}
+
+impl SessionState {
+    pub fn new() -> Self {
+        SessionState::default()
+    }
+
+    pub fn update(&mut self, packet: &Packet2020) {
+        let header = packet.header();
+        let (progress, _) = self.follow(header);
+        if progress == Progress::Flashback {
+            self.events
+                .retain(|event| event.header.frame_identifier <= header.frame_identifier);
+        }
+        self.session_time = header.session_time;
+        self.player_car_index = header.player_car_index;
+
+        match packet {
+            Packet2020::Motion(motion) => {
+                for (car, data) in self.cars.iter_mut().zip(&motion.motion_data) {
+                    car.motion = Some(data.clone());
+                }
+            }
+            Packet2020::Session(session) => self.session = Some(session.clone()),
+            Packet2020::Lap(lap) => {
+                for (car, data) in self.cars.iter_mut().zip(&lap.lap_data) {
+                    car.lap = Some(*data);
+                }
+            }
+            Packet2020::Event(event) => {
+                if self.events.len() == MAX_EVENTS {
+                    self.events.pop_front();
+                }
+                self.events.push_back(*event);
+            }
+            Packet2020::Participants(participants) => {
+                self.num_active_cars = participants.num_active_cars;
+                for (car, data) in self.cars.iter_mut().zip(&participants.participants) {
+                    car.participant = Some(data.clone());
+                }
+            }
+            Packet2020::CarSetups(setups) => {
+                for (car, data) in self.cars.iter_mut().zip(&setups.car_setup_data) {
+                    car.setup = Some(data.clone());
+                }
+            }
+            Packet2020::CarTelemetry(telemetry) => {
+                for (car, data) in self.cars.iter_mut().zip(&telemetry.car_telemetry_data) {
+                    car.telemetry = Some(data.clone());
+                }
+            }
+            Packet2020::CarStatus(status) => {
+                for (car, data) in self.cars.iter_mut().zip(&status.car_status_data) {
+                    car.status = Some(data.clone());
+                }
+            }
+            Packet2020::FinalClassification(classification) => {
+                for (car, data) in self
+                    .cars
+                    .iter_mut()
+                    .zip(&classification.final_classification_data)
+                {
+                    car.final_classification = Some(data.clone());
+                }
+            }
+            Packet2020::LobbyInfo(_) => {}
+        }
+    }
+
+    /// `None` until the first packet.
+    pub fn session_uid(&self) -> Option<u64> {
+        self.tracking.session_uid()
+    }
+
+    /// Session time of the latest packet.
+    pub fn session_time(&self) -> Duration {
+        self.session_time
+    }
+
+    /// Frame of the latest packet.
+    pub fn frame_identifier(&self) -> u32 {
+        self.tracking.frame_identifier()
+    }
+
+    /// Latest Session packet.
+    pub fn session(&self) -> Option<&PacketSessionData> {
+        self.session.as_ref()
+    }
+
+    pub fn track(&self) -> Option<&Track> {
+        self.session.as_ref().map(|session| &session.track_id)
+    }
+
+    pub fn weather(&self) -> Option<&Weather> {
+        self.session.as_ref().map(|session| &session.weather)
+    }
+
+    pub fn safety_car(&self) -> Option<&SafetyCar> {
+        self.session
+            .as_ref()
+            .map(|session| &session.safety_car_status)
+    }
+
+    /// Number of cars in the latest Participants packet.
+    pub fn num_active_cars(&self) -> u8 {
+        self.num_active_cars
+    }
+
+    pub fn car(&self, index: usize) -> Option<&CarState> {
+        self.cars.get(index)
+    }
+
+    pub fn cars(&self) -> &[CarState] {
+        &self.cars
+    }
+
+    /// Car of the player, `None` until the first packet.
+    pub fn player(&self) -> Option<&CarState> {
+        self.session_uid()?;
+        self.car(self.player_car_index as usize)
+    }
+
+    /// Latest `MAX_EVENTS` events of the session in the order they were
+    /// received.
+    pub fn events(&self) -> &VecDeque<PacketEventData> {
+        &self.events
+    }
+}
+
+impl Tracked for SessionState {
+    fn tracking(&mut self) -> &mut Tracking {
+        &mut self.tracking
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::analysis::fixtures::packet;
+    use crate::analysis::state::{SessionState, MAX_EVENTS};
+    use crate::f1_2020::event::{Event, PacketEventData};
+    use crate::f1_2020::packet::Packet2020;
+    use crate::f1_2020::session::{SafetyCar, Track};
+    use crate::f1_2020::team::Team;
+
+    #[test]
+    fn keeps_latest_data_per_car() {
+        let mut state = SessionState::new();
+        assert!(state.player().is_none());
+
+        let mut participants = packet(4, 1);
+        if let Packet2020::Participants(participants) = &mut participants {
+            participants.header.player_car_index = 3;
+            participants.num_active_cars = 20;
+            participants.participants[3].name = "PLAYER".to_string();
+            participants.participants[3].team = Team::McLaren;
+        }
+        state.update(&participants);
+
+        let mut session = packet(1, 2);
+        if let Packet2020::Session(session) = &mut session {
+            session.header.player_car_index = 3;
+            session.track_id = Track::Monza;
+            session.safety_car_status = SafetyCar::Virtual;
+        }
+        state.update(&session);
+
+        let mut lap = packet(2, 3);
+        if let Packet2020::Lap(lap) = &mut lap {
+            lap.header.player_car_index = 3;
+            lap.lap_data[3].current_lap_num = 7;
+        }
+        state.update(&lap);
+
+        let player = state.player().unwrap();
+        assert_eq!(Some("PLAYER"), player.name());
+        assert_eq!(Some(Team::McLaren), player.team());
+        assert_eq!(7, player.lap.unwrap().current_lap_num);
+        assert!(player.status.is_none());
+        assert_eq!(20, state.num_active_cars());
+        assert_eq!(Some(&Track::Monza), state.track());
+        assert_eq!(Some(&SafetyCar::Virtual), state.safety_car());
+        assert_eq!(3, state.frame_identifier());
+        assert!(state.car(22).is_none());
+    }
+
+    #[test]
+    fn resets_on_new_session() {
+        let mut state = SessionState::new();
+        state.update(&packet(7, 1));
+        let lap = packet(2, 2);
+        let header = *lap.header();
+        state.update(&lap);
+        state.update(&Packet2020::Event(PacketEventData {
+            header,
+            event: Event::SessionStarted,
+        }));
+        assert_eq!(1, state.events().len());
+
+        let mut session = packet(1, 1);
+        if let Packet2020::Session(session) = &mut session {
+            session.header.session_uid = 2;
+        }
+        state.update(&session);
+
+        assert_eq!(Some(2), state.session_uid());
+        assert!(state.events().is_empty());
+        assert!(state.car(0).unwrap().lap.is_none());
+        assert!(state.car(0).unwrap().status.is_none());
+        assert!(state.session().is_some());
+    }
+
+    #[test]
+    fn keeps_latest_events() {
+        let mut state = SessionState::new();
+        for frame in 0..MAX_EVENTS as u32 + 10 {
+            state.update(&Packet2020::Event(PacketEventData {
+                header: *packet(3, frame).header(),
+                event: Event::SessionStarted,
+            }));
+        }
+
+        let events = state.events();
+        assert_eq!(MAX_EVENTS, events.len());
+        assert_eq!(10, events[0].header.frame_identifier);
+        assert_eq!(
+            MAX_EVENTS as u32 + 9,
+            events[MAX_EVENTS - 1].header.frame_identifier
+        );
+    }
+
+    #[test]
+    fn drops_events_undone_by_a_flashback() {
+        let mut state = SessionState::new();
+        for frame in 1..=5 {
+            state.update(&Packet2020::Event(PacketEventData {
+                header: *packet(3, frame).header(),
+                event: Event::SessionStarted,
+            }));
+        }
+        state.update(&packet(2, 3));
+
+        let frames: Vec<u32> = state
+            .events()
+            .iter()
+            .map(|event| event.header.frame_identifier)
+            .collect();
+        assert_eq!(vec![1, 2, 3], frames);
+        assert_eq!(3, state.frame_identifier());
+    }
+}
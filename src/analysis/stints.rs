@@ -0,0 +1,572 @@
+use crate::analysis::{Progress, Tracked, Tracking};
+use crate::f1_2020::car::{ActualTyreCompound, CarStatusData, VisualTyreCompound, TOTAL_CARS};
+use crate::f1_2020::final_classification::FinalClassificationData;
+use crate::f1_2020::lap::{LapData, PitStatus};
+use crate::f1_2020::packet::Packet2020;
+use std::time::Duration;
+
+/// A visit to the pit lane.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PitStop {
+    /// Lap the car entered the pit lane on
+    pub lap: u8,
+    /// Lap the car left the pit lane on
+    pub exit_lap: u8,
+    pub entry_time: Duration,
+    /// Time from pit entry to pit exit, not what the stop cost
+    pub pit_lane_time: Duration,
+    /// Time spent in the pit box
+    pub stationary_time: Duration,
+    /// Time the laps from pit entry to pit exit took over the lap before the
+    /// stop, `None` until they are all complete
+    pub pit_lane_loss: Option<Duration>,
+}
+
+/// Laps run on one set of tyres.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stint {
+    pub actual_tyre_compound: ActualTyreCompound,
+    pub visual_tyre_compound: VisualTyreCompound,
+    pub start_lap: u8,
+    pub start_time: Duration,
+    /// Age of the tyres when fitted
+    pub start_tyres_age_laps: u8,
+    /// `None` while the stint is running
+    pub end_lap: Option<u8>,
+    pub end_time: Option<Duration>,
+    /// Pit lane time of the stop the tyres were changed in
+    pub pit_lane_time: Option<Duration>,
+    /// Pit lane loss of the stop the tyres were changed in
+    pub pit_lane_loss: Option<Duration>,
+}
+
+/// How the tracked history differs from the Final Classification packet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Discrepancy {
+    PitStops {
+        recorded: usize,
+        classified: u8,
+    },
+    Stints {
+        recorded: Vec<ActualTyreCompound>,
+        classified: Vec<ActualTyreCompound>,
+    },
+}
+
+/// Stop in progress.
+#[derive(Debug, Clone, Copy)]
+struct PitVisit {
+    lap: u8,
+    entry_time: Duration,
+    box_entry_time: Option<Duration>,
+    stationary_time: Duration,
+}
+
+#[derive(Debug, Clone)]
+struct CarStints {
+    /// `None` before the first Lap packet
+    lap: Option<u8>,
+    /// Times of the completed laps, by lap number from 1
+    lap_times: Vec<Duration>,
+    pit_status: PitStatus,
+    visit: Option<PitVisit>,
+    tyres_age_laps: u8,
+    stops: Vec<PitStop>,
+    stints: Vec<Stint>,
+    classification: Option<FinalClassificationData>,
+}
+
+impl Default for CarStints {
+    fn default() -> Self {
+        CarStints {
+            lap: None,
+            lap_times: Vec::new(),
+            pit_status: PitStatus::None,
+            visit: None,
+            tyres_age_laps: 0,
+            stops: Vec::new(),
+            stints: Vec::new(),
+            classification: None,
+        }
+    }
+}
+
+impl CarStints {
+    fn update_lap(&mut self, lap: &LapData, session_time: Duration) {
+        if let Some(completed) = self.lap.filter(|last| lap.current_lap_num == last + 1) {
+            let i = completed as usize - 1;
+            if self.lap_times.len() <= i {
+                self.lap_times.resize(i + 1, Duration::ZERO);
+            }
+            self.lap_times[i] = lap.last_lap_time;
+            self.price_stops();
+        }
+        self.lap = Some(lap.current_lap_num);
+        let (was, is) = (self.pit_status, lap.pit_status);
+        self.pit_status = is;
+
+        if was == PitStatus::None && is != PitStatus::None {
+            self.visit = Some(PitVisit {
+                lap: lap.current_lap_num,
+                entry_time: session_time,
+                box_entry_time: None,
+                stationary_time: Duration::ZERO,
+            });
+        }
+        let visit = match self.visit.as_mut() {
+            Some(visit) => visit,
+            None => return,
+        };
+        if was != PitStatus::PitArea && is == PitStatus::PitArea {
+            visit.box_entry_time = Some(session_time);
+        }
+        if was == PitStatus::PitArea && is != PitStatus::PitArea {
+            if let Some(box_entry_time) = visit.box_entry_time.take() {
+                visit.stationary_time += session_time.saturating_sub(box_entry_time);
+            }
+        }
+
+        if is == PitStatus::None {
+            let visit = *visit;
+            self.visit = None;
+            let stop = PitStop {
+                lap: visit.lap,
+                exit_lap: lap.current_lap_num,
+                entry_time: visit.entry_time,
+                pit_lane_time: session_time.saturating_sub(visit.entry_time),
+                stationary_time: visit.stationary_time,
+                pit_lane_loss: None,
+            };
+            self.stops.push(stop);
+
+            // Tyres changed during the stop end the stint before the current one
+            let len = self.stints.len();
+            if let Some(stint) = len.checked_sub(2).and_then(|i| self.stints.get_mut(i)) {
+                if stint.end_time.map_or(false, |end| end >= visit.entry_time) {
+                    stint.pit_lane_time = Some(stop.pit_lane_time);
+                }
+            }
+            self.price_stops();
+        }
+    }
+
+    /// Works out the pit lane loss of the stops whose laps are complete.
+    fn price_stops(&mut self) {
+        for i in 0..self.stops.len() {
+            if self.stops[i].pit_lane_loss.is_some() {
+                continue;
+            }
+            let stop = self.stops[i];
+            let loss = match self.pit_lane_loss(&stop) {
+                Some(loss) => loss,
+                None => continue,
+            };
+            self.stops[i].pit_lane_loss = Some(loss);
+
+            let exit_time = stop.entry_time + stop.pit_lane_time;
+            for stint in &mut self.stints {
+                if stint
+                    .end_time
+                    .map_or(false, |end| end >= stop.entry_time && end <= exit_time)
+                {
+                    stint.pit_lane_loss = Some(loss);
+                }
+            }
+        }
+    }
+
+    /// Time the laps from pit entry to pit exit took over as many laps at the
+    /// pace of the lap before the stop.
+    fn pit_lane_loss(&self, stop: &PitStop) -> Option<Duration> {
+        let lap_time = |lap: u8| {
+            self.lap_times
+                .get((lap as usize).checked_sub(1)?)
+                .copied()
+                .filter(|time| *time > Duration::ZERO)
+        };
+        if stop.exit_lap < stop.lap {
+            return None;
+        }
+
+        let reference = lap_time(stop.lap.checked_sub(1)?)?;
+        let mut total = Duration::ZERO;
+        for lap in stop.lap..=stop.exit_lap {
+            total += lap_time(lap)?;
+        }
+        let laps = (stop.exit_lap - stop.lap) as u32 + 1;
+        Some(total.saturating_sub(reference * laps))
+    }
+
+    fn update_status(&mut self, status: &CarStatusData, session_time: Duration) {
+        // Stints start once a Lap packet tells the lap they start on
+        let lap = match self.lap {
+            Some(lap) => lap,
+            None => return,
+        };
+        let changed = match self.stints.last() {
+            Some(stint) => {
+                stint.actual_tyre_compound != status.actual_tyre_compound
+                    || stint.visual_tyre_compound != status.visual_tyre_compound
+                    || status.tyres_age_laps < self.tyres_age_laps
+            }
+            None => true,
+        };
+        self.tyres_age_laps = status.tyres_age_laps;
+        if !changed {
+            return;
+        }
+
+        if let Some(stint) = self.stints.last_mut() {
+            stint.end_lap = Some(lap);
+            stint.end_time = Some(session_time);
+        }
+        self.stints.push(Stint {
+            actual_tyre_compound: status.actual_tyre_compound,
+            visual_tyre_compound: status.visual_tyre_compound,
+            start_lap: lap,
+            start_time: session_time,
+            start_tyres_age_laps: status.tyres_age_laps,
+            end_lap: None,
+            end_time: None,
+            pit_lane_time: None,
+            pit_lane_loss: None,
+        });
+    }
+
+    /// Forgets what a flashback to `session_time` undid.
+    fn rewind(&mut self, session_time: Duration) {
+        if self
+            .visit
+            .map_or(false, |visit| visit.entry_time > session_time)
+        {
+            self.visit = None;
+        }
+        self.stops
+            .retain(|stop| stop.entry_time + stop.pit_lane_time <= session_time);
+        self.stints.retain(|stint| stint.start_time <= session_time);
+        if let Some(stint) = self.stints.last_mut() {
+            if stint.end_time.map_or(false, |end| end > session_time) {
+                stint.end_lap = None;
+                stint.end_time = None;
+                stint.pit_lane_time = None;
+                stint.pit_lane_loss = None;
+            }
+        }
+        // Tyre age counts again from the start of the stint still running
+        self.tyres_age_laps = self
+            .stints
+            .last()
+            .map_or(0, |stint| stint.start_tyres_age_laps);
+    }
+
+    fn discrepancies(&self) -> Option<Vec<Discrepancy>> {
+        let classification = self.classification.as_ref()?;
+        let mut discrepancies = Vec::new();
+
+        if self.stops.len() != classification.num_pit_stops as usize {
+            discrepancies.push(Discrepancy::PitStops {
+                recorded: self.stops.len(),
+                classified: classification.num_pit_stops,
+            });
+        }
+
+        let recorded: Vec<ActualTyreCompound> = self
+            .stints
+            .iter()
+            .map(|stint| stint.actual_tyre_compound)
+            .collect();
+        let classified: Vec<ActualTyreCompound> = classification
+            .tyre_stints_actual
+            .iter()
+            .take(classification.num_tyre_stints as usize)
+            .copied()
+            .collect();
+        if recorded != classified {
+            discrepancies.push(Discrepancy::Stints {
+                recorded,
+                classified,
+            });
+        }
+
+        Some(discrepancies)
+    }
+}
+
+/// Records the pit stops and tyre stints of every car, from pit status
+/// changes in Lap packets and tyre changes in Car Status packets.
+///
+/// A stint starts with a different compound or with tyre age going down,
+/// so a set swapped for the same compound counts too. The pit lane loss of a
+/// stop is the time the laps from pit entry to pit exit took over the lap
+/// before the stop, so it is only known once that lap and the stop's laps
+/// were seen complete. A flashback forgets the stops and stints it undid.
+#[derive(Debug, Clone)]
+pub struct StintTracker {
+    tracking: Tracking,
+    cars: Vec<CarStints>,
+}
+
+impl Default for StintTracker {
+    fn default() -> Self {
+        StintTracker {
+            tracking: Tracking::default(),
+            cars: vec![CarStints::default(); TOTAL_CARS],
+        }
+    }
+}
+
+impl StintTracker {
+    pub fn new() -> Self {
+        StintTracker::default()
+    }
+
+    pub fn update(&mut self, packet: &Packet2020) {
+        let header = packet.header();
+        let (progress, _) = self.follow(header);
+        if progress == Progress::Flashback {
+            for car in &mut self.cars {
+                car.rewind(header.session_time);
+            }
+        }
+
+        match packet {
+            Packet2020::Lap(lap) => {
+                for (car, data) in self.cars.iter_mut().zip(&lap.lap_data) {
+                    car.update_lap(data, header.session_time);
+                }
+            }
+            Packet2020::CarStatus(status) => {
+                for (car, data) in self.cars.iter_mut().zip(&status.car_status_data) {
+                    car.update_status(data, header.session_time);
+                }
+            }
+            Packet2020::FinalClassification(classification) => {
+                for (car, data) in self
+                    .cars
+                    .iter_mut()
+                    .zip(&classification.final_classification_data)
+                {
+                    car.classification = Some(data.clone());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Completed pit stops of a car, oldest first.
+    pub fn pit_stops(&self, car: usize) -> &[PitStop] {
+        self.cars
+            .get(car)
+            .map(|car| car.stops.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// Stints of a car, the last one running until the session ends.
+    pub fn stints(&self, car: usize) -> &[Stint] {
+        self.cars
+            .get(car)
+            .map(|car| car.stints.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// Differences to the Final Classification packet, `None` before it
+    /// arrived.
+    pub fn discrepancies(&self, car: usize) -> Option<Vec<Discrepancy>> {
+        self.cars.get(car)?.discrepancies()
+    }
+}
+
+impl Tracked for StintTracker {
+    fn tracking(&mut self) -> &mut Tracking {
+        &mut self.tracking
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::analysis::fixtures::{lap, packet, status};
+    use crate::analysis::stints::{Discrepancy, StintTracker};
+    use crate::f1_2020::car::{ActualTyreCompound, VisualTyreCompound};
+    use crate::f1_2020::lap::PitStatus;
+    use crate::f1_2020::packet::Packet2020;
+    use std::time::Duration;
+
+    fn on_lap(frame: u32, lap_num: u8, pit_status: PitStatus) -> Packet2020 {
+        lap(frame, |laps| {
+            laps[0].current_lap_num = lap_num;
+            laps[0].pit_status = pit_status;
+        })
+    }
+
+    fn timed_lap(frame: u32, lap_num: u8, last_lap_secs: u64, pit_status: PitStatus) -> Packet2020 {
+        lap(frame, |laps| {
+            laps[0].current_lap_num = lap_num;
+            laps[0].last_lap_time = Duration::from_secs(last_lap_secs);
+            laps[0].pit_status = pit_status;
+        })
+    }
+
+    fn tyres(frame: u32, compound: ActualTyreCompound, age: u8) -> Packet2020 {
+        status(frame, |cars| {
+            cars[0].actual_tyre_compound = compound;
+            cars[0].visual_tyre_compound = VisualTyreCompound::Soft;
+            cars[0].tyres_age_laps = age;
+        })
+    }
+
+    #[test]
+    fn records_stops_and_stints() {
+        let mut tracker = StintTracker::new();
+        let packets = vec![
+            on_lap(0, 1, PitStatus::None),
+            tyres(0, ActualTyreCompound::C3, 2),
+            on_lap(20, 12, PitStatus::None),
+            tyres(20, ActualTyreCompound::C3, 13),
+            // 50ms frames: entry at 2s, 1s to the box, 2.5s stationary
+            on_lap(40, 12, PitStatus::Pitting),
+            on_lap(60, 12, PitStatus::PitArea),
+            tyres(70, ActualTyreCompound::C2, 0),
+            on_lap(110, 12, PitStatus::Pitting),
+            on_lap(140, 13, PitStatus::None),
+            tyres(140, ActualTyreCompound::C2, 0),
+            tyres(180, ActualTyreCompound::C2, 17),
+            // A fresh set of the same compound
+            on_lap(200, 30, PitStatus::Pitting),
+            tyres(210, ActualTyreCompound::C2, 0),
+            on_lap(220, 30, PitStatus::None),
+        ];
+        for packet in &packets {
+            tracker.update(packet);
+        }
+
+        let stops = tracker.pit_stops(0);
+        assert_eq!(2, stops.len());
+        assert_eq!(12, stops[0].lap);
+        assert_eq!(Duration::from_secs(2), stops[0].entry_time);
+        assert_eq!(Duration::from_secs(5), stops[0].pit_lane_time);
+        assert_eq!(Duration::from_millis(2500), stops[0].stationary_time);
+
+        let stints = tracker.stints(0);
+        assert_eq!(3, stints.len());
+        assert_eq!(ActualTyreCompound::C3, stints[0].actual_tyre_compound);
+        assert_eq!((1, Some(12)), (stints[0].start_lap, stints[0].end_lap));
+        assert_eq!(2, stints[0].start_tyres_age_laps);
+        assert_eq!(Some(Duration::from_secs(5)), stints[0].pit_lane_time);
+        assert_eq!((12, Some(30)), (stints[1].start_lap, stints[1].end_lap));
+        assert_eq!(Some(Duration::from_secs(1)), stints[1].pit_lane_time);
+        assert_eq!(None, stints[2].end_lap);
+        assert_eq!(None, tracker.discrepancies(0));
+    }
+
+    #[test]
+    fn prices_stops_against_the_lap_before() {
+        let mut tracker = StintTracker::new();
+        // No stint before a Lap packet tells the lap it starts on
+        tracker.update(&tyres(0, ActualTyreCompound::C3, 5));
+        assert!(tracker.stints(0).is_empty());
+
+        let packets = vec![
+            timed_lap(1, 9, 0, PitStatus::None),
+            tyres(1, ActualTyreCompound::C3, 5),
+            timed_lap(2, 10, 91, PitStatus::None),
+            timed_lap(3, 11, 90, PitStatus::None),
+            timed_lap(4, 11, 90, PitStatus::Pitting),
+            timed_lap(5, 11, 90, PitStatus::PitArea),
+            tyres(6, ActualTyreCompound::C2, 0),
+            // The pit lane crosses the line
+            timed_lap(7, 12, 100, PitStatus::Pitting),
+            timed_lap(8, 12, 100, PitStatus::None),
+        ];
+        for packet in &packets {
+            tracker.update(packet);
+        }
+        assert_eq!(9, tracker.stints(0)[0].start_lap);
+        let stop = tracker.pit_stops(0)[0];
+        assert_eq!((11, 12), (stop.lap, stop.exit_lap));
+        assert_eq!(None, stop.pit_lane_loss);
+
+        // Laps 11 and 12 took 100s and 105s against 90s for lap 10
+        tracker.update(&timed_lap(9, 13, 105, PitStatus::None));
+        let loss = Some(Duration::from_secs(25));
+        assert_eq!(loss, tracker.pit_stops(0)[0].pit_lane_loss);
+        assert_eq!(loss, tracker.stints(0)[0].pit_lane_loss);
+        assert_eq!(None, tracker.stints(0)[1].pit_lane_loss);
+    }
+
+    #[test]
+    fn cross_checks_final_classification() {
+        let mut tracker = StintTracker::new();
+        tracker.update(&on_lap(0, 10, PitStatus::None));
+        tracker.update(&tyres(0, ActualTyreCompound::C3, 0));
+        tracker.update(&on_lap(1, 10, PitStatus::Pitting));
+        tracker.update(&on_lap(2, 10, PitStatus::None));
+        tracker.update(&tyres(2, ActualTyreCompound::C1, 0));
+
+        let mut classification = packet(8, 3);
+        if let Packet2020::FinalClassification(classification) = &mut classification {
+            let car = &mut classification.final_classification_data[0];
+            car.num_pit_stops = 1;
+            car.num_tyre_stints = 2;
+            car.tyre_stints_actual[0] = ActualTyreCompound::C3;
+            car.tyre_stints_actual[1] = ActualTyreCompound::C2;
+        }
+        tracker.update(&classification);
+
+        assert_eq!(
+            Some(vec![Discrepancy::Stints {
+                recorded: vec![ActualTyreCompound::C3, ActualTyreCompound::C1],
+                classified: vec![ActualTyreCompound::C3, ActualTyreCompound::C2],
+            }]),
+            tracker.discrepancies(0)
+        );
+    }
+
+    #[test]
+    fn forgets_stops_undone_by_a_flashback() {
+        let mut tracker = StintTracker::new();
+        let packets = vec![
+            on_lap(0, 1, PitStatus::None),
+            tyres(0, ActualTyreCompound::C3, 0),
+            on_lap(20, 10, PitStatus::Pitting),
+            tyres(30, ActualTyreCompound::C2, 0),
+            on_lap(40, 10, PitStatus::None),
+        ];
+        for packet in &packets {
+            tracker.update(packet);
+        }
+        assert_eq!(1, tracker.pit_stops(0).len());
+        assert_eq!(2, tracker.stints(0).len());
+
+        // Back to before the stop, staying out on the same tyres
+        tracker.update(&on_lap(10, 9, PitStatus::None));
+        tracker.update(&tyres(11, ActualTyreCompound::C3, 8));
+        assert!(tracker.pit_stops(0).is_empty());
+        let stints = tracker.stints(0);
+        assert_eq!(1, stints.len());
+        assert_eq!(None, stints[0].end_lap);
+        assert_eq!(None, stints[0].pit_lane_time);
+    }
+
+    #[test]
+    fn keeps_the_stint_through_a_flashback_across_a_lap_line() {
+        let mut tracker = StintTracker::new();
+        let packets = vec![
+            on_lap(0, 1, PitStatus::None),
+            tyres(0, ActualTyreCompound::C3, 2),
+            on_lap(40, 5, PitStatus::None),
+            tyres(40, ActualTyreCompound::C3, 6),
+            // Back two laps on the same tyres
+            on_lap(30, 3, PitStatus::None),
+            tyres(30, ActualTyreCompound::C3, 4),
+        ];
+        for packet in &packets {
+            tracker.update(packet);
+        }
+
+        let stints = tracker.stints(0);
+        assert_eq!(1, stints.len());
+        assert_eq!(1, stints[0].start_lap);
+        assert_eq!(2, stints[0].start_tyres_age_laps);
+        assert_eq!(None, stints[0].end_lap);
+    }
+}
@@ -0,0 +1,295 @@
+use crate::analysis::{Tracked, Tracking};
+use crate::f1_2020::car::TOTAL_CARS;
+use crate::f1_2020::lap::{LapData, PitStatus, ResultStatus};
+use crate::f1_2020::packet::Packet2020;
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// One line of the timing tower.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TowerRow {
+    pub position: u8,
+    pub car_index: usize,
+    pub current_lap_num: u8,
+    /// Time since the leader was where the car is, `None` for the leader,
+    /// retired or disqualified cars, or when not yet known
+    pub gap_to_leader: Option<Duration>,
+    /// Time since the nearest running car ahead was where the car is
+    pub interval: Option<Duration>,
+    pub laps_down: u8,
+    pub pit_status: PitStatus,
+    pub result_status: ResultStatus,
+    pub last_lap_time: Duration,
+    pub total_distance: f32,
+}
+
+impl TowerRow {
+    pub fn is_retired(&self) -> bool {
+        matches!(
+            self.result_status,
+            ResultStatus::Retired | ResultStatus::NotClassified
+        )
+    }
+
+    pub fn is_disqualified(&self) -> bool {
+        self.result_status == ResultStatus::Disqualified
+    }
+}
+
+/// When a car was at which total distance, oldest first.
+#[derive(Debug, Clone, Default)]
+struct Trace {
+    samples: VecDeque<(f32, Duration)>,
+}
+
+impl Trace {
+    fn push(&mut self, distance: f32, session_time: Duration) {
+        // A flashback rewinds the distance, forget the laps undone
+        while matches!(self.samples.back(), Some((last, _)) if *last > distance) {
+            self.samples.pop_back();
+        }
+        self.samples.push_back((distance, session_time));
+    }
+
+    /// Forgets samples no gap can be needed for anymore, keeping one before
+    /// `distance` to interpolate from.
+    fn trim(&mut self, distance: f32) {
+        while self.samples.len() > 1 && self.samples[1].0 <= distance {
+            self.samples.pop_front();
+        }
+    }
+
+    /// Session time the car passed `distance`, interpolated between samples.
+    fn time_at(&self, distance: f32) -> Option<Duration> {
+        let after = self.samples.partition_point(|(d, _)| *d < distance);
+        let (d1, t1) = *self.samples.get(after)?;
+        if d1 == distance {
+            return Some(t1);
+        }
+        let (d0, t0) = *self.samples.get(after.checked_sub(1)?)?;
+
+        let fraction = ((distance - d0) / (d1 - d0)) as f64;
+        Some(t0 + (t1 - t0).mul_f64(fraction))
+    }
+}
+
+/// Positions, gaps and intervals of all running cars, recomputed from each
+/// Lap packet.
+///
+/// Gaps are the time since the car ahead was at the same total distance,
+/// which only needs Lap packets and stays meaningful across laps. Cars with
+/// an `Invalid` or `Inactive` result status, the empty slots of the grid, are
+/// left out. Retired, not classified and disqualified cars keep their row at
+/// their position, without a gap or interval. After a flashback, a car's gap
+/// is unknown until it gets back to where the slowest car was before it.
+#[derive(Debug, Clone)]
+pub struct TimingTower {
+    tracking: Tracking,
+    traces: Vec<Trace>,
+    rows: Vec<TowerRow>,
+}
+
+impl Default for TimingTower {
+    fn default() -> Self {
+        TimingTower {
+            tracking: Tracking::default(),
+            traces: vec![Trace::default(); TOTAL_CARS],
+            rows: Vec::new(),
+        }
+    }
+}
+
+impl TimingTower {
+    pub fn new() -> Self {
+        TimingTower::default()
+    }
+
+    /// Takes in a packet, returning the new rows for a Lap packet.
+    pub fn update(&mut self, packet: &Packet2020) -> Option<&[TowerRow]> {
+        let header = packet.header();
+        self.follow(header);
+        let lap = match packet {
+            Packet2020::Lap(lap) => lap,
+            _ => return None,
+        };
+
+        let mut cars: Vec<(usize, &LapData)> = lap
+            .lap_data
+            .iter()
+            .enumerate()
+            .filter(|(_, lap)| {
+                !matches!(
+                    lap.result_status,
+                    ResultStatus::Invalid | ResultStatus::Inactive
+                )
+            })
+            .collect();
+        cars.sort_by_key(|(_, lap)| lap.car_position);
+
+        for (index, lap) in &cars {
+            if let Some(trace) = self.traces.get_mut(*index) {
+                trace.push(lap.total_distance, header.session_time);
+            }
+        }
+
+        let leader = cars.first().copied();
+        let mut rows = Vec::with_capacity(cars.len());
+        for (i, (index, lap)) in cars.iter().enumerate() {
+            let running = is_running(lap);
+            let gap_to = |ahead: Option<&(usize, &LapData)>| {
+                let (ahead, _) = ahead?;
+                if !running || ahead == index {
+                    return None;
+                }
+                let passed = self.traces.get(*ahead)?.time_at(lap.total_distance)?;
+                Some(header.session_time.saturating_sub(passed))
+            };
+
+            let ahead = cars[..i].iter().rev().find(|(_, lap)| is_running(lap));
+            rows.push(TowerRow {
+                position: lap.car_position,
+                car_index: *index,
+                current_lap_num: lap.current_lap_num,
+                gap_to_leader: gap_to(leader.as_ref()),
+                interval: gap_to(ahead),
+                laps_down: leader.map_or(0, |(_, leader)| laps_down(leader, lap)),
+                pit_status: lap.pit_status,
+                result_status: lap.result_status,
+                last_lap_time: lap.last_lap_time,
+                total_distance: lap.total_distance,
+            });
+        }
+
+        // The slowest running car bounds how far back gaps look
+        let slowest = cars
+            .iter()
+            .filter(|(_, lap)| is_running(lap))
+            .map(|(_, lap)| lap.total_distance)
+            .fold(f32::INFINITY, f32::min);
+        for trace in &mut self.traces {
+            trace.trim(slowest);
+        }
+
+        self.rows = rows;
+        Some(&self.rows)
+    }
+
+    /// Rows of the latest Lap packet, ordered by position.
+    pub fn rows(&self) -> &[TowerRow] {
+        &self.rows
+    }
+}
+
+impl Tracked for TimingTower {
+    fn tracking(&mut self) -> &mut Tracking {
+        &mut self.tracking
+    }
+}
+
+fn is_running(lap: &LapData) -> bool {
+    !matches!(
+        lap.result_status,
+        ResultStatus::Retired | ResultStatus::NotClassified | ResultStatus::Disqualified
+    )
+}
+
+/// Whole laps the leader is ahead by, counting from where the car is on its lap.
+fn laps_down(leader: &LapData, lap: &LapData) -> u8 {
+    let laps = leader.current_lap_num.saturating_sub(lap.current_lap_num);
+    if laps > 0 && lap.lap_distance > leader.lap_distance {
+        laps - 1
+    } else {
+        laps
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::analysis::fixtures::lap;
+    use crate::analysis::timing::TimingTower;
+    use crate::f1_2020::lap::{LapData, ResultStatus};
+    use std::time::Duration;
+
+    /// Car 0 runs at 50 m/s, car 1 at 40 m/s and car 2 is lapped at 25 m/s,
+    /// all on a 1000m track, sampled once a second.
+    fn place(laps: &mut [LapData], second: u32) {
+        let speeds = [50.0, 40.0, 25.0];
+        for (car, speed) in speeds.iter().enumerate() {
+            let lap = &mut laps[car];
+            lap.result_status = ResultStatus::Active;
+            lap.total_distance = speed * second as f32;
+            lap.lap_distance = lap.total_distance % 1000.0;
+            lap.current_lap_num = 1 + (lap.total_distance / 1000.0) as u8;
+            lap.car_position = car as u8 + 1;
+        }
+    }
+
+    #[test]
+    fn computes_gaps_and_laps_down() {
+        let mut tower = TimingTower::new();
+        let mut rows = Vec::new();
+        // Frames are 50ms apart, so every 20th frame is a second
+        for second in 0..=50 {
+            let packet = lap(second * 20, |laps| place(laps, second));
+            rows = tower.update(&packet).unwrap().to_vec();
+        }
+
+        assert_eq!(3, rows.len());
+        assert_eq!(0, rows[0].car_index);
+        assert_eq!(None, rows[0].gap_to_leader);
+        assert_eq!(None, rows[0].interval);
+
+        // Car 1 is at 2000m, which car 0 passed at 40s
+        assert_eq!(Some(Duration::from_secs(10)), rows[1].gap_to_leader);
+        assert_eq!(Some(Duration::from_secs(10)), rows[1].interval);
+        assert_eq!(0, rows[1].laps_down);
+
+        // Car 2 is at 1250m: car 0 passed at 25s and car 1 at 31.25s
+        let gap = rows[2].gap_to_leader.unwrap();
+        assert!((gap.as_secs_f64() - 25.0).abs() < 0.01, "{:?}", gap);
+        let interval = rows[2].interval.unwrap();
+        assert!(
+            (interval.as_secs_f64() - 18.75).abs() < 0.01,
+            "{:?}",
+            interval
+        );
+        assert_eq!(1, rows[2].laps_down);
+    }
+
+    #[test]
+    fn keeps_retired_cars_without_gaps_and_leaves_out_empty_slots() {
+        let mut tower = TimingTower::new();
+        tower.update(&lap(0, |laps| place(laps, 0)));
+        let rows = tower
+            .update(&lap(20, |laps| {
+                place(laps, 1);
+                laps[1].result_status = ResultStatus::Retired;
+                laps[1].car_position = 3;
+                laps[2].car_position = 2;
+            }))
+            .unwrap();
+
+        // Cars 3 and up are zero filled, with an invalid result status
+        assert_eq!(3, rows.len());
+        assert_eq!(
+            vec![0, 2, 1],
+            rows.iter().map(|r| r.car_index).collect::<Vec<_>>()
+        );
+        assert!(rows[2].is_retired());
+        assert_eq!(None, rows[2].gap_to_leader);
+        assert_eq!(None, rows[2].interval);
+        assert!(rows[1].gap_to_leader.is_some());
+
+        // The interval is to the nearest running car ahead
+        let rows = tower
+            .update(&lap(40, |laps| {
+                place(laps, 2);
+                laps[1].result_status = ResultStatus::Retired;
+            }))
+            .unwrap();
+        assert!(rows[1].is_retired());
+        assert_eq!(2, rows[2].car_index);
+        assert_eq!(Some(Duration::from_secs(1)), rows[2].gap_to_leader);
+        assert_eq!(rows[2].gap_to_leader, rows[2].interval);
+    }
+}
@@ -0,0 +1,442 @@
+use crate::analysis::laps::LapHistory;
+use crate::analysis::{Tracked, Tracking};
+use crate::f1_2020::car::ActualTyreCompound;
+use crate::f1_2020::motion::Wheel;
+use crate::f1_2020::packet::Packet2020;
+use crate::f1_2020::session::Track;
+use std::time::Duration;
+
+/// Straight line fitted by least squares.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LinearFit {
+    pub intercept: f64,
+    pub slope: f64,
+}
+
+impl LinearFit {
+    /// Fits `y = intercept + slope * x`, `None` without two distinct `x`.
+    pub fn fit(points: &[(f64, f64)]) -> Option<LinearFit> {
+        let n = points.len() as f64;
+        let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / n;
+        let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / n;
+
+        let (covariance, variance) = points.iter().fold((0.0, 0.0), |(c, v), (x, y)| {
+            (
+                c + (x - mean_x) * (y - mean_y),
+                v + (x - mean_x) * (x - mean_x),
+            )
+        });
+        if variance <= 0.0 {
+            return None;
+        }
+
+        let slope = covariance / variance;
+        Some(LinearFit {
+            intercept: mean_y - slope * mean_x,
+            slope,
+        })
+    }
+
+    pub fn at(&self, x: f64) -> f64 {
+        self.intercept + self.slope * x
+    }
+}
+
+/// How a compound wears and loses pace on a track, against tyre age in laps.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Degradation {
+    pub track: Track,
+    pub actual_tyre_compound: ActualTyreCompound,
+    /// Wear percentage of each wheel at the end of a lap
+    pub wear: Option<Wheel<LinearFit>>,
+    /// Lap time in seconds, from valid laps without a pit stop
+    pub lap_time: Option<LinearFit>,
+    pub wear_samples: usize,
+    pub lap_time_samples: usize,
+}
+
+impl Degradation {
+    /// Wear percentage of each wheel once the tyres are `tyres_age_laps` old.
+    pub fn predicted_wear(&self, tyres_age_laps: u8) -> Option<Wheel<f32>> {
+        let wear = self.wear.as_ref()?;
+        let at = |fit: LinearFit| fit.at(tyres_age_laps as f64).max(0.0) as f32;
+        Some(Wheel {
+            rear_left: at(wear.rear_left),
+            rear_right: at(wear.rear_right),
+            front_left: at(wear.front_left),
+            front_right: at(wear.front_right),
+        })
+    }
+
+    /// Tyre age at which the most worn wheel reaches `threshold` percent,
+    /// `None` if it never does within 255 laps.
+    pub fn wear_limit_age(&self, threshold: f32) -> Option<u8> {
+        let wear = self.wear.as_ref()?;
+        let threshold = threshold as f64;
+        let age = |fit: LinearFit| {
+            if fit.intercept >= threshold {
+                Some(0.0)
+            } else if fit.slope > 0.0 {
+                Some(((threshold - fit.intercept) / fit.slope).ceil())
+            } else {
+                None
+            }
+        };
+
+        [
+            wear.rear_left,
+            wear.rear_right,
+            wear.front_left,
+            wear.front_right,
+        ]
+        .iter()
+        .filter_map(|fit| age(*fit))
+        .fold(None, |min: Option<f64>, age| {
+            Some(min.map_or(age, |min| min.min(age)))
+        })
+        .filter(|age| *age <= u8::MAX as f64)
+        .map(|age| age as u8)
+    }
+
+    /// Seconds each extra lap of tyre age adds to the lap time.
+    pub fn lap_time_loss(&self) -> Option<f64> {
+        self.lap_time.map(|fit| fit.slope)
+    }
+
+    /// Lap time once the tyres are `tyres_age_laps` old.
+    pub fn predicted_lap_time(&self, tyres_age_laps: u8) -> Option<Duration> {
+        let seconds = self.lap_time?.at(tyres_age_laps as f64);
+        Some(Duration::from_secs_f64(seconds.max(0.0)))
+    }
+}
+
+/// Lap a sample was taken at the end of.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct SampledLap {
+    session_uid: Option<u64>,
+    lap_number: u8,
+}
+
+/// Samples of one compound on one track, against tyre age.
+#[derive(Debug, Clone)]
+struct Samples {
+    track: Track,
+    actual_tyre_compound: ActualTyreCompound,
+    wear: Vec<(SampledLap, u8, Wheel<u8>)>,
+    lap_times: Vec<(SampledLap, u8, Duration)>,
+}
+
+impl Samples {
+    /// Drops the samples of laps of the session after `lap_number`.
+    fn forget_after(&mut self, session_uid: Option<u64>, lap_number: u8) {
+        let kept =
+            |lap: &SampledLap| lap.session_uid != session_uid || lap.lap_number <= lap_number;
+        self.wear.retain(|(lap, _, _)| kept(lap));
+        self.lap_times.retain(|(lap, _, _)| kept(lap));
+    }
+
+    fn degradation(&self) -> Degradation {
+        let wheel = |wheel: fn(&Wheel<u8>) -> u8| {
+            let points: Vec<(f64, f64)> = self
+                .wear
+                .iter()
+                .map(|(_, age, wear)| (*age as f64, wheel(wear) as f64))
+                .collect();
+            LinearFit::fit(&points)
+        };
+        let wear = match (
+            wheel(|wear| wear.rear_left),
+            wheel(|wear| wear.rear_right),
+            wheel(|wear| wear.front_left),
+            wheel(|wear| wear.front_right),
+        ) {
+            (Some(rear_left), Some(rear_right), Some(front_left), Some(front_right)) => {
+                Some(Wheel {
+                    rear_left,
+                    rear_right,
+                    front_left,
+                    front_right,
+                })
+            }
+            _ => None,
+        };
+
+        let lap_times: Vec<(f64, f64)> = self
+            .lap_times
+            .iter()
+            .map(|(_, age, time)| (*age as f64, time.as_secs_f64()))
+            .collect();
+
+        Degradation {
+            track: self.track.clone(),
+            actual_tyre_compound: self.actual_tyre_compound,
+            wear,
+            lap_time: LinearFit::fit(&lap_times),
+            wear_samples: self.wear.len(),
+            lap_time_samples: self.lap_times.len(),
+        }
+    }
+}
+
+/// Fits tyre wear and lap time against tyre age for one car, per compound
+/// and track, sampled at the end of each lap it completes.
+///
+/// Samples are kept across sessions, so practice laps at a track inform the
+/// race. Lap times are not corrected for fuel burn.
+#[derive(Debug, Clone)]
+pub struct TyreModel {
+    tracking: Tracking,
+    track: Option<Track>,
+    history: LapHistory,
+    /// Laps of `history` already sampled
+    sampled: usize,
+    tyres: Option<(ActualTyreCompound, Wheel<u8>)>,
+    samples: Vec<Samples>,
+}
+
+impl Default for TyreModel {
+    fn default() -> Self {
+        TyreModel {
+            tracking: Tracking::default(),
+            track: None,
+            history: LapHistory::new(),
+            sampled: 0,
+            tyres: None,
+            samples: Vec::new(),
+        }
+    }
+}
+
+impl TyreModel {
+    /// Model of the player's car.
+    pub fn new() -> Self {
+        TyreModel::default()
+    }
+
+    /// Model of the car at `index`.
+    pub fn for_car(index: usize) -> Self {
+        TyreModel {
+            tracking: Tracking::car(index),
+            ..TyreModel::default()
+        }
+    }
+
+    pub fn update(&mut self, packet: &Packet2020) {
+        let (_, car) = self.follow(packet.header());
+        self.history.update(packet);
+
+        match packet {
+            Packet2020::Session(session) => self.track = Some(session.track_id.clone()),
+            Packet2020::CarStatus(status) => {
+                self.tyres = status
+                    .car_status_data
+                    .get(car)
+                    .map(|status| (status.actual_tyre_compound, status.tyres_wear.clone()));
+            }
+            Packet2020::Lap(_) => self.sample(car),
+            _ => {}
+        }
+    }
+
+    fn sample(&mut self, car: usize) {
+        let laps = self.history.laps(car);
+        if laps.len() < self.sampled {
+            // Laps a flashback undid are sampled again once driven again
+            let session_uid = self.tracking.session_uid();
+            let kept = laps.last().map_or(0, |lap| lap.lap_number);
+            for samples in &mut self.samples {
+                samples.forget_after(session_uid, kept);
+            }
+            self.sampled = laps.len();
+        }
+        let track = match &self.track {
+            Some(track) => track,
+            None => {
+                self.sampled = laps.len();
+                return;
+            }
+        };
+
+        let session_uid = self.tracking.session_uid();
+        for lap in laps.iter().skip(self.sampled) {
+            let (compound, age) = match (lap.actual_tyre_compound, lap.tyres_age_laps) {
+                (Some(compound), Some(age)) => (compound, age),
+                _ => continue,
+            };
+            let samples = match self
+                .samples
+                .iter()
+                .position(|s| &s.track == track && s.actual_tyre_compound == compound)
+            {
+                Some(position) => &mut self.samples[position],
+                None => {
+                    self.samples.push(Samples {
+                        track: track.clone(),
+                        actual_tyre_compound: compound,
+                        wear: Vec::new(),
+                        lap_times: Vec::new(),
+                    });
+                    self.samples.last_mut().unwrap()
+                }
+            };
+
+            let sampled = SampledLap {
+                session_uid,
+                lap_number: lap.lap_number,
+            };
+            if let Some((_, wear)) = &self.tyres {
+                samples.wear.push((sampled, age, wear.clone()));
+            }
+            // The first lap's standing start and pit laps are not
+            // representative of the pace
+            if lap.valid
+                && !lap.pit_in
+                && !lap.pit_out
+                && lap.lap_number > 1
+                && lap.lap_time > Duration::ZERO
+            {
+                samples.lap_times.push((sampled, age, lap.lap_time));
+            }
+        }
+        self.sampled = laps.len();
+    }
+
+    /// Degradation of a compound on a track, `None` before any lap on it.
+    pub fn degradation(
+        &self,
+        track: &Track,
+        actual_tyre_compound: ActualTyreCompound,
+    ) -> Option<Degradation> {
+        self.samples
+            .iter()
+            .find(|s| &s.track == track && s.actual_tyre_compound == actual_tyre_compound)
+            .map(Samples::degradation)
+    }
+
+    /// Degradation of every compound and track sampled so far.
+    pub fn degradations(&self) -> Vec<Degradation> {
+        self.samples.iter().map(Samples::degradation).collect()
+    }
+
+    /// Degradation of the compound the car is on, at the current track.
+    pub fn current(&self) -> Option<Degradation> {
+        let (compound, _) = self.tyres.as_ref()?;
+        self.degradation(self.track.as_ref()?, *compound)
+    }
+}
+
+impl Tracked for TyreModel {
+    fn tracking(&mut self) -> &mut Tracking {
+        &mut self.tracking
+    }
+
+    fn start_over(&mut self) {
+        *self = TyreModel {
+            tracking: self.tracking,
+            samples: std::mem::take(&mut self.samples),
+            ..TyreModel::default()
+        };
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::analysis::fixtures::{lap, packet, status};
+    use crate::analysis::tyres::TyreModel;
+    use crate::f1_2020::car::ActualTyreCompound;
+    use crate::f1_2020::packet::Packet2020;
+    use crate::f1_2020::session::Track;
+    use std::time::Duration;
+
+    fn session(frame_identifier: u32, session_uid: u64, track: Track) -> Packet2020 {
+        let mut packet = packet(1, frame_identifier);
+        if let Packet2020::Session(session) = &mut packet {
+            session.header.session_uid = session_uid;
+            session.track_id = track;
+        }
+        packet
+    }
+
+    /// Five laps on C3s, the front left wearing 4% a lap from 1% and lap
+    /// times growing 0.2s a lap.
+    fn stint(model: &mut TyreModel) {
+        model.update(&session(0, 1, Track::Monza));
+        model.update(&lap(1, |laps| laps[0].current_lap_num = 1));
+        drive(model, 1..=5);
+    }
+
+    /// Completes the laps the tyres are `ages` old at the end of.
+    fn drive(model: &mut TyreModel, ages: std::ops::RangeInclusive<u8>) {
+        for age in ages {
+            let frame = age as u32 * 10;
+            model.update(&status(frame, |cars| {
+                let status = &mut cars[0];
+                status.actual_tyre_compound = ActualTyreCompound::C3;
+                status.tyres_age_laps = age;
+                status.tyres_wear.rear_left = 3 * age;
+                status.tyres_wear.rear_right = 2 * age;
+                status.tyres_wear.front_left = 4 * age + 1;
+                status.tyres_wear.front_right = age;
+            }));
+            model.update(&lap(frame + 1, |laps| {
+                laps[0].current_lap_num = age + 1;
+                laps[0].last_lap_time = Duration::from_millis(90_000 + 200 * age as u64);
+            }));
+        }
+    }
+
+    #[test]
+    fn fits_wear_and_lap_time() {
+        let mut model = TyreModel::new();
+        stint(&mut model);
+
+        let degradation = model.current().unwrap();
+        assert_eq!(Track::Monza, degradation.track);
+        assert_eq!(5, degradation.wear_samples);
+        // The first lap is left out of the pace
+        assert_eq!(4, degradation.lap_time_samples);
+
+        let wear = degradation.predicted_wear(10).unwrap();
+        assert!((wear.front_left - 41.0).abs() < 0.01, "{:?}", wear);
+        assert!((wear.rear_left - 30.0).abs() < 0.01, "{:?}", wear);
+        // The front left reaches 50% at 12.25 laps
+        assert_eq!(Some(13), degradation.wear_limit_age(50.0));
+        assert_eq!(None, degradation.wear_limit_age(5000.0));
+
+        let loss = degradation.lap_time_loss().unwrap();
+        assert!((loss - 0.2).abs() < 0.001, "{}", loss);
+        let time = degradation.predicted_lap_time(10).unwrap();
+        assert!((time.as_secs_f64() - 92.0).abs() < 0.001, "{:?}", time);
+    }
+
+    #[test]
+    fn keeps_samples_per_track_across_sessions() {
+        let mut model = TyreModel::new();
+        stint(&mut model);
+        model.update(&session(100, 2, Track::Silverstone));
+
+        assert!(model.current().is_none());
+        assert!(model
+            .degradation(&Track::Silverstone, ActualTyreCompound::C3)
+            .is_none());
+        let monza = model
+            .degradation(&Track::Monza, ActualTyreCompound::C3)
+            .unwrap();
+        assert_eq!(5, monza.wear_samples);
+        assert_eq!(1, model.degradations().len());
+    }
+
+    #[test]
+    fn samples_laps_driven_again_after_a_flashback_once() {
+        let mut model = TyreModel::new();
+        stint(&mut model);
+        let before = model.current().unwrap();
+
+        // Back into lap 3, which is driven again along with laps 4 and 5
+        model.update(&lap(25, |laps| laps[0].current_lap_num = 3));
+        assert_eq!(2, model.current().unwrap().wear_samples);
+        drive(&mut model, 3..=5);
+
+        assert_eq!(before, model.current().unwrap());
+    }
+}
@@ -0,0 +1,626 @@
+//! Indexed archives of F1 2020 packets for random access into long sessions.
+//!
+//! An archive starts with a 6 byte header: the magic `F1TA` and the format
+//! version as `u16`. Each packet follows as a `u16` length and the datagram
+//! the game sent for it. `ArchiveWriter::finish` then appends the index, one
+//! 31 byte entry per packet:
+//!
+//! | field            | type                                   |
+//! |------------------|----------------------------------------|
+//! | position         | `u64` file offset of the packet        |
+//! | session_uid      | `u64`                                  |
+//! | session_time     | `u64` nanoseconds                      |
+//! | frame_identifier | `u32`                                  |
+//! | packet_id        | `u8`                                   |
+//! | lap              | `u8`, player car's `current_lap_num`   |
+//! | sector           | `u8`, player car's `sector`            |
+//!
+//! and a 20 byte footer: the index's file offset and entry count as `u64`
+//! and the magic `F1TI`. Integers are little endian. Lap and sector are those
+//! of the latest Lap packet of the session, 0 before the first one. An
+//! archive that was never finished is indexed by scanning it when opened.
+
+use crate::decoder::{DecodeMode, Decoder};
+use crate::f1_2020::packet::{
+    encode_f12020, encode_packet_type, packet_type, parse_f12020, Packet2020, PacketID,
+};
+use crate::TelemetryError;
+use byteorder::{ByteOrder, LittleEndian, ReadBytesExt, WriteBytesExt};
+use std::convert::TryFrom;
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufReader, BufWriter, Cursor, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+use std::time::Duration;
+
+const MAGIC: &[u8; 4] = b"F1TA";
+const INDEX_MAGIC: &[u8; 4] = b"F1TI";
+/// Version written to the header of new archives.
+pub const ARCHIVE_VERSION: u16 = 1;
+const HEADER_SIZE: u64 = 6;
+const ENTRY_SIZE: u64 = 31;
+const FOOTER_SIZE: u64 = 20;
+
+/// Where a packet is stored and what it is keyed by.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct IndexEntry {
+    /// File offset of the packet
+    pub position: u64,
+    pub session_uid: u64,
+    pub session_time: Duration,
+    pub frame_identifier: u32,
+    pub packet_id: PacketID,
+    /// Player car's lap when the packet was archived
+    pub lap: u8,
+    /// Player car's sector when the packet was archived, 0 for sector 1
+    pub sector: u8,
+}
+
+/// Follows the player car's lap and sector through a stream of packets.
+#[derive(Debug, Default)]
+struct LapTracker {
+    session_uid: u64,
+    lap: u8,
+    sector: u8,
+}
+
+impl LapTracker {
+    fn entry(&mut self, position: u64, packet: &Packet2020) -> IndexEntry {
+        let header = packet.header();
+        if header.session_uid != self.session_uid {
+            *self = LapTracker {
+                session_uid: header.session_uid,
+                ..LapTracker::default()
+            };
+        }
+        if let Packet2020::Lap(lap) = packet {
+            if let Some(player) = lap.lap_data.get(header.player_car_index as usize) {
+                self.lap = player.current_lap_num;
+                self.sector = player.sector;
+            }
+        }
+
+        IndexEntry {
+            position,
+            session_uid: header.session_uid,
+            session_time: header.session_time,
+            frame_identifier: header.frame_identifier,
+            packet_id: packet.packet_id(),
+            lap: self.lap,
+            sector: self.sector,
+        }
+    }
+}
+
+/// Writes packets to an archive, see the module documentation for the format.
+pub struct ArchiveWriter<W: Write> {
+    writer: W,
+    position: u64,
+    index: Vec<IndexEntry>,
+    laps: LapTracker,
+}
+
+impl ArchiveWriter<BufWriter<File>> {
+    /// Creates an archive file at `path`, failing if the file already exists.
+    pub fn create<P: AsRef<Path>>(path: P) -> Result<Self, TelemetryError> {
+        let file = OpenOptions::new().write(true).create_new(true).open(path)?;
+        ArchiveWriter::new(BufWriter::new(file))
+    }
+}
+
+impl<W: Write> ArchiveWriter<W> {
+    /// Starts an archive on `writer` by writing its header.
+    pub fn new(mut writer: W) -> Result<Self, TelemetryError> {
+        writer.write_all(MAGIC)?;
+        writer.write_u16::<LittleEndian>(ARCHIVE_VERSION)?;
+
+        Ok(ArchiveWriter {
+            writer,
+            position: HEADER_SIZE,
+            index: Vec::new(),
+            laps: LapTracker::default(),
+        })
+    }
+
+    pub fn write(&mut self, packet: &Packet2020) -> Result<(), TelemetryError> {
+        // Index the datagram so keys match what is read back, e.g. the session
+        // time rounded to the f32 the game sends
+        self.write_datagram(&encode_f12020(packet)?)
+    }
+
+    /// Archives a datagram as received, which has to decode as an F1 2020
+    /// packet for it to be indexed.
+    pub fn write_datagram(&mut self, datagram: &[u8]) -> Result<(), TelemetryError> {
+        let packet = parse_datagram(datagram, &mut Decoder::new(DecodeMode::Lenient))?;
+        self.append(&packet, datagram)
+    }
+
+    /// Writes the index, returning the underlying writer.
+    pub fn finish(mut self) -> Result<W, TelemetryError> {
+        let mut index = Vec::with_capacity(self.index.len() * ENTRY_SIZE as usize);
+        for entry in &self.index {
+            write_entry(&mut index, entry)?;
+        }
+        index.write_u64::<LittleEndian>(self.position)?;
+        index.write_u64::<LittleEndian>(self.index.len() as u64)?;
+        index.write_all(INDEX_MAGIC)?;
+        self.writer.write_all(&index)?;
+        self.writer.flush()?;
+
+        Ok(self.writer)
+    }
+
+    fn append(&mut self, packet: &Packet2020, datagram: &[u8]) -> Result<(), TelemetryError> {
+        let length = u16::try_from(datagram.len())
+            .map_err(|_| TelemetryError::Unencodable { field: "datagram" })?;
+        let mut record = Vec::with_capacity(2 + datagram.len());
+        record.write_u16::<LittleEndian>(length)?;
+        record.write_all(datagram)?;
+        self.writer.write_all(&record)?;
+
+        let entry = self.laps.entry(self.position, packet);
+        self.index.push(entry);
+        self.position += record.len() as u64;
+
+        Ok(())
+    }
+}
+
+/// Random access to the packets of an archive through its index.
+pub struct Archive<R> {
+    reader: R,
+    index: Vec<IndexEntry>,
+    finished: bool,
+    decoder: Decoder,
+}
+
+impl Archive<BufReader<File>> {
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, TelemetryError> {
+        Archive::new(BufReader::new(File::open(path)?))
+    }
+}
+
+impl<R: Read + Seek> Archive<R> {
+    /// Checks the header and loads the index, scanning the packets if the
+    /// archive was not finished.
+    pub fn new(mut reader: R) -> Result<Self, TelemetryError> {
+        let mut header = [0; HEADER_SIZE as usize];
+        reader.seek(SeekFrom::Start(0))?;
+        reader.read_exact(&mut header)?;
+        if &header[..4] != MAGIC {
+            return Err(TelemetryError::InvalidCapture {
+                reason: "not an archive file",
+            });
+        }
+        if LittleEndian::read_u16(&header[4..]) != ARCHIVE_VERSION {
+            return Err(TelemetryError::InvalidCapture {
+                reason: "unsupported archive version",
+            });
+        }
+
+        let mut archive = Archive {
+            reader,
+            index: Vec::new(),
+            finished: true,
+            decoder: Decoder::default(),
+        };
+        if !archive.load_index()? {
+            archive.finished = false;
+            archive.scan_index()?;
+        }
+
+        Ok(archive)
+    }
+
+    /// Sets how values outside of a field's enumeration are decoded.
+    pub fn set_decode_mode(&mut self, mode: DecodeMode) {
+        self.decoder = Decoder::new(mode);
+    }
+
+    /// Whether the index was read from the file rather than rebuilt.
+    pub fn finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Entries of all packets in the order they were archived.
+    pub fn index(&self) -> &[IndexEntry] {
+        &self.index
+    }
+
+    /// Sessions in the archive, in the order they were archived.
+    pub fn sessions(&self) -> Vec<u64> {
+        let mut sessions: Vec<u64> = Vec::new();
+        for entry in &self.index {
+            if !sessions.contains(&entry.session_uid) {
+                sessions.push(entry.session_uid);
+            }
+        }
+        sessions
+    }
+
+    /// Position in the index of the first packet of a session at or after
+    /// `session_time`. Flashbacks take the session time back, so this is the
+    /// first time the session got there, before any flashback past it.
+    pub fn find_session_time(&self, session_uid: u64, session_time: Duration) -> Option<usize> {
+        self.find(session_uid, |entry| entry.session_time >= session_time)
+    }
+
+    /// Position in the index of the first packet of a frame of a session.
+    pub fn find_frame(&self, session_uid: u64, frame_identifier: u32) -> Option<usize> {
+        self.find(session_uid, |entry| {
+            entry.frame_identifier == frame_identifier
+        })
+    }
+
+    /// Position in the index of the first packet of a session once the player
+    /// car reached `lap` and `sector`, 0 being sector 1.
+    pub fn find_lap(&self, session_uid: u64, lap: u8, sector: u8) -> Option<usize> {
+        self.find(session_uid, |entry| {
+            (entry.lap, entry.sector) >= (lap, sector)
+        })
+    }
+
+    fn find(&self, session_uid: u64, f: impl Fn(&IndexEntry) -> bool) -> Option<usize> {
+        self.index
+            .iter()
+            .position(|entry| entry.session_uid == session_uid && f(entry))
+    }
+
+    /// Reads the packet at position `i` of the index.
+    pub fn read(&mut self, i: usize) -> Result<Packet2020, TelemetryError> {
+        let entry = self
+            .index
+            .get(i)
+            .copied()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "index out of range"))?;
+        self.reader.seek(SeekFrom::Start(entry.position))?;
+        let datagram = read_record(&mut self.reader)?.ok_or(TelemetryError::InvalidCapture {
+            reason: "index points past the end of the archive",
+        })?;
+
+        parse_datagram(&datagram, &mut self.decoder)
+    }
+
+    /// Iterates over the packets from position `i` of the index onwards.
+    pub fn packets_from(&mut self, i: usize) -> Packets<'_, R> {
+        Packets {
+            archive: self,
+            next: i,
+            packet_id: None,
+        }
+    }
+
+    /// Iterates over all packets of one type.
+    pub fn packets_of(&mut self, packet_id: PacketID) -> Packets<'_, R> {
+        Packets {
+            archive: self,
+            next: 0,
+            packet_id: Some(packet_id),
+        }
+    }
+
+    /// Reads the index through the footer, `false` if there is none.
+    fn load_index(&mut self) -> Result<bool, TelemetryError> {
+        let length = self.reader.seek(SeekFrom::End(0))?;
+        if length < HEADER_SIZE + FOOTER_SIZE {
+            return Ok(false);
+        }
+
+        self.reader.seek(SeekFrom::End(-(FOOTER_SIZE as i64)))?;
+        let mut footer = [0; FOOTER_SIZE as usize];
+        self.reader.read_exact(&mut footer)?;
+        let mut cursor = Cursor::new(&footer[..]);
+        let index_position = cursor.read_u64::<LittleEndian>()?;
+        let count = cursor.read_u64::<LittleEndian>()?;
+        let complete = index_position
+            .checked_add(FOOTER_SIZE)
+            .and_then(|end| count.checked_mul(ENTRY_SIZE)?.checked_add(end))
+            == Some(length);
+        if &footer[16..] != INDEX_MAGIC || !complete {
+            return Ok(false);
+        }
+
+        self.reader.seek(SeekFrom::Start(index_position))?;
+        let mut index = vec![0; (count * ENTRY_SIZE) as usize];
+        self.reader.read_exact(&mut index)?;
+        self.index = index
+            .chunks(ENTRY_SIZE as usize)
+            .map(read_entry)
+            .collect::<Result<_, _>>()?;
+
+        Ok(true)
+    }
+
+    /// Rebuilds the index from the packets, stopping before a partial or
+    /// undecodable tail.
+    fn scan_index(&mut self) -> Result<(), TelemetryError> {
+        self.reader.seek(SeekFrom::Start(HEADER_SIZE))?;
+        let mut decoder = Decoder::new(DecodeMode::Lenient);
+        let mut laps = LapTracker::default();
+        let mut position = HEADER_SIZE;
+
+        while let Some(datagram) = read_record(&mut self.reader)? {
+            let packet = match parse_datagram(&datagram, &mut decoder) {
+                Ok(packet) => packet,
+                Err(_) => break,
+            };
+            self.index.push(laps.entry(position, &packet));
+            position += 2 + datagram.len() as u64;
+        }
+
+        Ok(())
+    }
+}
+
+pub struct Packets<'a, R> {
+    archive: &'a mut Archive<R>,
+    next: usize,
+    packet_id: Option<PacketID>,
+}
+
+impl<R: Read + Seek> Iterator for Packets<'_, R> {
+    type Item = Result<Packet2020, TelemetryError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(entry) = self.archive.index.get(self.next) {
+            let i = self.next;
+            self.next += 1;
+            if self.packet_id.map_or(true, |id| id == entry.packet_id) {
+                return Some(self.archive.read(i));
+            }
+        }
+
+        None
+    }
+}
+
+fn parse_datagram(datagram: &[u8], decoder: &mut Decoder) -> Result<Packet2020, TelemetryError> {
+    let mut cursor = Cursor::new(datagram);
+    let format = cursor.clone().read_u16::<LittleEndian>()?;
+    if format != 2020 {
+        return Err(TelemetryError::UnsupportedFormat { format });
+    }
+
+    parse_f12020(&mut cursor, datagram.len(), decoder)
+}
+
+/// Reads a length prefixed datagram, `None` at the end of the file or
+/// before a partial record.
+fn read_record<R: Read>(reader: &mut R) -> Result<Option<Vec<u8>>, TelemetryError> {
+    let length = match reader.read_u16::<LittleEndian>() {
+        Ok(length) => length,
+        Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+    let mut datagram = vec![0; length as usize];
+    match reader.read_exact(&mut datagram) {
+        Ok(()) => Ok(Some(datagram)),
+        Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+fn write_entry(bytes: &mut Vec<u8>, entry: &IndexEntry) -> Result<(), TelemetryError> {
+    let session_time = u64::try_from(entry.session_time.as_nanos()).unwrap_or(u64::MAX);
+    bytes.write_u64::<LittleEndian>(entry.position)?;
+    bytes.write_u64::<LittleEndian>(entry.session_uid)?;
+    bytes.write_u64::<LittleEndian>(session_time)?;
+    bytes.write_u32::<LittleEndian>(entry.frame_identifier)?;
+    bytes.write_u8(encode_packet_type(entry.packet_id))?;
+    bytes.write_u8(entry.lap)?;
+    bytes.write_u8(entry.sector)?;
+
+    Ok(())
+}
+
+fn read_entry(bytes: &[u8]) -> Result<IndexEntry, TelemetryError> {
+    let mut cursor = Cursor::new(bytes);
+
+    Ok(IndexEntry {
+        position: cursor.read_u64::<LittleEndian>()?,
+        session_uid: cursor.read_u64::<LittleEndian>()?,
+        session_time: Duration::from_nanos(cursor.read_u64::<LittleEndian>()?),
+        frame_identifier: cursor.read_u32::<LittleEndian>()?,
+        packet_id: packet_type(cursor.read_u8()?)?,
+        lap: cursor.read_u8()?,
+        sector: cursor.read_u8()?,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::archive::{Archive, ArchiveWriter};
+    use crate::f1_2020::event::{Event, PacketEventData};
+    use crate::f1_2020::header::PacketHeader;
+    use crate::f1_2020::lap::{DriverStatus, LapData, PacketLapData, PitStatus, ResultStatus};
+    use crate::f1_2020::packet::{Packet2020, PacketID};
+    use crate::TelemetryError;
+    use std::io::Cursor;
+    use std::time::Duration;
+
+    fn header(session_uid: u64, packet_id: u8, frame_identifier: u32) -> PacketHeader {
+        PacketHeader {
+            packet_format: 2020,
+            major_version: 1,
+            minor_version: 0,
+            packet_version: 1,
+            packet_id,
+            session_uid,
+            session_time: Duration::from_millis(100) * frame_identifier,
+            frame_identifier,
+            player_car_index: 1,
+            secondary_player_car_index: 255,
+        }
+    }
+
+    fn lap(current_lap_num: u8, sector: u8) -> LapData {
+        LapData {
+            last_lap_time: Duration::ZERO,
+            current_lap_time: Duration::ZERO,
+            sector_1_time: Duration::ZERO,
+            sector_2_time: Duration::ZERO,
+            best_lap_time: Duration::ZERO,
+            best_lap_num: 0,
+            best_lap_sector_1_time: Duration::ZERO,
+            best_lap_sector_2_time: Duration::ZERO,
+            best_lap_sector_3_time: Duration::ZERO,
+            best_overall_sector_1_time: Duration::ZERO,
+            best_overall_sector_1_lap_num: 0,
+            best_overall_sector_2_time: Duration::ZERO,
+            best_overall_sector_2_lap_num: 0,
+            best_overall_sector_3_time: Duration::ZERO,
+            best_overall_sector_3_lap_num: 0,
+            lap_distance: 0.0,
+            total_distance: 0.0,
+            safety_car_delta: 0.0,
+            car_position: 1,
+            current_lap_num,
+            pit_status: PitStatus::None,
+            sector,
+            current_lap_invalid: false,
+            penalties: 0,
+            grid_position: 1,
+            driver_status: DriverStatus::OnTrack,
+            result_status: ResultStatus::Active,
+        }
+    }
+
+    /// Writes 30 frames with the player car taking three frames per sector,
+    /// starting on lap 1, and an Event packet every tenth frame.
+    fn write_session<W: std::io::Write>(writer: &mut ArchiveWriter<W>, session_uid: u64) {
+        for frame in 0..30 {
+            let player = lap(1 + frame as u8 / 9, (frame as u8 / 3) % 3);
+            writer
+                .write(&Packet2020::Lap(PacketLapData {
+                    header: header(session_uid, 2, frame),
+                    lap_data: vec![lap(1, 0), player]
+                        .into_iter()
+                        .chain((2..22).map(|_| lap(1, 0)))
+                        .collect(),
+                }))
+                .unwrap();
+            if frame % 10 == 0 {
+                writer
+                    .write(&Packet2020::Event(PacketEventData {
+                        header: header(session_uid, 3, frame),
+                        event: Event::SessionStarted,
+                    }))
+                    .unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn finds_packets_by_lap_time_frame_and_type() {
+        let mut writer = ArchiveWriter::new(Cursor::new(Vec::new())).unwrap();
+        write_session(&mut writer, 42);
+        let mut archive = Archive::new(writer.finish().unwrap()).unwrap();
+        assert!(archive.finished());
+        assert_eq!(33, archive.index().len());
+
+        // Lap 2, sector 2
+        let i = archive.find_lap(42, 2, 1).unwrap();
+        assert_eq!(12, archive.index()[i].frame_identifier);
+        match archive.read(i).unwrap() {
+            Packet2020::Lap(packet) => assert_eq!(12, packet.header.frame_identifier),
+            p => panic!("Unexpected packet {:?}", p.packet_id()),
+        }
+
+        let i = archive
+            .find_session_time(42, Duration::from_millis(2050))
+            .unwrap();
+        assert_eq!(21, archive.index()[i].frame_identifier);
+        let i = archive.find_frame(42, 20).unwrap();
+        assert_eq!(PacketID::LapData, archive.index()[i].packet_id);
+        assert_eq!(11, archive.packets_from(i).count());
+        assert_eq!(None, archive.find_lap(42, 5, 0));
+
+        let events: Vec<u32> = archive
+            .packets_of(PacketID::Event)
+            .map(|packet| packet.unwrap().header().frame_identifier)
+            .collect();
+        assert_eq!(vec![0, 10, 20], events);
+    }
+
+    #[test]
+    fn finds_packets_per_session() {
+        let mut writer = ArchiveWriter::new(Cursor::new(Vec::new())).unwrap();
+        write_session(&mut writer, 42);
+        write_session(&mut writer, 43);
+        let archive = Archive::new(writer.finish().unwrap()).unwrap();
+        assert_eq!(vec![42, 43], archive.sessions());
+
+        // Frames, session times and laps start over with the second session
+        let i = archive.find_frame(43, 5).unwrap();
+        assert_eq!(43, archive.index()[i].session_uid);
+        assert_eq!(5, archive.index()[i].frame_identifier);
+        let i = archive.find_session_time(43, Duration::ZERO).unwrap();
+        assert_eq!(33, i);
+        let i = archive.find_lap(43, 1, 0).unwrap();
+        assert_eq!(33, i);
+        let i = archive.find_lap(42, 4, 0).unwrap();
+        assert_eq!(42, archive.index()[i].session_uid);
+        assert_eq!(27, archive.index()[i].frame_identifier);
+        assert_eq!(None, archive.find_frame(44, 5));
+    }
+
+    #[test]
+    fn finds_session_time_before_a_flashback() {
+        let mut writer = ArchiveWriter::new(Cursor::new(Vec::new())).unwrap();
+        write_session(&mut writer, 42);
+        // Flashback to frame 15, then driving on
+        for frame in 15..20 {
+            writer
+                .write(&Packet2020::Event(PacketEventData {
+                    header: header(42, 3, frame),
+                    event: Event::SessionStarted,
+                }))
+                .unwrap();
+        }
+        let archive = Archive::new(writer.finish().unwrap()).unwrap();
+
+        let i = archive
+            .find_session_time(42, Duration::from_millis(1500))
+            .unwrap();
+        assert_eq!(15, archive.index()[i].frame_identifier);
+        assert!(i < 33);
+    }
+
+    #[test]
+    fn rebuilds_index_of_unfinished_archive() {
+        let mut finished = ArchiveWriter::new(Cursor::new(Vec::new())).unwrap();
+        write_session(&mut finished, 42);
+        let finished = Archive::new(finished.finish().unwrap()).unwrap();
+
+        let mut bytes = Vec::new();
+        write_session(&mut ArchiveWriter::new(&mut bytes).unwrap(), 42);
+        // Partial record left behind by a crash
+        bytes.extend_from_slice(&[200, 0, 1, 2]);
+
+        let mut archive = Archive::new(Cursor::new(bytes)).unwrap();
+        assert!(!archive.finished());
+        assert_eq!(finished.index(), archive.index());
+        assert!(archive.read(32).is_ok());
+    }
+
+    #[test]
+    fn rebuilds_index_behind_an_invalid_footer() {
+        let mut writer = ArchiveWriter::new(Cursor::new(Vec::new())).unwrap();
+        write_session(&mut writer, 42);
+        let mut bytes = writer.finish().unwrap().into_inner();
+        let finished = Archive::new(Cursor::new(bytes.clone())).unwrap();
+
+        let footer = bytes.len() - 20;
+        bytes[footer..footer + 8].copy_from_slice(&u64::MAX.to_le_bytes());
+        let archive = Archive::new(Cursor::new(bytes)).unwrap();
+        assert!(!archive.finished());
+        assert_eq!(finished.index(), archive.index());
+    }
+
+    #[test]
+    fn rejects_other_files() {
+        assert!(matches!(
+            Archive::new(Cursor::new(b"F1TC\x01\x00".to_vec())),
+            Err(TelemetryError::InvalidCapture { .. })
+        ));
+    }
+}
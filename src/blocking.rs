@@ -0,0 +1,179 @@
+use crate::capture::Recorder;
+use crate::client::{decode, record, socket_addr};
+use crate::{packet, DecodeMode, Protocol, TelemetryError};
+use std::io::ErrorKind;
+use std::net::{SocketAddr, UdpSocket};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Telemetry client receiving on a blocking `std::net::UdpSocket`.
+pub struct Telemetry {
+    socket: UdpSocket,
+    protocol: Protocol,
+    mode: DecodeMode,
+    substitutions: AtomicUsize,
+    recorder: Option<Mutex<Recorder>>,
+}
+
+impl Telemetry {
+    pub fn new(ip: &str, port: u16) -> Result<Self, TelemetryError> {
+        Telemetry::with_protocol(ip, port, Protocol::Standard)
+    }
+
+    pub fn with_protocol(ip: &str, port: u16, protocol: Protocol) -> Result<Self, TelemetryError> {
+        let socket = UdpSocket::bind(socket_addr(ip, port)?)?;
+
+        Ok(Telemetry {
+            socket,
+            protocol,
+            mode: DecodeMode::Strict,
+            substitutions: AtomicUsize::new(0),
+            recorder: None,
+        })
+    }
+
+    /// Address the socket is bound to, which tells the port picked for port 0.
+    pub fn local_addr(&self) -> Result<SocketAddr, TelemetryError> {
+        Ok(self.socket.local_addr()?)
+    }
+
+    /// Sets how long `next` waits for a datagram, `None` waits forever.
+    pub fn set_read_timeout(&self, timeout: Option<Duration>) -> Result<(), TelemetryError> {
+        self.socket.set_read_timeout(timeout)?;
+        Ok(())
+    }
+
+    /// Sets how values outside of a field's enumeration are decoded.
+    pub fn set_decode_mode(&mut self, mode: DecodeMode) {
+        self.mode = mode;
+    }
+
+    /// Total number of values decoded as `Unknown` in lenient mode.
+    pub fn substitutions(&self) -> usize {
+        self.substitutions.load(Ordering::Relaxed)
+    }
+
+    /// Writes every datagram received from now on to `recorder`, before it
+    /// is decoded.
+    pub fn set_recorder(&mut self, recorder: Recorder) {
+        self.recorder = Some(Mutex::new(recorder));
+    }
+
+    /// Waits for the next datagram. Fails with `TelemetryError::Io` of kind
+    /// `WouldBlock` or `TimedOut` when the read timeout elapses.
+    pub fn next(&self) -> Result<packet::Packet, TelemetryError> {
+        let mut buf = [0; 2048];
+        let (size, source) = self.socket.recv_from(&mut buf)?;
+
+        record(&self.recorder, source, &buf[..size])?;
+        decode(&buf[..size], self.protocol, self.mode, &self.substitutions)
+    }
+
+    /// Iterates over received packets until the read timeout elapses.
+    pub fn iter(&self) -> Iter<'_> {
+        Iter { telemetry: self }
+    }
+}
+
+pub struct Iter<'a> {
+    telemetry: &'a Telemetry,
+}
+
+impl Iterator for Iter<'_> {
+    type Item = Result<packet::Packet, TelemetryError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.telemetry.next() {
+            Err(TelemetryError::Io(e))
+                if e.kind() == ErrorKind::WouldBlock || e.kind() == ErrorKind::TimedOut =>
+            {
+                None
+            }
+            result => Some(result),
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a Telemetry {
+    type Item = Result<packet::Packet, TelemetryError>;
+    type IntoIter = Iter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::blocking::Telemetry;
+    use crate::capture::{CaptureReader, Recorder};
+    use crate::TelemetryError;
+    use byteorder::{LittleEndian, WriteBytesExt};
+    use std::net::UdpSocket;
+    use std::time::Duration;
+
+    #[test]
+    fn test_telemetry_iter() {
+        let client = Telemetry::new("127.0.0.1", 0).unwrap();
+        let target = client.local_addr().unwrap();
+        client
+            .set_read_timeout(Some(Duration::from_millis(200)))
+            .unwrap();
+
+        let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+        for format in [2016u16, 2017] {
+            let mut send_buf = Vec::with_capacity(24);
+            send_buf.write_u16::<LittleEndian>(format).unwrap();
+            send_buf.resize(24, 0);
+            socket.send_to(&send_buf, target).unwrap();
+        }
+
+        let formats: Vec<u16> = client
+            .iter()
+            .map(|result| match result {
+                Err(TelemetryError::UnsupportedFormat { format }) => format,
+                r => panic!("Unexpected result {:?}", r),
+            })
+            .collect();
+        assert_eq!(vec![2016, 2017], formats);
+    }
+
+    #[test]
+    fn test_telemetry_next_timeout() {
+        let client = Telemetry::new("127.0.0.1", 0).unwrap();
+        client
+            .set_read_timeout(Some(Duration::from_millis(10)))
+            .unwrap();
+
+        assert!(matches!(client.next(), Err(TelemetryError::Io(_))));
+    }
+
+    #[test]
+    fn test_telemetry_records_datagrams() {
+        let path = std::env::temp_dir().join(format!(
+            "f1-telemetry-{}-blocking.capture",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&path);
+
+        let mut client = Telemetry::new("127.0.0.1", 0).unwrap();
+        client.set_recorder(Recorder::create(&path).unwrap());
+
+        let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+        socket
+            .send_to(&[0xdf, 0x07, 1], client.local_addr().unwrap())
+            .unwrap();
+        assert!(client.next().is_err());
+
+        let records: Vec<_> = CaptureReader::open(&path)
+            .unwrap()
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(1, records.len());
+        assert_eq!(socket.local_addr().unwrap(), records[0].source);
+        assert_eq!(vec![0xdf, 0x07, 1], records[0].datagram);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,397 @@
+//! Append-only capture files of raw datagrams.
+//!
+//! A capture starts with an 18 byte header: the magic `F1TC`, the format
+//! version as `u16` and the wall clock start time as `u64` seconds and `u32`
+//! nanoseconds since the Unix epoch. One record per datagram follows:
+//!
+//! | field   | type                                        |
+//! |---------|---------------------------------------------|
+//! | offset  | `u64` nanoseconds since the start time      |
+//! | family  | `u8`, 4 or 6                                |
+//! | address | 4 or 16 bytes                               |
+//! | port    | `u16`                                       |
+//! | length  | `u16`                                       |
+//! | data    | `length` bytes of the datagram              |
+//!
+//! Integers are little endian. Every record is written with a single
+//! `write_all`, so a crash leaves at most one partial record at the end of the
+//! file. Readers stop before it and `Recorder::append` truncates it.
+
+use crate::error::TelemetryError;
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use std::convert::TryFrom;
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufReader, Read, Seek, SeekFrom, Write};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::path::Path;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+const MAGIC: &[u8; 4] = b"F1TC";
+/// Version written to the header of new captures.
+pub const CAPTURE_VERSION: u16 = 1;
+const HEADER_SIZE: usize = 18;
+
+/// A datagram as it was received.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Record {
+    /// Arrival time relative to the capture's start time
+    pub offset: Duration,
+    /// Address the datagram was sent from
+    pub source: SocketAddr,
+    pub datagram: Vec<u8>,
+}
+
+/// Writes datagrams to a capture, see the module documentation for the format.
+pub struct Recorder {
+    writer: Box<dyn Write + Send>,
+    start_time: SystemTime,
+    base_offset: Duration,
+    started: Instant,
+}
+
+impl Recorder {
+    /// Creates a capture file at `path`, failing if the file already exists.
+    pub fn create<P: AsRef<Path>>(path: P) -> Result<Self, TelemetryError> {
+        let file = OpenOptions::new().write(true).create_new(true).open(path)?;
+        Recorder::new(file)
+    }
+
+    /// Starts a capture on `writer` by writing its header.
+    pub fn new<W: Write + Send + 'static>(mut writer: W) -> Result<Self, TelemetryError> {
+        let start_time = SystemTime::now();
+        write_header(&mut writer, start_time)?;
+        writer.flush()?;
+
+        Ok(Recorder {
+            writer: Box::new(writer),
+            start_time,
+            base_offset: Duration::ZERO,
+            started: Instant::now(),
+        })
+    }
+
+    /// Reopens the capture at `path` to continue recording into it. A partial
+    /// record left behind by a crash is cut off first.
+    pub fn append<P: AsRef<Path>>(path: P) -> Result<Self, TelemetryError> {
+        let mut file = OpenOptions::new().read(true).write(true).open(path)?;
+
+        let mut reader = CaptureReader::new(BufReader::new(&mut file))?;
+        while reader.next_record()?.is_some() {}
+        let start_time = reader.start_time();
+        let end = reader.position();
+        drop(reader);
+
+        file.set_len(end)?;
+        file.seek(SeekFrom::Start(end))?;
+
+        Ok(Recorder {
+            writer: Box::new(file),
+            start_time,
+            base_offset: SystemTime::now()
+                .duration_since(start_time)
+                .unwrap_or_default(),
+            started: Instant::now(),
+        })
+    }
+
+    pub fn start_time(&self) -> SystemTime {
+        self.start_time
+    }
+
+    /// Appends a datagram that arrived just now from `source`.
+    pub fn record(&mut self, source: SocketAddr, datagram: &[u8]) -> Result<(), TelemetryError> {
+        let offset = self.base_offset + self.started.elapsed();
+        self.write(offset, source, datagram)
+    }
+
+    /// Appends a record as is, e.g. when copying from another capture.
+    pub fn write_record(&mut self, record: &Record) -> Result<(), TelemetryError> {
+        self.write(record.offset, record.source, &record.datagram)
+    }
+
+    fn write(
+        &mut self,
+        offset: Duration,
+        source: SocketAddr,
+        datagram: &[u8],
+    ) -> Result<(), TelemetryError> {
+        let length = u16::try_from(datagram.len())
+            .map_err(|_| TelemetryError::Unencodable { field: "datagram" })?;
+
+        let mut bytes = Vec::with_capacity(31 + datagram.len());
+        bytes.write_u64::<LittleEndian>(u64::try_from(offset.as_nanos()).unwrap_or(u64::MAX))?;
+        match source.ip() {
+            IpAddr::V4(ip) => {
+                bytes.write_u8(4)?;
+                bytes.write_all(&ip.octets())?;
+            }
+            IpAddr::V6(ip) => {
+                bytes.write_u8(6)?;
+                bytes.write_all(&ip.octets())?;
+            }
+        }
+        bytes.write_u16::<LittleEndian>(source.port())?;
+        bytes.write_u16::<LittleEndian>(length)?;
+        bytes.write_all(datagram)?;
+
+        self.writer.write_all(&bytes)?;
+        self.writer.flush()?;
+
+        Ok(())
+    }
+}
+
+fn write_header<W: Write>(writer: &mut W, start_time: SystemTime) -> Result<(), TelemetryError> {
+    let since_epoch = start_time.duration_since(UNIX_EPOCH).unwrap_or_default();
+
+    let mut bytes = Vec::with_capacity(HEADER_SIZE);
+    bytes.write_all(MAGIC)?;
+    bytes.write_u16::<LittleEndian>(CAPTURE_VERSION)?;
+    bytes.write_u64::<LittleEndian>(since_epoch.as_secs())?;
+    bytes.write_u32::<LittleEndian>(since_epoch.subsec_nanos())?;
+    writer.write_all(&bytes)?;
+
+    Ok(())
+}
+
+/// Reads the records of a capture in the order they were written.
+pub struct CaptureReader<R> {
+    reader: R,
+    version: u16,
+    start_time: SystemTime,
+    position: u64,
+    truncated: bool,
+}
+
+impl CaptureReader<BufReader<File>> {
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, TelemetryError> {
+        CaptureReader::new(BufReader::new(File::open(path)?))
+    }
+}
+
+impl<R: Read> CaptureReader<R> {
+    /// Reads the capture header from `reader`.
+    pub fn new(mut reader: R) -> Result<Self, TelemetryError> {
+        let mut header = [0; HEADER_SIZE];
+        if read_full(&mut reader, &mut header)? < HEADER_SIZE || &header[..4] != MAGIC {
+            return Err(TelemetryError::InvalidCapture {
+                reason: "missing capture header",
+            });
+        }
+
+        let mut fields = &header[4..];
+        let version = fields.read_u16::<LittleEndian>()?;
+        if version == 0 || version > CAPTURE_VERSION {
+            return Err(TelemetryError::InvalidCapture {
+                reason: "unsupported capture version",
+            });
+        }
+        let secs = fields.read_u64::<LittleEndian>()?;
+        let nanos = fields.read_u32::<LittleEndian>()?;
+
+        Ok(CaptureReader {
+            reader,
+            version,
+            start_time: UNIX_EPOCH + Duration::new(secs, nanos),
+            position: HEADER_SIZE as u64,
+            truncated: false,
+        })
+    }
+
+    pub fn version(&self) -> u16 {
+        self.version
+    }
+
+    pub fn start_time(&self) -> SystemTime {
+        self.start_time
+    }
+
+    /// Byte offset just past the last complete record read so far.
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Whether reading stopped at a partial record at the end of the capture.
+    pub fn truncated(&self) -> bool {
+        self.truncated
+    }
+
+    /// Reads the next record. Returns `None` at the end of the capture,
+    /// including when only a partial record is left.
+    pub fn next_record(&mut self) -> Result<Option<Record>, TelemetryError> {
+        if self.truncated {
+            return Ok(None);
+        }
+
+        let mut head = [0; 9];
+        match read_full(&mut self.reader, &mut head)? {
+            0 => return Ok(None),
+            9 => {}
+            _ => return self.truncate(),
+        }
+        let mut fields = &head[..];
+        let offset = Duration::from_nanos(fields.read_u64::<LittleEndian>()?);
+        let address_size = match fields.read_u8()? {
+            4 => 4,
+            6 => 16,
+            _ => {
+                return Err(TelemetryError::InvalidCapture {
+                    reason: "unknown address family",
+                })
+            }
+        };
+
+        let mut address = [0; 20];
+        let address = &mut address[..address_size + 4];
+        if read_full(&mut self.reader, address)? < address.len() {
+            return self.truncate();
+        }
+        let ip = if address_size == 4 {
+            IpAddr::V4(Ipv4Addr::from(<[u8; 4]>::try_from(&address[..4]).unwrap()))
+        } else {
+            IpAddr::V6(Ipv6Addr::from(
+                <[u8; 16]>::try_from(&address[..16]).unwrap(),
+            ))
+        };
+        let mut fields = &address[address_size..];
+        let port = fields.read_u16::<LittleEndian>()?;
+        let length = fields.read_u16::<LittleEndian>()? as usize;
+
+        let mut datagram = vec![0; length];
+        if read_full(&mut self.reader, &mut datagram)? < length {
+            return self.truncate();
+        }
+
+        self.position += (head.len() + address.len() + length) as u64;
+        Ok(Some(Record {
+            offset,
+            source: SocketAddr::new(ip, port),
+            datagram,
+        }))
+    }
+
+    fn truncate(&mut self) -> Result<Option<Record>, TelemetryError> {
+        self.truncated = true;
+        Ok(None)
+    }
+}
+
+impl<R: Read> Iterator for CaptureReader<R> {
+    type Item = Result<Record, TelemetryError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_record().transpose()
+    }
+}
+
+/// Fills `buf` as far as the reader allows, returning the number of bytes read.
+fn read_full<R: Read>(reader: &mut R, buf: &mut [u8]) -> io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+
+    Ok(filled)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::capture::{CaptureReader, Record, Recorder, CAPTURE_VERSION};
+    use crate::TelemetryError;
+    use std::fs::{self, OpenOptions};
+    use std::net::SocketAddr;
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    fn capture_path(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "f1-telemetry-{}-{}.capture",
+            std::process::id(),
+            name
+        ));
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    fn record(offset_ms: u64, source: &str, datagram: &[u8]) -> Record {
+        Record {
+            offset: Duration::from_millis(offset_ms),
+            source: source.parse().unwrap(),
+            datagram: datagram.to_vec(),
+        }
+    }
+
+    #[test]
+    fn records_round_trip() {
+        let path = capture_path("round-trip");
+        let records = vec![
+            record(0, "192.168.1.20:57125", &[0xe4, 0x07, 1, 2, 3]),
+            record(16, "[::1]:20777", &[]),
+            record(33, "10.0.0.2:1", &[0xff; 1464]),
+        ];
+
+        let mut recorder = Recorder::create(&path).unwrap();
+        for r in &records {
+            recorder.write_record(r).unwrap();
+        }
+        let source: SocketAddr = "127.0.0.1:9".parse().unwrap();
+        recorder.record(source, &[7, 7]).unwrap();
+
+        let mut reader = CaptureReader::open(&path).unwrap();
+        assert_eq!(CAPTURE_VERSION, reader.version());
+        assert_eq!(recorder.start_time(), reader.start_time());
+
+        let read: Vec<Record> = reader.by_ref().map(Result::unwrap).collect();
+        assert_eq!(records[..], read[..3]);
+        assert_eq!(source, read[3].source);
+        assert_eq!(vec![7, 7], read[3].datagram);
+        assert!(!reader.truncated());
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn truncated_tail_is_recovered() {
+        let path = capture_path("truncated");
+        let mut recorder = Recorder::create(&path).unwrap();
+        recorder
+            .write_record(&record(1, "127.0.0.1:20777", &[1; 24]))
+            .unwrap();
+        recorder
+            .write_record(&record(2, "127.0.0.1:20777", &[2; 24]))
+            .unwrap();
+        drop(recorder);
+
+        // Cut the second record short, as a crash during the write would.
+        let size = fs::metadata(&path).unwrap().len();
+        let file = OpenOptions::new().write(true).open(&path).unwrap();
+        file.set_len(size - 10).unwrap();
+
+        let mut reader = CaptureReader::open(&path).unwrap();
+        assert_eq!(1, reader.by_ref().count());
+        assert!(reader.truncated());
+
+        let mut recorder = Recorder::append(&path).unwrap();
+        recorder
+            .write_record(&record(3, "127.0.0.1:20777", &[3; 24]))
+            .unwrap();
+
+        let mut reader = CaptureReader::open(&path).unwrap();
+        let datagrams: Vec<u8> = reader.by_ref().map(|r| r.unwrap().datagram[0]).collect();
+        assert_eq!(vec![1, 3], datagrams);
+        assert!(!reader.truncated());
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn rejects_files_without_header() {
+        let result = CaptureReader::new(&b"F1TX\x01\x00"[..]);
+        assert!(matches!(result, Err(TelemetryError::InvalidCapture { .. })));
+    }
+}
@@ -0,0 +1,41 @@
+use crate::capture::Recorder;
+use crate::packet::Packet;
+use crate::{parse_with, DecodeMode, Decoder, Protocol, TelemetryError};
+use std::io::{Error, ErrorKind};
+use std::net::{IpAddr, SocketAddr};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, PoisonError};
+
+pub(crate) fn socket_addr(ip: &str, port: u16) -> Result<SocketAddr, TelemetryError> {
+    let ip = IpAddr::from_str(ip).map_err(|e| Error::new(ErrorKind::InvalidInput, e))?;
+    Ok(SocketAddr::new(ip, port))
+}
+
+/// Decodes a received datagram, adding lenient substitutions to `substitutions`.
+pub(crate) fn decode(
+    bytes: &[u8],
+    protocol: Protocol,
+    mode: DecodeMode,
+    substitutions: &AtomicUsize,
+) -> Result<Packet, TelemetryError> {
+    let mut decoder = Decoder::new(mode);
+    let result = parse_with(bytes, protocol, &mut decoder);
+    substitutions.fetch_add(decoder.substitutions(), Ordering::Relaxed);
+    result
+}
+
+/// Appends a received datagram to the client's recorder, if one is set.
+pub(crate) fn record(
+    recorder: &Option<Mutex<Recorder>>,
+    source: SocketAddr,
+    datagram: &[u8],
+) -> Result<(), TelemetryError> {
+    match recorder {
+        Some(recorder) => recorder
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .record(source, datagram),
+        None => Ok(()),
+    }
+}
@@ -0,0 +1,95 @@
+use crate::error::TelemetryError;
+
+/// How a value outside of a field's enumeration is handled.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum DecodeMode {
+    /// Reject the whole packet with `TelemetryError::InvalidEnumValue`.
+    Strict,
+    /// Decode the value as the enum's `Unknown` variant and keep going.
+    Lenient,
+}
+
+/// Decoding options threaded through the parsers of a single packet.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Decoder {
+    mode: DecodeMode,
+    substitutions: usize,
+}
+
+impl Decoder {
+    pub fn new(mode: DecodeMode) -> Self {
+        Decoder {
+            mode,
+            substitutions: 0,
+        }
+    }
+
+    pub fn mode(&self) -> DecodeMode {
+        self.mode
+    }
+
+    /// Number of values decoded as `Unknown` because of lenient mode.
+    pub fn substitutions(&self) -> usize {
+        self.substitutions
+    }
+
+    pub(crate) fn unknown<T, V>(
+        &mut self,
+        field: &'static str,
+        value: V,
+        unknown: T,
+    ) -> Result<T, TelemetryError>
+    where
+        V: Into<i64>,
+    {
+        match self.mode {
+            DecodeMode::Strict => Err(TelemetryError::InvalidEnumValue {
+                field,
+                value: value.into(),
+            }),
+            DecodeMode::Lenient => {
+                self.substitutions += 1;
+                Ok(unknown)
+            }
+        }
+    }
+}
+
+impl Default for Decoder {
+    fn default() -> Self {
+        Decoder::new(DecodeMode::Strict)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::decoder::{DecodeMode, Decoder};
+    use crate::error::TelemetryError;
+    use crate::f1_2020::session::{parse_track, parse_weather, Track, Weather};
+
+    #[test]
+    fn strict_rejects_unknown_value() {
+        let mut decoder = Decoder::new(DecodeMode::Strict);
+        let result = parse_track(60, &mut decoder);
+
+        assert!(matches!(
+            result,
+            Err(TelemetryError::InvalidEnumValue {
+                field: "track",
+                value: 60
+            })
+        ));
+        assert_eq!(0, decoder.substitutions());
+    }
+
+    #[test]
+    fn lenient_substitutes_unknown_value() {
+        let mut decoder = Decoder::new(DecodeMode::Lenient);
+
+        assert_eq!(Track::Unknown(60), parse_track(60, &mut decoder).unwrap());
+        assert_eq!(Weather::Unknown(9), parse_weather(9, &mut decoder).unwrap());
+        assert_eq!(Weather::Storm, parse_weather(5, &mut decoder).unwrap());
+        assert_eq!(Track::Unknown(-1), parse_track(-1, &mut decoder).unwrap());
+        assert_eq!(2, decoder.substitutions());
+    }
+}
@@ -0,0 +1,100 @@
+use std::fmt;
+use std::io;
+use std::time::Duration;
+
+/// Error returned when a datagram cannot be received or decoded.
+#[derive(Debug)]
+pub enum TelemetryError {
+    /// `packet_format` in the header is not a game this crate can decode.
+    UnsupportedFormat {
+        format: u16,
+    },
+    /// Packet id in the header is not defined for the packet format.
+    UnknownPacketId {
+        id: u8,
+    },
+    /// Datagram size does not match the size the packet type requires.
+    SizeMismatch {
+        packet: &'static str,
+        expected: usize,
+        got: usize,
+    },
+    /// A field holds a value outside of its enumeration. Event codes are
+    /// reported as their four bytes read as a little endian `u32`.
+    InvalidEnumValue {
+        field: &'static str,
+        value: i64,
+    },
+    /// A time field holds a negative, infinite or NaN number of seconds.
+    InvalidDuration {
+        field: &'static str,
+        value: f32,
+    },
+    /// A value has no representation in the wire format being encoded, such
+    /// as an enum variant introduced by a later game or a list longer than
+    /// the packet's fixed array.
+    Unencodable {
+        field: &'static str,
+    },
+    /// A capture file is malformed beyond a truncated final record.
+    InvalidCapture {
+        reason: &'static str,
+    },
+    Io(io::Error),
+}
+
+impl fmt::Display for TelemetryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TelemetryError::UnsupportedFormat { format } => {
+                write!(f, "unsupported packet format {}", format)
+            }
+            TelemetryError::UnknownPacketId { id } => write!(f, "unknown packet id {}", id),
+            TelemetryError::SizeMismatch {
+                packet,
+                expected,
+                got,
+            } => write!(
+                f,
+                "{} packet size mismatch: expected {} bytes, got {}",
+                packet, expected, got
+            ),
+            TelemetryError::InvalidEnumValue { field, value } => {
+                write!(f, "invalid {} value {}", field, value)
+            }
+            TelemetryError::InvalidDuration { field, value } => {
+                write!(f, "invalid {} duration {}", field, value)
+            }
+            TelemetryError::Unencodable { field } => {
+                write!(f, "{} cannot be encoded in this packet format", field)
+            }
+            TelemetryError::InvalidCapture { reason } => write!(f, "invalid capture: {}", reason),
+            TelemetryError::Io(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for TelemetryError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            TelemetryError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for TelemetryError {
+    fn from(e: io::Error) -> Self {
+        TelemetryError::Io(e)
+    }
+}
+
+/// Converts a time field in seconds, rejecting values `Duration` cannot hold.
+pub(crate) fn parse_duration(field: &'static str, value: f32) -> Result<Duration, TelemetryError> {
+    Duration::try_from_secs_f32(value).map_err(|_| TelemetryError::InvalidDuration { field, value })
+}
+
+/// Converts a time field back to seconds, the inverse of `parse_duration`.
+pub(crate) fn encode_duration(value: Duration) -> f32 {
+    value.as_secs_f64() as f32
+}
@@ -0,0 +1,11 @@
+pub mod car;
+pub mod car_setup;
+pub mod car_status;
+pub mod car_telemetry;
+pub mod event;
+pub mod header;
+pub mod lap;
+pub mod motion;
+pub mod packet;
+pub mod participants;
+pub mod session;
@@ -0,0 +1,101 @@
+use crate::f1_2018::header::PacketHeader;
+use crate::f1_2019::car::CarSetupData;
+use crate::f1_2020::car::{AntiLockBrakes, DRSStatus, ERSDeploymentMode, FuelMix, TractionControl};
+use crate::f1_2020::motion::Wheel;
+use crate::f1_2020::session::ZoneFlag;
+
+pub const TOTAL_CARS: usize = 20;
+pub const CAR_SETUP_MIN_SIZE: usize = 841;
+pub const CAR_STATUS_MIN_SIZE: usize = 1061;
+pub const CAR_TELEMETRY_MIN_SIZE: usize = 1085;
+
+use derivative::Derivative;
+
+#[derive(Debug, PartialEq, Clone, Derivative)]
+#[derivative(Eq)]
+pub struct PacketCarSetupData {
+    pub header: PacketHeader,
+    pub car_setup_data: Vec<CarSetupData>,
+}
+
+#[derive(Debug, PartialEq, Clone, Derivative)]
+#[derivative(Eq)]
+pub struct CarTelemetryData {
+    pub speed: u16,
+    /// Amount of throttle applied (0 to 100)
+    pub throttle: u8,
+    /// Steering (-100 (full lock left) to 100 (full lock right))
+    pub steer: i8,
+    /// Amount of brake applied (0 to 100)
+    pub brake: u8,
+    pub clutch: u8,
+    pub gear: i8,
+    pub engine_rpm: u16,
+    pub drs: bool,
+    pub rev_lights_percent: u8,
+    pub brakes_temperature: Wheel<u16>,
+    pub tyres_surface_temperature: Wheel<u16>,
+    pub tyres_inner_temperature: Wheel<u16>,
+    pub engine_temperature: u16,
+    pub tyre_pressures: Wheel<f32>,
+}
+
+#[derive(Debug, PartialEq, Clone, Derivative)]
+#[derivative(Eq)]
+pub struct PacketCarTelemetryData {
+    pub header: PacketHeader,
+    pub car_telemetry_data: Vec<CarTelemetryData>,
+    pub button_status: u32,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum TyreCompound {
+    HyperSoft, // 0
+    UltraSoft,
+    SuperSoft,
+    Soft,
+    Medium,
+    Hard,
+    SuperHard,
+    Inter,
+    Wet,
+    Unknown(u8),
+}
+
+#[derive(Debug, PartialEq, Clone, Derivative)]
+#[derivative(Eq)]
+pub struct CarStatusData {
+    pub traction_control: TractionControl,
+    pub anti_lock_brakes: AntiLockBrakes,
+    pub fuel_mix: FuelMix,
+    pub front_brake_bias: u8,
+    pub pit_limiter: bool,
+    pub fuel_in_tank: f32,
+    pub fuel_capacity: f32,
+    pub max_rpm: u16,
+    pub idle_rpm: u16,
+    pub max_gears: u8,
+    pub drs_allowed: DRSStatus,
+    pub tyres_wear: Wheel<u8>,
+    pub tyre_compound: TyreCompound,
+    pub tyres_damage: Wheel<u8>,
+    pub front_left_wing_damage: u8,
+    pub front_right_wing_damage: u8,
+    pub rear_wing_damage: u8,
+    pub engine_damage: u8,
+    pub gear_box_damage: u8,
+    pub exhaust_damage: u8,
+    pub vehicle_fia_flags: ZoneFlag,
+    pub ers_store_energy: f32,
+    pub ers_deploy_mode: ERSDeploymentMode,
+    pub ers_harvested_this_lap_mguk: f32,
+    pub ers_harvested_this_lap_mguh: f32,
+    pub ers_deployed_this_lap: f32,
+}
+
+#[derive(Debug, PartialEq, Clone, Derivative)]
+#[derivative(Eq)]
+pub struct PacketCarStatusData {
+    pub header: PacketHeader,
+    pub car_status_data: Vec<CarStatusData>,
+}
@@ -0,0 +1,36 @@
+use crate::error::TelemetryError;
+use crate::f1_2018::car::{PacketCarSetupData, CAR_SETUP_MIN_SIZE, TOTAL_CARS};
+use crate::f1_2018::header::PacketHeader;
+use crate::f1_2019::car_setup::parse_car_setup;
+use std::io::Cursor;
+
+pub fn parse_car_setup_data(
+    cursor: &mut Cursor<&[u8]>,
+    header: PacketHeader,
+    size: usize,
+) -> Result<PacketCarSetupData, TelemetryError> {
+    ensure_car_setup_size(size)?;
+
+    let mut car_setup_data = Vec::with_capacity(TOTAL_CARS);
+    for _ in 0..TOTAL_CARS {
+        let csd = parse_car_setup(cursor)?;
+        car_setup_data.push(csd);
+    }
+
+    Ok(PacketCarSetupData {
+        header,
+        car_setup_data,
+    })
+}
+
+fn ensure_car_setup_size(size: usize) -> Result<(), TelemetryError> {
+    if size == CAR_SETUP_MIN_SIZE {
+        return Ok(());
+    }
+
+    Err(TelemetryError::SizeMismatch {
+        packet: "Car setup",
+        expected: CAR_SETUP_MIN_SIZE,
+        got: size,
+    })
+}
@@ -0,0 +1,96 @@
+use crate::error::TelemetryError;
+use crate::f1_2018::car::{
+    CarTelemetryData, PacketCarTelemetryData, CAR_TELEMETRY_MIN_SIZE, TOTAL_CARS,
+};
+use crate::f1_2018::header::PacketHeader;
+use crate::f1_2020::motion::Wheel;
+use byteorder::{LittleEndian, ReadBytesExt};
+use std::io::Cursor;
+
+pub fn parse_car_telemetry_data(
+    cursor: &mut Cursor<&[u8]>,
+    header: PacketHeader,
+    size: usize,
+) -> Result<PacketCarTelemetryData, TelemetryError> {
+    ensure_car_telemetry_size(size)?;
+
+    let mut car_telemetry_data = Vec::with_capacity(TOTAL_CARS);
+    for _ in 0..TOTAL_CARS {
+        let ctd = parse_car_telemetry(cursor)?;
+        car_telemetry_data.push(ctd);
+    }
+
+    let button_status = cursor.read_u32::<LittleEndian>()?;
+
+    Ok(PacketCarTelemetryData {
+        header,
+        car_telemetry_data,
+        button_status,
+    })
+}
+
+fn parse_car_telemetry(cursor: &mut Cursor<&[u8]>) -> Result<CarTelemetryData, TelemetryError> {
+    let speed = cursor.read_u16::<LittleEndian>()?;
+    let throttle = cursor.read_u8()?;
+    let steer = cursor.read_i8()?;
+    let brake = cursor.read_u8()?;
+    let clutch = cursor.read_u8()?;
+    let gear = cursor.read_i8()?;
+    let engine_rpm = cursor.read_u16::<LittleEndian>()?;
+    let drs = cursor.read_u8()? == 1;
+    let rev_lights_percent = cursor.read_u8()?;
+    let brakes_temperature = Wheel {
+        rear_left: cursor.read_u16::<LittleEndian>()?,
+        rear_right: cursor.read_u16::<LittleEndian>()?,
+        front_left: cursor.read_u16::<LittleEndian>()?,
+        front_right: cursor.read_u16::<LittleEndian>()?,
+    };
+    let tyres_surface_temperature = Wheel {
+        rear_left: cursor.read_u16::<LittleEndian>()?,
+        rear_right: cursor.read_u16::<LittleEndian>()?,
+        front_left: cursor.read_u16::<LittleEndian>()?,
+        front_right: cursor.read_u16::<LittleEndian>()?,
+    };
+    let tyres_inner_temperature = Wheel {
+        rear_left: cursor.read_u16::<LittleEndian>()?,
+        rear_right: cursor.read_u16::<LittleEndian>()?,
+        front_left: cursor.read_u16::<LittleEndian>()?,
+        front_right: cursor.read_u16::<LittleEndian>()?,
+    };
+    let engine_temperature = cursor.read_u16::<LittleEndian>()?;
+    let tyre_pressures = Wheel {
+        rear_left: cursor.read_f32::<LittleEndian>()?,
+        rear_right: cursor.read_f32::<LittleEndian>()?,
+        front_left: cursor.read_f32::<LittleEndian>()?,
+        front_right: cursor.read_f32::<LittleEndian>()?,
+    };
+
+    Ok(CarTelemetryData {
+        speed,
+        throttle,
+        steer,
+        brake,
+        clutch,
+        gear,
+        engine_rpm,
+        drs,
+        rev_lights_percent,
+        brakes_temperature,
+        tyres_surface_temperature,
+        tyres_inner_temperature,
+        engine_temperature,
+        tyre_pressures,
+    })
+}
+
+fn ensure_car_telemetry_size(size: usize) -> Result<(), TelemetryError> {
+    if size == CAR_TELEMETRY_MIN_SIZE {
+        return Ok(());
+    }
+
+    Err(TelemetryError::SizeMismatch {
+        packet: "Car telemetry",
+        expected: CAR_TELEMETRY_MIN_SIZE,
+        got: size,
+    })
+}
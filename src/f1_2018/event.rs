@@ -0,0 +1,62 @@
+use crate::decoder::Decoder;
+use crate::error::TelemetryError;
+use crate::f1_2018::header::PacketHeader;
+use std::io::{Cursor, Read};
+
+const EVENT_MIN_SIZE: usize = 25;
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum Event {
+    SessionStarted,
+    SessionEnded,
+    /// Event code the decoder does not know, in lenient mode
+    Unknown([u8; 4]),
+}
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub struct PacketEventData {
+    pub header: PacketHeader,
+    pub event: Event,
+}
+
+pub fn parse_event_data(
+    cursor: &mut Cursor<&[u8]>,
+    header: PacketHeader,
+    size: usize,
+    decoder: &mut Decoder,
+) -> Result<PacketEventData, TelemetryError> {
+    ensure_event_size(size)?;
+
+    let event = parse_event(cursor, decoder)?;
+    Ok(PacketEventData { header, event })
+}
+
+pub fn parse_event(
+    cursor: &mut Cursor<&[u8]>,
+    decoder: &mut Decoder,
+) -> Result<Event, TelemetryError> {
+    let mut event_code = [0; 4];
+    cursor.read_exact(&mut event_code)?;
+
+    match &event_code {
+        b"SSTA" => Ok(Event::SessionStarted),
+        b"SEND" => Ok(Event::SessionEnded),
+        _ => decoder.unknown(
+            "event_code",
+            u32::from_le_bytes(event_code),
+            Event::Unknown(event_code),
+        ),
+    }
+}
+
+fn ensure_event_size(size: usize) -> Result<(), TelemetryError> {
+    if size == EVENT_MIN_SIZE {
+        return Ok(());
+    }
+
+    Err(TelemetryError::SizeMismatch {
+        packet: "Event",
+        expected: EVENT_MIN_SIZE,
+        got: size,
+    })
+}
@@ -0,0 +1,46 @@
+use crate::decoder::Decoder;
+use crate::error::TelemetryError;
+use crate::f1_2018::car::TOTAL_CARS;
+use crate::f1_2018::header::PacketHeader;
+use crate::f1_2019::lap::{parse_lap, LapData};
+use std::io::Cursor;
+
+const LAP_DATA_MIN_SIZE: usize = 841;
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct PacketLapData {
+    pub header: PacketHeader,
+    pub lap_data: Vec<LapData>,
+}
+
+pub fn parse_lap_data(
+    cursor: &mut Cursor<&[u8]>,
+    header: PacketHeader,
+    size: usize,
+    decoder: &mut Decoder,
+) -> Result<PacketLapData, TelemetryError> {
+    ensure_lap_data_size(size)?;
+
+    let mut laps = Vec::with_capacity(TOTAL_CARS);
+    for _ in 0..TOTAL_CARS {
+        let lap = parse_lap(cursor, decoder)?;
+        laps.push(lap);
+    }
+
+    Ok(PacketLapData {
+        header,
+        lap_data: laps,
+    })
+}
+
+fn ensure_lap_data_size(size: usize) -> Result<(), TelemetryError> {
+    if size < LAP_DATA_MIN_SIZE {
+        return Err(TelemetryError::SizeMismatch {
+            packet: "Lap data",
+            expected: LAP_DATA_MIN_SIZE,
+            got: size,
+        });
+    }
+
+    Ok(())
+}
@@ -0,0 +1,320 @@
+use crate::decoder::Decoder;
+use crate::error::TelemetryError;
+use crate::f1_2018::car::{PacketCarSetupData, PacketCarStatusData, PacketCarTelemetryData};
+use crate::f1_2018::car_setup::parse_car_setup_data;
+use crate::f1_2018::car_status::parse_car_status_data;
+use crate::f1_2018::car_telemetry::parse_car_telemetry_data;
+use crate::f1_2018::event::{parse_event_data, PacketEventData};
+use crate::f1_2018::header::parse_headers;
+use crate::f1_2018::lap::{parse_lap_data, PacketLapData};
+use crate::f1_2018::motion::{parse_motion_data, PacketMotionData};
+use crate::f1_2018::participants::{parse_participants_data, PacketParticipantsData};
+use crate::f1_2018::session::{parse_session, PacketSessionData};
+use std::io::Cursor;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum PacketID {
+    Motion,
+    Session,
+    LapData,
+    Event,
+    Participants,
+    CarSetups,
+    CarTelemetry,
+    CarStatus,
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum Packet2018 {
+    Motion(PacketMotionData),
+    Session(PacketSessionData),
+    Lap(PacketLapData),
+    Event(PacketEventData),
+    Participants(PacketParticipantsData),
+    CarSetups(PacketCarSetupData),
+    CarTelemetry(PacketCarTelemetryData),
+    CarStatus(PacketCarStatusData),
+}
+
+pub fn parse_f12018(
+    cursor: &mut Cursor<&[u8]>,
+    size: usize,
+    decoder: &mut Decoder,
+) -> Result<Packet2018, TelemetryError> {
+    let header = parse_headers(cursor, size)?;
+    match packet_type(header.packet_id)? {
+        PacketID::Motion => {
+            let motion = parse_motion_data(cursor, header, size)?;
+            Ok(Packet2018::Motion(motion))
+        }
+        PacketID::Session => {
+            let session = parse_session(cursor, header, size, decoder)?;
+            Ok(Packet2018::Session(session))
+        }
+        PacketID::LapData => {
+            let lap = parse_lap_data(cursor, header, size, decoder)?;
+            Ok(Packet2018::Lap(lap))
+        }
+        PacketID::Event => {
+            let event = parse_event_data(cursor, header, size, decoder)?;
+            Ok(Packet2018::Event(event))
+        }
+        PacketID::Participants => {
+            let participants = parse_participants_data(cursor, header, size, decoder)?;
+            Ok(Packet2018::Participants(participants))
+        }
+        PacketID::CarSetups => {
+            let car_setups = parse_car_setup_data(cursor, header, size)?;
+            Ok(Packet2018::CarSetups(car_setups))
+        }
+        PacketID::CarTelemetry => {
+            let car_telemetry = parse_car_telemetry_data(cursor, header, size)?;
+            Ok(Packet2018::CarTelemetry(car_telemetry))
+        }
+        PacketID::CarStatus => {
+            let car_status = parse_car_status_data(cursor, header, size, decoder)?;
+            Ok(Packet2018::CarStatus(car_status))
+        }
+    }
+}
+
+pub fn packet_type(packet_id: u8) -> Result<PacketID, TelemetryError> {
+    match packet_id {
+        0 => Ok(PacketID::Motion),
+        1 => Ok(PacketID::Session),
+        2 => Ok(PacketID::LapData),
+        3 => Ok(PacketID::Event),
+        4 => Ok(PacketID::Participants),
+        5 => Ok(PacketID::CarSetups),
+        6 => Ok(PacketID::CarTelemetry),
+        7 => Ok(PacketID::CarStatus),
+        _ => Err(TelemetryError::UnknownPacketId { id: packet_id }),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::decoder::Decoder;
+    use crate::error::TelemetryError;
+    use crate::f1_2018::car::TyreCompound;
+    use crate::f1_2018::event::Event;
+    use crate::f1_2018::packet::{parse_f12018, Packet2018};
+    use crate::f1_2020::car::ERSDeploymentMode;
+    use std::io::Cursor;
+    use std::time::Duration;
+
+    use byteorder::{ByteOrder, LittleEndian, WriteBytesExt};
+
+    const HEADER_SIZE: usize = 21;
+
+    /// Header of a packet followed by zeros up to `size`.
+    fn packet(packet_id: u8, size: usize) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(size);
+        buf.write_u16::<LittleEndian>(2018).unwrap();
+        buf.write_u8(1).unwrap();
+        buf.write_u8(packet_id).unwrap();
+        buf.write_u64::<LittleEndian>(1).unwrap();
+        buf.write_f32::<LittleEndian>(1.0).unwrap();
+        buf.write_u32::<LittleEndian>(1).unwrap();
+        buf.write_u8(0).unwrap();
+        buf.resize(size, 0);
+        buf
+    }
+
+    fn parse(buf: &[u8]) -> Packet2018 {
+        parse_f12018(&mut Cursor::new(buf), buf.len(), &mut Decoder::default()).unwrap()
+    }
+
+    #[test]
+    fn rejects_packets_of_the_wrong_size() {
+        let sizes = [
+            (0, 1341),
+            (1, 147),
+            (2, 841),
+            (3, 25),
+            (4, 1082),
+            (5, 841),
+            (6, 1085),
+            (7, 1061),
+        ];
+        for (packet_id, size) in sizes {
+            let buf = packet(packet_id, size - 1);
+            let result = parse_f12018(
+                &mut Cursor::new(buf.as_slice()),
+                buf.len(),
+                &mut Decoder::default(),
+            );
+            match result {
+                Err(TelemetryError::SizeMismatch { expected, got, .. }) => {
+                    assert_eq!((size, size - 1), (expected, got))
+                }
+                r => panic!("Unexpected result {:?} for packet {}", r, packet_id),
+            }
+        }
+    }
+
+    #[test]
+    fn parse_motion_packet() {
+        let mut buf = packet(0, 1341);
+        // World position of the last car and the front wheels angle
+        LittleEndian::write_f32(&mut buf[HEADER_SIZE + 19 * 60..], 12.5);
+        LittleEndian::write_f32(&mut buf[1337..], -0.25);
+
+        match parse(&buf) {
+            Packet2018::Motion(motion) => {
+                assert_eq!(20, motion.motion_data.len());
+                assert_eq!(12.5, motion.motion_data[19].world_position_x);
+                assert_eq!(-0.25, motion.front_wheels_angle);
+            }
+            p => panic!("Unexpected packet {:?}", p),
+        }
+    }
+
+    #[test]
+    fn parse_session_packet() {
+        let mut buf = packet(1, 147);
+        buf[HEADER_SIZE + 3] = 53;
+        LittleEndian::write_u16(&mut buf[HEADER_SIZE + 4..], 5793);
+
+        match parse(&buf) {
+            Packet2018::Session(session) => {
+                assert_eq!(53, session.total_laps);
+                assert_eq!(5793, session.track_length);
+            }
+            p => panic!("Unexpected packet {:?}", p),
+        }
+    }
+
+    #[test]
+    fn parse_lap_packet() {
+        let mut buf = packet(2, 841);
+        let car = HEADER_SIZE + 19 * 41;
+        LittleEndian::write_f32(&mut buf[car..], 81.5);
+        buf[car + 32] = 20;
+        buf[car + 33] = 14;
+
+        match parse(&buf) {
+            Packet2018::Lap(lap) => {
+                let car = &lap.lap_data[19];
+                assert_eq!(Duration::from_millis(81_500), car.last_lap_time);
+                assert_eq!(20, car.car_position);
+                assert_eq!(14, car.current_lap_num);
+            }
+            p => panic!("Unexpected packet {:?}", p),
+        }
+    }
+
+    #[test]
+    fn parse_event_packet() {
+        let mut buf = packet(3, 25);
+        buf[HEADER_SIZE..].copy_from_slice(b"SEND");
+
+        match parse(&buf) {
+            Packet2018::Event(event) => assert_eq!(Event::SessionEnded, event.event),
+            p => panic!("Unexpected packet {:?}", p),
+        }
+    }
+
+    #[test]
+    fn parse_participants_packet() {
+        let mut buf = packet(4, 1082);
+        buf[HEADER_SIZE] = 20;
+        let car = HEADER_SIZE + 1 + 19 * 53;
+        buf[car + 1] = 9;
+        buf[car + 3] = 44;
+        buf[car + 5..car + 13].copy_from_slice(b"HAMILTON");
+
+        match parse(&buf) {
+            Packet2018::Participants(participants) => {
+                assert_eq!(20, participants.num_cars);
+                let car = &participants.participants[19];
+                assert_eq!(9, car.driver_id);
+                assert_eq!(44, car.race_number);
+                assert_eq!("HAMILTON", car.name);
+            }
+            p => panic!("Unexpected packet {:?}", p),
+        }
+    }
+
+    #[test]
+    fn parse_car_setups_packet() {
+        let mut buf = packet(5, 841);
+        let car = HEADER_SIZE + 19 * 41;
+        buf[car] = 7;
+        LittleEndian::write_f32(&mut buf[car + 37..], 12.5);
+
+        match parse(&buf) {
+            Packet2018::CarSetups(setups) => {
+                assert_eq!(7, setups.car_setup_data[19].front_wing);
+                assert_eq!(12.5, setups.car_setup_data[19].fuel_load);
+            }
+            p => panic!("Unexpected packet {:?}", p),
+        }
+    }
+
+    #[test]
+    fn parse_car_telemetry_packet() {
+        let mut buf = packet(6, 1085);
+        let car = HEADER_SIZE + 19 * 53;
+        LittleEndian::write_u16(&mut buf[car..], 312);
+        buf[car + 6] = 7;
+        LittleEndian::write_u32(&mut buf[1081..], 0x0001);
+
+        match parse(&buf) {
+            Packet2018::CarTelemetry(telemetry) => {
+                assert_eq!(312, telemetry.car_telemetry_data[19].speed);
+                assert_eq!(7, telemetry.car_telemetry_data[19].gear);
+                assert_eq!(0x0001, telemetry.button_status);
+            }
+            p => panic!("Unexpected packet {:?}", p),
+        }
+    }
+
+    #[test]
+    fn parse_car_status_packet() {
+        let mut buf = Vec::with_capacity(2048);
+        buf.write_u16::<LittleEndian>(2018).unwrap();
+        buf.write_u8(1).unwrap();
+        buf.write_u8(7).unwrap();
+        buf.write_u64::<LittleEndian>(1).unwrap();
+        buf.write_f32::<LittleEndian>(1.0).unwrap();
+        buf.write_u32::<LittleEndian>(1).unwrap();
+        buf.write_u8(0).unwrap();
+        for car in 0..20u8 {
+            buf.extend_from_slice(&[2, 1, 1, 56, 0]);
+            buf.write_f32::<LittleEndian>(50.0).unwrap();
+            buf.write_f32::<LittleEndian>(105.0).unwrap();
+            buf.write_u16::<LittleEndian>(12_000).unwrap();
+            buf.write_u16::<LittleEndian>(4_000).unwrap();
+            buf.extend_from_slice(&[8, 1]);
+            buf.extend_from_slice(&[car, 0, 0, 0]);
+            buf.write_u8(1).unwrap();
+            buf.extend_from_slice(&[0, 0, 0, 0]);
+            buf.extend_from_slice(&[0, 0, 0, 0, 0, car]);
+            buf.write_i8(0).unwrap();
+            buf.write_f32::<LittleEndian>(4_000_000.0).unwrap();
+            buf.write_u8(4).unwrap();
+            buf.write_f32::<LittleEndian>(0.0).unwrap();
+            buf.write_f32::<LittleEndian>(0.0).unwrap();
+            buf.write_f32::<LittleEndian>(0.0).unwrap();
+        }
+
+        let mut cursor = Cursor::new(buf.as_slice());
+        let len = cursor.get_ref().len();
+        assert_eq!(1061, len);
+        let result = parse_f12018(&mut cursor, len, &mut Decoder::default()).unwrap();
+
+        match result {
+            Packet2018::CarStatus(status) => {
+                assert_eq!(2018, status.header.packet_format);
+                assert_eq!(20, status.car_status_data.len());
+                let car = &status.car_status_data[19];
+                assert_eq!(19, car.tyres_wear.rear_left);
+                assert_eq!(TyreCompound::UltraSoft, car.tyre_compound);
+                assert_eq!(19, car.exhaust_damage);
+                assert_eq!(ERSDeploymentMode::Overtake, car.ers_deploy_mode);
+            }
+            p => panic!("Unexpected packet {:?}", p),
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use crate::decoder::Decoder;
+use crate::error::TelemetryError;
+use crate::f1_2018::car::TOTAL_CARS;
+use crate::f1_2018::header::PacketHeader;
+use crate::f1_2020::nationality::Nationality;
+use crate::f1_2020::participants::{parse_name, parse_nationality};
+use byteorder::ReadBytesExt;
+use std::io::Cursor;
+
+const PARTICIPANTS_MIN_SIZE: usize = 1082;
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct ParticipantData {
+    // Whether the vehicle is AI (1) or Human (0) controlled
+    pub ai_controlled: bool, // u8,
+    // Driver id - see the 2018 appendix, the ids differ from later games
+    pub driver_id: u8,
+    // Team id - see the 2018 appendix
+    pub team_id: u8,
+    // Race number of the car
+    pub race_number: u8,
+    // Nationality of the driver
+    pub nationality: Nationality, // u8,
+    // Name of participant in UTF-8 format – null terminated
+    // Will be truncated with … (U+2026) if too long
+    pub name: String,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct PacketParticipantsData {
+    // Header
+    pub header: PacketHeader,
+    // Number of cars in the data
+    pub num_cars: u8,
+    pub participants: Vec<ParticipantData>,
+}
+
+pub fn parse_participants_data(
+    cursor: &mut Cursor<&[u8]>,
+    header: PacketHeader,
+    size: usize,
+    decoder: &mut Decoder,
+) -> Result<PacketParticipantsData, TelemetryError> {
+    ensure_participants_size(size)?;
+
+    let num_cars = cursor.read_u8()?;
+
+    let mut participants = Vec::with_capacity(TOTAL_CARS);
+    for _ in 0..TOTAL_CARS {
+        let participant = parse_participant(cursor, decoder)?;
+        participants.push(participant);
+    }
+
+    Ok(PacketParticipantsData {
+        header,
+        num_cars,
+        participants,
+    })
+}
+
+fn ensure_participants_size(size: usize) -> Result<(), TelemetryError> {
+    if size < PARTICIPANTS_MIN_SIZE {
+        return Err(TelemetryError::SizeMismatch {
+            packet: "Participants",
+            expected: PARTICIPANTS_MIN_SIZE,
+            got: size,
+        });
+    }
+
+    Ok(())
+}
+
+fn parse_participant(
+    cursor: &mut Cursor<&[u8]>,
+    decoder: &mut Decoder,
+) -> Result<ParticipantData, TelemetryError> {
+    let ai_controlled = cursor.read_u8()? == 1;
+    let driver_id = cursor.read_u8()?;
+    let team_id = cursor.read_u8()?;
+    let race_number = cursor.read_u8()?;
+    let nationality = parse_nationality(cursor.read_u8()?, decoder)?;
+    let name = parse_name(cursor)?;
+
+    Ok(ParticipantData {
+        ai_controlled,
+        driver_id,
+        team_id,
+        race_number,
+        nationality,
+        name,
+    })
+}
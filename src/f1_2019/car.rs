@@ -0,0 +1,113 @@
+use crate::f1_2019::header::PacketHeader;
+use crate::f1_2020::car::{
+    ActualTyreCompound, AntiLockBrakes, DRSStatus, ERSDeploymentMode, FuelMix, SurfaceType,
+    TractionControl, VisualTyreCompound,
+};
+use crate::f1_2020::motion::Wheel;
+use crate::f1_2020::session::ZoneFlag;
+
+pub const TOTAL_CARS: usize = 20;
+pub const CAR_SETUP_MIN_SIZE: usize = 843;
+pub const CAR_STATUS_MIN_SIZE: usize = 1143;
+pub const CAR_TELEMETRY_MIN_SIZE: usize = 1347;
+
+use derivative::Derivative;
+
+#[derive(Debug, PartialEq, Clone, Derivative)]
+#[derivative(Eq)]
+pub struct CarSetupData {
+    pub front_wing: u8,
+    pub rear_wing: u8,
+    pub on_throttle: u8,
+    pub off_throttle: u8,
+    pub front_camber: f32,
+    pub rear_camber: f32,
+    pub front_toe: f32,
+    pub rear_toe: f32,
+    pub front_suspension: u8,
+    pub rear_suspension: u8,
+    pub front_anti_roll_bar: u8,
+    pub rear_anti_roll_bar: u8,
+    pub front_suspension_height: u8,
+    pub rear_suspension_height: u8,
+    pub brake_pressure: u8,
+    pub brake_bias: u8,
+    pub front_tyre_pressure: f32,
+    pub rear_tyre_pressure: f32,
+    pub ballast: u8,
+    pub fuel_load: f32,
+}
+
+#[derive(Debug, PartialEq, Clone, Derivative)]
+#[derivative(Eq)]
+pub struct PacketCarSetupData {
+    pub header: PacketHeader,
+    pub car_setup_data: Vec<CarSetupData>,
+}
+
+#[derive(Debug, PartialEq, Clone, Derivative)]
+#[derivative(Eq)]
+pub struct CarTelemetryData {
+    pub speed: u16,
+    pub throttle: f32,
+    pub steer: f32,
+    pub brake: f32,
+    pub clutch: u8,
+    pub gear: i8,
+    pub engine_rpm: u16,
+    pub drs: bool,
+    pub rev_lights_percent: u8,
+    pub brakes_temperature: Wheel<u16>,
+    pub tyres_surface_temperature: Wheel<u16>,
+    pub tyres_inner_temperature: Wheel<u16>,
+    pub engine_temperature: u16,
+    pub tyre_pressures: Wheel<f32>,
+    pub surface_types: Wheel<SurfaceType>,
+}
+
+#[derive(Debug, PartialEq, Clone, Derivative)]
+#[derivative(Eq)]
+pub struct PacketCarTelemetryData {
+    pub header: PacketHeader,
+    pub car_telemetry_data: Vec<CarTelemetryData>,
+    pub button_status: u32,
+}
+
+#[derive(Debug, PartialEq, Clone, Derivative)]
+#[derivative(Eq)]
+pub struct CarStatusData {
+    pub traction_control: TractionControl,
+    pub anti_lock_brakes: AntiLockBrakes,
+    pub fuel_mix: FuelMix,
+    pub front_brake_bias: u8,
+    pub pit_limiter: bool,
+    pub fuel_in_tank: f32,
+    pub fuel_capacity: f32,
+    pub fuel_remaining_laps: f32,
+    pub max_rpm: u16,
+    pub idle_rpm: u16,
+    pub max_gears: u8,
+    pub drs_allowed: DRSStatus,
+    pub tyres_wear: Wheel<u8>,
+    pub actual_tyre_compound: ActualTyreCompound,
+    pub visual_tyre_compound: VisualTyreCompound,
+    pub tyres_damage: Wheel<u8>,
+    pub front_left_wing_damage: u8,
+    pub front_right_wing_damage: u8,
+    pub rear_wing_damage: u8,
+    pub engine_damage: u8,
+    pub gear_box_damage: u8,
+    pub vehicle_fia_flags: ZoneFlag,
+    pub ers_store_energy: f32,
+    pub ers_deploy_mode: ERSDeploymentMode,
+    pub ers_harvested_this_lap_mguk: f32,
+    pub ers_harvested_this_lap_mguh: f32,
+    pub ers_deployed_this_lap: f32,
+}
+
+#[derive(Debug, PartialEq, Clone, Derivative)]
+#[derivative(Eq)]
+pub struct PacketCarStatusData {
+    pub header: PacketHeader,
+    pub car_status_data: Vec<CarStatusData>,
+}
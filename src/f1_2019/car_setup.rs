@@ -0,0 +1,82 @@
+use crate::error::TelemetryError;
+use crate::f1_2019::car::{CarSetupData, PacketCarSetupData, CAR_SETUP_MIN_SIZE, TOTAL_CARS};
+use crate::f1_2019::header::PacketHeader;
+use byteorder::{LittleEndian, ReadBytesExt};
+use std::io::Cursor;
+
+pub fn parse_car_setup_data(
+    cursor: &mut Cursor<&[u8]>,
+    header: PacketHeader,
+    size: usize,
+) -> Result<PacketCarSetupData, TelemetryError> {
+    ensure_car_setup_size(size)?;
+
+    let mut car_setup_data = Vec::with_capacity(TOTAL_CARS);
+    for _ in 0..TOTAL_CARS {
+        let csd = parse_car_setup(cursor)?;
+        car_setup_data.push(csd);
+    }
+
+    Ok(PacketCarSetupData {
+        header,
+        car_setup_data,
+    })
+}
+
+pub fn parse_car_setup(cursor: &mut Cursor<&[u8]>) -> Result<CarSetupData, TelemetryError> {
+    let front_wing = cursor.read_u8()?;
+    let rear_wing = cursor.read_u8()?;
+    let on_throttle = cursor.read_u8()?;
+    let off_throttle = cursor.read_u8()?;
+    let front_camber = cursor.read_f32::<LittleEndian>()?;
+    let rear_camber = cursor.read_f32::<LittleEndian>()?;
+    let front_toe = cursor.read_f32::<LittleEndian>()?;
+    let rear_toe = cursor.read_f32::<LittleEndian>()?;
+    let front_suspension = cursor.read_u8()?;
+    let rear_suspension = cursor.read_u8()?;
+    let front_anti_roll_bar = cursor.read_u8()?;
+    let rear_anti_roll_bar = cursor.read_u8()?;
+    let front_suspension_height = cursor.read_u8()?;
+    let rear_suspension_height = cursor.read_u8()?;
+    let brake_pressure = cursor.read_u8()?;
+    let brake_bias = cursor.read_u8()?;
+    let front_tyre_pressure = cursor.read_f32::<LittleEndian>()?;
+    let rear_tyre_pressure = cursor.read_f32::<LittleEndian>()?;
+    let ballast = cursor.read_u8()?;
+    let fuel_load = cursor.read_f32::<LittleEndian>()?;
+
+    Ok(CarSetupData {
+        front_wing,
+        rear_wing,
+        on_throttle,
+        off_throttle,
+        front_camber,
+        rear_camber,
+        front_toe,
+        rear_toe,
+        front_suspension,
+        rear_suspension,
+        front_anti_roll_bar,
+        rear_anti_roll_bar,
+        front_suspension_height,
+        rear_suspension_height,
+        brake_pressure,
+        brake_bias,
+        front_tyre_pressure,
+        rear_tyre_pressure,
+        ballast,
+        fuel_load,
+    })
+}
+
+fn ensure_car_setup_size(size: usize) -> Result<(), TelemetryError> {
+    if size == CAR_SETUP_MIN_SIZE {
+        return Ok(());
+    }
+
+    Err(TelemetryError::SizeMismatch {
+        packet: "Car setup",
+        expected: CAR_SETUP_MIN_SIZE,
+        got: size,
+    })
+}
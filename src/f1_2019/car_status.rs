@@ -0,0 +1,137 @@
+use crate::decoder::Decoder;
+use crate::error::TelemetryError;
+use crate::f1_2019::car::{CarStatusData, PacketCarStatusData, CAR_STATUS_MIN_SIZE, TOTAL_CARS};
+use crate::f1_2019::header::PacketHeader;
+use crate::f1_2020::car::ERSDeploymentMode;
+use crate::f1_2020::car_status::{
+    parse_actual_tyre_compound, parse_anti_lock_brakes, parse_drs, parse_fuel_mix,
+    parse_traction_control, parse_visual_tyre_compound,
+};
+use crate::f1_2020::motion::Wheel;
+use crate::f1_2020::session::parse_flag;
+use byteorder::{LittleEndian, ReadBytesExt};
+use std::io::Cursor;
+
+pub fn parse_car_status_data(
+    cursor: &mut Cursor<&[u8]>,
+    header: PacketHeader,
+    size: usize,
+    decoder: &mut Decoder,
+) -> Result<PacketCarStatusData, TelemetryError> {
+    ensure_car_status_size(size)?;
+
+    let mut car_status_data = Vec::with_capacity(TOTAL_CARS);
+    for _ in 0..TOTAL_CARS {
+        let csd = parse_car_status(cursor, decoder)?;
+        car_status_data.push(csd);
+    }
+
+    Ok(PacketCarStatusData {
+        header,
+        car_status_data,
+    })
+}
+
+fn parse_car_status(
+    cursor: &mut Cursor<&[u8]>,
+    decoder: &mut Decoder,
+) -> Result<CarStatusData, TelemetryError> {
+    let traction_control = parse_traction_control(cursor.read_u8()?, decoder)?;
+    let anti_lock_brakes = parse_anti_lock_brakes(cursor.read_u8()?, decoder)?;
+    let fuel_mix = parse_fuel_mix(cursor.read_u8()?, decoder)?;
+    let front_brake_bias = cursor.read_u8()?;
+    let pit_limiter = cursor.read_u8()? == 1;
+    let fuel_in_tank = cursor.read_f32::<LittleEndian>()?;
+    let fuel_capacity = cursor.read_f32::<LittleEndian>()?;
+    let fuel_remaining_laps = cursor.read_f32::<LittleEndian>()?;
+    let max_rpm = cursor.read_u16::<LittleEndian>()?;
+    let idle_rpm = cursor.read_u16::<LittleEndian>()?;
+    let max_gears = cursor.read_u8()?;
+    let drs_allowed = parse_drs(cursor.read_i8()?, decoder)?;
+    let tyres_wear = Wheel {
+        rear_left: cursor.read_u8()?,
+        rear_right: cursor.read_u8()?,
+        front_left: cursor.read_u8()?,
+        front_right: cursor.read_u8()?,
+    };
+    let actual_tyre_compound = parse_actual_tyre_compound(cursor.read_u8()?, decoder)?;
+    let visual_tyre_compound = parse_visual_tyre_compound(cursor.read_u8()?, decoder)?;
+    let tyres_damage = Wheel {
+        rear_left: cursor.read_u8()?,
+        rear_right: cursor.read_u8()?,
+        front_left: cursor.read_u8()?,
+        front_right: cursor.read_u8()?,
+    };
+    let front_left_wing_damage = cursor.read_u8()?;
+    let front_right_wing_damage = cursor.read_u8()?;
+    let rear_wing_damage = cursor.read_u8()?;
+    let engine_damage = cursor.read_u8()?;
+    let gear_box_damage = cursor.read_u8()?;
+    let vehicle_fia_flags = parse_flag(cursor.read_i8()?, decoder)?;
+    let ers_store_energy = cursor.read_f32::<LittleEndian>()?;
+    let ers_deploy_mode = parse_ers_deployment_mode(cursor.read_u8()?, decoder)?;
+    let ers_harvested_this_lap_mguk = cursor.read_f32::<LittleEndian>()?;
+    let ers_harvested_this_lap_mguh = cursor.read_f32::<LittleEndian>()?;
+    let ers_deployed_this_lap = cursor.read_f32::<LittleEndian>()?;
+
+    Ok(CarStatusData {
+        traction_control,
+        anti_lock_brakes,
+        fuel_mix,
+        front_brake_bias,
+        pit_limiter,
+        fuel_in_tank,
+        fuel_capacity,
+        fuel_remaining_laps,
+        max_rpm,
+        idle_rpm,
+        max_gears,
+        drs_allowed,
+        tyres_wear,
+        actual_tyre_compound,
+        visual_tyre_compound,
+        tyres_damage,
+        front_left_wing_damage,
+        front_right_wing_damage,
+        rear_wing_damage,
+        engine_damage,
+        gear_box_damage,
+        vehicle_fia_flags,
+        ers_store_energy,
+        ers_deploy_mode,
+        ers_harvested_this_lap_mguk,
+        ers_harvested_this_lap_mguh,
+        ers_deployed_this_lap,
+    })
+}
+
+fn ensure_car_status_size(size: usize) -> Result<(), TelemetryError> {
+    if size == CAR_STATUS_MIN_SIZE {
+        return Ok(());
+    }
+
+    Err(TelemetryError::SizeMismatch {
+        packet: "Car status",
+        expected: CAR_STATUS_MIN_SIZE,
+        got: size,
+    })
+}
+
+pub fn parse_ers_deployment_mode(
+    value: u8,
+    decoder: &mut Decoder,
+) -> Result<ERSDeploymentMode, TelemetryError> {
+    match value {
+        0 => Ok(ERSDeploymentMode::None),
+        1 => Ok(ERSDeploymentMode::Low),
+        2 => Ok(ERSDeploymentMode::Medium),
+        3 => Ok(ERSDeploymentMode::High),
+        4 => Ok(ERSDeploymentMode::Overtake),
+        5 => Ok(ERSDeploymentMode::Hotlap),
+        _ => decoder.unknown(
+            "ers_deployment_mode",
+            value,
+            ERSDeploymentMode::Unknown(value),
+        ),
+    }
+}
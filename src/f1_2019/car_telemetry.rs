@@ -0,0 +1,109 @@
+use crate::decoder::Decoder;
+use crate::error::TelemetryError;
+use crate::f1_2019::car::{
+    CarTelemetryData, PacketCarTelemetryData, CAR_TELEMETRY_MIN_SIZE, TOTAL_CARS,
+};
+use crate::f1_2019::header::PacketHeader;
+use crate::f1_2020::car_telemetry::parse_surface_type;
+use crate::f1_2020::motion::Wheel;
+use byteorder::{LittleEndian, ReadBytesExt};
+use std::io::Cursor;
+
+pub fn parse_car_telemetry_data(
+    cursor: &mut Cursor<&[u8]>,
+    header: PacketHeader,
+    size: usize,
+    decoder: &mut Decoder,
+) -> Result<PacketCarTelemetryData, TelemetryError> {
+    ensure_car_telemetry_size(size)?;
+
+    let mut car_telemetry_data = Vec::with_capacity(TOTAL_CARS);
+    for _ in 0..TOTAL_CARS {
+        let ctd = parse_car_telemetry(cursor, decoder)?;
+        car_telemetry_data.push(ctd);
+    }
+
+    let button_status = cursor.read_u32::<LittleEndian>()?;
+
+    Ok(PacketCarTelemetryData {
+        header,
+        car_telemetry_data,
+        button_status,
+    })
+}
+
+fn parse_car_telemetry(
+    cursor: &mut Cursor<&[u8]>,
+    decoder: &mut Decoder,
+) -> Result<CarTelemetryData, TelemetryError> {
+    let speed = cursor.read_u16::<LittleEndian>()?;
+    let throttle = cursor.read_f32::<LittleEndian>()?;
+    let steer = cursor.read_f32::<LittleEndian>()?;
+    let brake = cursor.read_f32::<LittleEndian>()?;
+    let clutch = cursor.read_u8()?;
+    let gear = cursor.read_i8()?;
+    let engine_rpm = cursor.read_u16::<LittleEndian>()?;
+    let drs = cursor.read_u8()? == 1;
+    let rev_lights_percent = cursor.read_u8()?;
+    let brakes_temperature = Wheel {
+        rear_left: cursor.read_u16::<LittleEndian>()?,
+        rear_right: cursor.read_u16::<LittleEndian>()?,
+        front_left: cursor.read_u16::<LittleEndian>()?,
+        front_right: cursor.read_u16::<LittleEndian>()?,
+    };
+    let tyres_surface_temperature = Wheel {
+        rear_left: cursor.read_u16::<LittleEndian>()?,
+        rear_right: cursor.read_u16::<LittleEndian>()?,
+        front_left: cursor.read_u16::<LittleEndian>()?,
+        front_right: cursor.read_u16::<LittleEndian>()?,
+    };
+    let tyres_inner_temperature = Wheel {
+        rear_left: cursor.read_u16::<LittleEndian>()?,
+        rear_right: cursor.read_u16::<LittleEndian>()?,
+        front_left: cursor.read_u16::<LittleEndian>()?,
+        front_right: cursor.read_u16::<LittleEndian>()?,
+    };
+    let engine_temperature = cursor.read_u16::<LittleEndian>()?;
+    let tyre_pressures = Wheel {
+        rear_left: cursor.read_f32::<LittleEndian>()?,
+        rear_right: cursor.read_f32::<LittleEndian>()?,
+        front_left: cursor.read_f32::<LittleEndian>()?,
+        front_right: cursor.read_f32::<LittleEndian>()?,
+    };
+    let surface_types = Wheel {
+        rear_left: parse_surface_type(cursor.read_u8()?, decoder)?,
+        rear_right: parse_surface_type(cursor.read_u8()?, decoder)?,
+        front_left: parse_surface_type(cursor.read_u8()?, decoder)?,
+        front_right: parse_surface_type(cursor.read_u8()?, decoder)?,
+    };
+
+    Ok(CarTelemetryData {
+        speed,
+        throttle,
+        steer,
+        brake,
+        clutch,
+        gear,
+        engine_rpm,
+        drs,
+        rev_lights_percent,
+        brakes_temperature,
+        tyres_surface_temperature,
+        tyres_inner_temperature,
+        engine_temperature,
+        tyre_pressures,
+        surface_types,
+    })
+}
+
+fn ensure_car_telemetry_size(size: usize) -> Result<(), TelemetryError> {
+    if size == CAR_TELEMETRY_MIN_SIZE {
+        return Ok(());
+    }
+
+    Err(TelemetryError::SizeMismatch {
+        packet: "Car telemetry",
+        expected: CAR_TELEMETRY_MIN_SIZE,
+        got: size,
+    })
+}
@@ -0,0 +1,98 @@
+use crate::decoder::Decoder;
+use crate::error::{parse_duration, TelemetryError};
+use crate::f1_2019::header::PacketHeader;
+use crate::f1_2020::event::{FastestLap, RaceWinner, Retirement, TeamMateInPits};
+use byteorder::{LittleEndian, ReadBytesExt};
+use std::io::{Cursor, Read};
+
+const EVENT_MIN_SIZE: usize = 32;
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum Event {
+    SessionStarted,
+    SessionEnded,
+    FastestLap(FastestLap),
+    Retirement(Retirement),
+    DRSEnabled,
+    DRSDisabled,
+    TeamMateInPits(TeamMateInPits),
+    ChequeredFlag,
+    RaceWinner(RaceWinner),
+    /// Event code the decoder does not know, in lenient mode
+    Unknown([u8; 4]),
+}
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub struct PacketEventData {
+    pub header: PacketHeader,
+    pub event: Event,
+}
+
+pub fn parse_event_data(
+    cursor: &mut Cursor<&[u8]>,
+    header: PacketHeader,
+    size: usize,
+    decoder: &mut Decoder,
+) -> Result<PacketEventData, TelemetryError> {
+    ensure_event_size(size)?;
+
+    let event = parse_event(cursor, decoder)?;
+    Ok(PacketEventData { header, event })
+}
+
+pub fn parse_event(
+    cursor: &mut Cursor<&[u8]>,
+    decoder: &mut Decoder,
+) -> Result<Event, TelemetryError> {
+    let mut event_code = [0; 4];
+    cursor.read_exact(&mut event_code)?;
+
+    match &event_code {
+        b"SSTA" => Ok(Event::SessionStarted),
+        b"SEND" => Ok(Event::SessionEnded),
+        b"FTLP" => {
+            let vehicle_index = cursor.read_u8()?;
+            let lap_time = parse_duration("lap_time", cursor.read_f32::<LittleEndian>()?)?;
+
+            Ok(Event::FastestLap(FastestLap {
+                vehicle_index,
+                lap_time,
+            }))
+        }
+        b"RTMT" => {
+            let vehicle_index = cursor.read_u8()?;
+
+            Ok(Event::Retirement(Retirement { vehicle_index }))
+        }
+        b"DRSE" => Ok(Event::DRSEnabled),
+        b"DRSD" => Ok(Event::DRSDisabled),
+        b"TMPT" => {
+            let vehicle_index = cursor.read_u8()?;
+
+            Ok(Event::TeamMateInPits(TeamMateInPits { vehicle_index }))
+        }
+        b"CHQF" => Ok(Event::ChequeredFlag),
+        b"RCWN" => {
+            let vehicle_index = cursor.read_u8()?;
+
+            Ok(Event::RaceWinner(RaceWinner { vehicle_index }))
+        }
+        _ => decoder.unknown(
+            "event_code",
+            u32::from_le_bytes(event_code),
+            Event::Unknown(event_code),
+        ),
+    }
+}
+
+fn ensure_event_size(size: usize) -> Result<(), TelemetryError> {
+    if size == EVENT_MIN_SIZE {
+        return Ok(());
+    }
+
+    Err(TelemetryError::SizeMismatch {
+        packet: "Event",
+        expected: EVENT_MIN_SIZE,
+        got: size,
+    })
+}
@@ -0,0 +1,97 @@
+use crate::error::{parse_duration, TelemetryError};
+use byteorder::{LittleEndian, ReadBytesExt};
+use std::io::Cursor;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, PartialOrd)]
+pub struct PacketHeader {
+    pub packet_format: u16,
+    pub major_version: u8,
+    pub minor_version: u8,
+    pub packet_version: u8,
+    pub packet_id: u8,
+    pub session_uid: u64,
+    pub session_time: Duration,
+    pub frame_identifier: u32,
+    pub player_car_index: u8,
+}
+
+const HEADER_MIN_SIZE: usize = 23;
+
+pub fn parse_headers(
+    cursor: &mut Cursor<&[u8]>,
+    size: usize,
+) -> Result<PacketHeader, TelemetryError> {
+    ensure_header_size(size)?;
+
+    let packet_format = cursor.read_u16::<LittleEndian>()?;
+    let major_version = cursor.read_u8()?;
+    let minor_version = cursor.read_u8()?;
+    let packet_version = cursor.read_u8()?;
+    let packet_id = cursor.read_u8()?;
+    let session_uid = cursor.read_u64::<LittleEndian>()?;
+    let session_time = parse_duration("session_time", cursor.read_f32::<LittleEndian>()?)?;
+    let frame_identifier = cursor.read_u32::<LittleEndian>()?;
+    let player_car_index = cursor.read_u8()?;
+
+    Ok(PacketHeader {
+        packet_format,
+        major_version,
+        minor_version,
+        packet_version,
+        packet_id,
+        session_uid,
+        session_time,
+        frame_identifier,
+        player_car_index,
+    })
+}
+
+fn ensure_header_size(size: usize) -> Result<(), TelemetryError> {
+    if size < HEADER_MIN_SIZE {
+        return Err(TelemetryError::SizeMismatch {
+            packet: "Header",
+            expected: HEADER_MIN_SIZE,
+            got: size,
+        });
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::f1_2019::header::parse_headers;
+    use std::io::Cursor;
+
+    use byteorder::{LittleEndian, WriteBytesExt};
+
+    #[test]
+    fn parse_header_success() {
+        let mut buf = Vec::with_capacity(2048);
+        buf.write_u16::<LittleEndian>(2019).unwrap();
+        buf.write_u8(1).unwrap();
+        buf.write_u8(22).unwrap();
+        buf.write_u8(1).unwrap();
+        buf.write_u8(7).unwrap();
+        buf.write_u64::<LittleEndian>(u64::MAX).unwrap();
+        buf.write_f32::<LittleEndian>(2.5).unwrap();
+        buf.write_u32::<LittleEndian>(42).unwrap();
+        buf.write_u8(19).unwrap();
+
+        let mut cursor = Cursor::new(buf.as_slice());
+        let len = cursor.get_ref().len();
+        let result = parse_headers(&mut cursor, len).unwrap();
+
+        assert_eq!(2019, result.packet_format);
+        assert_eq!(1, result.major_version);
+        assert_eq!(22, result.minor_version);
+        assert_eq!(1, result.packet_version);
+        assert_eq!(7, result.packet_id);
+        assert_eq!(u64::MAX, result.session_uid);
+        assert_eq!(2500, result.session_time.as_millis());
+        assert_eq!(42, result.frame_identifier);
+        assert_eq!(19, result.player_car_index);
+        assert_eq!(23, cursor.position());
+    }
+}
@@ -0,0 +1,123 @@
+use crate::decoder::Decoder;
+use crate::error::{parse_duration, TelemetryError};
+use crate::f1_2019::car::TOTAL_CARS;
+use crate::f1_2019::header::PacketHeader;
+use crate::f1_2020::lap::{
+    parse_driver_status, parse_pit_status, parse_result_status, DriverStatus, PitStatus,
+    ResultStatus,
+};
+use byteorder::{LittleEndian, ReadBytesExt};
+use derivative::Derivative;
+use std::io::Cursor;
+use std::time::Duration;
+
+const LAP_DATA_MIN_SIZE: usize = 843;
+
+#[derive(Debug, PartialEq, Clone, Copy, Derivative)]
+#[derivative(Eq)]
+pub struct LapData {
+    /// Last lap time in seconds
+    pub last_lap_time: Duration,
+    /// Current time around the lap in seconds
+    pub current_lap_time: Duration,
+    /// Best lap time of the session in seconds
+    pub best_lap_time: Duration,
+    /// Sector 1 time in seconds
+    pub sector_1_time: Duration,
+    /// Sector 2 time in seconds
+    pub sector_2_time: Duration,
+    pub lap_distance: f32,
+    pub total_distance: f32,
+    /// Delta in seconds for safety car
+    pub safety_car_delta: f32,
+    pub car_position: u8,
+    pub current_lap_num: u8,
+    pub pit_status: PitStatus,
+    pub sector: u8,
+    pub current_lap_invalid: bool,
+    pub penalties: u8,
+    pub grid_position: u8,
+    pub driver_status: DriverStatus,
+    pub result_status: ResultStatus,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct PacketLapData {
+    pub header: PacketHeader,
+    pub lap_data: Vec<LapData>,
+}
+
+pub fn parse_lap_data(
+    cursor: &mut Cursor<&[u8]>,
+    header: PacketHeader,
+    size: usize,
+    decoder: &mut Decoder,
+) -> Result<PacketLapData, TelemetryError> {
+    ensure_lap_data_size(size)?;
+
+    let mut laps = Vec::with_capacity(TOTAL_CARS);
+    for _ in 0..TOTAL_CARS {
+        let lap = parse_lap(cursor, decoder)?;
+        laps.push(lap);
+    }
+
+    Ok(PacketLapData {
+        header,
+        lap_data: laps,
+    })
+}
+
+pub fn parse_lap(
+    cursor: &mut Cursor<&[u8]>,
+    decoder: &mut Decoder,
+) -> Result<LapData, TelemetryError> {
+    let last_lap_time = parse_duration("last_lap_time", cursor.read_f32::<LittleEndian>()?)?;
+    let current_lap_time = parse_duration("current_lap_time", cursor.read_f32::<LittleEndian>()?)?;
+    let best_lap_time = parse_duration("best_lap_time", cursor.read_f32::<LittleEndian>()?)?;
+    let sector_1_time = parse_duration("sector_1_time", cursor.read_f32::<LittleEndian>()?)?;
+    let sector_2_time = parse_duration("sector_2_time", cursor.read_f32::<LittleEndian>()?)?;
+    let lap_distance = cursor.read_f32::<LittleEndian>()?;
+    let total_distance = cursor.read_f32::<LittleEndian>()?;
+    let safety_car_delta = cursor.read_f32::<LittleEndian>()?;
+    let car_position = cursor.read_u8()?;
+    let current_lap_num = cursor.read_u8()?;
+    let pit_status = parse_pit_status(cursor, decoder)?;
+    let sector = cursor.read_u8()?;
+    let current_lap_invalid = cursor.read_u8()? == 1;
+    let penalties = cursor.read_u8()?;
+    let grid_position = cursor.read_u8()?;
+    let driver_status = parse_driver_status(cursor, decoder)?;
+    let result_status = parse_result_status(cursor, decoder)?;
+
+    Ok(LapData {
+        last_lap_time,
+        current_lap_time,
+        best_lap_time,
+        sector_1_time,
+        sector_2_time,
+        lap_distance,
+        total_distance,
+        safety_car_delta,
+        car_position,
+        current_lap_num,
+        pit_status,
+        sector,
+        current_lap_invalid,
+        penalties,
+        grid_position,
+        driver_status,
+        result_status,
+    })
+}
+
+fn ensure_lap_data_size(size: usize) -> Result<(), TelemetryError> {
+    if size < LAP_DATA_MIN_SIZE {
+        return Err(TelemetryError::SizeMismatch {
+            packet: "Lap data",
+            expected: LAP_DATA_MIN_SIZE,
+            got: size,
+        });
+    }
+
+    Ok(())
+}
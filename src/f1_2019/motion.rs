@@ -0,0 +1,124 @@
+use crate::error::TelemetryError;
+use crate::f1_2019::car::TOTAL_CARS;
+use crate::f1_2019::header::PacketHeader;
+use crate::f1_2020::motion::{parse_car_motion, CarMotionData, Wheel};
+use byteorder::{LittleEndian, ReadBytesExt};
+use std::io::Cursor;
+
+use derivative::Derivative;
+
+const MOTION_MIN_SIZE: usize = 1343;
+
+#[derive(Debug, PartialEq, Clone, Derivative)]
+#[derivative(Eq)]
+pub struct PacketMotionData {
+    pub header: PacketHeader,
+    pub motion_data: Vec<CarMotionData>,
+    pub suspension_position: Wheel<f32>,
+    pub suspension_velocity: Wheel<f32>,
+    pub suspension_acceleration: Wheel<f32>,
+    pub wheel_speed: Wheel<f32>,
+    pub wheel_slip: Wheel<f32>,
+    pub local_velocity_x: f32,
+    pub local_velocity_y: f32,
+    pub local_velocity_z: f32,
+    pub angular_velocity_x: f32,
+    pub angular_velocity_y: f32,
+    pub angular_velocity_z: f32,
+    pub angular_acceleration_x: f32,
+    pub angular_acceleration_y: f32,
+    pub angular_acceleration_z: f32,
+    pub front_wheels_angle: f32,
+}
+
+pub fn parse_motion_data(
+    cursor: &mut Cursor<&[u8]>,
+    header: PacketHeader,
+    size: usize,
+) -> Result<PacketMotionData, TelemetryError> {
+    ensure_motion_size(size)?;
+
+    let mut car_motion_data = Vec::with_capacity(TOTAL_CARS);
+    for _ in 0..TOTAL_CARS {
+        let car_motion = parse_car_motion(cursor)?;
+        car_motion_data.push(car_motion);
+    }
+
+    let suspension_position = Wheel {
+        rear_left: cursor.read_f32::<LittleEndian>()?,
+        rear_right: cursor.read_f32::<LittleEndian>()?,
+        front_left: cursor.read_f32::<LittleEndian>()?,
+        front_right: cursor.read_f32::<LittleEndian>()?,
+    };
+
+    let suspension_velocity = Wheel {
+        rear_left: cursor.read_f32::<LittleEndian>()?,
+        rear_right: cursor.read_f32::<LittleEndian>()?,
+        front_left: cursor.read_f32::<LittleEndian>()?,
+        front_right: cursor.read_f32::<LittleEndian>()?,
+    };
+
+    let suspension_acceleration = Wheel {
+        rear_left: cursor.read_f32::<LittleEndian>()?,
+        rear_right: cursor.read_f32::<LittleEndian>()?,
+        front_left: cursor.read_f32::<LittleEndian>()?,
+        front_right: cursor.read_f32::<LittleEndian>()?,
+    };
+
+    let wheel_speed = Wheel {
+        rear_left: cursor.read_f32::<LittleEndian>()?,
+        rear_right: cursor.read_f32::<LittleEndian>()?,
+        front_left: cursor.read_f32::<LittleEndian>()?,
+        front_right: cursor.read_f32::<LittleEndian>()?,
+    };
+
+    let wheel_slip = Wheel {
+        rear_left: cursor.read_f32::<LittleEndian>()?,
+        rear_right: cursor.read_f32::<LittleEndian>()?,
+        front_left: cursor.read_f32::<LittleEndian>()?,
+        front_right: cursor.read_f32::<LittleEndian>()?,
+    };
+
+    let local_velocity_x = cursor.read_f32::<LittleEndian>()?;
+    let local_velocity_y = cursor.read_f32::<LittleEndian>()?;
+    let local_velocity_z = cursor.read_f32::<LittleEndian>()?;
+    let angular_velocity_x = cursor.read_f32::<LittleEndian>()?;
+    let angular_velocity_y = cursor.read_f32::<LittleEndian>()?;
+    let angular_velocity_z = cursor.read_f32::<LittleEndian>()?;
+    let angular_acceleration_x = cursor.read_f32::<LittleEndian>()?;
+    let angular_acceleration_y = cursor.read_f32::<LittleEndian>()?;
+    let angular_acceleration_z = cursor.read_f32::<LittleEndian>()?;
+    let front_wheels_angle = cursor.read_f32::<LittleEndian>()?;
+
+    Ok(PacketMotionData {
+        header,
+        motion_data: car_motion_data,
+        suspension_position,
+        suspension_velocity,
+        suspension_acceleration,
+        wheel_speed,
+        wheel_slip,
+        local_velocity_x,
+        local_velocity_y,
+        local_velocity_z,
+        angular_velocity_x,
+        angular_velocity_y,
+        angular_velocity_z,
+        angular_acceleration_x,
+        angular_acceleration_y,
+        angular_acceleration_z,
+        front_wheels_angle,
+    })
+}
+
+fn ensure_motion_size(size: usize) -> Result<(), TelemetryError> {
+    if size < MOTION_MIN_SIZE {
+        return Err(TelemetryError::SizeMismatch {
+            packet: "Motion",
+            expected: MOTION_MIN_SIZE,
+            got: size,
+        });
+    }
+
+    Ok(())
+}
@@ -0,0 +1,309 @@
+use crate::decoder::Decoder;
+use crate::error::TelemetryError;
+use crate::f1_2019::car::{PacketCarSetupData, PacketCarStatusData, PacketCarTelemetryData};
+use crate::f1_2019::car_setup::parse_car_setup_data;
+use crate::f1_2019::car_status::parse_car_status_data;
+use crate::f1_2019::car_telemetry::parse_car_telemetry_data;
+use crate::f1_2019::event::{parse_event_data, PacketEventData};
+use crate::f1_2019::header::parse_headers;
+use crate::f1_2019::lap::{parse_lap_data, PacketLapData};
+use crate::f1_2019::motion::{parse_motion_data, PacketMotionData};
+use crate::f1_2019::participants::{parse_participants_data, PacketParticipantsData};
+use crate::f1_2019::session::{parse_session, PacketSessionData};
+use std::io::Cursor;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum PacketID {
+    Motion,
+    Session,
+    LapData,
+    Event,
+    Participants,
+    CarSetups,
+    CarTelemetry,
+    CarStatus,
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum Packet2019 {
+    Motion(PacketMotionData),
+    Session(PacketSessionData),
+    Lap(PacketLapData),
+    Event(PacketEventData),
+    Participants(PacketParticipantsData),
+    CarSetups(PacketCarSetupData),
+    CarTelemetry(PacketCarTelemetryData),
+    CarStatus(PacketCarStatusData),
+}
+
+pub fn parse_f12019(
+    cursor: &mut Cursor<&[u8]>,
+    size: usize,
+    decoder: &mut Decoder,
+) -> Result<Packet2019, TelemetryError> {
+    let header = parse_headers(cursor, size)?;
+    match packet_type(header.packet_id)? {
+        PacketID::Motion => {
+            let motion = parse_motion_data(cursor, header, size)?;
+            Ok(Packet2019::Motion(motion))
+        }
+        PacketID::Session => {
+            let session = parse_session(cursor, header, size, decoder)?;
+            Ok(Packet2019::Session(session))
+        }
+        PacketID::LapData => {
+            let lap = parse_lap_data(cursor, header, size, decoder)?;
+            Ok(Packet2019::Lap(lap))
+        }
+        PacketID::Event => {
+            let event = parse_event_data(cursor, header, size, decoder)?;
+            Ok(Packet2019::Event(event))
+        }
+        PacketID::Participants => {
+            let participants = parse_participants_data(cursor, header, size, decoder)?;
+            Ok(Packet2019::Participants(participants))
+        }
+        PacketID::CarSetups => {
+            let car_setups = parse_car_setup_data(cursor, header, size)?;
+            Ok(Packet2019::CarSetups(car_setups))
+        }
+        PacketID::CarTelemetry => {
+            let car_telemetry = parse_car_telemetry_data(cursor, header, size, decoder)?;
+            Ok(Packet2019::CarTelemetry(car_telemetry))
+        }
+        PacketID::CarStatus => {
+            let car_status = parse_car_status_data(cursor, header, size, decoder)?;
+            Ok(Packet2019::CarStatus(car_status))
+        }
+    }
+}
+
+pub fn packet_type(packet_id: u8) -> Result<PacketID, TelemetryError> {
+    match packet_id {
+        0 => Ok(PacketID::Motion),
+        1 => Ok(PacketID::Session),
+        2 => Ok(PacketID::LapData),
+        3 => Ok(PacketID::Event),
+        4 => Ok(PacketID::Participants),
+        5 => Ok(PacketID::CarSetups),
+        6 => Ok(PacketID::CarTelemetry),
+        7 => Ok(PacketID::CarStatus),
+        _ => Err(TelemetryError::UnknownPacketId { id: packet_id }),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::decoder::Decoder;
+    use crate::error::TelemetryError;
+    use crate::f1_2019::event::Event;
+    use crate::f1_2019::packet::{parse_f12019, Packet2019};
+    use std::io::Cursor;
+    use std::time::Duration;
+
+    use byteorder::{ByteOrder, LittleEndian, WriteBytesExt};
+
+    const HEADER_SIZE: usize = 23;
+
+    /// Header of a packet followed by zeros up to `size`.
+    fn packet(packet_id: u8, size: usize) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(size);
+        buf.write_u16::<LittleEndian>(2019).unwrap();
+        buf.write_u8(1).unwrap();
+        buf.write_u8(22).unwrap();
+        buf.write_u8(1).unwrap();
+        buf.write_u8(packet_id).unwrap();
+        buf.write_u64::<LittleEndian>(1).unwrap();
+        buf.write_f32::<LittleEndian>(1.0).unwrap();
+        buf.write_u32::<LittleEndian>(1).unwrap();
+        buf.write_u8(0).unwrap();
+        buf.resize(size, 0);
+        buf
+    }
+
+    fn parse(buf: &[u8]) -> Packet2019 {
+        parse_f12019(&mut Cursor::new(buf), buf.len(), &mut Decoder::default()).unwrap()
+    }
+
+    #[test]
+    fn rejects_packets_of_the_wrong_size() {
+        let sizes = [
+            (0, 1343),
+            (1, 149),
+            (2, 843),
+            (3, 32),
+            (4, 1104),
+            (5, 843),
+            (6, 1347),
+            (7, 1143),
+        ];
+        for (packet_id, size) in sizes {
+            let buf = packet(packet_id, size - 1);
+            let result = parse_f12019(
+                &mut Cursor::new(buf.as_slice()),
+                buf.len(),
+                &mut Decoder::default(),
+            );
+            match result {
+                Err(TelemetryError::SizeMismatch { expected, got, .. }) => {
+                    assert_eq!((size, size - 1), (expected, got))
+                }
+                r => panic!("Unexpected result {:?} for packet {}", r, packet_id),
+            }
+        }
+    }
+
+    #[test]
+    fn parse_motion_packet() {
+        let mut buf = packet(0, 1343);
+        // World position of the last car and the front wheels angle
+        LittleEndian::write_f32(&mut buf[HEADER_SIZE + 19 * 60..], 12.5);
+        LittleEndian::write_f32(&mut buf[1339..], -0.25);
+
+        match parse(&buf) {
+            Packet2019::Motion(motion) => {
+                assert_eq!(20, motion.motion_data.len());
+                assert_eq!(12.5, motion.motion_data[19].world_position_x);
+                assert_eq!(-0.25, motion.front_wheels_angle);
+            }
+            p => panic!("Unexpected packet {:?}", p),
+        }
+    }
+
+    #[test]
+    fn parse_session_packet() {
+        let mut buf = packet(1, 149);
+        buf[HEADER_SIZE + 3] = 53;
+        LittleEndian::write_u16(&mut buf[HEADER_SIZE + 4..], 5793);
+
+        match parse(&buf) {
+            Packet2019::Session(session) => {
+                assert_eq!(53, session.total_laps);
+                assert_eq!(5793, session.track_length);
+            }
+            p => panic!("Unexpected packet {:?}", p),
+        }
+    }
+
+    #[test]
+    fn parse_lap_packet() {
+        let mut buf = packet(2, 843);
+        let car = HEADER_SIZE + 19 * 41;
+        LittleEndian::write_f32(&mut buf[car..], 81.5);
+        buf[car + 32] = 20;
+        buf[car + 33] = 14;
+
+        match parse(&buf) {
+            Packet2019::Lap(lap) => {
+                let car = &lap.lap_data[19];
+                assert_eq!(Duration::from_millis(81_500), car.last_lap_time);
+                assert_eq!(20, car.car_position);
+                assert_eq!(14, car.current_lap_num);
+            }
+            p => panic!("Unexpected packet {:?}", p),
+        }
+    }
+
+    #[test]
+    fn parse_participants_packet() {
+        let mut buf = packet(4, 1104);
+        buf[HEADER_SIZE] = 20;
+        let car = HEADER_SIZE + 1 + 19 * 54;
+        buf[car + 3] = 44;
+        buf[car + 5..car + 13].copy_from_slice(b"HAMILTON");
+
+        match parse(&buf) {
+            Packet2019::Participants(participants) => {
+                assert_eq!(20, participants.num_active_cars);
+                assert_eq!(44, participants.participants[19].race_number);
+                assert_eq!("HAMILTON", participants.participants[19].name);
+            }
+            p => panic!("Unexpected packet {:?}", p),
+        }
+    }
+
+    #[test]
+    fn parse_car_setups_packet() {
+        let mut buf = packet(5, 843);
+        let car = HEADER_SIZE + 19 * 41;
+        buf[car] = 7;
+        LittleEndian::write_f32(&mut buf[car + 37..], 12.5);
+
+        match parse(&buf) {
+            Packet2019::CarSetups(setups) => {
+                assert_eq!(7, setups.car_setup_data[19].front_wing);
+                assert_eq!(12.5, setups.car_setup_data[19].fuel_load);
+            }
+            p => panic!("Unexpected packet {:?}", p),
+        }
+    }
+
+    #[test]
+    fn parse_car_telemetry_packet() {
+        let mut buf = packet(6, 1347);
+        let car = HEADER_SIZE + 19 * 66;
+        LittleEndian::write_u16(&mut buf[car..], 312);
+        buf[car + 15] = 7;
+        LittleEndian::write_u32(&mut buf[1343..], 0x0001);
+
+        match parse(&buf) {
+            Packet2019::CarTelemetry(telemetry) => {
+                assert_eq!(312, telemetry.car_telemetry_data[19].speed);
+                assert_eq!(7, telemetry.car_telemetry_data[19].gear);
+                assert_eq!(0x0001, telemetry.button_status);
+            }
+            p => panic!("Unexpected packet {:?}", p),
+        }
+    }
+
+    #[test]
+    fn parse_car_status_packet() {
+        let mut buf = packet(7, 1143);
+        let car = HEADER_SIZE + 19 * 56;
+        LittleEndian::write_f32(&mut buf[car + 5..], 12.5);
+        buf[car + 23] = 19;
+        LittleEndian::write_f32(&mut buf[car + 39..], 4_000_000.0);
+
+        match parse(&buf) {
+            Packet2019::CarStatus(status) => {
+                let car = &status.car_status_data[19];
+                assert_eq!(12.5, car.fuel_in_tank);
+                assert_eq!(19, car.tyres_wear.rear_left);
+                assert_eq!(4_000_000.0, car.ers_store_energy);
+            }
+            p => panic!("Unexpected packet {:?}", p),
+        }
+    }
+
+    #[test]
+    fn parse_event_packet() {
+        let mut buf = Vec::with_capacity(32);
+        buf.write_u16::<LittleEndian>(2019).unwrap();
+        buf.write_u8(1).unwrap();
+        buf.write_u8(22).unwrap();
+        buf.write_u8(1).unwrap();
+        buf.write_u8(3).unwrap();
+        buf.write_u64::<LittleEndian>(1).unwrap();
+        buf.write_f32::<LittleEndian>(1.0).unwrap();
+        buf.write_u32::<LittleEndian>(1).unwrap();
+        buf.write_u8(0).unwrap();
+        buf.extend_from_slice(b"RCWN");
+        buf.write_u8(4).unwrap();
+        buf.extend_from_slice(&[0; 4]);
+
+        let mut cursor = Cursor::new(buf.as_slice());
+        let len = cursor.get_ref().len();
+        let result = parse_f12019(&mut cursor, len, &mut Decoder::default()).unwrap();
+
+        match result {
+            Packet2019::Event(event) => {
+                assert_eq!(2019, event.header.packet_format);
+                match event.event {
+                    Event::RaceWinner(winner) => assert_eq!(4, winner.vehicle_index),
+                    e => panic!("Unexpected event {:?}", e),
+                }
+            }
+            p => panic!("Unexpected packet {:?}", p),
+        }
+    }
+}
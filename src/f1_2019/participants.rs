@@ -0,0 +1,140 @@
+use crate::decoder::Decoder;
+use crate::error::TelemetryError;
+use crate::f1_2019::car::TOTAL_CARS;
+use crate::f1_2019::header::PacketHeader;
+use crate::f1_2020::participants::{
+    parse_driver, parse_name, parse_nationality, parse_your_telemetry, ParticipantData,
+};
+use crate::f1_2020::team::Team;
+use byteorder::ReadBytesExt;
+use std::io::Cursor;
+
+const PARTICIPANTS_MIN_SIZE: usize = 1104;
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct PacketParticipantsData {
+    // Header
+    pub header: PacketHeader,
+    // Number of active cars in the data – should match number of
+    // cars on HUD
+    pub num_active_cars: u8,
+    pub participants: Vec<ParticipantData>,
+}
+
+pub fn parse_participants_data(
+    cursor: &mut Cursor<&[u8]>,
+    header: PacketHeader,
+    size: usize,
+    decoder: &mut Decoder,
+) -> Result<PacketParticipantsData, TelemetryError> {
+    ensure_participants_size(size)?;
+
+    let num_active_cars = cursor.read_u8()?;
+
+    let mut participants = Vec::with_capacity(TOTAL_CARS);
+    for _ in 0..TOTAL_CARS {
+        let participant = parse_participant(cursor, decoder)?;
+        participants.push(participant);
+    }
+
+    Ok(PacketParticipantsData {
+        header,
+        num_active_cars,
+        participants,
+    })
+}
+
+fn ensure_participants_size(size: usize) -> Result<(), TelemetryError> {
+    if size < PARTICIPANTS_MIN_SIZE {
+        return Err(TelemetryError::SizeMismatch {
+            packet: "Participants",
+            expected: PARTICIPANTS_MIN_SIZE,
+            got: size,
+        });
+    }
+
+    Ok(())
+}
+
+fn parse_participant(
+    cursor: &mut Cursor<&[u8]>,
+    decoder: &mut Decoder,
+) -> Result<ParticipantData, TelemetryError> {
+    let ai_controlled = cursor.read_u8()? == 1;
+    let driver = parse_driver(cursor.read_u8()?, decoder)?;
+    let team = parse_team(cursor.read_u8()?, decoder)?;
+    let race_number = cursor.read_u8()?;
+    let nationality = parse_nationality(cursor.read_u8()?, decoder)?;
+    let name = parse_name(cursor)?;
+    let your_telemetry = parse_your_telemetry(cursor.read_u8()?, decoder)?;
+
+    Ok(ParticipantData {
+        ai_controlled,
+        driver,
+        team,
+        race_number,
+        nationality,
+        name,
+        your_telemetry,
+    })
+}
+
+pub fn parse_team(value: u8, decoder: &mut Decoder) -> Result<Team, TelemetryError> {
+    match value {
+        0 => Ok(Team::Mercedes),
+        1 => Ok(Team::Ferrari),
+        2 => Ok(Team::RedBullRacing),
+        3 => Ok(Team::Williams),
+        4 => Ok(Team::RacingPoint),
+        5 => Ok(Team::Renault),
+        6 => Ok(Team::ToroRosso),
+        7 => Ok(Team::Haas),
+        8 => Ok(Team::McLaren),
+        9 => Ok(Team::AlfaRomeo),
+        10 => Ok(Team::McLaren1988),
+        11 => Ok(Team::McLaren1991),
+        12 => Ok(Team::Williams1992),
+        13 => Ok(Team::Ferrari1995),
+        14 => Ok(Team::Williams1996),
+        15 => Ok(Team::McLaren1998),
+        16 => Ok(Team::Ferrari2002),
+        17 => Ok(Team::Ferrari2004),
+        18 => Ok(Team::Renault2006),
+        19 => Ok(Team::Ferrari2007),
+        21 => Ok(Team::RedBull2010),
+        22 => Ok(Team::Ferrari1976),
+        23 => Ok(Team::ARTGrandPrix),
+        24 => Ok(Team::CamposVexatecRacing),
+        25 => Ok(Team::Carlin),
+        26 => Ok(Team::CharouzRacingSystem),
+        27 => Ok(Team::DAMS),
+        28 => Ok(Team::RussianTime),
+        29 => Ok(Team::MPMotorsport),
+        30 => Ok(Team::Pertamina),
+        31 => Ok(Team::McLaren1990),
+        32 => Ok(Team::Trident),
+        33 => Ok(Team::BWTArden),
+        34 => Ok(Team::McLaren1976),
+        35 => Ok(Team::Lotus1972),
+        36 => Ok(Team::Ferrari1979),
+        37 => Ok(Team::McLaren1982),
+        38 => Ok(Team::Williams2003),
+        39 => Ok(Team::Brawn2009),
+        40 => Ok(Team::Lotus1978),
+        42 => Ok(Team::ArtGP2019),
+        43 => Ok(Team::Campos2019),
+        44 => Ok(Team::Carlin2019),
+        45 => Ok(Team::SauberJuniorCharouz2019),
+        46 => Ok(Team::Dams2019),
+        47 => Ok(Team::UniVirtuosi2019),
+        48 => Ok(Team::MPMotorsport2019),
+        49 => Ok(Team::Prema2019),
+        50 => Ok(Team::Trident2019),
+        51 => Ok(Team::Arden2019),
+        63 => Ok(Team::Ferrari1990),
+        64 => Ok(Team::McLaren2010),
+        65 => Ok(Team::Ferrari2010),
+        255 => Ok(Team::MyTeam),
+        _ => decoder.unknown("team", value, Team::Unknown(value)),
+    }
+}
@@ -0,0 +1,141 @@
+use crate::decoder::Decoder;
+use crate::error::TelemetryError;
+use crate::f1_2019::header::PacketHeader;
+use crate::f1_2020::session::{
+    parse_flag, parse_formula, parse_network_game, parse_safety_car, parse_session_type,
+    parse_track, parse_weather, Formula, MarshalZone, NetworkGame, SafetyCar, SessionType, Track,
+    Weather,
+};
+use byteorder::{LittleEndian, ReadBytesExt};
+use derivative::Derivative;
+use std::io::Cursor;
+
+const SESSION_MIN_SIZE: usize = 149;
+const MARSHAL_ZONE_MAX: usize = 21;
+
+#[derive(Debug, PartialEq, Clone, Derivative)]
+#[derivative(Eq)]
+pub struct PacketSessionData {
+    /// Header
+    pub header: PacketHeader,
+    /// Weather - 0 = clear, 1 = light cloud, 2 = overcast, 3 = light rain, 4 = heavy rain, 5 = storm
+    pub weather: Weather, //u8,
+    /// Track temp. in degrees celsius
+    pub track_temperature: i8,
+    /// Air temp. in degrees celsius
+    pub air_temperature: i8,
+    /// Total number of laps in this race
+    pub total_laps: u8,
+    /// Track length in metres
+    pub track_length: u16,
+    /// 0 = unknown, 1 = P1, 2 = P2, 3 = P3, 4 = Short P
+    /// 5 = Q1, 6 = Q2, 7 = Q3, 8 = Short Q, 9 = OSQ
+    /// 10 = R, 11 = R2, 12 = Time Trial
+    pub session_type: SessionType, //u8,
+    /// -1 for unknown, 0-21 for tracks, see appendix
+    pub track_id: Track, //i8,
+    /// Formula, 0 = F1 Modern, 1 = F1 Classic, 2 = F2,
+    /// 3 = F1 Generic
+    pub formula: Formula, // u8,
+    /// Time left in session in seconds
+    pub session_time_left: u16,
+    /// Session duration in seconds
+    pub session_duration: u16,
+    /// Pit speed limit in kilometres per hour
+    pub pit_speed_limit: u8,
+    /// Whether the game is paused
+    pub game_paused: u8,
+    /// Whether the player is spectating
+    pub is_spectating: u8,
+    /// Index of the car being spectated
+    pub spectator_car_index: u8,
+    /// SLI Pro support, 0 = inactive, 1 = active
+    pub sli_pro_native_support: u8,
+    /// Number of marshal zones to follow
+    pub num_marshal_zones: u8,
+    /// List of marshal zones – max 21
+    pub marshal_zone: Vec<MarshalZone>,
+    /// 0 = no safety car, 1 = full safety car
+    /// 2 = virtual safety car
+    pub safety_car_status: SafetyCar, //u8,
+    /// 0 = offline, 1 = online
+    pub network_game: NetworkGame, // u8,
+}
+
+pub fn parse_session(
+    cursor: &mut Cursor<&[u8]>,
+    header: PacketHeader,
+    size: usize,
+    decoder: &mut Decoder,
+) -> Result<PacketSessionData, TelemetryError> {
+    ensure_session_size(size)?;
+
+    let weather = parse_weather(cursor.read_u8()?, decoder)?;
+    let track_temperature = cursor.read_i8()?;
+    let air_temperature = cursor.read_i8()?;
+    let total_laps = cursor.read_u8()?;
+    let track_length = cursor.read_u16::<LittleEndian>()?;
+    let session_type = parse_session_type(cursor.read_u8()?, decoder)?;
+    let track_id = parse_track(cursor.read_i8()?, decoder)?;
+    let formula = parse_formula(cursor.read_u8()?, decoder)?;
+    let session_time_left = cursor.read_u16::<LittleEndian>()?;
+    let session_duration = cursor.read_u16::<LittleEndian>()?;
+    let pit_speed_limit = cursor.read_u8()?;
+    let game_paused = cursor.read_u8()?;
+    let is_spectating = cursor.read_u8()?;
+    let spectator_car_index = cursor.read_u8()?;
+    let sli_pro_native_support = cursor.read_u8()?;
+
+    // The marshal zone array is fixed size on the wire, only the first
+    // `num_marshal_zones` entries are meaningful.
+    let num_marshal_zones = cursor.read_u8()?;
+    let mut marshal_zone = Vec::with_capacity(MARSHAL_ZONE_MAX);
+    for i in 0..MARSHAL_ZONE_MAX {
+        let zone_start = cursor.read_f32::<LittleEndian>()?;
+        let zone_flag = cursor.read_i8()?;
+        if i < num_marshal_zones as usize {
+            marshal_zone.push(MarshalZone {
+                zone_start,
+                zone_flag: parse_flag(zone_flag, decoder)?,
+            });
+        }
+    }
+
+    let safety_car_status = parse_safety_car(cursor.read_u8()?, decoder)?;
+    let network_game = parse_network_game(cursor.read_u8()?, decoder)?;
+
+    Ok(PacketSessionData {
+        header,
+        weather,
+        track_temperature,
+        air_temperature,
+        total_laps,
+        track_length,
+        session_type,
+        track_id,
+        formula,
+        session_time_left,
+        session_duration,
+        pit_speed_limit,
+        game_paused,
+        is_spectating,
+        spectator_car_index,
+        sli_pro_native_support,
+        num_marshal_zones,
+        marshal_zone,
+        safety_car_status,
+        network_game,
+    })
+}
+
+fn ensure_session_size(size: usize) -> Result<(), TelemetryError> {
+    if size == SESSION_MIN_SIZE {
+        return Ok(());
+    }
+
+    Err(TelemetryError::SizeMismatch {
+        packet: "Session",
+        expected: SESSION_MIN_SIZE,
+        got: size,
+    })
+}
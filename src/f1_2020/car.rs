@@ -62,17 +62,18 @@ pub enum SurfaceType {
     Cobblestone,
     Metal,
     Ridged,
-    Unknown,
+    Unknown(u8),
 }
 
 #[derive(Debug, Eq, PartialEq, Copy, Clone)]
 pub enum MFDPanel {
-    CarSetup = 0,
-    Pits = 1,
-    Damage = 2,
-    Engine = 3,
-    Temperatures = 4,
-    Closed = 255,
+    CarSetup,     // 0
+    Pits,         // 1
+    Damage,       // 2
+    Engine,       // 3
+    Temperatures, // 4
+    Closed,       // 255
+    Unknown(u8),
 }
 
 #[derive(Debug, PartialEq, Clone, Derivative)]
@@ -111,20 +112,23 @@ pub enum TractionControl {
     Off,
     Low,
     High,
+    Unknown(u8),
 }
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub enum AntiLockBrakes {
     Off,
     On,
+    Unknown(u8),
 }
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub enum FuelMix {
-    Lean = 0,
-    Standard = 1,
-    Rich = 2,
-    Max = 3,
+    Lean,     // 0
+    Standard, // 1
+    Rich,     // 2
+    Max,      // 3
+    Unknown(u8),
 }
 
 impl FuelMix {
@@ -134,15 +138,16 @@ impl FuelMix {
             FuelMix::Standard => "Standard",
             FuelMix::Rich => "Rich",
             FuelMix::Max => "Max",
+            FuelMix::Unknown(_) => "Unknown",
         }
     }
 }
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub enum DRSStatus {
-    NotAllowed = 0,
-    Allowed = 1,
-    Unknown = 2,
+    NotAllowed, // 0
+    Allowed,    // 1
+    Unknown(i8),
 }
 
 impl DRSStatus {
@@ -150,19 +155,24 @@ impl DRSStatus {
         match self {
             DRSStatus::NotAllowed => "Not Allowed",
             DRSStatus::Allowed => "Allowed",
-            DRSStatus::Unknown => "Unknown",
+            DRSStatus::Unknown(_) => "Unknown",
         }
     }
 }
 
+/// Wire values differ per game: F1 2018 and 2019 send 0 = none, 1 = low,
+/// 2 = medium, 3 = high, 4 = overtake, 5 = hotlap; F1 2020 sends 0 = none,
+/// 1 = medium, 2 = overtake, 3 = hotlap; F1 2021 sends 0 = none, 1 = medium,
+/// 2 = hotlap, 3 = overtake.
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub enum ERSDeploymentMode {
-    None = 0,
-    Low = 1,
-    Medium = 2,
-    High = 3,
-    Overtake = 4,
-    Hotlap = 5,
+    None,
+    Low, // F1 2018 and 2019 only
+    Medium,
+    High, // F1 2018 and 2019 only
+    Overtake,
+    Hotlap,
+    Unknown(u8),
 }
 
 impl ERSDeploymentMode {
@@ -174,44 +184,46 @@ impl ERSDeploymentMode {
             ERSDeploymentMode::High => "High",
             ERSDeploymentMode::Overtake => "Overtake",
             ERSDeploymentMode::Hotlap => "Hotlap",
+            ERSDeploymentMode::Unknown(_) => "Unknown",
         }
     }
 }
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub enum ActualTyreCompound {
-    C5 = 16,
-    C4 = 17,
-    C3 = 18,
-    C2 = 19,
-    C1 = 20,
-    Inter = 7,
-    Wet = 8,
-    F1ClassicDry = 9,
-    F1ClassicWet = 10,
-    F2SuperSoft = 11,
-    F2Soft = 12,
-    F2Medium = 13,
-    F2Hard = 14,
-    F2Wet = 15,
-    Unknown = 0,
+    C5,           // 16
+    C4,           // 17
+    C3,           // 18
+    C2,           // 19
+    C1,           // 20
+    C0,           // 21
+    Inter,        // 7
+    Wet,          // 8
+    F1ClassicDry, // 9
+    F1ClassicWet, // 10
+    F2SuperSoft,  // 11
+    F2Soft,       // 12
+    F2Medium,     // 13
+    F2Hard,       // 14
+    F2Wet,        // 15
+    Unknown(u8),
 }
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub enum VisualTyreCompound {
-    Soft = 16,
-    Medium = 17,
-    Hard = 18,
-    Inter = 7,
-    Wet = 8,
-    F1ClassicDry = 9,
-    F1ClassicWet = 10,
-    F2SuperSoft = 11,
-    F2Soft = 12,
-    F2Medium = 13,
-    F2Hard = 14,
-    F2Wet = 15,
-    Unknown = 0,
+    Soft,         // 16
+    Medium,       // 17
+    Hard,         // 18
+    Inter,        // 7
+    Wet,          // 8
+    F1ClassicDry, // 9
+    F1ClassicWet, // 10
+    F2SuperSoft,  // 11
+    F2Soft,       // 12
+    F2Medium,     // 13
+    F2Hard,       // 14
+    F2Wet,        // 15
+    Unknown(u8),
 }
 
 impl VisualTyreCompound {
@@ -229,7 +241,7 @@ impl VisualTyreCompound {
             VisualTyreCompound::F2Medium => "Medium",
             VisualTyreCompound::F2Hard => "Hard",
             VisualTyreCompound::F2Wet => "Wet",
-            VisualTyreCompound::Unknown => "[N/A]",
+            VisualTyreCompound::Unknown(_) => "[N/A]",
         }
     }
 }
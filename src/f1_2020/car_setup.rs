@@ -1,20 +1,21 @@
+use crate::error::TelemetryError;
 use crate::f1_2020::car::{
     CarSetupData, PacketCarSetupData, TyrePressure, CAR_SETUP_MIN_SIZE, TOTAL_CARS,
 };
-use crate::f1_2020::header::PacketHeader;
-use async_std::io::{Cursor, Error, ErrorKind};
-use byteorder_async::{LittleEndian, ReaderToByteOrder};
+use crate::f1_2020::header::{write_header, PacketHeader};
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use std::io::{Cursor, Write};
 
-pub async fn parse_car_setup_data(
-    cursor: &mut Cursor<Vec<u8>>,
+pub fn parse_car_setup_data(
+    cursor: &mut Cursor<&[u8]>,
     header: PacketHeader,
     size: usize,
-) -> Result<PacketCarSetupData, Error> {
+) -> Result<PacketCarSetupData, TelemetryError> {
     ensure_car_setup_size(size)?;
 
     let mut car_setup_data = Vec::with_capacity(TOTAL_CARS);
     for _ in 0..TOTAL_CARS {
-        let csd = parse_car_setup(cursor).await?;
+        let csd = parse_car_setup(cursor)?;
         car_setup_data.push(csd);
     }
 
@@ -24,33 +25,33 @@ pub async fn parse_car_setup_data(
     })
 }
 
-async fn parse_car_setup(cursor: &mut Cursor<Vec<u8>>) -> Result<CarSetupData, Error> {
-    let front_wing = cursor.byte_order().read_u8().await?;
-    let rear_wing = cursor.byte_order().read_u8().await?;
-    let on_throttle = cursor.byte_order().read_u8().await?;
-    let off_throttle = cursor.byte_order().read_u8().await?;
-    let front_camber = cursor.byte_order().read_f32::<LittleEndian>().await?;
-    let rear_camber = cursor.byte_order().read_f32::<LittleEndian>().await?;
-    let front_toe = cursor.byte_order().read_f32::<LittleEndian>().await?;
-    let rear_toe = cursor.byte_order().read_f32::<LittleEndian>().await?;
-    let front_suspension = cursor.byte_order().read_u8().await?;
-    let rear_suspension = cursor.byte_order().read_u8().await?;
-    let front_anti_roll_bar = cursor.byte_order().read_u8().await?;
-    let rear_anti_roll_bar = cursor.byte_order().read_u8().await?;
-    let front_suspension_height = cursor.byte_order().read_u8().await?;
-    let rear_suspension_height = cursor.byte_order().read_u8().await?;
-    let brake_pressure = cursor.byte_order().read_u8().await?;
-    let brake_bias = cursor.byte_order().read_u8().await?;
+pub fn parse_car_setup(cursor: &mut Cursor<&[u8]>) -> Result<CarSetupData, TelemetryError> {
+    let front_wing = cursor.read_u8()?;
+    let rear_wing = cursor.read_u8()?;
+    let on_throttle = cursor.read_u8()?;
+    let off_throttle = cursor.read_u8()?;
+    let front_camber = cursor.read_f32::<LittleEndian>()?;
+    let rear_camber = cursor.read_f32::<LittleEndian>()?;
+    let front_toe = cursor.read_f32::<LittleEndian>()?;
+    let rear_toe = cursor.read_f32::<LittleEndian>()?;
+    let front_suspension = cursor.read_u8()?;
+    let rear_suspension = cursor.read_u8()?;
+    let front_anti_roll_bar = cursor.read_u8()?;
+    let rear_anti_roll_bar = cursor.read_u8()?;
+    let front_suspension_height = cursor.read_u8()?;
+    let rear_suspension_height = cursor.read_u8()?;
+    let brake_pressure = cursor.read_u8()?;
+    let brake_bias = cursor.read_u8()?;
     let rear_tyre_pressure = TyrePressure {
-        left: cursor.byte_order().read_f32::<LittleEndian>().await?,
-        right: cursor.byte_order().read_f32::<LittleEndian>().await?,
+        left: cursor.read_f32::<LittleEndian>()?,
+        right: cursor.read_f32::<LittleEndian>()?,
     };
     let front_tyre_pressure = TyrePressure {
-        left: cursor.byte_order().read_f32::<LittleEndian>().await?,
-        right: cursor.byte_order().read_f32::<LittleEndian>().await?,
+        left: cursor.read_f32::<LittleEndian>()?,
+        right: cursor.read_f32::<LittleEndian>()?,
     };
-    let ballast = cursor.byte_order().read_u8().await?;
-    let fuel_load = cursor.byte_order().read_f32::<LittleEndian>().await?;
+    let ballast = cursor.read_u8()?;
+    let fuel_load = cursor.read_f32::<LittleEndian>()?;
 
     Ok(CarSetupData {
         front_wing,
@@ -76,13 +77,62 @@ async fn parse_car_setup(cursor: &mut Cursor<Vec<u8>>) -> Result<CarSetupData, E
     })
 }
 
-fn ensure_car_setup_size(size: usize) -> Result<(), Error> {
+pub fn write_car_setup_data<W: Write>(
+    writer: &mut W,
+    packet: &PacketCarSetupData,
+) -> Result<(), TelemetryError> {
+    if packet.car_setup_data.len() != TOTAL_CARS {
+        return Err(TelemetryError::Unencodable {
+            field: "car_setup_data",
+        });
+    }
+
+    write_header(writer, &packet.header)?;
+    for setup in &packet.car_setup_data {
+        write_car_setup(writer, setup)?;
+    }
+
+    Ok(())
+}
+
+pub fn write_car_setup<W: Write>(
+    writer: &mut W,
+    setup: &CarSetupData,
+) -> Result<(), TelemetryError> {
+    writer.write_u8(setup.front_wing)?;
+    writer.write_u8(setup.rear_wing)?;
+    writer.write_u8(setup.on_throttle)?;
+    writer.write_u8(setup.off_throttle)?;
+    writer.write_f32::<LittleEndian>(setup.front_camber)?;
+    writer.write_f32::<LittleEndian>(setup.rear_camber)?;
+    writer.write_f32::<LittleEndian>(setup.front_toe)?;
+    writer.write_f32::<LittleEndian>(setup.rear_toe)?;
+    writer.write_u8(setup.front_suspension)?;
+    writer.write_u8(setup.rear_suspension)?;
+    writer.write_u8(setup.front_anti_roll_bar)?;
+    writer.write_u8(setup.rear_anti_roll_bar)?;
+    writer.write_u8(setup.front_suspension_height)?;
+    writer.write_u8(setup.rear_suspension_height)?;
+    writer.write_u8(setup.brake_pressure)?;
+    writer.write_u8(setup.brake_bias)?;
+    writer.write_f32::<LittleEndian>(setup.rear_tyre_pressure.left)?;
+    writer.write_f32::<LittleEndian>(setup.rear_tyre_pressure.right)?;
+    writer.write_f32::<LittleEndian>(setup.front_tyre_pressure.left)?;
+    writer.write_f32::<LittleEndian>(setup.front_tyre_pressure.right)?;
+    writer.write_u8(setup.ballast)?;
+    writer.write_f32::<LittleEndian>(setup.fuel_load)?;
+
+    Ok(())
+}
+
+fn ensure_car_setup_size(size: usize) -> Result<(), TelemetryError> {
     if size == CAR_SETUP_MIN_SIZE {
         return Ok(());
     }
 
-    Err(Error::new(
-        ErrorKind::InvalidData,
-        "Car setup size is too small",
-    ))
+    Err(TelemetryError::SizeMismatch {
+        packet: "Car setup",
+        expected: CAR_SETUP_MIN_SIZE,
+        got: size,
+    })
 }
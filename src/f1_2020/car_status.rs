@@ -1,23 +1,26 @@
+use crate::decoder::Decoder;
+use crate::error::TelemetryError;
 use crate::f1_2020::car::{
     ActualTyreCompound, AntiLockBrakes, CarStatusData, DRSStatus, ERSDeploymentMode, FuelMix,
     PacketCarStatusData, TractionControl, VisualTyreCompound, CAR_STATUS_MIN_SIZE, TOTAL_CARS,
 };
-use crate::f1_2020::header::PacketHeader;
+use crate::f1_2020::header::{write_header, PacketHeader};
 use crate::f1_2020::motion::Wheel;
-use crate::f1_2020::session::parse_flag;
-use async_std::io::{Cursor, Error, ErrorKind};
-use byteorder_async::{LittleEndian, ReaderToByteOrder};
+use crate::f1_2020::session::{encode_flag, parse_flag};
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use std::io::{Cursor, Write};
 
-pub async fn parse_car_status_data(
-    cursor: &mut Cursor<Vec<u8>>,
+pub fn parse_car_status_data(
+    cursor: &mut Cursor<&[u8]>,
     header: PacketHeader,
     size: usize,
-) -> Result<PacketCarStatusData, Error> {
+    decoder: &mut Decoder,
+) -> Result<PacketCarStatusData, TelemetryError> {
     ensure_car_status_size(size)?;
 
     let mut car_status_data = Vec::with_capacity(TOTAL_CARS);
     for _ in 0..TOTAL_CARS {
-        let csd = parse_car_status(cursor).await?;
+        let csd = parse_car_status(cursor, decoder)?;
         car_status_data.push(csd);
     }
 
@@ -27,47 +30,50 @@ pub async fn parse_car_status_data(
     })
 }
 
-async fn parse_car_status(cursor: &mut Cursor<Vec<u8>>) -> Result<CarStatusData, Error> {
-    let traction_control = parse_traction_control(cursor.byte_order().read_u8().await?)?;
-    let anti_lock_brakes = parse_anti_lock_brakes(cursor.byte_order().read_u8().await?)?;
-    let fuel_mix = parse_fuel_mix(cursor.byte_order().read_u8().await?)?;
-    let front_brake_bias = cursor.byte_order().read_u8().await?;
-    let pit_limiter = cursor.byte_order().read_u8().await? == 1;
-    let fuel_in_tank = cursor.byte_order().read_f32::<LittleEndian>().await?;
-    let fuel_capacity = cursor.byte_order().read_f32::<LittleEndian>().await?;
-    let fuel_remaining_laps = cursor.byte_order().read_f32::<LittleEndian>().await?;
-    let max_rpm = cursor.byte_order().read_u16::<LittleEndian>().await?;
-    let idle_rpm = cursor.byte_order().read_u16::<LittleEndian>().await?;
-    let max_gears = cursor.byte_order().read_u8().await?;
-    let drs_allowed = parse_drs(cursor.byte_order().read_i8().await?)?;
-    let drs_activation_distance = cursor.byte_order().read_u16::<LittleEndian>().await?;
+fn parse_car_status(
+    cursor: &mut Cursor<&[u8]>,
+    decoder: &mut Decoder,
+) -> Result<CarStatusData, TelemetryError> {
+    let traction_control = parse_traction_control(cursor.read_u8()?, decoder)?;
+    let anti_lock_brakes = parse_anti_lock_brakes(cursor.read_u8()?, decoder)?;
+    let fuel_mix = parse_fuel_mix(cursor.read_u8()?, decoder)?;
+    let front_brake_bias = cursor.read_u8()?;
+    let pit_limiter = cursor.read_u8()? == 1;
+    let fuel_in_tank = cursor.read_f32::<LittleEndian>()?;
+    let fuel_capacity = cursor.read_f32::<LittleEndian>()?;
+    let fuel_remaining_laps = cursor.read_f32::<LittleEndian>()?;
+    let max_rpm = cursor.read_u16::<LittleEndian>()?;
+    let idle_rpm = cursor.read_u16::<LittleEndian>()?;
+    let max_gears = cursor.read_u8()?;
+    let drs_allowed = parse_drs(cursor.read_i8()?, decoder)?;
+    let drs_activation_distance = cursor.read_u16::<LittleEndian>()?;
     let tyres_wear = Wheel {
-        rear_left: cursor.byte_order().read_u8().await?,
-        rear_right: cursor.byte_order().read_u8().await?,
-        front_left: cursor.byte_order().read_u8().await?,
-        front_right: cursor.byte_order().read_u8().await?,
+        rear_left: cursor.read_u8()?,
+        rear_right: cursor.read_u8()?,
+        front_left: cursor.read_u8()?,
+        front_right: cursor.read_u8()?,
     };
-    let actual_tyre_compound = parse_actual_tyre_compound(cursor.byte_order().read_u8().await?)?;
-    let visual_tyre_compound = parse_visual_tyre_compound(cursor.byte_order().read_u8().await?)?;
-    let tyres_age_laps = cursor.byte_order().read_u8().await?;
+    let actual_tyre_compound = parse_actual_tyre_compound(cursor.read_u8()?, decoder)?;
+    let visual_tyre_compound = parse_visual_tyre_compound(cursor.read_u8()?, decoder)?;
+    let tyres_age_laps = cursor.read_u8()?;
     let tyres_damage = Wheel {
-        rear_left: cursor.byte_order().read_u8().await?,
-        rear_right: cursor.byte_order().read_u8().await?,
-        front_left: cursor.byte_order().read_u8().await?,
-        front_right: cursor.byte_order().read_u8().await?,
+        rear_left: cursor.read_u8()?,
+        rear_right: cursor.read_u8()?,
+        front_left: cursor.read_u8()?,
+        front_right: cursor.read_u8()?,
     };
-    let front_left_wing_damage = cursor.byte_order().read_u8().await?;
-    let front_right_wing_damage = cursor.byte_order().read_u8().await?;
-    let rear_wing_damage = cursor.byte_order().read_u8().await?;
-    let drs_fault = cursor.byte_order().read_u8().await? == 1;
-    let engine_damage = cursor.byte_order().read_u8().await?;
-    let gear_box_damage = cursor.byte_order().read_u8().await?;
-    let vehicle_fia_flags = parse_flag(cursor.byte_order().read_i8().await?)?;
-    let ers_store_energy = cursor.byte_order().read_f32::<LittleEndian>().await?;
-    let ers_deploy_mode = parse_ers_deployment_mode(cursor.byte_order().read_u8().await?)?;
-    let ers_harvested_this_lap_mguk = cursor.byte_order().read_f32::<LittleEndian>().await?;
-    let ers_harvested_this_lap_mguh = cursor.byte_order().read_f32::<LittleEndian>().await?;
-    let ers_deployed_this_lap = cursor.byte_order().read_f32::<LittleEndian>().await?;
+    let front_left_wing_damage = cursor.read_u8()?;
+    let front_right_wing_damage = cursor.read_u8()?;
+    let rear_wing_damage = cursor.read_u8()?;
+    let drs_fault = cursor.read_u8()? == 1;
+    let engine_damage = cursor.read_u8()?;
+    let gear_box_damage = cursor.read_u8()?;
+    let vehicle_fia_flags = parse_flag(cursor.read_i8()?, decoder)?;
+    let ers_store_energy = cursor.read_f32::<LittleEndian>()?;
+    let ers_deploy_mode = parse_ers_deployment_mode(cursor.read_u8()?, decoder)?;
+    let ers_harvested_this_lap_mguk = cursor.read_f32::<LittleEndian>()?;
+    let ers_harvested_this_lap_mguh = cursor.read_f32::<LittleEndian>()?;
+    let ers_deployed_this_lap = cursor.read_f32::<LittleEndian>()?;
 
     Ok(CarStatusData {
         traction_control,
@@ -103,62 +109,174 @@ async fn parse_car_status(cursor: &mut Cursor<Vec<u8>>) -> Result<CarStatusData,
     })
 }
 
-fn ensure_car_status_size(size: usize) -> Result<(), Error> {
+pub fn write_car_status_data<W: Write>(
+    writer: &mut W,
+    packet: &PacketCarStatusData,
+) -> Result<(), TelemetryError> {
+    if packet.car_status_data.len() != TOTAL_CARS {
+        return Err(TelemetryError::Unencodable {
+            field: "car_status_data",
+        });
+    }
+
+    write_header(writer, &packet.header)?;
+    for status in &packet.car_status_data {
+        write_car_status(writer, status)?;
+    }
+
+    Ok(())
+}
+
+fn write_car_status<W: Write>(
+    writer: &mut W,
+    status: &CarStatusData,
+) -> Result<(), TelemetryError> {
+    writer.write_u8(encode_traction_control(&status.traction_control)?)?;
+    writer.write_u8(encode_anti_lock_brakes(&status.anti_lock_brakes)?)?;
+    writer.write_u8(encode_fuel_mix(&status.fuel_mix)?)?;
+    writer.write_u8(status.front_brake_bias)?;
+    writer.write_u8(status.pit_limiter as u8)?;
+    writer.write_f32::<LittleEndian>(status.fuel_in_tank)?;
+    writer.write_f32::<LittleEndian>(status.fuel_capacity)?;
+    writer.write_f32::<LittleEndian>(status.fuel_remaining_laps)?;
+    writer.write_u16::<LittleEndian>(status.max_rpm)?;
+    writer.write_u16::<LittleEndian>(status.idle_rpm)?;
+    writer.write_u8(status.max_gears)?;
+    writer.write_i8(encode_drs(&status.drs_allowed)?)?;
+    writer.write_u16::<LittleEndian>(status.drs_activation_distance)?;
+    let tyres_wear = &status.tyres_wear;
+    writer.write_u8(tyres_wear.rear_left)?;
+    writer.write_u8(tyres_wear.rear_right)?;
+    writer.write_u8(tyres_wear.front_left)?;
+    writer.write_u8(tyres_wear.front_right)?;
+    writer.write_u8(encode_actual_tyre_compound(&status.actual_tyre_compound)?)?;
+    writer.write_u8(encode_visual_tyre_compound(&status.visual_tyre_compound)?)?;
+    writer.write_u8(status.tyres_age_laps)?;
+    let tyres_damage = &status.tyres_damage;
+    writer.write_u8(tyres_damage.rear_left)?;
+    writer.write_u8(tyres_damage.rear_right)?;
+    writer.write_u8(tyres_damage.front_left)?;
+    writer.write_u8(tyres_damage.front_right)?;
+    writer.write_u8(status.front_left_wing_damage)?;
+    writer.write_u8(status.front_right_wing_damage)?;
+    writer.write_u8(status.rear_wing_damage)?;
+    writer.write_u8(status.drs_fault as u8)?;
+    writer.write_u8(status.engine_damage)?;
+    writer.write_u8(status.gear_box_damage)?;
+    writer.write_i8(encode_flag(&status.vehicle_fia_flags)?)?;
+    writer.write_f32::<LittleEndian>(status.ers_store_energy)?;
+    writer.write_u8(encode_ers_deployment_mode(&status.ers_deploy_mode)?)?;
+    writer.write_f32::<LittleEndian>(status.ers_harvested_this_lap_mguk)?;
+    writer.write_f32::<LittleEndian>(status.ers_harvested_this_lap_mguh)?;
+    writer.write_f32::<LittleEndian>(status.ers_deployed_this_lap)?;
+
+    Ok(())
+}
+
+fn ensure_car_status_size(size: usize) -> Result<(), TelemetryError> {
     if size == CAR_STATUS_MIN_SIZE {
         return Ok(());
     }
 
-    Err(Error::new(
-        ErrorKind::InvalidData,
-        "Car status size is too small",
-    ))
+    Err(TelemetryError::SizeMismatch {
+        packet: "Car status",
+        expected: CAR_STATUS_MIN_SIZE,
+        got: size,
+    })
 }
 
-fn parse_traction_control(value: u8) -> Result<TractionControl, Error> {
+pub fn parse_traction_control(
+    value: u8,
+    decoder: &mut Decoder,
+) -> Result<TractionControl, TelemetryError> {
     match value {
         0 => Ok(TractionControl::Off),
         1 => Ok(TractionControl::Low),
         2 => Ok(TractionControl::High),
-        _ => Err(Error::new(
-            ErrorKind::InvalidData,
-            "Traction control invalid",
-        )),
+        _ => decoder.unknown("traction_control", value, TractionControl::Unknown(value)),
     }
 }
 
-fn parse_fuel_mix(value: u8) -> Result<FuelMix, Error> {
+pub fn encode_traction_control(value: &TractionControl) -> Result<u8, TelemetryError> {
+    match value {
+        TractionControl::Off => Ok(0),
+        TractionControl::Low => Ok(1),
+        TractionControl::High => Ok(2),
+        TractionControl::Unknown(raw) => Ok(*raw),
+    }
+}
+
+pub fn parse_fuel_mix(value: u8, decoder: &mut Decoder) -> Result<FuelMix, TelemetryError> {
     match value {
         0 => Ok(FuelMix::Lean),
         1 => Ok(FuelMix::Standard),
         2 => Ok(FuelMix::Rich),
         3 => Ok(FuelMix::Max),
-        _ => Err(Error::new(ErrorKind::InvalidData, "Fuel mix invalid")),
+        _ => decoder.unknown("fuel_mix", value, FuelMix::Unknown(value)),
     }
 }
 
-fn parse_drs(value: i8) -> Result<DRSStatus, Error> {
+pub fn encode_fuel_mix(value: &FuelMix) -> Result<u8, TelemetryError> {
+    match value {
+        FuelMix::Lean => Ok(0),
+        FuelMix::Standard => Ok(1),
+        FuelMix::Rich => Ok(2),
+        FuelMix::Max => Ok(3),
+        FuelMix::Unknown(raw) => Ok(*raw),
+    }
+}
+
+pub fn parse_drs(value: i8, decoder: &mut Decoder) -> Result<DRSStatus, TelemetryError> {
     match value {
         0 => Ok(DRSStatus::NotAllowed),
         1 => Ok(DRSStatus::Allowed),
-        -1 => Ok(DRSStatus::Unknown),
-        _ => Err(Error::new(ErrorKind::InvalidData, "DRS status invalid")),
+        -1 => Ok(DRSStatus::Unknown(value)),
+        _ => decoder.unknown("drs", value, DRSStatus::Unknown(value)),
+    }
+}
+
+pub fn encode_drs(value: &DRSStatus) -> Result<i8, TelemetryError> {
+    match value {
+        DRSStatus::NotAllowed => Ok(0),
+        DRSStatus::Allowed => Ok(1),
+        DRSStatus::Unknown(raw) => Ok(*raw),
     }
 }
 
-fn parse_ers_deployment_mode(value: u8) -> Result<ERSDeploymentMode, Error> {
+pub fn parse_ers_deployment_mode(
+    value: u8,
+    decoder: &mut Decoder,
+) -> Result<ERSDeploymentMode, TelemetryError> {
     match value {
         0 => Ok(ERSDeploymentMode::None),
         1 => Ok(ERSDeploymentMode::Medium),
         2 => Ok(ERSDeploymentMode::Overtake),
         3 => Ok(ERSDeploymentMode::Hotlap),
-        _ => Err(Error::new(
-            ErrorKind::InvalidData,
-            "ERS Deployment mode invalid",
-        )),
+        _ => decoder.unknown(
+            "ers_deployment_mode",
+            value,
+            ERSDeploymentMode::Unknown(value),
+        ),
+    }
+}
+
+pub fn encode_ers_deployment_mode(value: &ERSDeploymentMode) -> Result<u8, TelemetryError> {
+    match value {
+        ERSDeploymentMode::None => Ok(0),
+        ERSDeploymentMode::Medium => Ok(1),
+        ERSDeploymentMode::Overtake => Ok(2),
+        ERSDeploymentMode::Hotlap => Ok(3),
+        ERSDeploymentMode::Unknown(raw) => Ok(*raw),
+        _ => Err(TelemetryError::Unencodable {
+            field: "ers_deployment_mode",
+        }),
     }
 }
 
-pub fn parse_actual_tyre_compound(value: u8) -> Result<ActualTyreCompound, Error> {
+pub fn parse_actual_tyre_compound(
+    value: u8,
+    decoder: &mut Decoder,
+) -> Result<ActualTyreCompound, TelemetryError> {
     match value {
         16 => Ok(ActualTyreCompound::C5),
         17 => Ok(ActualTyreCompound::C4),
@@ -174,12 +292,42 @@ pub fn parse_actual_tyre_compound(value: u8) -> Result<ActualTyreCompound, Error
         13 => Ok(ActualTyreCompound::F2Medium),
         14 => Ok(ActualTyreCompound::F2Hard),
         15 => Ok(ActualTyreCompound::F2Wet),
-        0 | 255 => Ok(ActualTyreCompound::Unknown),
-        _ => Err(Error::new(ErrorKind::InvalidData, "Tyre compound invalid")),
+        0 | 255 => Ok(ActualTyreCompound::Unknown(value)),
+        _ => decoder.unknown(
+            "actual_tyre_compound",
+            value,
+            ActualTyreCompound::Unknown(value),
+        ),
     }
 }
 
-pub fn parse_visual_tyre_compound(value: u8) -> Result<VisualTyreCompound, Error> {
+pub fn encode_actual_tyre_compound(value: &ActualTyreCompound) -> Result<u8, TelemetryError> {
+    match value {
+        ActualTyreCompound::C5 => Ok(16),
+        ActualTyreCompound::C4 => Ok(17),
+        ActualTyreCompound::C3 => Ok(18),
+        ActualTyreCompound::C2 => Ok(19),
+        ActualTyreCompound::C1 => Ok(20),
+        ActualTyreCompound::Inter => Ok(7),
+        ActualTyreCompound::Wet => Ok(8),
+        ActualTyreCompound::F1ClassicDry => Ok(9),
+        ActualTyreCompound::F1ClassicWet => Ok(10),
+        ActualTyreCompound::F2SuperSoft => Ok(11),
+        ActualTyreCompound::F2Soft => Ok(12),
+        ActualTyreCompound::F2Medium => Ok(13),
+        ActualTyreCompound::F2Hard => Ok(14),
+        ActualTyreCompound::F2Wet => Ok(15),
+        ActualTyreCompound::Unknown(raw) => Ok(*raw),
+        _ => Err(TelemetryError::Unencodable {
+            field: "actual_tyre_compound",
+        }),
+    }
+}
+
+pub fn parse_visual_tyre_compound(
+    value: u8,
+    decoder: &mut Decoder,
+) -> Result<VisualTyreCompound, TelemetryError> {
     match value {
         16 => Ok(VisualTyreCompound::Soft),
         17 => Ok(VisualTyreCompound::Medium),
@@ -193,21 +341,48 @@ pub fn parse_visual_tyre_compound(value: u8) -> Result<VisualTyreCompound, Error
         13 => Ok(VisualTyreCompound::F2Medium),
         14 => Ok(VisualTyreCompound::F2Hard),
         15 => Ok(VisualTyreCompound::F2Wet),
-        0 => Ok(VisualTyreCompound::Unknown),
-        _ => Err(Error::new(
-            ErrorKind::InvalidData,
-            "Tyre compound visual invalid",
-        )),
+        0 => Ok(VisualTyreCompound::Unknown(value)),
+        _ => decoder.unknown(
+            "visual_tyre_compound",
+            value,
+            VisualTyreCompound::Unknown(value),
+        ),
+    }
+}
+
+pub fn encode_visual_tyre_compound(value: &VisualTyreCompound) -> Result<u8, TelemetryError> {
+    match value {
+        VisualTyreCompound::Soft => Ok(16),
+        VisualTyreCompound::Medium => Ok(17),
+        VisualTyreCompound::Hard => Ok(18),
+        VisualTyreCompound::Inter => Ok(7),
+        VisualTyreCompound::Wet => Ok(8),
+        VisualTyreCompound::F1ClassicDry => Ok(9),
+        VisualTyreCompound::F1ClassicWet => Ok(10),
+        VisualTyreCompound::F2SuperSoft => Ok(11),
+        VisualTyreCompound::F2Soft => Ok(12),
+        VisualTyreCompound::F2Medium => Ok(13),
+        VisualTyreCompound::F2Hard => Ok(14),
+        VisualTyreCompound::F2Wet => Ok(15),
+        VisualTyreCompound::Unknown(raw) => Ok(*raw),
     }
 }
 
-pub fn parse_anti_lock_brakes(value: u8) -> Result<AntiLockBrakes, Error> {
+pub fn parse_anti_lock_brakes(
+    value: u8,
+    decoder: &mut Decoder,
+) -> Result<AntiLockBrakes, TelemetryError> {
     match value {
         0 => Ok(AntiLockBrakes::Off),
         1 => Ok(AntiLockBrakes::On),
-        _ => Err(Error::new(
-            ErrorKind::InvalidData,
-            "Anti lock brakes invalid",
-        )),
+        _ => decoder.unknown("anti_lock_brakes", value, AntiLockBrakes::Unknown(value)),
+    }
+}
+
+pub fn encode_anti_lock_brakes(value: &AntiLockBrakes) -> Result<u8, TelemetryError> {
+    match value {
+        AntiLockBrakes::Off => Ok(0),
+        AntiLockBrakes::On => Ok(1),
+        AntiLockBrakes::Unknown(raw) => Ok(*raw),
     }
 }
@@ -1,29 +1,32 @@
+use crate::decoder::Decoder;
+use crate::error::TelemetryError;
 use crate::f1_2020::car::{
     CarTelemetryData, MFDPanel, PacketCarTelemetryData, SurfaceType, CAR_TELEMETRY_MIN_SIZE,
     TOTAL_CARS,
 };
-use crate::f1_2020::header::PacketHeader;
+use crate::f1_2020::header::{write_header, PacketHeader};
 use crate::f1_2020::motion::Wheel;
-use async_std::io::{Cursor, Error, ErrorKind};
-use byteorder_async::{LittleEndian, ReaderToByteOrder};
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use std::io::{Cursor, Write};
 
-pub async fn parse_car_telemetry_data(
-    cursor: &mut Cursor<Vec<u8>>,
+pub fn parse_car_telemetry_data(
+    cursor: &mut Cursor<&[u8]>,
     header: PacketHeader,
     size: usize,
-) -> Result<PacketCarTelemetryData, Error> {
+    decoder: &mut Decoder,
+) -> Result<PacketCarTelemetryData, TelemetryError> {
     ensure_car_telemetry_size(size)?;
 
     let mut car_telemetry_data = Vec::with_capacity(TOTAL_CARS);
     for _ in 0..TOTAL_CARS {
-        let ctd = parse_car_telemetry(cursor).await?;
+        let ctd = parse_car_telemetry(cursor, decoder)?;
         car_telemetry_data.push(ctd);
     }
 
-    let button_status = cursor.byte_order().read_u32::<LittleEndian>().await?;
-    let mfd_panel_index = parse_mfd_panel(cursor.byte_order().read_u8().await?)?;
-    let mfd_panel_index_secondary_player = parse_mfd_panel(cursor.byte_order().read_u8().await?)?;
-    let suggested_gear = cursor.byte_order().read_i8().await?;
+    let button_status = cursor.read_u32::<LittleEndian>()?;
+    let mfd_panel_index = parse_mfd_panel(cursor.read_u8()?, decoder)?;
+    let mfd_panel_index_secondary_player = parse_mfd_panel(cursor.read_u8()?, decoder)?;
+    let suggested_gear = cursor.read_i8()?;
 
     Ok(PacketCarTelemetryData {
         header,
@@ -35,46 +38,49 @@ pub async fn parse_car_telemetry_data(
     })
 }
 
-async fn parse_car_telemetry(cursor: &mut Cursor<Vec<u8>>) -> Result<CarTelemetryData, Error> {
-    let speed = cursor.byte_order().read_u16::<LittleEndian>().await?;
-    let throttle = cursor.byte_order().read_f32::<LittleEndian>().await?;
-    let steer = cursor.byte_order().read_f32::<LittleEndian>().await?;
-    let brake = cursor.byte_order().read_f32::<LittleEndian>().await?;
-    let clutch = cursor.byte_order().read_u8().await?;
-    let gear = cursor.byte_order().read_i8().await?;
-    let engine_rpm = cursor.byte_order().read_u16::<LittleEndian>().await?;
-    let drs = cursor.byte_order().read_u8().await? == 1;
-    let rev_lights_percent = cursor.byte_order().read_u8().await?;
+fn parse_car_telemetry(
+    cursor: &mut Cursor<&[u8]>,
+    decoder: &mut Decoder,
+) -> Result<CarTelemetryData, TelemetryError> {
+    let speed = cursor.read_u16::<LittleEndian>()?;
+    let throttle = cursor.read_f32::<LittleEndian>()?;
+    let steer = cursor.read_f32::<LittleEndian>()?;
+    let brake = cursor.read_f32::<LittleEndian>()?;
+    let clutch = cursor.read_u8()?;
+    let gear = cursor.read_i8()?;
+    let engine_rpm = cursor.read_u16::<LittleEndian>()?;
+    let drs = cursor.read_u8()? == 1;
+    let rev_lights_percent = cursor.read_u8()?;
     let brakes_temperature = Wheel {
-        rear_left: cursor.byte_order().read_u16::<LittleEndian>().await?,
-        rear_right: cursor.byte_order().read_u16::<LittleEndian>().await?,
-        front_left: cursor.byte_order().read_u16::<LittleEndian>().await?,
-        front_right: cursor.byte_order().read_u16::<LittleEndian>().await?,
+        rear_left: cursor.read_u16::<LittleEndian>()?,
+        rear_right: cursor.read_u16::<LittleEndian>()?,
+        front_left: cursor.read_u16::<LittleEndian>()?,
+        front_right: cursor.read_u16::<LittleEndian>()?,
     };
     let tyres_surface_temperature = Wheel {
-        rear_left: cursor.byte_order().read_u8().await?,
-        rear_right: cursor.byte_order().read_u8().await?,
-        front_left: cursor.byte_order().read_u8().await?,
-        front_right: cursor.byte_order().read_u8().await?,
+        rear_left: cursor.read_u8()?,
+        rear_right: cursor.read_u8()?,
+        front_left: cursor.read_u8()?,
+        front_right: cursor.read_u8()?,
     };
     let tyres_inner_temperature = Wheel {
-        rear_left: cursor.byte_order().read_u8().await?,
-        rear_right: cursor.byte_order().read_u8().await?,
-        front_left: cursor.byte_order().read_u8().await?,
-        front_right: cursor.byte_order().read_u8().await?,
+        rear_left: cursor.read_u8()?,
+        rear_right: cursor.read_u8()?,
+        front_left: cursor.read_u8()?,
+        front_right: cursor.read_u8()?,
     };
-    let engine_temperature = cursor.byte_order().read_u16::<LittleEndian>().await?;
+    let engine_temperature = cursor.read_u16::<LittleEndian>()?;
     let tyre_pressures = Wheel {
-        rear_left: cursor.byte_order().read_f32::<LittleEndian>().await?,
-        rear_right: cursor.byte_order().read_f32::<LittleEndian>().await?,
-        front_left: cursor.byte_order().read_f32::<LittleEndian>().await?,
-        front_right: cursor.byte_order().read_f32::<LittleEndian>().await?,
+        rear_left: cursor.read_f32::<LittleEndian>()?,
+        rear_right: cursor.read_f32::<LittleEndian>()?,
+        front_left: cursor.read_f32::<LittleEndian>()?,
+        front_right: cursor.read_f32::<LittleEndian>()?,
     };
     let surface_types = Wheel {
-        rear_left: parse_surface_type(cursor.byte_order().read_u8().await?)?,
-        rear_right: parse_surface_type(cursor.byte_order().read_u8().await?)?,
-        front_left: parse_surface_type(cursor.byte_order().read_u8().await?)?,
-        front_right: parse_surface_type(cursor.byte_order().read_u8().await?)?,
+        rear_left: parse_surface_type(cursor.read_u8()?, decoder)?,
+        rear_right: parse_surface_type(cursor.read_u8()?, decoder)?,
+        front_left: parse_surface_type(cursor.read_u8()?, decoder)?,
+        front_right: parse_surface_type(cursor.read_u8()?, decoder)?,
     };
 
     Ok(CarTelemetryData {
@@ -96,18 +102,84 @@ async fn parse_car_telemetry(cursor: &mut Cursor<Vec<u8>>) -> Result<CarTelemetr
     })
 }
 
-fn ensure_car_telemetry_size(size: usize) -> Result<(), Error> {
+pub fn write_car_telemetry_data<W: Write>(
+    writer: &mut W,
+    packet: &PacketCarTelemetryData,
+) -> Result<(), TelemetryError> {
+    if packet.car_telemetry_data.len() != TOTAL_CARS {
+        return Err(TelemetryError::Unencodable {
+            field: "car_telemetry_data",
+        });
+    }
+
+    write_header(writer, &packet.header)?;
+    for telemetry in &packet.car_telemetry_data {
+        write_car_telemetry(writer, telemetry)?;
+    }
+
+    writer.write_u32::<LittleEndian>(packet.button_status)?;
+    writer.write_u8(encode_mfd_panel(&packet.mfd_panel_index)?)?;
+    writer.write_u8(encode_mfd_panel(&packet.mfd_panel_index_secondary_player)?)?;
+    writer.write_i8(packet.suggested_gear)?;
+
+    Ok(())
+}
+
+fn write_car_telemetry<W: Write>(
+    writer: &mut W,
+    telemetry: &CarTelemetryData,
+) -> Result<(), TelemetryError> {
+    writer.write_u16::<LittleEndian>(telemetry.speed)?;
+    writer.write_f32::<LittleEndian>(telemetry.throttle)?;
+    writer.write_f32::<LittleEndian>(telemetry.steer)?;
+    writer.write_f32::<LittleEndian>(telemetry.brake)?;
+    writer.write_u8(telemetry.clutch)?;
+    writer.write_i8(telemetry.gear)?;
+    writer.write_u16::<LittleEndian>(telemetry.engine_rpm)?;
+    writer.write_u8(telemetry.drs as u8)?;
+    writer.write_u8(telemetry.rev_lights_percent)?;
+    let brakes_temperature = &telemetry.brakes_temperature;
+    writer.write_u16::<LittleEndian>(brakes_temperature.rear_left)?;
+    writer.write_u16::<LittleEndian>(brakes_temperature.rear_right)?;
+    writer.write_u16::<LittleEndian>(brakes_temperature.front_left)?;
+    writer.write_u16::<LittleEndian>(brakes_temperature.front_right)?;
+    for temperature in [
+        &telemetry.tyres_surface_temperature,
+        &telemetry.tyres_inner_temperature,
+    ] {
+        writer.write_u8(temperature.rear_left)?;
+        writer.write_u8(temperature.rear_right)?;
+        writer.write_u8(temperature.front_left)?;
+        writer.write_u8(temperature.front_right)?;
+    }
+    writer.write_u16::<LittleEndian>(telemetry.engine_temperature)?;
+    let tyre_pressures = &telemetry.tyre_pressures;
+    writer.write_f32::<LittleEndian>(tyre_pressures.rear_left)?;
+    writer.write_f32::<LittleEndian>(tyre_pressures.rear_right)?;
+    writer.write_f32::<LittleEndian>(tyre_pressures.front_left)?;
+    writer.write_f32::<LittleEndian>(tyre_pressures.front_right)?;
+    let surface_types = &telemetry.surface_types;
+    writer.write_u8(encode_surface_type(&surface_types.rear_left)?)?;
+    writer.write_u8(encode_surface_type(&surface_types.rear_right)?)?;
+    writer.write_u8(encode_surface_type(&surface_types.front_left)?)?;
+    writer.write_u8(encode_surface_type(&surface_types.front_right)?)?;
+
+    Ok(())
+}
+
+fn ensure_car_telemetry_size(size: usize) -> Result<(), TelemetryError> {
     if size == CAR_TELEMETRY_MIN_SIZE {
         return Ok(());
     }
 
-    Err(Error::new(
-        ErrorKind::InvalidData,
-        "Car telemetry size is too small",
-    ))
+    Err(TelemetryError::SizeMismatch {
+        packet: "Car telemetry",
+        expected: CAR_TELEMETRY_MIN_SIZE,
+        got: size,
+    })
 }
 
-fn parse_surface_type(value: u8) -> Result<SurfaceType, Error> {
+pub fn parse_surface_type(value: u8, decoder: &mut Decoder) -> Result<SurfaceType, TelemetryError> {
     match value {
         0 => Ok(SurfaceType::Tarmac),
         1 => Ok(SurfaceType::RumbleStrip),
@@ -121,12 +193,30 @@ fn parse_surface_type(value: u8) -> Result<SurfaceType, Error> {
         9 => Ok(SurfaceType::Cobblestone),
         10 => Ok(SurfaceType::Metal),
         11 => Ok(SurfaceType::Ridged),
-        12 => Ok(SurfaceType::Unknown),
-        _ => Err(Error::new(ErrorKind::InvalidData, "Surface type invalid")),
+        12 => Ok(SurfaceType::Unknown(value)),
+        _ => decoder.unknown("surface_type", value, SurfaceType::Unknown(value)),
+    }
+}
+
+pub fn encode_surface_type(value: &SurfaceType) -> Result<u8, TelemetryError> {
+    match value {
+        SurfaceType::Tarmac => Ok(0),
+        SurfaceType::RumbleStrip => Ok(1),
+        SurfaceType::Concrete => Ok(2),
+        SurfaceType::Rock => Ok(3),
+        SurfaceType::Gravel => Ok(4),
+        SurfaceType::Mud => Ok(5),
+        SurfaceType::Sand => Ok(6),
+        SurfaceType::Grass => Ok(7),
+        SurfaceType::Water => Ok(8),
+        SurfaceType::Cobblestone => Ok(9),
+        SurfaceType::Metal => Ok(10),
+        SurfaceType::Ridged => Ok(11),
+        SurfaceType::Unknown(raw) => Ok(*raw),
     }
 }
 
-fn parse_mfd_panel(value: u8) -> Result<MFDPanel, Error> {
+pub fn parse_mfd_panel(value: u8, decoder: &mut Decoder) -> Result<MFDPanel, TelemetryError> {
     match value {
         0 => Ok(MFDPanel::CarSetup),
         1 => Ok(MFDPanel::Pits),
@@ -134,9 +224,18 @@ fn parse_mfd_panel(value: u8) -> Result<MFDPanel, Error> {
         3 => Ok(MFDPanel::Engine),
         4 => Ok(MFDPanel::Temperatures),
         255 => Ok(MFDPanel::Closed),
-        _ => Err(Error::new(
-            ErrorKind::InvalidData,
-            "MFD panel index invalid",
-        )),
+        _ => decoder.unknown("mfd_panel", value, MFDPanel::Unknown(value)),
+    }
+}
+
+pub fn encode_mfd_panel(value: &MFDPanel) -> Result<u8, TelemetryError> {
+    match value {
+        MFDPanel::CarSetup => Ok(0),
+        MFDPanel::Pits => Ok(1),
+        MFDPanel::Damage => Ok(2),
+        MFDPanel::Engine => Ok(3),
+        MFDPanel::Temperatures => Ok(4),
+        MFDPanel::Closed => Ok(255),
+        MFDPanel::Unknown(raw) => Ok(*raw),
     }
 }
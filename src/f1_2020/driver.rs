@@ -80,8 +80,9 @@ pub enum Driver {
     RalphBoschung,
     DevonButler,
     LukasWebber,
-    Player,
-    Unknown,
+    /// Human player, with the raw driver id of 100 and up
+    Player(u8),
+    Unknown(u8),
 }
 
 impl Driver {
@@ -113,7 +114,7 @@ impl Driver {
             Driver::AnthoineHubert => "Anthoine Hubert",
             Driver::RobertKubica => "Robert Kubica",
             Driver::GuanyaZhou => "Guanya Zhou",
-            Driver::Player => "Player",
+            Driver::Player(_) => "Player",
             _ => "[N/A]",
         }
     }
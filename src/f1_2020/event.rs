@@ -1,11 +1,16 @@
-use crate::f1_2020::header::PacketHeader;
-use async_std::io::{Cursor, Error, ErrorKind};
-use byteorder_async::{LittleEndian, ReaderToByteOrder};
+use crate::decoder::Decoder;
+use crate::error::{encode_duration, parse_duration, TelemetryError};
+use crate::f1_2020::header::{write_header, PacketHeader};
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use std::convert::TryFrom;
+use std::io::{Cursor, Read, Write};
 use std::time::Duration;
 
 use derivative::Derivative;
 
 const EVENT_MIN_SIZE: usize = 35;
+/// Event code followed by the largest event details, the penalty.
+const EVENT_DATA_SIZE: usize = 11;
 
 #[derive(Debug, Eq, PartialEq, Copy, Clone)]
 pub struct FastestLap {
@@ -53,6 +58,7 @@ pub enum PenaltyType {
     ThisAndPreviousLapInvalidatedWithoutReason,
     Retired,
     BlackFlagTimer,
+    Unknown(u8),
 }
 
 #[derive(Debug, Eq, PartialEq, Copy, Clone)]
@@ -109,6 +115,7 @@ pub enum InfringementType {
     RetryPenalty,
     IllegalTimeGain,
     MandatoryPitstop,
+    Unknown(u8),
 }
 
 #[derive(Debug, Eq, PartialEq, Copy, Clone)]
@@ -148,6 +155,8 @@ pub enum Event {
     RaceWinner(RaceWinner),
     Penalty(Penalty),
     SpeedTrap(SpeedTrap),
+    /// Event code the decoder does not know, in lenient mode
+    Unknown([u8; 4]),
 }
 
 #[derive(Debug, PartialEq, Copy, Clone, Derivative)]
@@ -157,63 +166,63 @@ pub struct PacketEventData {
     pub event: Event,
 }
 
-pub async fn parse_event_data(
-    cursor: &mut Cursor<Vec<u8>>,
+pub fn parse_event_data(
+    cursor: &mut Cursor<&[u8]>,
     header: PacketHeader,
     size: usize,
-) -> Result<PacketEventData, Error> {
+    decoder: &mut Decoder,
+) -> Result<PacketEventData, TelemetryError> {
     ensure_event_size(size)?;
 
-    let event = parse_event(cursor).await?;
+    let event = parse_event(cursor, decoder)?;
     Ok(PacketEventData { header, event })
 }
 
-pub async fn parse_event(cursor: &mut Cursor<Vec<u8>>) -> Result<Event, Error> {
-    let mut event_chars: Vec<char> = Vec::with_capacity(4);
-    for _ in 0..4 {
-        event_chars.push(cursor.byte_order().read_u8().await? as char);
-    }
-    let event_code = event_chars.into_iter().collect::<String>();
+pub fn parse_event(
+    cursor: &mut Cursor<&[u8]>,
+    decoder: &mut Decoder,
+) -> Result<Event, TelemetryError> {
+    let mut event_code = [0; 4];
+    cursor.read_exact(&mut event_code)?;
 
-    match event_code.as_str() {
-        "SSTA" => Ok(Event::SessionStarted),
-        "SEND" => Ok(Event::SessionEnded),
-        "FTLP" => {
-            let vehicle_index = cursor.byte_order().read_u8().await?;
-            let lap_time =
-                Duration::from_secs_f32(cursor.byte_order().read_f32::<LittleEndian>().await?);
+    match &event_code {
+        b"SSTA" => Ok(Event::SessionStarted),
+        b"SEND" => Ok(Event::SessionEnded),
+        b"FTLP" => {
+            let vehicle_index = cursor.read_u8()?;
+            let lap_time = parse_duration("lap_time", cursor.read_f32::<LittleEndian>()?)?;
 
             Ok(Event::FastestLap(FastestLap {
                 vehicle_index,
                 lap_time,
             }))
         }
-        "RTMT" => {
-            let vehicle_index = cursor.byte_order().read_u8().await?;
+        b"RTMT" => {
+            let vehicle_index = cursor.read_u8()?;
 
             Ok(Event::Retirement(Retirement { vehicle_index }))
         }
-        "DRSE" => Ok(Event::DRSEnabled),
-        "DRSD" => Ok(Event::DRSDisabled),
-        "TMPT" => {
-            let vehicle_index = cursor.byte_order().read_u8().await?;
+        b"DRSE" => Ok(Event::DRSEnabled),
+        b"DRSD" => Ok(Event::DRSDisabled),
+        b"TMPT" => {
+            let vehicle_index = cursor.read_u8()?;
 
             Ok(Event::TeamMateInPits(TeamMateInPits { vehicle_index }))
         }
-        "CHQF" => Ok(Event::ChequeredFlag),
-        "RCWN" => {
-            let vehicle_index = cursor.byte_order().read_u8().await?;
+        b"CHQF" => Ok(Event::ChequeredFlag),
+        b"RCWN" => {
+            let vehicle_index = cursor.read_u8()?;
 
             Ok(Event::RaceWinner(RaceWinner { vehicle_index }))
         }
-        "PENA" => {
-            let penalty_type = parse_penalty_type(cursor.byte_order().read_u8().await?)?;
-            let infringement_type = parse_infringement_type(cursor.byte_order().read_u8().await?)?;
-            let vehicle_index = cursor.byte_order().read_u8().await?;
-            let other_vehicle_index = cursor.byte_order().read_u8().await?;
-            let time = Duration::from_secs(cursor.byte_order().read_u8().await? as u64);
-            let lap_num = cursor.byte_order().read_u8().await?;
-            let places_gained = cursor.byte_order().read_u8().await?;
+        b"PENA" => {
+            let penalty_type = parse_penalty_type(cursor.read_u8()?, decoder)?;
+            let infringement_type = parse_infringement_type(cursor.read_u8()?, decoder)?;
+            let vehicle_index = cursor.read_u8()?;
+            let other_vehicle_index = cursor.read_u8()?;
+            let time = Duration::from_secs(cursor.read_u8()? as u64);
+            let lap_num = cursor.read_u8()?;
+            let places_gained = cursor.read_u8()?;
 
             Ok(Event::Penalty(Penalty {
                 vehicle_index,
@@ -225,20 +234,88 @@ pub async fn parse_event(cursor: &mut Cursor<Vec<u8>>) -> Result<Event, Error> {
                 places_gained,
             }))
         }
-        "SPTP" => {
-            let vehicle_index = cursor.byte_order().read_u8().await?;
-            let speed = cursor.byte_order().read_f32::<LittleEndian>().await?;
+        b"SPTP" => {
+            let vehicle_index = cursor.read_u8()?;
+            let speed = cursor.read_f32::<LittleEndian>()?;
 
             Ok(Event::SpeedTrap(SpeedTrap {
                 vehicle_index,
                 speed,
             }))
         }
-        _ => Err(Error::new(ErrorKind::InvalidData, "Invalid event code")),
+        _ => decoder.unknown(
+            "event_code",
+            u32::from_le_bytes(event_code),
+            Event::Unknown(event_code),
+        ),
+    }
+}
+
+pub fn write_event_data<W: Write>(
+    writer: &mut W,
+    packet: &PacketEventData,
+) -> Result<(), TelemetryError> {
+    write_header(writer, &packet.header)?;
+
+    // Event details are a union on the wire, shorter events are zero padded.
+    let mut data = Vec::with_capacity(EVENT_DATA_SIZE);
+    write_event(&mut data, &packet.event)?;
+    data.resize(EVENT_DATA_SIZE, 0);
+    writer.write_all(&data)?;
+
+    Ok(())
+}
+
+pub fn write_event<W: Write>(writer: &mut W, event: &Event) -> Result<(), TelemetryError> {
+    match event {
+        Event::SessionStarted => writer.write_all(b"SSTA")?,
+        Event::SessionEnded => writer.write_all(b"SEND")?,
+        Event::FastestLap(fastest_lap) => {
+            writer.write_all(b"FTLP")?;
+            writer.write_u8(fastest_lap.vehicle_index)?;
+            writer.write_f32::<LittleEndian>(encode_duration(fastest_lap.lap_time))?;
+        }
+        Event::Retirement(retirement) => {
+            writer.write_all(b"RTMT")?;
+            writer.write_u8(retirement.vehicle_index)?;
+        }
+        Event::DRSEnabled => writer.write_all(b"DRSE")?,
+        Event::DRSDisabled => writer.write_all(b"DRSD")?,
+        Event::TeamMateInPits(team_mate_in_pits) => {
+            writer.write_all(b"TMPT")?;
+            writer.write_u8(team_mate_in_pits.vehicle_index)?;
+        }
+        Event::ChequeredFlag => writer.write_all(b"CHQF")?,
+        Event::RaceWinner(race_winner) => {
+            writer.write_all(b"RCWN")?;
+            writer.write_u8(race_winner.vehicle_index)?;
+        }
+        Event::Penalty(penalty) => {
+            let time = u8::try_from(penalty.time.as_secs())
+                .map_err(|_| TelemetryError::Unencodable { field: "time" })?;
+
+            writer.write_all(b"PENA")?;
+            writer.write_u8(encode_penalty_type(&penalty.penalty_type)?)?;
+            writer.write_u8(encode_infringement_type(&penalty.infringement_type)?)?;
+            writer.write_u8(penalty.vehicle_index)?;
+            writer.write_u8(penalty.other_vehicle_index)?;
+            writer.write_u8(time)?;
+            writer.write_u8(penalty.lap_num)?;
+            writer.write_u8(penalty.places_gained)?;
+        }
+        Event::SpeedTrap(speed_trap) => {
+            writer.write_all(b"SPTP")?;
+            writer.write_u8(speed_trap.vehicle_index)?;
+            writer.write_f32::<LittleEndian>(speed_trap.speed)?;
+        }
+        // Details of unknown events are not kept, they go out zero filled
+        Event::Unknown(event_code) => writer.write_all(event_code)?,
     }
+
+    Ok(())
 }
 
-fn parse_penalty_type(value: u8) -> Result<PenaltyType, Error> {
+pub fn parse_penalty_type(value: u8, decoder: &mut Decoder) -> Result<PenaltyType, TelemetryError> {
     match value {
         0 => Ok(PenaltyType::DriveThrough),
         1 => Ok(PenaltyType::StopGo),
@@ -258,10 +335,37 @@ fn parse_penalty_type(value: u8) -> Result<PenaltyType, Error> {
         15 => Ok(PenaltyType::ThisAndPreviousLapInvalidatedWithoutReason),
         16 => Ok(PenaltyType::Retired),
         17 => Ok(PenaltyType::BlackFlagTimer),
-        _ => Err(Error::new(ErrorKind::InvalidData, "Invalid penalty type")),
+        _ => decoder.unknown("penalty_type", value, PenaltyType::Unknown(value)),
+    }
+}
+
+pub fn encode_penalty_type(value: &PenaltyType) -> Result<u8, TelemetryError> {
+    match value {
+        PenaltyType::DriveThrough => Ok(0),
+        PenaltyType::StopGo => Ok(1),
+        PenaltyType::GridPenalty => Ok(2),
+        PenaltyType::PenaltyReminder => Ok(3),
+        PenaltyType::TimePenalty => Ok(4),
+        PenaltyType::Warning => Ok(5),
+        PenaltyType::Disqualified => Ok(6),
+        PenaltyType::RemovedFromFormationLap => Ok(7),
+        PenaltyType::ParkedTooLongTimer => Ok(8),
+        PenaltyType::TyreRegulations => Ok(9),
+        PenaltyType::ThisLapInvalidated => Ok(10),
+        PenaltyType::ThisAndNextLapInvalidated => Ok(11),
+        PenaltyType::ThisLapInvalidatedWithoutReason => Ok(12),
+        PenaltyType::ThisAndNextLapInvalidatedWithoutReason => Ok(13),
+        PenaltyType::ThisAndPreviousLapInvalidated => Ok(14),
+        PenaltyType::ThisAndPreviousLapInvalidatedWithoutReason => Ok(15),
+        PenaltyType::Retired => Ok(16),
+        PenaltyType::BlackFlagTimer => Ok(17),
+        PenaltyType::Unknown(raw) => Ok(*raw),
     }
 }
-fn parse_infringement_type(value: u8) -> Result<InfringementType, Error> {
+pub fn parse_infringement_type(
+    value: u8,
+    decoder: &mut Decoder,
+) -> Result<InfringementType, TelemetryError> {
     match value {
         0 => Ok(InfringementType::BlockingBySlowDriving),
         1 => Ok(InfringementType::BlockingByWrongWayDriving),
@@ -315,20 +419,76 @@ fn parse_infringement_type(value: u8) -> Result<InfringementType, Error> {
         49 => Ok(InfringementType::RetryPenalty),
         50 => Ok(InfringementType::IllegalTimeGain),
         51 => Ok(InfringementType::MandatoryPitstop),
-        _ => Err(Error::new(
-            ErrorKind::InvalidData,
-            "Invalid infringement type",
-        )),
+        _ => decoder.unknown("infringement_type", value, InfringementType::Unknown(value)),
+    }
+}
+
+pub fn encode_infringement_type(value: &InfringementType) -> Result<u8, TelemetryError> {
+    match value {
+        InfringementType::BlockingBySlowDriving => Ok(0),
+        InfringementType::BlockingByWrongWayDriving => Ok(1),
+        InfringementType::ReversingOffTheStartLine => Ok(2),
+        InfringementType::BigCollision => Ok(3),
+        InfringementType::SmallCollision => Ok(4),
+        InfringementType::CollisionFailedToHandBackPositionSingle => Ok(5),
+        InfringementType::CollisionFailedToHandBackPositionMultiple => Ok(6),
+        InfringementType::CornerCuttingGainedTime => Ok(7),
+        InfringementType::CornerCuttingOvertakeSingle => Ok(8),
+        InfringementType::CornerCuttingOvertakeMultiple => Ok(9),
+        InfringementType::CrossedPitExitLane => Ok(10),
+        InfringementType::IgnoringBlueFlags => Ok(11),
+        InfringementType::IgnoringYellowFlags => Ok(12),
+        InfringementType::IgnoringDriveThrough => Ok(13),
+        InfringementType::TooManyDriveThroughs => Ok(14),
+        InfringementType::DriveThroughReminderServeWithinNLaps => Ok(15),
+        InfringementType::DriveThroughReminderServeThisLap => Ok(16),
+        InfringementType::PitLaneSpeeding => Ok(17),
+        InfringementType::ParkedForTooLong => Ok(18),
+        InfringementType::IgnoringTyreRegulations => Ok(19),
+        InfringementType::TooManyPenalties => Ok(20),
+        InfringementType::MultipleWarnings => Ok(21),
+        InfringementType::ApproachingDisqualification => Ok(22),
+        InfringementType::TyreRegulationsSelectSingle => Ok(23),
+        InfringementType::TyreRegulationsSelectMultiple => Ok(24),
+        InfringementType::LapInvalidatedCornerCutting => Ok(25),
+        InfringementType::LapInvalidatedRunningWide => Ok(26),
+        InfringementType::CornerCuttingRanWideGainedTimeMinor => Ok(27),
+        InfringementType::CornerCuttingRanWideGainedTimeSignificant => Ok(28),
+        InfringementType::CornerCuttingRanWideGainedTimeExtreme => Ok(29),
+        InfringementType::LapInvalidatedWallRiding => Ok(30),
+        InfringementType::LapInvalidatedFlashbackUsed => Ok(31),
+        InfringementType::LapInvalidatedResetToTrack => Ok(32),
+        InfringementType::BlockingThePitlane => Ok(33),
+        InfringementType::JumpStart => Ok(34),
+        InfringementType::SafetyCarToCarCollision => Ok(35),
+        InfringementType::SafetyCarIllegalOvertake => Ok(36),
+        InfringementType::SafetyCarExceedingAllowedPace => Ok(37),
+        InfringementType::VirtualSafetyCarExceedingAllowedPace => Ok(38),
+        InfringementType::FormationLapBelowAllowedSpeed => Ok(39),
+        InfringementType::RetiredMechanicalFailure => Ok(40),
+        InfringementType::RetiredTerminallyDamaged => Ok(41),
+        InfringementType::SafetyCarFallingTooFarBack => Ok(42),
+        InfringementType::BlackFlagTimer => Ok(43),
+        InfringementType::UnservedStopGoPenalty => Ok(44),
+        InfringementType::UnservedDriveThroughPenalty => Ok(45),
+        InfringementType::EngineComponentChange => Ok(46),
+        InfringementType::GearboxChange => Ok(47),
+        InfringementType::LeagueGridPenalty => Ok(48),
+        InfringementType::RetryPenalty => Ok(49),
+        InfringementType::IllegalTimeGain => Ok(50),
+        InfringementType::MandatoryPitstop => Ok(51),
+        InfringementType::Unknown(raw) => Ok(*raw),
     }
 }
 
-fn ensure_event_size(size: usize) -> Result<(), Error> {
+fn ensure_event_size(size: usize) -> Result<(), TelemetryError> {
     if size == EVENT_MIN_SIZE {
         return Ok(());
     }
 
-    Err(Error::new(
-        ErrorKind::InvalidData,
-        "Event size is too small",
-    ))
+    Err(TelemetryError::SizeMismatch {
+        packet: "Event",
+        expected: EVENT_MIN_SIZE,
+        got: size,
+    })
 }
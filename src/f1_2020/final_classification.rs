@@ -1,53 +1,60 @@
-use async_std::io::{Cursor, Error, ErrorKind};
-use byteorder_async::{LittleEndian, ReaderToByteOrder};
+use crate::decoder::Decoder;
+use crate::error::{encode_duration, parse_duration, TelemetryError};
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 use derivative::Derivative;
+use std::io::{Cursor, Write};
 use std::time::Duration;
 
 use crate::f1_2020::car::{ActualTyreCompound, VisualTyreCompound, TOTAL_CARS};
-use crate::f1_2020::car_status::{parse_actual_tyre_compound, parse_visual_tyre_compound};
-use crate::f1_2020::header::PacketHeader;
-use crate::f1_2020::lap::{parse_result_status, ResultStatus};
+use crate::f1_2020::car_status::{
+    encode_actual_tyre_compound, encode_visual_tyre_compound, parse_actual_tyre_compound,
+    parse_visual_tyre_compound,
+};
+use crate::f1_2020::header::{write_header, PacketHeader};
+use crate::f1_2020::lap::{encode_result_status, parse_result_status, ResultStatus};
 
 const FINAL_CLASSIFICATION_MIN_SIZE: usize = 839;
+const TYRE_STINTS_MAX: usize = 8;
 
 #[derive(Debug, PartialEq, Clone, Derivative)]
 #[derivative(Eq)]
 pub struct FinalClassificationData {
-    position: u8,
-    num_laps: u8,
-    grid_position: u8,
-    points: u8,
-    num_pit_stops: u8,
-    result_status: ResultStatus,
-    best_lap_time: Duration,   // seconds
-    total_race_time: Duration, // seconds
-    penalties_time: u8,
-    num_penalties: u8,
-    num_tyre_stints: u8,
-    tyre_stints_actual: Vec<ActualTyreCompound>,
-    tyre_stints_visual: Vec<VisualTyreCompound>,
+    pub position: u8,
+    pub num_laps: u8,
+    pub grid_position: u8,
+    pub points: u8,
+    pub num_pit_stops: u8,
+    pub result_status: ResultStatus,
+    pub best_lap_time: Duration,   // seconds
+    pub total_race_time: Duration, // seconds
+    pub penalties_time: u8,
+    pub num_penalties: u8,
+    pub num_tyre_stints: u8,
+    pub tyre_stints_actual: Vec<ActualTyreCompound>,
+    pub tyre_stints_visual: Vec<VisualTyreCompound>,
 }
 
 #[derive(Debug, PartialEq, Clone, Derivative)]
 #[derivative(Eq)]
 pub struct PacketFinalClassificationData {
-    header: PacketHeader,
-    num_cars: u8,
-    final_classification_data: Vec<FinalClassificationData>,
+    pub header: PacketHeader,
+    pub num_cars: u8,
+    pub final_classification_data: Vec<FinalClassificationData>,
 }
 
-pub async fn parse_final_classification_data(
-    cursor: &mut Cursor<Vec<u8>>,
+pub fn parse_final_classification_data(
+    cursor: &mut Cursor<&[u8]>,
     header: PacketHeader,
     size: usize,
-) -> Result<PacketFinalClassificationData, Error> {
+    decoder: &mut Decoder,
+) -> Result<PacketFinalClassificationData, TelemetryError> {
     ensure_final_classification_size(size)?;
 
-    let num_cars = cursor.byte_order().read_u8().await?;
+    let num_cars = cursor.read_u8()?;
 
     let mut final_classification_data = Vec::with_capacity(TOTAL_CARS);
     for _ in 0..TOTAL_CARS {
-        let data = parse_final_classification(cursor).await?;
+        let data = parse_final_classification(cursor, decoder)?;
         final_classification_data.push(data);
     }
 
@@ -58,32 +65,31 @@ pub async fn parse_final_classification_data(
     })
 }
 
-pub async fn parse_final_classification(
-    cursor: &mut Cursor<Vec<u8>>,
-) -> Result<FinalClassificationData, Error> {
-    let position = cursor.byte_order().read_u8().await?;
-    let num_laps = cursor.byte_order().read_u8().await?;
-    let grid_position = cursor.byte_order().read_u8().await?;
-    let points = cursor.byte_order().read_u8().await?;
-    let num_pit_stops = cursor.byte_order().read_u8().await?;
-    let result_status = parse_result_status(cursor).await?;
-    let best_lap_time =
-        Duration::from_secs_f32(cursor.byte_order().read_f32::<LittleEndian>().await?);
-    let total_race_time =
-        Duration::from_secs_f64(cursor.byte_order().read_f64::<LittleEndian>().await?);
-    let penalties_time = cursor.byte_order().read_u8().await?;
-    let num_penalties = cursor.byte_order().read_u8().await?;
-    let num_tyre_stints = cursor.byte_order().read_u8().await?;
-
-    let mut tyre_stints_actual = Vec::with_capacity(8);
-    for _ in 0..8 {
-        let tc = parse_actual_tyre_compound(cursor.byte_order().read_u8().await?)?;
+pub fn parse_final_classification(
+    cursor: &mut Cursor<&[u8]>,
+    decoder: &mut Decoder,
+) -> Result<FinalClassificationData, TelemetryError> {
+    let position = cursor.read_u8()?;
+    let num_laps = cursor.read_u8()?;
+    let grid_position = cursor.read_u8()?;
+    let points = cursor.read_u8()?;
+    let num_pit_stops = cursor.read_u8()?;
+    let result_status = parse_result_status(cursor, decoder)?;
+    let best_lap_time = parse_duration("best_lap_time", cursor.read_f32::<LittleEndian>()?)?;
+    let total_race_time = Duration::from_secs_f64(cursor.read_f64::<LittleEndian>()?);
+    let penalties_time = cursor.read_u8()?;
+    let num_penalties = cursor.read_u8()?;
+    let num_tyre_stints = cursor.read_u8()?;
+
+    let mut tyre_stints_actual = Vec::with_capacity(TYRE_STINTS_MAX);
+    for _ in 0..TYRE_STINTS_MAX {
+        let tc = parse_actual_tyre_compound(cursor.read_u8()?, decoder)?;
         tyre_stints_actual.push(tc);
     }
 
-    let mut tyre_stints_visual = Vec::with_capacity(8);
-    for _ in 0..8 {
-        let tc = parse_visual_tyre_compound(cursor.byte_order().read_u8().await?)?;
+    let mut tyre_stints_visual = Vec::with_capacity(TYRE_STINTS_MAX);
+    for _ in 0..TYRE_STINTS_MAX {
+        let tc = parse_visual_tyre_compound(cursor.read_u8()?, decoder)?;
         tyre_stints_visual.push(tc);
     }
 
@@ -104,13 +110,69 @@ pub async fn parse_final_classification(
     })
 }
 
-fn ensure_final_classification_size(size: usize) -> Result<(), Error> {
+pub fn write_final_classification_data<W: Write>(
+    writer: &mut W,
+    packet: &PacketFinalClassificationData,
+) -> Result<(), TelemetryError> {
+    if packet.final_classification_data.len() != TOTAL_CARS {
+        return Err(TelemetryError::Unencodable {
+            field: "final_classification_data",
+        });
+    }
+
+    write_header(writer, &packet.header)?;
+    writer.write_u8(packet.num_cars)?;
+    for data in &packet.final_classification_data {
+        write_final_classification(writer, data)?;
+    }
+
+    Ok(())
+}
+
+pub fn write_final_classification<W: Write>(
+    writer: &mut W,
+    data: &FinalClassificationData,
+) -> Result<(), TelemetryError> {
+    if data.tyre_stints_actual.len() != TYRE_STINTS_MAX {
+        return Err(TelemetryError::Unencodable {
+            field: "tyre_stints_actual",
+        });
+    }
+    if data.tyre_stints_visual.len() != TYRE_STINTS_MAX {
+        return Err(TelemetryError::Unencodable {
+            field: "tyre_stints_visual",
+        });
+    }
+
+    writer.write_u8(data.position)?;
+    writer.write_u8(data.num_laps)?;
+    writer.write_u8(data.grid_position)?;
+    writer.write_u8(data.points)?;
+    writer.write_u8(data.num_pit_stops)?;
+    writer.write_u8(encode_result_status(&data.result_status)?)?;
+    writer.write_f32::<LittleEndian>(encode_duration(data.best_lap_time))?;
+    writer.write_f64::<LittleEndian>(data.total_race_time.as_secs_f64())?;
+    writer.write_u8(data.penalties_time)?;
+    writer.write_u8(data.num_penalties)?;
+    writer.write_u8(data.num_tyre_stints)?;
+    for compound in &data.tyre_stints_actual {
+        writer.write_u8(encode_actual_tyre_compound(compound)?)?;
+    }
+    for compound in &data.tyre_stints_visual {
+        writer.write_u8(encode_visual_tyre_compound(compound)?)?;
+    }
+
+    Ok(())
+}
+
+fn ensure_final_classification_size(size: usize) -> Result<(), TelemetryError> {
     if size == FINAL_CLASSIFICATION_MIN_SIZE {
         return Ok(());
     }
 
-    Err(Error::new(
-        ErrorKind::InvalidData,
-        "Final classification size is too small",
-    ))
+    Err(TelemetryError::SizeMismatch {
+        packet: "Final classification",
+        expected: FINAL_CLASSIFICATION_MIN_SIZE,
+        got: size,
+    })
 }
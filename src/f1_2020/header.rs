@@ -1,5 +1,6 @@
-use async_std::io::{Cursor, Error, ErrorKind};
-use byteorder_async::{LittleEndian, ReaderToByteOrder};
+use crate::error::{encode_duration, parse_duration, TelemetryError};
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use std::io::{Cursor, Write};
 use std::time::Duration;
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq, PartialOrd)]
@@ -18,23 +19,22 @@ pub struct PacketHeader {
 
 const HEADER_MIN_SIZE: usize = 24;
 
-pub async fn parse_headers(
-    cursor: &mut Cursor<Vec<u8>>,
+pub fn parse_headers(
+    cursor: &mut Cursor<&[u8]>,
     size: usize,
-) -> Result<PacketHeader, Error> {
+) -> Result<PacketHeader, TelemetryError> {
     ensure_header_size(size)?;
 
-    let packet_format = cursor.byte_order().read_u16::<LittleEndian>().await?;
-    let major_version = cursor.byte_order().read_u8().await?;
-    let minor_version = cursor.byte_order().read_u8().await?;
-    let packet_version = cursor.byte_order().read_u8().await?;
-    let packet_id = cursor.byte_order().read_u8().await?;
-    let session_uid = cursor.byte_order().read_u64::<LittleEndian>().await?;
-    let session_time =
-        Duration::from_secs_f32(cursor.byte_order().read_f32::<LittleEndian>().await?);
-    let frame_identifier = cursor.byte_order().read_u32::<LittleEndian>().await?;
-    let player_car_index = cursor.byte_order().read_u8().await?;
-    let secondary_player_car_index = cursor.byte_order().read_u8().await?;
+    let packet_format = cursor.read_u16::<LittleEndian>()?;
+    let major_version = cursor.read_u8()?;
+    let minor_version = cursor.read_u8()?;
+    let packet_version = cursor.read_u8()?;
+    let packet_id = cursor.read_u8()?;
+    let session_uid = cursor.read_u64::<LittleEndian>()?;
+    let session_time = parse_duration("session_time", cursor.read_f32::<LittleEndian>()?)?;
+    let frame_identifier = cursor.read_u32::<LittleEndian>()?;
+    let player_car_index = cursor.read_u8()?;
+    let secondary_player_car_index = cursor.read_u8()?;
 
     Ok(PacketHeader {
         packet_format,
@@ -50,63 +50,87 @@ pub async fn parse_headers(
     })
 }
 
-fn ensure_header_size(size: usize) -> Result<(), Error> {
+pub fn write_header<W: Write>(writer: &mut W, header: &PacketHeader) -> Result<(), TelemetryError> {
+    writer.write_u16::<LittleEndian>(header.packet_format)?;
+    writer.write_u8(header.major_version)?;
+    writer.write_u8(header.minor_version)?;
+    writer.write_u8(header.packet_version)?;
+    writer.write_u8(header.packet_id)?;
+    writer.write_u64::<LittleEndian>(header.session_uid)?;
+    writer.write_f32::<LittleEndian>(encode_duration(header.session_time))?;
+    writer.write_u32::<LittleEndian>(header.frame_identifier)?;
+    writer.write_u8(header.player_car_index)?;
+    writer.write_u8(header.secondary_player_car_index)?;
+
+    Ok(())
+}
+
+fn ensure_header_size(size: usize) -> Result<(), TelemetryError> {
     if size < HEADER_MIN_SIZE {
-        return Err(Error::new(
-            ErrorKind::InvalidData,
-            "Header size is too small",
-        ));
+        return Err(TelemetryError::SizeMismatch {
+            packet: "Header",
+            expected: HEADER_MIN_SIZE,
+            got: size,
+        });
     }
 
-    return Ok(());
+    Ok(())
 }
 
 #[cfg(test)]
 mod test {
+    use crate::error::TelemetryError;
     use crate::f1_2020::header::parse_headers;
-    use async_std::io::Cursor;
+    use std::io::Cursor;
 
-    use byteorder_async::{LittleEndian, WriteBytesExt};
+    use byteorder::{LittleEndian, WriteBytesExt};
 
-    #[async_std::test]
-    async fn parse_header_error() {
+    #[test]
+    fn parse_header_error() {
         let mut buf = Vec::with_capacity(1);
         buf.write_u16::<LittleEndian>(0).unwrap();
 
-        let mut cursor = Cursor::new(buf);
+        let mut cursor = Cursor::new(buf.as_slice());
         let len = cursor.get_ref().len();
 
-        let result = parse_headers(&mut cursor, len).await.map_err(|e| e.kind());
-        assert_eq!(result, Err(async_std::io::ErrorKind::InvalidData));
+        let result = parse_headers(&mut cursor, len);
+        assert!(matches!(
+            result,
+            Err(TelemetryError::SizeMismatch {
+                packet: "Header",
+                expected: 24,
+                got: 2
+            })
+        ));
     }
 
-    #[async_std::test]
-    async fn parse_header_success() {
+    #[test]
+    fn parse_header_success() {
         let mut buf = Vec::with_capacity(2048);
         buf.write_u16::<LittleEndian>(2020).unwrap();
         buf.write_u8(1).unwrap();
         buf.write_u8(2).unwrap();
         buf.write_u8(3).unwrap();
         buf.write_u8(0).unwrap();
-        buf.write_u64::<LittleEndian>(u64::max_value()).unwrap();
+        buf.write_u64::<LittleEndian>(u64::MAX).unwrap();
         buf.write_f32::<LittleEndian>(1.0).unwrap();
-        buf.write_u32::<LittleEndian>(u32::max_value()).unwrap();
+        buf.write_u32::<LittleEndian>(u32::MAX).unwrap();
         buf.write_u8(19).unwrap();
         buf.write_u8(255).unwrap();
 
-        let mut cursor = Cursor::new(buf);
+        let mut cursor = Cursor::new(buf.as_slice());
         let len = cursor.get_mut().len();
-        let result = parse_headers(&mut cursor, len).await.unwrap();
+        let result = parse_headers(&mut cursor, len).unwrap();
 
         assert_eq!(2020, result.packet_format);
         assert_eq!(1, result.major_version);
         assert_eq!(2, result.minor_version);
         assert_eq!(3, result.packet_version);
         assert_eq!(0, result.packet_id);
-        assert_eq!(u64::max_value(), result.session_uid);
+        assert_eq!(u64::MAX, result.session_uid);
         assert_eq!(1, result.session_time.as_secs());
         assert_eq!(1000, result.session_time.as_millis());
-        assert_eq!(u32::max_value(), result.frame_identifier);
+        assert_eq!(u32::MAX, result.frame_identifier);
         assert_eq!(19, result.player_car_index);
         assert_eq!(255, result.secondary_player_car_index);
     }
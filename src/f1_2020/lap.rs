@@ -1,8 +1,11 @@
+use crate::decoder::Decoder;
+use crate::error::{encode_duration, parse_duration, TelemetryError};
 use crate::f1_2020::car::TOTAL_CARS;
-use crate::f1_2020::header::PacketHeader;
-use async_std::io::{Cursor, Error, ErrorKind};
-use byteorder_async::{LittleEndian, ReaderToByteOrder};
+use crate::f1_2020::header::{write_header, PacketHeader};
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 use derivative::Derivative;
+use std::convert::TryFrom;
+use std::io::{Cursor, Write};
 use std::time::Duration;
 
 const LAP_DATA_MIN_SIZE: usize = 1190;
@@ -12,6 +15,7 @@ pub enum PitStatus {
     None,
     Pitting,
     PitArea,
+    Unknown(u8),
 }
 
 #[derive(Debug, Eq, PartialEq, Clone, Copy)]
@@ -21,6 +25,7 @@ pub enum DriverStatus {
     InLap,
     OutLap,
     OnTrack,
+    Unknown(u8),
 }
 
 #[derive(Debug, Eq, PartialEq, Clone, Copy)]
@@ -32,6 +37,7 @@ pub enum ResultStatus {
     Disqualified,
     NotClassified,
     Retired,
+    Unknown(u8),
 }
 
 #[derive(Debug, PartialEq, Clone, Copy, Derivative)]
@@ -69,7 +75,7 @@ pub struct LapData {
     pub total_distance: f32,
     // #[derivative(Eq="ignore")]
     /// Delta in seconds for safety car
-    pub safety_car_delta: Duration,
+    pub safety_car_delta: f32,
     pub car_position: u8,
     pub current_lap_num: u8,
     pub pit_status: PitStatus,
@@ -87,16 +93,17 @@ pub struct PacketLapData {
     pub lap_data: Vec<LapData>,
 }
 
-pub async fn parse_lap_data(
-    cursor: &mut Cursor<Vec<u8>>,
+pub fn parse_lap_data(
+    cursor: &mut Cursor<&[u8]>,
     header: PacketHeader,
     size: usize,
-) -> Result<PacketLapData, Error> {
+    decoder: &mut Decoder,
+) -> Result<PacketLapData, TelemetryError> {
     ensure_lap_data_size(size)?;
 
     let mut laps = Vec::with_capacity(TOTAL_CARS);
     for _ in 0..TOTAL_CARS {
-        let lap = parse_lap(cursor).await?;
+        let lap = parse_lap(cursor, decoder)?;
         laps.push(lap);
     }
 
@@ -106,46 +113,37 @@ pub async fn parse_lap_data(
     })
 }
 
-async fn parse_lap(cursor: &mut Cursor<Vec<u8>>) -> Result<LapData, Error> {
-    let last_lap_time =
-        Duration::from_secs_f32(cursor.byte_order().read_f32::<LittleEndian>().await?); // in seconds
-    let current_lap_time =
-        Duration::from_secs_f32(cursor.byte_order().read_f32::<LittleEndian>().await?); // in seconds
-    let sector_1_time =
-        Duration::from_millis(cursor.byte_order().read_u16::<LittleEndian>().await? as u64); // in ms
-    let sector_2_time =
-        Duration::from_millis(cursor.byte_order().read_u16::<LittleEndian>().await? as u64); // in ms
-    let best_lap_time =
-        Duration::from_secs_f32(cursor.byte_order().read_f32::<LittleEndian>().await?); // in seconds
-    let best_lap_num = cursor.byte_order().read_u8().await?;
-    let best_lap_sector_1_time =
-        Duration::from_millis(cursor.byte_order().read_u16::<LittleEndian>().await? as u64); // in ms
-    let best_lap_sector_2_time =
-        Duration::from_millis(cursor.byte_order().read_u16::<LittleEndian>().await? as u64); // in ms
-    let best_lap_sector_3_time =
-        Duration::from_millis(cursor.byte_order().read_u16::<LittleEndian>().await? as u64); // in ms
+fn parse_lap(cursor: &mut Cursor<&[u8]>, decoder: &mut Decoder) -> Result<LapData, TelemetryError> {
+    let last_lap_time = parse_duration("last_lap_time", cursor.read_f32::<LittleEndian>()?)?; // in seconds
+    let current_lap_time = parse_duration("current_lap_time", cursor.read_f32::<LittleEndian>()?)?; // in seconds
+    let sector_1_time = Duration::from_millis(cursor.read_u16::<LittleEndian>()? as u64); // in ms
+    let sector_2_time = Duration::from_millis(cursor.read_u16::<LittleEndian>()? as u64); // in ms
+    let best_lap_time = parse_duration("best_lap_time", cursor.read_f32::<LittleEndian>()?)?; // in seconds
+    let best_lap_num = cursor.read_u8()?;
+    let best_lap_sector_1_time = Duration::from_millis(cursor.read_u16::<LittleEndian>()? as u64); // in ms
+    let best_lap_sector_2_time = Duration::from_millis(cursor.read_u16::<LittleEndian>()? as u64); // in ms
+    let best_lap_sector_3_time = Duration::from_millis(cursor.read_u16::<LittleEndian>()? as u64); // in ms
     let best_overall_sector_1_time =
-        Duration::from_millis(cursor.byte_order().read_u16::<LittleEndian>().await? as u64); // in ms
-    let best_overall_sector_1_lap_num = cursor.byte_order().read_u8().await?;
+        Duration::from_millis(cursor.read_u16::<LittleEndian>()? as u64); // in ms
+    let best_overall_sector_1_lap_num = cursor.read_u8()?;
     let best_overall_sector_2_time =
-        Duration::from_millis(cursor.byte_order().read_u16::<LittleEndian>().await? as u64); // in ms
-    let best_overall_sector_2_lap_num = cursor.byte_order().read_u8().await?;
+        Duration::from_millis(cursor.read_u16::<LittleEndian>()? as u64); // in ms
+    let best_overall_sector_2_lap_num = cursor.read_u8()?;
     let best_overall_sector_3_time =
-        Duration::from_millis(cursor.byte_order().read_u16::<LittleEndian>().await? as u64); // in ms
-    let best_overall_sector_3_lap_num = cursor.byte_order().read_u8().await?;
-    let lap_distance = cursor.byte_order().read_f32::<LittleEndian>().await?;
-    let total_distance = cursor.byte_order().read_f32::<LittleEndian>().await?;
-    let safety_car_delta =
-        Duration::from_secs_f32(cursor.byte_order().read_f32::<LittleEndian>().await?); // in seconds
-    let car_position = cursor.byte_order().read_u8().await?;
-    let current_lap_num = cursor.byte_order().read_u8().await?;
-    let pit_status = parse_pit_status(cursor).await?;
-    let sector = cursor.byte_order().read_u8().await?;
-    let current_lap_invalid = cursor.byte_order().read_u8().await? == 1;
-    let penalties = cursor.byte_order().read_u8().await?;
-    let grid_position = cursor.byte_order().read_u8().await?;
-    let driver_status = parse_driver_status(cursor).await?;
-    let result_status = parse_result_status(cursor).await?;
+        Duration::from_millis(cursor.read_u16::<LittleEndian>()? as u64); // in ms
+    let best_overall_sector_3_lap_num = cursor.read_u8()?;
+    let lap_distance = cursor.read_f32::<LittleEndian>()?;
+    let total_distance = cursor.read_f32::<LittleEndian>()?;
+    let safety_car_delta = cursor.read_f32::<LittleEndian>()?; // in seconds
+    let car_position = cursor.read_u8()?;
+    let current_lap_num = cursor.read_u8()?;
+    let pit_status = parse_pit_status(cursor, decoder)?;
+    let sector = cursor.read_u8()?;
+    let current_lap_invalid = cursor.read_u8()? == 1;
+    let penalties = cursor.read_u8()?;
+    let grid_position = cursor.read_u8()?;
+    let driver_status = parse_driver_status(cursor, decoder)?;
+    let result_status = parse_result_status(cursor, decoder)?;
 
     Ok(LapData {
         last_lap_time,
@@ -178,28 +176,130 @@ async fn parse_lap(cursor: &mut Cursor<Vec<u8>>) -> Result<LapData, Error> {
     })
 }
 
-pub async fn parse_pit_status(cursor: &mut Cursor<Vec<u8>>) -> Result<PitStatus, Error> {
-    match cursor.byte_order().read_u8().await? {
+pub fn write_lap_data<W: Write>(
+    writer: &mut W,
+    packet: &PacketLapData,
+) -> Result<(), TelemetryError> {
+    if packet.lap_data.len() != TOTAL_CARS {
+        return Err(TelemetryError::Unencodable { field: "lap_data" });
+    }
+
+    write_header(writer, &packet.header)?;
+    for lap in &packet.lap_data {
+        write_lap(writer, lap)?;
+    }
+
+    Ok(())
+}
+
+fn write_lap<W: Write>(writer: &mut W, lap: &LapData) -> Result<(), TelemetryError> {
+    writer.write_f32::<LittleEndian>(encode_duration(lap.last_lap_time))?;
+    writer.write_f32::<LittleEndian>(encode_duration(lap.current_lap_time))?;
+    writer.write_u16::<LittleEndian>(encode_millis("sector_1_time", lap.sector_1_time)?)?;
+    writer.write_u16::<LittleEndian>(encode_millis("sector_2_time", lap.sector_2_time)?)?;
+    writer.write_f32::<LittleEndian>(encode_duration(lap.best_lap_time))?;
+    writer.write_u8(lap.best_lap_num)?;
+    writer.write_u16::<LittleEndian>(encode_millis(
+        "best_lap_sector_1_time",
+        lap.best_lap_sector_1_time,
+    )?)?;
+    writer.write_u16::<LittleEndian>(encode_millis(
+        "best_lap_sector_2_time",
+        lap.best_lap_sector_2_time,
+    )?)?;
+    writer.write_u16::<LittleEndian>(encode_millis(
+        "best_lap_sector_3_time",
+        lap.best_lap_sector_3_time,
+    )?)?;
+    writer.write_u16::<LittleEndian>(encode_millis(
+        "best_overall_sector_1_time",
+        lap.best_overall_sector_1_time,
+    )?)?;
+    writer.write_u8(lap.best_overall_sector_1_lap_num)?;
+    writer.write_u16::<LittleEndian>(encode_millis(
+        "best_overall_sector_2_time",
+        lap.best_overall_sector_2_time,
+    )?)?;
+    writer.write_u8(lap.best_overall_sector_2_lap_num)?;
+    writer.write_u16::<LittleEndian>(encode_millis(
+        "best_overall_sector_3_time",
+        lap.best_overall_sector_3_time,
+    )?)?;
+    writer.write_u8(lap.best_overall_sector_3_lap_num)?;
+    writer.write_f32::<LittleEndian>(lap.lap_distance)?;
+    writer.write_f32::<LittleEndian>(lap.total_distance)?;
+    writer.write_f32::<LittleEndian>(lap.safety_car_delta)?;
+    writer.write_u8(lap.car_position)?;
+    writer.write_u8(lap.current_lap_num)?;
+    writer.write_u8(encode_pit_status(&lap.pit_status)?)?;
+    writer.write_u8(lap.sector)?;
+    writer.write_u8(lap.current_lap_invalid as u8)?;
+    writer.write_u8(lap.penalties)?;
+    writer.write_u8(lap.grid_position)?;
+    writer.write_u8(encode_driver_status(&lap.driver_status)?)?;
+    writer.write_u8(encode_result_status(&lap.result_status)?)?;
+
+    Ok(())
+}
+
+fn encode_millis(field: &'static str, value: Duration) -> Result<u16, TelemetryError> {
+    u16::try_from(value.as_millis()).map_err(|_| TelemetryError::Unencodable { field })
+}
+
+pub fn parse_pit_status(
+    cursor: &mut Cursor<&[u8]>,
+    decoder: &mut Decoder,
+) -> Result<PitStatus, TelemetryError> {
+    let value = cursor.read_u8()?;
+    match value {
         0 => Ok(PitStatus::None),
         1 => Ok(PitStatus::Pitting),
         2 => Ok(PitStatus::PitArea),
-        _ => Err(Error::new(ErrorKind::InvalidData, "Invalid pit status")),
+        _ => decoder.unknown("pit_status", value, PitStatus::Unknown(value)),
     }
 }
 
-pub async fn parse_driver_status(cursor: &mut Cursor<Vec<u8>>) -> Result<DriverStatus, Error> {
-    match cursor.byte_order().read_u8().await? {
+pub fn encode_pit_status(value: &PitStatus) -> Result<u8, TelemetryError> {
+    match value {
+        PitStatus::None => Ok(0),
+        PitStatus::Pitting => Ok(1),
+        PitStatus::PitArea => Ok(2),
+        PitStatus::Unknown(raw) => Ok(*raw),
+    }
+}
+
+pub fn parse_driver_status(
+    cursor: &mut Cursor<&[u8]>,
+    decoder: &mut Decoder,
+) -> Result<DriverStatus, TelemetryError> {
+    let value = cursor.read_u8()?;
+    match value {
         0 => Ok(DriverStatus::Garage),
         1 => Ok(DriverStatus::FlyingLap),
         2 => Ok(DriverStatus::InLap),
         3 => Ok(DriverStatus::OutLap),
         4 => Ok(DriverStatus::OnTrack),
-        _ => Err(Error::new(ErrorKind::InvalidData, "Invalid driver status")),
+        _ => decoder.unknown("driver_status", value, DriverStatus::Unknown(value)),
+    }
+}
+
+pub fn encode_driver_status(value: &DriverStatus) -> Result<u8, TelemetryError> {
+    match value {
+        DriverStatus::Garage => Ok(0),
+        DriverStatus::FlyingLap => Ok(1),
+        DriverStatus::InLap => Ok(2),
+        DriverStatus::OutLap => Ok(3),
+        DriverStatus::OnTrack => Ok(4),
+        DriverStatus::Unknown(raw) => Ok(*raw),
     }
 }
 
-pub async fn parse_result_status(cursor: &mut Cursor<Vec<u8>>) -> Result<ResultStatus, Error> {
-    match cursor.byte_order().read_u8().await? {
+pub fn parse_result_status(
+    cursor: &mut Cursor<&[u8]>,
+    decoder: &mut Decoder,
+) -> Result<ResultStatus, TelemetryError> {
+    let value = cursor.read_u8()?;
+    match value {
         0 => Ok(ResultStatus::Invalid),
         1 => Ok(ResultStatus::Inactive),
         2 => Ok(ResultStatus::Active),
@@ -207,17 +307,31 @@ pub async fn parse_result_status(cursor: &mut Cursor<Vec<u8>>) -> Result<ResultS
         4 => Ok(ResultStatus::Disqualified),
         5 => Ok(ResultStatus::NotClassified),
         6 => Ok(ResultStatus::Retired),
-        _ => Err(Error::new(ErrorKind::InvalidData, "Invalid result status")),
+        _ => decoder.unknown("result_status", value, ResultStatus::Unknown(value)),
+    }
+}
+
+pub fn encode_result_status(value: &ResultStatus) -> Result<u8, TelemetryError> {
+    match value {
+        ResultStatus::Invalid => Ok(0),
+        ResultStatus::Inactive => Ok(1),
+        ResultStatus::Active => Ok(2),
+        ResultStatus::Finished => Ok(3),
+        ResultStatus::Disqualified => Ok(4),
+        ResultStatus::NotClassified => Ok(5),
+        ResultStatus::Retired => Ok(6),
+        ResultStatus::Unknown(raw) => Ok(*raw),
     }
 }
 
-fn ensure_lap_data_size(size: usize) -> Result<(), Error> {
+fn ensure_lap_data_size(size: usize) -> Result<(), TelemetryError> {
     if size < LAP_DATA_MIN_SIZE {
-        return Err(Error::new(
-            ErrorKind::InvalidData,
-            "Lap data size is too small",
-        ));
+        return Err(TelemetryError::SizeMismatch {
+            packet: "Lap data",
+            expected: LAP_DATA_MIN_SIZE,
+            got: size,
+        });
     }
 
-    return Ok(());
+    Ok(())
 }
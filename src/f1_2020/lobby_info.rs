@@ -1,11 +1,15 @@
-use async_std::io::{Cursor, Error, ErrorKind};
+use crate::decoder::Decoder;
+use crate::error::TelemetryError;
+use std::io::{Cursor, Write};
 
 use crate::f1_2020::car::TOTAL_CARS;
-use crate::f1_2020::header::PacketHeader;
+use crate::f1_2020::header::{write_header, PacketHeader};
 use crate::f1_2020::nationality::Nationality;
-use crate::f1_2020::participants::{parse_nationality, parse_team};
+use crate::f1_2020::participants::{
+    encode_nationality, encode_team, parse_nationality, parse_team, write_name,
+};
 use crate::f1_2020::team::Team;
-use byteorder_async::ReaderToByteOrder;
+use byteorder::{ReadBytesExt, WriteBytesExt};
 
 const LOBBY_INFO_MIN_SIZE: usize = 1169;
 
@@ -14,36 +18,38 @@ pub enum ReadyStatus {
     NotReady,
     Ready,
     Spectating,
+    Unknown(u8),
 }
 
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub struct LobbyInfoData {
-    ai_controlled: bool,
-    team: Team,
-    nationality: Nationality,
-    name: String,
-    ready_status: ReadyStatus,
+    pub ai_controlled: bool,
+    pub team: Team,
+    pub nationality: Nationality,
+    pub name: String,
+    pub ready_status: ReadyStatus,
 }
 
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub struct PacketLobbyInfoData {
-    header: PacketHeader,
-    num_players: u8,
-    lobby_info_data: Vec<LobbyInfoData>,
+    pub header: PacketHeader,
+    pub num_players: u8,
+    pub lobby_info_data: Vec<LobbyInfoData>,
 }
 
-pub async fn parse_lobby_info_data(
-    cursor: &mut Cursor<Vec<u8>>,
+pub fn parse_lobby_info_data(
+    cursor: &mut Cursor<&[u8]>,
     header: PacketHeader,
     size: usize,
-) -> Result<PacketLobbyInfoData, Error> {
+    decoder: &mut Decoder,
+) -> Result<PacketLobbyInfoData, TelemetryError> {
     ensure_lobby_info_size(size)?;
 
-    let num_players = cursor.byte_order().read_u8().await?;
+    let num_players = cursor.read_u8()?;
 
     let mut lobby_info_data = Vec::with_capacity(TOTAL_CARS);
     for _ in 0..TOTAL_CARS {
-        let data = parse_lobby_info(cursor).await?;
+        let data = parse_lobby_info(cursor, decoder)?;
         lobby_info_data.push(data);
     }
 
@@ -54,16 +60,19 @@ pub async fn parse_lobby_info_data(
     })
 }
 
-pub async fn parse_lobby_info(cursor: &mut Cursor<Vec<u8>>) -> Result<LobbyInfoData, Error> {
-    let ai_controlled = cursor.byte_order().read_u8().await? == 1;
-    let team = parse_team(cursor.byte_order().read_u8().await?)?;
-    let nationality = parse_nationality(cursor.byte_order().read_u8().await?)?;
+pub fn parse_lobby_info(
+    cursor: &mut Cursor<&[u8]>,
+    decoder: &mut Decoder,
+) -> Result<LobbyInfoData, TelemetryError> {
+    let ai_controlled = cursor.read_u8()? == 1;
+    let team = parse_team(cursor.read_u8()?, decoder)?;
+    let nationality = parse_nationality(cursor.read_u8()?, decoder)?;
     let mut name: Vec<char> = Vec::with_capacity(48);
     for _ in 0..48 {
-        name.push(cursor.byte_order().read_u8().await? as char);
+        name.push(cursor.read_u8()? as char);
     }
 
-    let ready_status = parse_ready_status(cursor.byte_order().read_u8().await?)?;
+    let ready_status = parse_ready_status(cursor.read_u8()?, decoder)?;
 
     Ok(LobbyInfoData {
         ai_controlled,
@@ -74,22 +83,55 @@ pub async fn parse_lobby_info(cursor: &mut Cursor<Vec<u8>>) -> Result<LobbyInfoD
     })
 }
 
-fn parse_ready_status(value: u8) -> Result<ReadyStatus, Error> {
+pub fn write_lobby_info_data<W: Write>(
+    writer: &mut W,
+    packet: &PacketLobbyInfoData,
+) -> Result<(), TelemetryError> {
+    if packet.lobby_info_data.len() != TOTAL_CARS {
+        return Err(TelemetryError::Unencodable {
+            field: "lobby_info_data",
+        });
+    }
+
+    write_header(writer, &packet.header)?;
+    writer.write_u8(packet.num_players)?;
+    for data in &packet.lobby_info_data {
+        writer.write_u8(data.ai_controlled as u8)?;
+        writer.write_u8(encode_team(&data.team)?)?;
+        writer.write_u8(encode_nationality(&data.nationality)?)?;
+        write_name(writer, &data.name)?;
+        writer.write_u8(encode_ready_status(&data.ready_status)?)?;
+    }
+
+    Ok(())
+}
+
+pub fn parse_ready_status(value: u8, decoder: &mut Decoder) -> Result<ReadyStatus, TelemetryError> {
     match value {
         0 => Ok(ReadyStatus::NotReady),
         1 => Ok(ReadyStatus::Ready),
         2 => Ok(ReadyStatus::Spectating),
-        _ => Err(Error::new(ErrorKind::InvalidData, "Ready status invalid")),
+        _ => decoder.unknown("ready_status", value, ReadyStatus::Unknown(value)),
+    }
+}
+
+pub fn encode_ready_status(value: &ReadyStatus) -> Result<u8, TelemetryError> {
+    match value {
+        ReadyStatus::NotReady => Ok(0),
+        ReadyStatus::Ready => Ok(1),
+        ReadyStatus::Spectating => Ok(2),
+        ReadyStatus::Unknown(raw) => Ok(*raw),
     }
 }
 
-fn ensure_lobby_info_size(size: usize) -> Result<(), Error> {
+fn ensure_lobby_info_size(size: usize) -> Result<(), TelemetryError> {
     if size == LOBBY_INFO_MIN_SIZE {
         return Ok(());
     }
 
-    Err(Error::new(
-        ErrorKind::InvalidData,
-        "Lobby info size is too small",
-    ))
+    Err(TelemetryError::SizeMismatch {
+        packet: "Lobby info",
+        expected: LOBBY_INFO_MIN_SIZE,
+        got: size,
+    })
 }
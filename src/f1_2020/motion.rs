@@ -1,7 +1,8 @@
+use crate::error::TelemetryError;
 use crate::f1_2020::car::TOTAL_CARS;
-use crate::f1_2020::header::PacketHeader;
-use async_std::io::{Cursor, Error, ErrorKind};
-use byteorder_async::{LittleEndian, ReaderToByteOrder};
+use crate::f1_2020::header::{write_header, PacketHeader};
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use std::io::{Cursor, Write};
 
 use derivative::Derivative;
 
@@ -62,64 +63,64 @@ where
     pub front_right: T,
 }
 
-pub async fn parse_motion_data(
-    cursor: &mut Cursor<Vec<u8>>,
+pub fn parse_motion_data(
+    cursor: &mut Cursor<&[u8]>,
     header: PacketHeader,
     size: usize,
-) -> Result<PacketMotionData, Error> {
+) -> Result<PacketMotionData, TelemetryError> {
     ensure_motion_size(size)?;
 
     let mut car_motion_data = Vec::with_capacity(TOTAL_CARS);
     for _ in 0..TOTAL_CARS {
-        let car_motion = parse_car_motion(cursor).await?;
+        let car_motion = parse_car_motion(cursor)?;
         car_motion_data.push(car_motion);
     }
 
     let suspension_position = Wheel {
-        rear_left: cursor.byte_order().read_f32::<LittleEndian>().await?,
-        rear_right: cursor.byte_order().read_f32::<LittleEndian>().await?,
-        front_left: cursor.byte_order().read_f32::<LittleEndian>().await?,
-        front_right: cursor.byte_order().read_f32::<LittleEndian>().await?,
+        rear_left: cursor.read_f32::<LittleEndian>()?,
+        rear_right: cursor.read_f32::<LittleEndian>()?,
+        front_left: cursor.read_f32::<LittleEndian>()?,
+        front_right: cursor.read_f32::<LittleEndian>()?,
     };
 
     let suspension_velocity = Wheel {
-        rear_left: cursor.byte_order().read_f32::<LittleEndian>().await?,
-        rear_right: cursor.byte_order().read_f32::<LittleEndian>().await?,
-        front_left: cursor.byte_order().read_f32::<LittleEndian>().await?,
-        front_right: cursor.byte_order().read_f32::<LittleEndian>().await?,
+        rear_left: cursor.read_f32::<LittleEndian>()?,
+        rear_right: cursor.read_f32::<LittleEndian>()?,
+        front_left: cursor.read_f32::<LittleEndian>()?,
+        front_right: cursor.read_f32::<LittleEndian>()?,
     };
 
     let suspension_acceleration = Wheel {
-        rear_left: cursor.byte_order().read_f32::<LittleEndian>().await?,
-        rear_right: cursor.byte_order().read_f32::<LittleEndian>().await?,
-        front_left: cursor.byte_order().read_f32::<LittleEndian>().await?,
-        front_right: cursor.byte_order().read_f32::<LittleEndian>().await?,
+        rear_left: cursor.read_f32::<LittleEndian>()?,
+        rear_right: cursor.read_f32::<LittleEndian>()?,
+        front_left: cursor.read_f32::<LittleEndian>()?,
+        front_right: cursor.read_f32::<LittleEndian>()?,
     };
 
     let wheel_speed = Wheel {
-        rear_left: cursor.byte_order().read_f32::<LittleEndian>().await?,
-        rear_right: cursor.byte_order().read_f32::<LittleEndian>().await?,
-        front_left: cursor.byte_order().read_f32::<LittleEndian>().await?,
-        front_right: cursor.byte_order().read_f32::<LittleEndian>().await?,
+        rear_left: cursor.read_f32::<LittleEndian>()?,
+        rear_right: cursor.read_f32::<LittleEndian>()?,
+        front_left: cursor.read_f32::<LittleEndian>()?,
+        front_right: cursor.read_f32::<LittleEndian>()?,
     };
 
     let wheel_slip = Wheel {
-        rear_left: cursor.byte_order().read_f32::<LittleEndian>().await?,
-        rear_right: cursor.byte_order().read_f32::<LittleEndian>().await?,
-        front_left: cursor.byte_order().read_f32::<LittleEndian>().await?,
-        front_right: cursor.byte_order().read_f32::<LittleEndian>().await?,
+        rear_left: cursor.read_f32::<LittleEndian>()?,
+        rear_right: cursor.read_f32::<LittleEndian>()?,
+        front_left: cursor.read_f32::<LittleEndian>()?,
+        front_right: cursor.read_f32::<LittleEndian>()?,
     };
 
-    let local_velocity_x = cursor.byte_order().read_f32::<LittleEndian>().await?;
-    let local_velocity_y = cursor.byte_order().read_f32::<LittleEndian>().await?;
-    let local_velocity_z = cursor.byte_order().read_f32::<LittleEndian>().await?;
-    let angular_velocity_x = cursor.byte_order().read_f32::<LittleEndian>().await?;
-    let angular_velocity_y = cursor.byte_order().read_f32::<LittleEndian>().await?;
-    let angular_velocity_z = cursor.byte_order().read_f32::<LittleEndian>().await?;
-    let angular_acceleration_x = cursor.byte_order().read_f32::<LittleEndian>().await?;
-    let angular_acceleration_y = cursor.byte_order().read_f32::<LittleEndian>().await?;
-    let angular_acceleration_z = cursor.byte_order().read_f32::<LittleEndian>().await?;
-    let front_wheels_angle = cursor.byte_order().read_f32::<LittleEndian>().await?;
+    let local_velocity_x = cursor.read_f32::<LittleEndian>()?;
+    let local_velocity_y = cursor.read_f32::<LittleEndian>()?;
+    let local_velocity_z = cursor.read_f32::<LittleEndian>()?;
+    let angular_velocity_x = cursor.read_f32::<LittleEndian>()?;
+    let angular_velocity_y = cursor.read_f32::<LittleEndian>()?;
+    let angular_velocity_z = cursor.read_f32::<LittleEndian>()?;
+    let angular_acceleration_x = cursor.read_f32::<LittleEndian>()?;
+    let angular_acceleration_y = cursor.read_f32::<LittleEndian>()?;
+    let angular_acceleration_z = cursor.read_f32::<LittleEndian>()?;
+    let front_wheels_angle = cursor.read_f32::<LittleEndian>()?;
 
     Ok(PacketMotionData {
         header,
@@ -142,25 +143,25 @@ pub async fn parse_motion_data(
     })
 }
 
-async fn parse_car_motion(cursor: &mut Cursor<Vec<u8>>) -> Result<CarMotionData, Error> {
-    let world_position_x = cursor.byte_order().read_f32::<LittleEndian>().await?;
-    let world_position_y = cursor.byte_order().read_f32::<LittleEndian>().await?;
-    let world_position_z = cursor.byte_order().read_f32::<LittleEndian>().await?;
-    let world_velocity_x = cursor.byte_order().read_f32::<LittleEndian>().await?;
-    let world_velocity_y = cursor.byte_order().read_f32::<LittleEndian>().await?;
-    let world_velocity_z = cursor.byte_order().read_f32::<LittleEndian>().await?;
-    let world_forward_dir_x = cursor.byte_order().read_i16::<LittleEndian>().await?;
-    let world_forward_dir_y = cursor.byte_order().read_i16::<LittleEndian>().await?;
-    let world_forward_dir_z = cursor.byte_order().read_i16::<LittleEndian>().await?;
-    let world_right_dir_x = cursor.byte_order().read_i16::<LittleEndian>().await?;
-    let world_right_dir_y = cursor.byte_order().read_i16::<LittleEndian>().await?;
-    let world_right_dir_z = cursor.byte_order().read_i16::<LittleEndian>().await?;
-    let g_force_lateral = cursor.byte_order().read_f32::<LittleEndian>().await?;
-    let g_force_longitudinal = cursor.byte_order().read_f32::<LittleEndian>().await?;
-    let g_force_vertical = cursor.byte_order().read_f32::<LittleEndian>().await?;
-    let yaw = cursor.byte_order().read_f32::<LittleEndian>().await?;
-    let pitch = cursor.byte_order().read_f32::<LittleEndian>().await?;
-    let roll = cursor.byte_order().read_f32::<LittleEndian>().await?;
+pub fn parse_car_motion(cursor: &mut Cursor<&[u8]>) -> Result<CarMotionData, TelemetryError> {
+    let world_position_x = cursor.read_f32::<LittleEndian>()?;
+    let world_position_y = cursor.read_f32::<LittleEndian>()?;
+    let world_position_z = cursor.read_f32::<LittleEndian>()?;
+    let world_velocity_x = cursor.read_f32::<LittleEndian>()?;
+    let world_velocity_y = cursor.read_f32::<LittleEndian>()?;
+    let world_velocity_z = cursor.read_f32::<LittleEndian>()?;
+    let world_forward_dir_x = cursor.read_i16::<LittleEndian>()?;
+    let world_forward_dir_y = cursor.read_i16::<LittleEndian>()?;
+    let world_forward_dir_z = cursor.read_i16::<LittleEndian>()?;
+    let world_right_dir_x = cursor.read_i16::<LittleEndian>()?;
+    let world_right_dir_y = cursor.read_i16::<LittleEndian>()?;
+    let world_right_dir_z = cursor.read_i16::<LittleEndian>()?;
+    let g_force_lateral = cursor.read_f32::<LittleEndian>()?;
+    let g_force_longitudinal = cursor.read_f32::<LittleEndian>()?;
+    let g_force_vertical = cursor.read_f32::<LittleEndian>()?;
+    let yaw = cursor.read_f32::<LittleEndian>()?;
+    let pitch = cursor.read_f32::<LittleEndian>()?;
+    let roll = cursor.read_f32::<LittleEndian>()?;
 
     Ok(CarMotionData {
         world_position_x,
@@ -184,13 +185,82 @@ async fn parse_car_motion(cursor: &mut Cursor<Vec<u8>>) -> Result<CarMotionData,
     })
 }
 
-fn ensure_motion_size(size: usize) -> Result<(), Error> {
+pub fn write_motion_data<W: Write>(
+    writer: &mut W,
+    packet: &PacketMotionData,
+) -> Result<(), TelemetryError> {
+    if packet.motion_data.len() != TOTAL_CARS {
+        return Err(TelemetryError::Unencodable {
+            field: "motion_data",
+        });
+    }
+
+    write_header(writer, &packet.header)?;
+    for car_motion in &packet.motion_data {
+        write_car_motion(writer, car_motion)?;
+    }
+
+    for wheel in [
+        &packet.suspension_position,
+        &packet.suspension_velocity,
+        &packet.suspension_acceleration,
+        &packet.wheel_speed,
+        &packet.wheel_slip,
+    ] {
+        writer.write_f32::<LittleEndian>(wheel.rear_left)?;
+        writer.write_f32::<LittleEndian>(wheel.rear_right)?;
+        writer.write_f32::<LittleEndian>(wheel.front_left)?;
+        writer.write_f32::<LittleEndian>(wheel.front_right)?;
+    }
+
+    writer.write_f32::<LittleEndian>(packet.local_velocity_x)?;
+    writer.write_f32::<LittleEndian>(packet.local_velocity_y)?;
+    writer.write_f32::<LittleEndian>(packet.local_velocity_z)?;
+    writer.write_f32::<LittleEndian>(packet.angular_velocity_x)?;
+    writer.write_f32::<LittleEndian>(packet.angular_velocity_y)?;
+    writer.write_f32::<LittleEndian>(packet.angular_velocity_z)?;
+    writer.write_f32::<LittleEndian>(packet.angular_acceleration_x)?;
+    writer.write_f32::<LittleEndian>(packet.angular_acceleration_y)?;
+    writer.write_f32::<LittleEndian>(packet.angular_acceleration_z)?;
+    writer.write_f32::<LittleEndian>(packet.front_wheels_angle)?;
+
+    Ok(())
+}
+
+pub fn write_car_motion<W: Write>(
+    writer: &mut W,
+    car_motion: &CarMotionData,
+) -> Result<(), TelemetryError> {
+    writer.write_f32::<LittleEndian>(car_motion.world_position_x)?;
+    writer.write_f32::<LittleEndian>(car_motion.world_position_y)?;
+    writer.write_f32::<LittleEndian>(car_motion.world_position_z)?;
+    writer.write_f32::<LittleEndian>(car_motion.world_velocity_x)?;
+    writer.write_f32::<LittleEndian>(car_motion.world_velocity_y)?;
+    writer.write_f32::<LittleEndian>(car_motion.world_velocity_z)?;
+    writer.write_i16::<LittleEndian>(car_motion.world_forward_dir_x)?;
+    writer.write_i16::<LittleEndian>(car_motion.world_forward_dir_y)?;
+    writer.write_i16::<LittleEndian>(car_motion.world_forward_dir_z)?;
+    writer.write_i16::<LittleEndian>(car_motion.world_right_dir_x)?;
+    writer.write_i16::<LittleEndian>(car_motion.world_right_dir_y)?;
+    writer.write_i16::<LittleEndian>(car_motion.world_right_dir_z)?;
+    writer.write_f32::<LittleEndian>(car_motion.g_force_lateral)?;
+    writer.write_f32::<LittleEndian>(car_motion.g_force_longitudinal)?;
+    writer.write_f32::<LittleEndian>(car_motion.g_force_vertical)?;
+    writer.write_f32::<LittleEndian>(car_motion.yaw)?;
+    writer.write_f32::<LittleEndian>(car_motion.pitch)?;
+    writer.write_f32::<LittleEndian>(car_motion.roll)?;
+
+    Ok(())
+}
+
+fn ensure_motion_size(size: usize) -> Result<(), TelemetryError> {
     if size < MOTION_MIN_SIZE {
-        return Err(Error::new(
-            ErrorKind::InvalidData,
-            "Motion size is too small",
-        ));
+        return Err(TelemetryError::SizeMismatch {
+            packet: "Motion",
+            expected: MOTION_MIN_SIZE,
+            got: size,
+        });
     }
 
-    return Ok(());
+    Ok(())
 }
@@ -89,4 +89,5 @@ pub enum Nationality {
     Vietnamese,
     Welsh,
     Invalid,
+    Unknown(u8),
 }
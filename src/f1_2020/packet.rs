@@ -1,18 +1,24 @@
+use crate::decoder::Decoder;
+use crate::error::TelemetryError;
 use crate::f1_2020::car::{PacketCarSetupData, PacketCarStatusData, PacketCarTelemetryData};
-use crate::f1_2020::car_setup::parse_car_setup_data;
-use crate::f1_2020::car_status::parse_car_status_data;
-use crate::f1_2020::car_telemetry::parse_car_telemetry_data;
-use crate::f1_2020::event::{parse_event_data, PacketEventData};
+use crate::f1_2020::car_setup::{parse_car_setup_data, write_car_setup_data};
+use crate::f1_2020::car_status::{parse_car_status_data, write_car_status_data};
+use crate::f1_2020::car_telemetry::{parse_car_telemetry_data, write_car_telemetry_data};
+use crate::f1_2020::event::{parse_event_data, write_event_data, PacketEventData};
 use crate::f1_2020::final_classification::{
-    parse_final_classification_data, PacketFinalClassificationData,
+    parse_final_classification_data, write_final_classification_data, PacketFinalClassificationData,
 };
-use crate::f1_2020::header::parse_headers;
-use crate::f1_2020::lap::{parse_lap_data, PacketLapData};
-use crate::f1_2020::lobby_info::{parse_lobby_info_data, PacketLobbyInfoData};
-use crate::f1_2020::motion::{parse_motion_data, PacketMotionData};
-use crate::f1_2020::participants::{parse_participants_data, PacketParticipantsData};
-use crate::f1_2020::session::{parse_session, PacketSessionData};
-use async_std::io::{Cursor, Error, ErrorKind};
+use crate::f1_2020::header::{parse_headers, PacketHeader};
+use crate::f1_2020::lap::{parse_lap_data, write_lap_data, PacketLapData};
+use crate::f1_2020::lobby_info::{
+    parse_lobby_info_data, write_lobby_info_data, PacketLobbyInfoData,
+};
+use crate::f1_2020::motion::{parse_motion_data, write_motion_data, PacketMotionData};
+use crate::f1_2020::participants::{
+    parse_participants_data, write_participants_data, PacketParticipantsData,
+};
+use crate::f1_2020::session::{parse_session, write_session, PacketSessionData};
+use std::io::{Cursor, Write};
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub enum PacketID {
@@ -42,54 +48,90 @@ pub enum Packet2020 {
     LobbyInfo(PacketLobbyInfoData),
 }
 
-pub async fn parse_f12020(cursor: &mut Cursor<Vec<u8>>, size: usize) -> Result<Packet2020, Error> {
-    let header = parse_headers(cursor, size).await?;
+impl Packet2020 {
+    pub fn header(&self) -> &PacketHeader {
+        match self {
+            Packet2020::Motion(packet) => &packet.header,
+            Packet2020::Session(packet) => &packet.header,
+            Packet2020::Lap(packet) => &packet.header,
+            Packet2020::Event(packet) => &packet.header,
+            Packet2020::Participants(packet) => &packet.header,
+            Packet2020::CarSetups(packet) => &packet.header,
+            Packet2020::CarTelemetry(packet) => &packet.header,
+            Packet2020::CarStatus(packet) => &packet.header,
+            Packet2020::FinalClassification(packet) => &packet.header,
+            Packet2020::LobbyInfo(packet) => &packet.header,
+        }
+    }
+
+    pub fn packet_id(&self) -> PacketID {
+        match self {
+            Packet2020::Motion(_) => PacketID::Motion,
+            Packet2020::Session(_) => PacketID::Session,
+            Packet2020::Lap(_) => PacketID::LapData,
+            Packet2020::Event(_) => PacketID::Event,
+            Packet2020::Participants(_) => PacketID::Participants,
+            Packet2020::CarSetups(_) => PacketID::CarSetups,
+            Packet2020::CarTelemetry(_) => PacketID::CarTelemetry,
+            Packet2020::CarStatus(_) => PacketID::CarStatus,
+            Packet2020::FinalClassification(_) => PacketID::FinalClassification,
+            Packet2020::LobbyInfo(_) => PacketID::LobbyInfo,
+        }
+    }
+}
+
+pub fn parse_f12020(
+    cursor: &mut Cursor<&[u8]>,
+    size: usize,
+    decoder: &mut Decoder,
+) -> Result<Packet2020, TelemetryError> {
+    let header = parse_headers(cursor, size)?;
     match packet_type(header.packet_id)? {
         PacketID::Motion => {
-            let motion = parse_motion_data(cursor, header, size).await?;
+            let motion = parse_motion_data(cursor, header, size)?;
             Ok(Packet2020::Motion(motion))
         }
         PacketID::Session => {
-            let session = parse_session(cursor, header, size).await?;
+            let session = parse_session(cursor, header, size, decoder)?;
             Ok(Packet2020::Session(session))
         }
         PacketID::LapData => {
-            let lap = parse_lap_data(cursor, header, size).await?;
+            let lap = parse_lap_data(cursor, header, size, decoder)?;
             Ok(Packet2020::Lap(lap))
         }
         PacketID::Event => {
-            let event = parse_event_data(cursor, header, size).await?;
+            let event = parse_event_data(cursor, header, size, decoder)?;
             Ok(Packet2020::Event(event))
         }
         PacketID::Participants => {
-            let participants = parse_participants_data(cursor, header, size).await?;
+            let participants = parse_participants_data(cursor, header, size, decoder)?;
             Ok(Packet2020::Participants(participants))
         }
         PacketID::CarSetups => {
-            let car_setups = parse_car_setup_data(cursor, header, size).await?;
+            let car_setups = parse_car_setup_data(cursor, header, size)?;
             Ok(Packet2020::CarSetups(car_setups))
         }
         PacketID::CarTelemetry => {
-            let car_telemetry = parse_car_telemetry_data(cursor, header, size).await?;
+            let car_telemetry = parse_car_telemetry_data(cursor, header, size, decoder)?;
             Ok(Packet2020::CarTelemetry(car_telemetry))
         }
         PacketID::CarStatus => {
-            let car_status = parse_car_status_data(cursor, header, size).await?;
+            let car_status = parse_car_status_data(cursor, header, size, decoder)?;
             Ok(Packet2020::CarStatus(car_status))
         }
         PacketID::FinalClassification => {
             let final_classification =
-                parse_final_classification_data(cursor, header, size).await?;
+                parse_final_classification_data(cursor, header, size, decoder)?;
             Ok(Packet2020::FinalClassification(final_classification))
         }
         PacketID::LobbyInfo => {
-            let lobby_info = parse_lobby_info_data(cursor, header, size).await?;
+            let lobby_info = parse_lobby_info_data(cursor, header, size, decoder)?;
             Ok(Packet2020::LobbyInfo(lobby_info))
         }
     }
 }
 
-pub fn packet_type(packet_id: u8) -> Result<PacketID, Error> {
+pub fn packet_type(packet_id: u8) -> Result<PacketID, TelemetryError> {
     match packet_id {
         0 => Ok(PacketID::Motion),
         1 => Ok(PacketID::Session),
@@ -101,9 +143,793 @@ pub fn packet_type(packet_id: u8) -> Result<PacketID, Error> {
         7 => Ok(PacketID::CarStatus),
         8 => Ok(PacketID::FinalClassification),
         9 => Ok(PacketID::LobbyInfo),
-        _ => Err(Error::new(
-            ErrorKind::InvalidData,
-            "Failed to parse packet_type",
-        )),
+        _ => Err(TelemetryError::UnknownPacketId { id: packet_id }),
+    }
+}
+
+pub fn encode_packet_type(packet_id: PacketID) -> u8 {
+    match packet_id {
+        PacketID::Motion => 0,
+        PacketID::Session => 1,
+        PacketID::LapData => 2,
+        PacketID::Event => 3,
+        PacketID::Participants => 4,
+        PacketID::CarSetups => 5,
+        PacketID::CarTelemetry => 6,
+        PacketID::CarStatus => 7,
+        PacketID::FinalClassification => 8,
+        PacketID::LobbyInfo => 9,
+    }
+}
+
+/// Encodes a packet into the datagram the game would send for it.
+pub fn encode_f12020(packet: &Packet2020) -> Result<Vec<u8>, TelemetryError> {
+    let mut bytes = Vec::with_capacity(2048);
+    write_f12020(&mut bytes, packet)?;
+
+    Ok(bytes)
+}
+
+pub fn write_f12020<W: Write>(writer: &mut W, packet: &Packet2020) -> Result<(), TelemetryError> {
+    // The header is written as is, reject one the decoder would not route
+    // back to this packet type.
+    let header = packet.header();
+    if header.packet_format != 2020 {
+        return Err(TelemetryError::Unencodable {
+            field: "packet_format",
+        });
+    }
+    if packet_type(header.packet_id).ok() != Some(packet.packet_id()) {
+        return Err(TelemetryError::Unencodable { field: "packet_id" });
+    }
+
+    match packet {
+        Packet2020::Motion(motion) => write_motion_data(writer, motion),
+        Packet2020::Session(session) => write_session(writer, session),
+        Packet2020::Lap(lap) => write_lap_data(writer, lap),
+        Packet2020::Event(event) => write_event_data(writer, event),
+        Packet2020::Participants(participants) => write_participants_data(writer, participants),
+        Packet2020::CarSetups(car_setups) => write_car_setup_data(writer, car_setups),
+        Packet2020::CarTelemetry(car_telemetry) => write_car_telemetry_data(writer, car_telemetry),
+        Packet2020::CarStatus(car_status) => write_car_status_data(writer, car_status),
+        Packet2020::FinalClassification(final_classification) => {
+            write_final_classification_data(writer, final_classification)
+        }
+        Packet2020::LobbyInfo(lobby_info) => write_lobby_info_data(writer, lobby_info),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::decoder::{DecodeMode, Decoder};
+    use crate::error::TelemetryError;
+    use crate::f1_2020::car::{
+        ActualTyreCompound, AntiLockBrakes, CarSetupData, CarStatusData, CarTelemetryData,
+        DRSStatus, ERSDeploymentMode, FuelMix, MFDPanel, PacketCarSetupData, PacketCarStatusData,
+        PacketCarTelemetryData, SurfaceType, TractionControl, TyrePressure, VisualTyreCompound,
+        TOTAL_CARS,
+    };
+    use crate::f1_2020::car_status::{
+        encode_actual_tyre_compound, encode_ers_deployment_mode, encode_fuel_mix,
+        encode_visual_tyre_compound, parse_actual_tyre_compound, parse_ers_deployment_mode,
+        parse_fuel_mix, parse_visual_tyre_compound,
+    };
+    use crate::f1_2020::driver::Driver;
+    use crate::f1_2020::event::{
+        Event, FastestLap, InfringementType, PacketEventData, Penalty, PenaltyType, SpeedTrap,
+    };
+    use crate::f1_2020::final_classification::{
+        FinalClassificationData, PacketFinalClassificationData,
+    };
+    use crate::f1_2020::header::PacketHeader;
+    use crate::f1_2020::lap::{DriverStatus, LapData, PacketLapData, PitStatus, ResultStatus};
+    use crate::f1_2020::lobby_info::{LobbyInfoData, PacketLobbyInfoData, ReadyStatus};
+    use crate::f1_2020::motion::{CarMotionData, PacketMotionData, Wheel};
+    use crate::f1_2020::nationality::Nationality;
+    use crate::f1_2020::packet::{encode_f12020, Packet2020};
+    use crate::f1_2020::participants::{
+        encode_driver, PacketParticipantsData, ParticipantData, YourTelemetry,
+    };
+    use crate::f1_2020::session::{
+        encode_session_type, encode_track, parse_session_type, parse_track, Formula, MarshalZone,
+        NetworkGame, PacketSessionData, SafetyCar, SessionType, Track, Weather,
+        WeatherForecastSample, ZoneFlag,
+    };
+    use crate::f1_2020::team::Team;
+    use crate::packet::Packet;
+    use crate::Protocol;
+    use std::fmt::Debug;
+    use std::time::Duration;
+
+    fn header(packet_id: u8) -> PacketHeader {
+        PacketHeader {
+            packet_format: 2020,
+            major_version: 1,
+            minor_version: 18,
+            packet_version: 1,
+            packet_id,
+            session_uid: 0x0123_4567_89ab_cdef,
+            session_time: Duration::from_millis(83_250),
+            frame_identifier: 4711,
+            player_car_index: 3,
+            secondary_player_car_index: 255,
+        }
+    }
+
+    fn wheel<T: Copy>(rear_left: T, rear_right: T, front_left: T, front_right: T) -> Wheel<T> {
+        Wheel {
+            rear_left,
+            rear_right,
+            front_left,
+            front_right,
+        }
+    }
+
+    /// Encodes the packet, checks the datagram size and that decoding it,
+    /// then encoding the result again, reproduces both.
+    fn assert_round_trip(packet: Packet2020, size: usize) {
+        let bytes = encode_f12020(&packet).unwrap();
+        assert_eq!(size, bytes.len());
+
+        let decoded = match crate::parse(&bytes).unwrap() {
+            Packet::F12020(decoded) => decoded,
+            other => panic!("decoded as {:?}", other),
+        };
+        assert_eq!(packet, decoded);
+        assert_eq!(bytes, encode_f12020(&decoded).unwrap());
+    }
+
+    #[test]
+    fn round_trip_motion() {
+        let motion_data = (0..TOTAL_CARS)
+            .map(|i| CarMotionData {
+                world_position_x: i as f32 * 10.5,
+                world_position_y: -2.25,
+                world_position_z: 300.125,
+                world_velocity_x: 55.5,
+                world_velocity_y: 0.0,
+                world_velocity_z: -12.75,
+                world_forward_dir_x: 32767,
+                world_forward_dir_y: -1,
+                world_forward_dir_z: i as i16,
+                world_right_dir_x: -32768,
+                world_right_dir_y: 2,
+                world_right_dir_z: 3,
+                g_force_lateral: 1.5,
+                g_force_longitudinal: -3.25,
+                g_force_vertical: 0.75,
+                yaw: 3.0,
+                pitch: -0.5,
+                roll: 0.125,
+            })
+            .collect();
+
+        assert_round_trip(
+            Packet2020::Motion(PacketMotionData {
+                header: header(0),
+                motion_data,
+                suspension_position: wheel(1.0, 2.0, 3.0, 4.0),
+                suspension_velocity: wheel(-1.0, -2.0, -3.0, -4.0),
+                suspension_acceleration: wheel(0.5, 0.25, 0.125, 0.0625),
+                wheel_speed: wheel(80.0, 80.5, 81.0, 81.5),
+                wheel_slip: wheel(0.0, 0.1, 0.2, 0.3),
+                local_velocity_x: 1.0,
+                local_velocity_y: 2.0,
+                local_velocity_z: 3.0,
+                angular_velocity_x: 4.0,
+                angular_velocity_y: 5.0,
+                angular_velocity_z: 6.0,
+                angular_acceleration_x: 7.0,
+                angular_acceleration_y: 8.0,
+                angular_acceleration_z: 9.0,
+                front_wheels_angle: -0.25,
+            }),
+            1464,
+        );
+    }
+
+    #[test]
+    fn round_trip_session() {
+        let weather_forecast_sample = (0..20)
+            .map(|i| WeatherForecastSample {
+                session_type: if i < 4 {
+                    SessionType::R
+                } else {
+                    SessionType::Unknown(0)
+                },
+                time_offset: i * 5,
+                weather: Weather::LightRain,
+                track_temperature: 31,
+                air_temperature: -4,
+            })
+            .collect();
+
+        assert_round_trip(
+            Packet2020::Session(PacketSessionData {
+                header: header(1),
+                weather: Weather::Overcast,
+                track_temperature: 28,
+                air_temperature: 19,
+                total_laps: 53,
+                track_length: 5793,
+                session_type: SessionType::R,
+                track_id: Track::Monza,
+                formula: Formula::F1Modern,
+                session_time_left: 7200,
+                session_duration: 7200,
+                pit_speed_limit: 80,
+                game_paused: 0,
+                is_spectating: 1,
+                spectator_car_index: 7,
+                sli_pro_native_support: 0,
+                num_marshal_zones: 3,
+                marshal_zone: vec![
+                    MarshalZone {
+                        zone_start: 0.125,
+                        zone_flag: ZoneFlag::Green,
+                    },
+                    MarshalZone {
+                        zone_start: 0.5,
+                        zone_flag: ZoneFlag::Yellow,
+                    },
+                    MarshalZone {
+                        zone_start: 0.75,
+                        zone_flag: ZoneFlag::Unknown(-1),
+                    },
+                ],
+                safety_car_status: SafetyCar::Virtual,
+                network_game: NetworkGame::Online,
+                num_weather_forecast_samples: 4,
+                weather_forecast_sample,
+            }),
+            251,
+        );
+    }
+
+    #[test]
+    fn round_trip_lap() {
+        let lap_data = (0..TOTAL_CARS)
+            .map(|i| LapData {
+                last_lap_time: Duration::from_millis(81_500),
+                current_lap_time: Duration::from_millis(40_250),
+                sector_1_time: Duration::from_millis(27_123),
+                sector_2_time: Duration::from_millis(29_456),
+                best_lap_time: Duration::from_millis(80_750),
+                best_lap_num: 12,
+                best_lap_sector_1_time: Duration::from_millis(26_999),
+                best_lap_sector_2_time: Duration::from_millis(28_001),
+                best_lap_sector_3_time: Duration::from_millis(25_750),
+                best_overall_sector_1_time: Duration::from_millis(26_500),
+                best_overall_sector_1_lap_num: 9,
+                best_overall_sector_2_time: Duration::from_millis(27_900),
+                best_overall_sector_2_lap_num: 10,
+                best_overall_sector_3_time: Duration::from_millis(25_600),
+                best_overall_sector_3_lap_num: 11,
+                lap_distance: 2500.5,
+                total_distance: -120.25,
+                safety_car_delta: -1.5,
+                car_position: i as u8 + 1,
+                current_lap_num: 14,
+                pit_status: PitStatus::PitArea,
+                sector: 1,
+                current_lap_invalid: i % 2 == 0,
+                penalties: 5,
+                grid_position: 22 - i as u8,
+                driver_status: DriverStatus::FlyingLap,
+                result_status: ResultStatus::Active,
+            })
+            .collect();
+
+        assert_round_trip(
+            Packet2020::Lap(PacketLapData {
+                header: header(2),
+                lap_data,
+            }),
+            1190,
+        );
+    }
+
+    #[test]
+    fn round_trip_event() {
+        let events = vec![
+            Event::SessionStarted,
+            Event::ChequeredFlag,
+            Event::FastestLap(FastestLap {
+                vehicle_index: 4,
+                lap_time: Duration::from_millis(79_875),
+            }),
+            Event::Penalty(Penalty {
+                penalty_type: PenaltyType::TimePenalty,
+                infringement_type: InfringementType::CornerCuttingGainedTime,
+                vehicle_index: 6,
+                other_vehicle_index: 255,
+                time: Duration::from_secs(5),
+                lap_num: 17,
+                places_gained: 0,
+            }),
+            Event::SpeedTrap(SpeedTrap {
+                vehicle_index: 1,
+                speed: 342.5,
+            }),
+        ];
+
+        for event in events {
+            assert_round_trip(
+                Packet2020::Event(PacketEventData {
+                    header: header(3),
+                    event,
+                }),
+                35,
+            );
+        }
+    }
+
+    #[test]
+    fn round_trip_participants() {
+        let participants = (0..TOTAL_CARS)
+            .map(|i| ParticipantData {
+                ai_controlled: i != 3,
+                // Human players have driver ids from 100 on
+                driver: if i == 3 {
+                    Driver::Player(104)
+                } else {
+                    Driver::LewisHamilton
+                },
+                team: Team::Mercedes,
+                race_number: 44,
+                nationality: Nationality::British,
+                name: format!("DRIVER {}", i),
+                your_telemetry: YourTelemetry::Public,
+            })
+            .collect();
+
+        assert_round_trip(
+            Packet2020::Participants(PacketParticipantsData {
+                header: header(4),
+                num_active_cars: 20,
+                participants,
+            }),
+            1213,
+        );
+    }
+
+    #[test]
+    fn round_trip_car_setups() {
+        let car_setup_data = (0..TOTAL_CARS)
+            .map(|i| CarSetupData {
+                front_wing: 5,
+                rear_wing: 7,
+                on_throttle: 60,
+                off_throttle: 55,
+                front_camber: -2.5,
+                rear_camber: -1.25,
+                front_toe: 0.0625,
+                rear_toe: 0.25,
+                front_suspension: 4,
+                rear_suspension: 3,
+                front_anti_roll_bar: 2,
+                rear_anti_roll_bar: 6,
+                front_suspension_height: 3,
+                rear_suspension_height: 7,
+                brake_pressure: 100,
+                brake_bias: 56,
+                rear_tyre_pressure: TyrePressure {
+                    left: 20.5,
+                    right: 20.5,
+                },
+                front_tyre_pressure: TyrePressure {
+                    left: 23.0,
+                    right: 23.5,
+                },
+                ballast: i as u8,
+                fuel_load: 105.0,
+            })
+            .collect();
+
+        assert_round_trip(
+            Packet2020::CarSetups(PacketCarSetupData {
+                header: header(5),
+                car_setup_data,
+            }),
+            1102,
+        );
+    }
+
+    #[test]
+    fn round_trip_car_telemetry() {
+        let car_telemetry_data = (0..TOTAL_CARS)
+            .map(|i| CarTelemetryData {
+                speed: 312,
+                throttle: 1.0,
+                steer: -0.25,
+                brake: 0.0,
+                clutch: 0,
+                gear: if i == 0 { -1 } else { 8 },
+                engine_rpm: 11_800,
+                drs: i % 3 == 0,
+                rev_lights_percent: 90,
+                brakes_temperature: wheel(550, 560, 700, 710),
+                tyres_surface_temperature: wheel(95, 96, 101, 102),
+                tyres_inner_temperature: wheel(100, 100, 104, 105),
+                engine_temperature: 112,
+                tyre_pressures: wheel(21.5, 21.5, 23.25, 23.25),
+                surface_types: wheel(
+                    SurfaceType::Tarmac,
+                    SurfaceType::RumbleStrip,
+                    SurfaceType::Grass,
+                    SurfaceType::Gravel,
+                ),
+            })
+            .collect();
+
+        assert_round_trip(
+            Packet2020::CarTelemetry(PacketCarTelemetryData {
+                header: header(6),
+                car_telemetry_data,
+                button_status: 0x0000_0804,
+                mfd_panel_index: MFDPanel::Closed,
+                mfd_panel_index_secondary_player: MFDPanel::Damage,
+                suggested_gear: 0,
+            }),
+            1307,
+        );
+    }
+
+    #[test]
+    fn round_trip_car_status() {
+        let car_status_data = (0..TOTAL_CARS)
+            .map(|i| CarStatusData {
+                traction_control: TractionControl::Low,
+                anti_lock_brakes: AntiLockBrakes::On,
+                fuel_mix: FuelMix::Rich,
+                front_brake_bias: 57,
+                pit_limiter: i == 5,
+                fuel_in_tank: 42.5,
+                fuel_capacity: 110.0,
+                fuel_remaining_laps: 3.25,
+                max_rpm: 12_500,
+                idle_rpm: 4_000,
+                max_gears: 8,
+                drs_allowed: DRSStatus::Allowed,
+                drs_activation_distance: 120,
+                tyres_wear: wheel(10, 11, 12, 13),
+                actual_tyre_compound: ActualTyreCompound::C3,
+                visual_tyre_compound: VisualTyreCompound::Medium,
+                tyres_age_laps: 9,
+                tyres_damage: wheel(1, 2, 3, 4),
+                front_left_wing_damage: 20,
+                front_right_wing_damage: 0,
+                rear_wing_damage: 5,
+                drs_fault: false,
+                engine_damage: 7,
+                gear_box_damage: 3,
+                vehicle_fia_flags: ZoneFlag::Blue,
+                ers_store_energy: 2_000_000.0,
+                ers_deploy_mode: ERSDeploymentMode::Overtake,
+                ers_harvested_this_lap_mguk: 150_000.0,
+                ers_harvested_this_lap_mguh: 250_000.5,
+                ers_deployed_this_lap: 400_000.25,
+            })
+            .collect();
+
+        assert_round_trip(
+            Packet2020::CarStatus(PacketCarStatusData {
+                header: header(7),
+                car_status_data,
+            }),
+            1344,
+        );
+    }
+
+    #[test]
+    fn round_trip_final_classification() {
+        let final_classification_data = (0..TOTAL_CARS)
+            .map(|i| FinalClassificationData {
+                position: i as u8 + 1,
+                num_laps: 53,
+                grid_position: 4,
+                points: 12,
+                num_pit_stops: 2,
+                result_status: ResultStatus::Finished,
+                best_lap_time: Duration::from_millis(81_250),
+                total_race_time: Duration::from_millis(4_950_125),
+                penalties_time: 5,
+                num_penalties: 1,
+                num_tyre_stints: 3,
+                tyre_stints_actual: vec![
+                    ActualTyreCompound::C4,
+                    ActualTyreCompound::C2,
+                    ActualTyreCompound::Inter,
+                    ActualTyreCompound::Unknown(0),
+                    ActualTyreCompound::Unknown(0),
+                    ActualTyreCompound::Unknown(0),
+                    ActualTyreCompound::Unknown(0),
+                    ActualTyreCompound::Unknown(0),
+                ],
+                tyre_stints_visual: vec![
+                    VisualTyreCompound::Soft,
+                    VisualTyreCompound::Hard,
+                    VisualTyreCompound::Inter,
+                    VisualTyreCompound::Unknown(0),
+                    VisualTyreCompound::Unknown(0),
+                    VisualTyreCompound::Unknown(0),
+                    VisualTyreCompound::Unknown(0),
+                    VisualTyreCompound::Unknown(0),
+                ],
+            })
+            .collect();
+
+        assert_round_trip(
+            Packet2020::FinalClassification(PacketFinalClassificationData {
+                header: header(8),
+                num_cars: 20,
+                final_classification_data,
+            }),
+            839,
+        );
+    }
+
+    #[test]
+    fn round_trip_lobby_info() {
+        let lobby_info_data = (0..TOTAL_CARS)
+            .map(|i| {
+                let mut name = format!("Player {}", i);
+                // Lobby names keep the zero padding of the wire array.
+                while name.len() < 48 {
+                    name.push('\0');
+                }
+
+                LobbyInfoData {
+                    ai_controlled: i > 1,
+                    team: Team::McLaren,
+                    nationality: Nationality::Invalid,
+                    name,
+                    ready_status: ReadyStatus::Ready,
+                }
+            })
+            .collect();
+
+        assert_round_trip(
+            Packet2020::LobbyInfo(PacketLobbyInfoData {
+                header: header(9),
+                num_players: 2,
+                lobby_info_data,
+            }),
+            1169,
+        );
+    }
+
+    #[test]
+    fn unknown_event_code_depends_on_decode_mode() {
+        let mut bytes = encode_f12020(&Packet2020::Event(PacketEventData {
+            header: header(3),
+            event: Event::SessionStarted,
+        }))
+        .unwrap();
+        bytes[24..28].copy_from_slice(b"NEWE");
+
+        assert!(matches!(
+            crate::parse(&bytes),
+            Err(TelemetryError::InvalidEnumValue {
+                field: "event_code",
+                value: 0x4557_454e,
+            })
+        ));
+
+        let mut decoder = Decoder::new(DecodeMode::Lenient);
+        match crate::parse_with(&bytes, Protocol::Standard, &mut decoder).unwrap() {
+            Packet::F12020(Packet2020::Event(event)) => {
+                assert_eq!(Event::Unknown(*b"NEWE"), event.event);
+                assert_eq!(bytes, encode_f12020(&Packet2020::Event(event)).unwrap());
+            }
+            other => panic!("decoded as {:?}", other),
+        }
+        assert_eq!(1, decoder.substitutions());
+    }
+
+    #[test]
+    fn encode_rejects_packets_the_decoder_cannot_read_back() {
+        let mismatched = Packet2020::Event(PacketEventData {
+            header: header(1),
+            event: Event::DRSEnabled,
+        });
+        assert!(matches!(
+            encode_f12020(&mismatched),
+            Err(TelemetryError::Unencodable { field: "packet_id" })
+        ));
+
+        let lobby = Packet2020::LobbyInfo(PacketLobbyInfoData {
+            header: header(9),
+            num_players: 0,
+            lobby_info_data: Vec::new(),
+        });
+        assert!(matches!(
+            encode_f12020(&lobby),
+            Err(TelemetryError::Unencodable {
+                field: "lobby_info_data"
+            })
+        ));
+
+        assert!(matches!(
+            encode_driver(&Driver::Player(5)),
+            Err(TelemetryError::Unencodable { field: "driver" })
+        ));
+
+        assert!(matches!(
+            encode_session_type(&SessionType::R3),
+            Err(TelemetryError::Unencodable {
+                field: "session_type"
+            })
+        ));
+    }
+
+    /// Checks every wire value of the 2020 spec decodes to its variant and
+    /// encodes back, and that values the spec leaves out are rejected.
+    fn assert_spec<W, T>(
+        spec: &[(W, T)],
+        undefined: &[W],
+        parse: fn(W, &mut Decoder) -> Result<T, TelemetryError>,
+        encode: fn(&T) -> Result<W, TelemetryError>,
+    ) where
+        W: Copy + Debug + PartialEq,
+        T: Debug + PartialEq,
+    {
+        for (value, variant) in spec {
+            let mut decoder = Decoder::new(DecodeMode::Strict);
+            assert_eq!(
+                variant,
+                &parse(*value, &mut decoder).unwrap(),
+                "decoding {:?}",
+                value
+            );
+            assert_eq!(*value, encode(variant).unwrap(), "encoding {:?}", variant);
+        }
+        for value in undefined {
+            let mut decoder = Decoder::new(DecodeMode::Strict);
+            assert!(
+                matches!(
+                    parse(*value, &mut decoder),
+                    Err(TelemetryError::InvalidEnumValue { .. })
+                ),
+                "decoding {:?}",
+                value
+            );
+        }
+    }
+
+    #[test]
+    fn car_status_enums_match_spec() {
+        assert_spec(
+            &[
+                (0, ERSDeploymentMode::None),
+                (1, ERSDeploymentMode::Medium),
+                (2, ERSDeploymentMode::Overtake),
+                (3, ERSDeploymentMode::Hotlap),
+            ],
+            &[4, 5, 255],
+            parse_ers_deployment_mode,
+            encode_ers_deployment_mode,
+        );
+        for mode in &[ERSDeploymentMode::Low, ERSDeploymentMode::High] {
+            assert!(encode_ers_deployment_mode(mode).is_err(), "{:?}", mode);
+        }
+
+        assert_spec(
+            &[
+                (0, FuelMix::Lean),
+                (1, FuelMix::Standard),
+                (2, FuelMix::Rich),
+                (3, FuelMix::Max),
+            ],
+            &[4],
+            parse_fuel_mix,
+            encode_fuel_mix,
+        );
+
+        assert_spec(
+            &[
+                (0, ActualTyreCompound::Unknown(0)),
+                (7, ActualTyreCompound::Inter),
+                (8, ActualTyreCompound::Wet),
+                (9, ActualTyreCompound::F1ClassicDry),
+                (10, ActualTyreCompound::F1ClassicWet),
+                (11, ActualTyreCompound::F2SuperSoft),
+                (12, ActualTyreCompound::F2Soft),
+                (13, ActualTyreCompound::F2Medium),
+                (14, ActualTyreCompound::F2Hard),
+                (15, ActualTyreCompound::F2Wet),
+                (16, ActualTyreCompound::C5),
+                (17, ActualTyreCompound::C4),
+                (18, ActualTyreCompound::C3),
+                (19, ActualTyreCompound::C2),
+                (20, ActualTyreCompound::C1),
+                (255, ActualTyreCompound::Unknown(255)),
+            ],
+            &[6, 21],
+            parse_actual_tyre_compound,
+            encode_actual_tyre_compound,
+        );
+        assert!(encode_actual_tyre_compound(&ActualTyreCompound::C0).is_err());
+
+        assert_spec(
+            &[
+                (0, VisualTyreCompound::Unknown(0)),
+                (7, VisualTyreCompound::Inter),
+                (8, VisualTyreCompound::Wet),
+                (9, VisualTyreCompound::F1ClassicDry),
+                (10, VisualTyreCompound::F1ClassicWet),
+                (11, VisualTyreCompound::F2SuperSoft),
+                (12, VisualTyreCompound::F2Soft),
+                (13, VisualTyreCompound::F2Medium),
+                (14, VisualTyreCompound::F2Hard),
+                (15, VisualTyreCompound::F2Wet),
+                (16, VisualTyreCompound::Soft),
+                (17, VisualTyreCompound::Medium),
+                (18, VisualTyreCompound::Hard),
+            ],
+            &[6, 19],
+            parse_visual_tyre_compound,
+            encode_visual_tyre_compound,
+        );
+    }
+
+    #[test]
+    fn session_enums_match_spec() {
+        assert_spec(
+            &[
+                (0, SessionType::Unknown(0)),
+                (1, SessionType::P1),
+                (2, SessionType::P2),
+                (3, SessionType::P3),
+                (4, SessionType::ShortP),
+                (5, SessionType::Q1),
+                (6, SessionType::Q2),
+                (7, SessionType::Q3),
+                (8, SessionType::ShortQ),
+                (9, SessionType::OSQ),
+                (10, SessionType::R),
+                (11, SessionType::R2),
+                (12, SessionType::TimeTrial),
+            ],
+            &[13],
+            parse_session_type,
+            encode_session_type,
+        );
+
+        assert_spec(
+            &[
+                (-1, Track::Unknown(-1)),
+                (0, Track::Melbourne),
+                (1, Track::PaulRicard),
+                (2, Track::Shanghai),
+                (3, Track::Sakhir),
+                (4, Track::Catalunya),
+                (5, Track::Monaco),
+                (6, Track::Montreal),
+                (7, Track::Silverstone),
+                (8, Track::Hockenheim),
+                (9, Track::Hungaroring),
+                (10, Track::Spa),
+                (11, Track::Monza),
+                (12, Track::Singapore),
+                (13, Track::Suzuka),
+                (14, Track::AbuDhabi),
+                (15, Track::Texas),
+                (16, Track::Brazil),
+                (17, Track::Austria),
+                (18, Track::Sochi),
+                (19, Track::Mexico),
+                (20, Track::Baku),
+                (21, Track::SakhirShort),
+                (22, Track::SilverstoneShort),
+                (23, Track::TexasShort),
+                (24, Track::SuzukaShort),
+                (25, Track::Hanoi),
+                (26, Track::Zandvoort),
+            ],
+            &[27, -2],
+            parse_track,
+            encode_track,
+        );
     }
 }
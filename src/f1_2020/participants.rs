@@ -1,17 +1,22 @@
+use crate::decoder::Decoder;
+use crate::error::TelemetryError;
 use crate::f1_2020::car::TOTAL_CARS;
 use crate::f1_2020::driver::Driver;
-use crate::f1_2020::header::PacketHeader;
+use crate::f1_2020::header::{write_header, PacketHeader};
 use crate::f1_2020::nationality::Nationality;
 use crate::f1_2020::team::Team;
-use async_std::io::{Cursor, Error, ErrorKind};
-use byteorder_async::ReaderToByteOrder;
+use byteorder::{ReadBytesExt, WriteBytesExt};
+use std::convert::TryFrom;
+use std::io::{Cursor, Write};
 
 const PARTICIPANTS_MIN_SIZE: usize = 1213;
+const NAME_SIZE: usize = 48;
 
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub enum YourTelemetry {
     Restricted, //0
     Public,
+    Unknown(u8),
 }
 
 #[derive(Debug, Eq, PartialEq, Clone)]
@@ -43,18 +48,19 @@ pub struct PacketParticipantsData {
     pub participants: Vec<ParticipantData>,
 }
 
-pub async fn parse_participants_data(
-    cursor: &mut Cursor<Vec<u8>>,
+pub fn parse_participants_data(
+    cursor: &mut Cursor<&[u8]>,
     header: PacketHeader,
     size: usize,
-) -> Result<PacketParticipantsData, Error> {
+    decoder: &mut Decoder,
+) -> Result<PacketParticipantsData, TelemetryError> {
     ensure_participants_size(size)?;
 
-    let num_active_cars = cursor.byte_order().read_u8().await?;
+    let num_active_cars = cursor.read_u8()?;
 
     let mut participants = Vec::with_capacity(TOTAL_CARS);
     for _ in 0..TOTAL_CARS {
-        let participant = parse_participant(cursor).await?;
+        let participant = parse_participant(cursor, decoder)?;
         participants.push(participant);
     }
 
@@ -65,33 +71,48 @@ pub async fn parse_participants_data(
     })
 }
 
-fn ensure_participants_size(size: usize) -> Result<(), Error> {
+fn ensure_participants_size(size: usize) -> Result<(), TelemetryError> {
     if size < PARTICIPANTS_MIN_SIZE {
-        return Err(Error::new(
-            ErrorKind::InvalidData,
-            "Participants size is too small",
-        ));
+        return Err(TelemetryError::SizeMismatch {
+            packet: "Participants",
+            expected: PARTICIPANTS_MIN_SIZE,
+            got: size,
+        });
     }
 
-    return Ok(());
+    Ok(())
 }
 
-pub fn parse_your_telemetry(value: u8) -> Result<YourTelemetry, Error> {
+pub fn parse_your_telemetry(
+    value: u8,
+    decoder: &mut Decoder,
+) -> Result<YourTelemetry, TelemetryError> {
     match value {
         0 => Ok(YourTelemetry::Restricted),
         1 => Ok(YourTelemetry::Public),
-        _ => Err(Error::new(ErrorKind::InvalidData, "Invalid telemetry")),
+        _ => decoder.unknown("your_telemetry", value, YourTelemetry::Unknown(value)),
     }
 }
 
-async fn parse_participant(cursor: &mut Cursor<Vec<u8>>) -> Result<ParticipantData, Error> {
-    let ai_controlled = cursor.byte_order().read_u8().await? == 1;
-    let driver = parse_driver(cursor.byte_order().read_u8().await?)?;
-    let team = parse_team(cursor.byte_order().read_u8().await?)?;
-    let race_number = cursor.byte_order().read_u8().await?;
-    let nationality = parse_nationality(cursor.byte_order().read_u8().await?)?;
-    let name = parse_name(cursor).await?;
-    let your_telemetry = parse_your_telemetry(cursor.byte_order().read_u8().await?)?;
+pub fn encode_your_telemetry(value: &YourTelemetry) -> Result<u8, TelemetryError> {
+    match value {
+        YourTelemetry::Restricted => Ok(0),
+        YourTelemetry::Public => Ok(1),
+        YourTelemetry::Unknown(raw) => Ok(*raw),
+    }
+}
+
+fn parse_participant(
+    cursor: &mut Cursor<&[u8]>,
+    decoder: &mut Decoder,
+) -> Result<ParticipantData, TelemetryError> {
+    let ai_controlled = cursor.read_u8()? == 1;
+    let driver = parse_driver(cursor.read_u8()?, decoder)?;
+    let team = parse_team(cursor.read_u8()?, decoder)?;
+    let race_number = cursor.read_u8()?;
+    let nationality = parse_nationality(cursor.read_u8()?, decoder)?;
+    let name = parse_name(cursor)?;
+    let your_telemetry = parse_your_telemetry(cursor.read_u8()?, decoder)?;
 
     Ok(ParticipantData {
         ai_controlled,
@@ -104,12 +125,12 @@ async fn parse_participant(cursor: &mut Cursor<Vec<u8>>) -> Result<ParticipantDa
     })
 }
 
-async fn parse_name(cursor: &mut Cursor<Vec<u8>>) -> Result<String, Error> {
+pub fn parse_name(cursor: &mut Cursor<&[u8]>) -> Result<String, TelemetryError> {
     let cursor_position = cursor.position();
-    let mut letters = Vec::with_capacity(48);
+    let mut letters = Vec::with_capacity(NAME_SIZE);
 
-    for _ in 0..48 {
-        let letter = cursor.byte_order().read_u8().await? as char;
+    for _ in 0..NAME_SIZE {
+        let letter = cursor.read_u8()? as char;
 
         if letter == '\0' {
             break;
@@ -118,11 +139,54 @@ async fn parse_name(cursor: &mut Cursor<Vec<u8>>) -> Result<String, Error> {
         }
     }
 
-    cursor.set_position(cursor_position + 48);
+    cursor.set_position(cursor_position + NAME_SIZE as u64);
     Ok(letters.iter().collect())
 }
 
-fn parse_driver(value: u8) -> Result<Driver, Error> {
+pub fn write_participants_data<W: Write>(
+    writer: &mut W,
+    packet: &PacketParticipantsData,
+) -> Result<(), TelemetryError> {
+    if packet.participants.len() != TOTAL_CARS {
+        return Err(TelemetryError::Unencodable {
+            field: "participants",
+        });
+    }
+
+    write_header(writer, &packet.header)?;
+    writer.write_u8(packet.num_active_cars)?;
+    for participant in &packet.participants {
+        writer.write_u8(participant.ai_controlled as u8)?;
+        writer.write_u8(encode_driver(&participant.driver)?)?;
+        writer.write_u8(encode_team(&participant.team)?)?;
+        writer.write_u8(participant.race_number)?;
+        writer.write_u8(encode_nationality(&participant.nationality)?)?;
+        write_name(writer, &participant.name)?;
+        writer.write_u8(encode_your_telemetry(&participant.your_telemetry)?)?;
+    }
+
+    Ok(())
+}
+
+/// Writes a name as the fixed size, zero padded array `parse_name` reads.
+pub fn write_name<W: Write>(writer: &mut W, name: &str) -> Result<(), TelemetryError> {
+    let mut letters = Vec::with_capacity(NAME_SIZE);
+    for letter in name.chars() {
+        let letter =
+            u8::try_from(letter).map_err(|_| TelemetryError::Unencodable { field: "name" })?;
+        letters.push(letter);
+    }
+    if letters.len() > NAME_SIZE {
+        return Err(TelemetryError::Unencodable { field: "name" });
+    }
+
+    letters.resize(NAME_SIZE, 0);
+    writer.write_all(&letters)?;
+
+    Ok(())
+}
+
+pub fn parse_driver(value: u8, decoder: &mut Decoder) -> Result<Driver, TelemetryError> {
     match value {
         0 => Ok(Driver::CarlosSainz),
         1 => Ok(Driver::DaniilKvyat),
@@ -202,12 +266,98 @@ fn parse_driver(value: u8) -> Result<Driver, Error> {
         87 => Ok(Driver::AnthoineHubert),
         88 => Ok(Driver::GuilianoAlesi),
         89 => Ok(Driver::RalphBoschung),
-        p if p >= 100 => Ok(Driver::Player),
-        _ => Err(Error::new(ErrorKind::InvalidData, "Invalid driver")),
+        p if p >= 100 => Ok(Driver::Player(p)),
+        _ => decoder.unknown("driver", value, Driver::Unknown(value)),
     }
 }
 
-pub fn parse_team(value: u8) -> Result<Team, Error> {
+pub fn encode_driver(value: &Driver) -> Result<u8, TelemetryError> {
+    match value {
+        Driver::CarlosSainz => Ok(0),
+        Driver::DaniilKvyat => Ok(1),
+        Driver::DanielRicciardo => Ok(2),
+        Driver::KimiRaikkonen => Ok(6),
+        Driver::LewisHamilton => Ok(7),
+        Driver::MaxVerstappen => Ok(9),
+        Driver::NicoHulkenburg => Ok(10),
+        Driver::KevinMagnussen => Ok(11),
+        Driver::RomainGrosjean => Ok(12),
+        Driver::SebastianVettel => Ok(13),
+        Driver::SergioPerez => Ok(14),
+        Driver::ValtteriBottas => Ok(15),
+        Driver::EstebanOcon => Ok(17),
+        Driver::LanceStroll => Ok(19),
+        Driver::ArronBarnes => Ok(20),
+        Driver::MartinGiles => Ok(21),
+        Driver::AlexMurray => Ok(22),
+        Driver::LucasRoth => Ok(23),
+        Driver::IgorCorreia => Ok(24),
+        Driver::SophieLevasseur => Ok(25),
+        Driver::JonasSchiffer => Ok(26),
+        Driver::AlainForest => Ok(27),
+        Driver::JayLetourneau => Ok(28),
+        Driver::EstoSaari => Ok(29),
+        Driver::YasarAtiyeh => Ok(30),
+        Driver::CallistoCalabresi => Ok(31),
+        Driver::NaotaIzum => Ok(32),
+        Driver::HowardClarke => Ok(33),
+        Driver::WilhelmKaufmann => Ok(34),
+        Driver::MarieLaursen => Ok(35),
+        Driver::FlavioNieves => Ok(36),
+        Driver::PeterBelousov => Ok(37),
+        Driver::KlimekMichalski => Ok(38),
+        Driver::SantiagoMoreno => Ok(39),
+        Driver::BenjaminCoppens => Ok(40),
+        Driver::NoahVisser => Ok(41),
+        Driver::GertWaldmuller => Ok(42),
+        Driver::JulianQuesada => Ok(43),
+        Driver::DanielJones => Ok(44),
+        Driver::ArtemMarkelov => Ok(45),
+        Driver::TadasukeMakino => Ok(46),
+        Driver::SeanGelael => Ok(47),
+        Driver::NyckDeVries => Ok(48),
+        Driver::JackAitken => Ok(49),
+        Driver::GeorgeRussell => Ok(50),
+        Driver::MaximilianGunther => Ok(51),
+        Driver::NireiFukuzumi => Ok(52),
+        Driver::LucaGhiotto => Ok(53),
+        Driver::LandoNorris => Ok(54),
+        Driver::SergioSetteCamara => Ok(55),
+        Driver::LouisDeletraz => Ok(56),
+        Driver::AntonioFuoco => Ok(57),
+        Driver::CharlesLeclerc => Ok(58),
+        Driver::PierreGasly => Ok(59),
+        Driver::AlexanderAlbon => Ok(62),
+        Driver::NicholasLatifi => Ok(63),
+        Driver::DorianBoccolacci => Ok(64),
+        Driver::NikoKari => Ok(65),
+        Driver::RobertoMerhi => Ok(66),
+        Driver::ArjunMaini => Ok(67),
+        Driver::AlessioLorandi => Ok(68),
+        Driver::RubenMeijer => Ok(69),
+        Driver::RashidNair => Ok(70),
+        Driver::JackTremblay => Ok(71),
+        Driver::AntonioGiovinazzi => Ok(74),
+        Driver::RobertKubica => Ok(75),
+        Driver::NobuharuMatsushita => Ok(78),
+        Driver::NikitaMazepin => Ok(79),
+        Driver::GuanyaZhou => Ok(80),
+        Driver::MickSchumacher => Ok(81),
+        Driver::CallumIlott => Ok(82),
+        Driver::JuanManuelCorrea => Ok(83),
+        Driver::JordanKing => Ok(84),
+        Driver::MahaveerRaghunathan => Ok(85),
+        Driver::TatianaCalderon => Ok(86),
+        Driver::AnthoineHubert => Ok(87),
+        Driver::GuilianoAlesi => Ok(88),
+        Driver::RalphBoschung => Ok(89),
+        Driver::Player(raw) if *raw >= 100 => Ok(*raw),
+        Driver::Unknown(raw) => Ok(*raw),
+        _ => Err(TelemetryError::Unencodable { field: "driver" }),
+    }
+}
+
+pub fn parse_team(value: u8, decoder: &mut Decoder) -> Result<Team, TelemetryError> {
     match value {
         0 => Ok(Team::Mercedes),
         1 => Ok(Team::Ferrari),
@@ -266,11 +416,75 @@ pub fn parse_team(value: u8) -> Result<Team, Error> {
         55 => Ok(Team::Ferrari2000),
         56 => Ok(Team::Jordan1991),
         255 => Ok(Team::MyTeam),
-        _ => Err(Error::new(ErrorKind::InvalidData, "Invalid team")),
+        _ => decoder.unknown("team", value, Team::Unknown(value)),
+    }
+}
+
+pub fn encode_team(value: &Team) -> Result<u8, TelemetryError> {
+    match value {
+        Team::Mercedes => Ok(0),
+        Team::Ferrari => Ok(1),
+        Team::RedBullRacing => Ok(2),
+        Team::Williams => Ok(3),
+        Team::RacingPoint => Ok(4),
+        Team::Renault => Ok(5),
+        Team::Haas => Ok(7),
+        Team::McLaren => Ok(8),
+        Team::AlfaRomeo => Ok(9),
+        Team::McLaren1988 => Ok(10),
+        Team::McLaren1991 => Ok(11),
+        Team::Williams1992 => Ok(12),
+        Team::Ferrari1995 => Ok(13),
+        Team::Williams1996 => Ok(14),
+        Team::McLaren1998 => Ok(15),
+        Team::Ferrari2002 => Ok(16),
+        Team::Ferrari2004 => Ok(17),
+        Team::Renault2006 => Ok(18),
+        Team::Ferrari2007 => Ok(19),
+        Team::RedBull2010 => Ok(21),
+        Team::Ferrari1976 => Ok(22),
+        Team::ARTGrandPrix => Ok(23),
+        Team::CamposVexatecRacing => Ok(24),
+        Team::Carlin => Ok(25),
+        Team::CharouzRacingSystem => Ok(26),
+        Team::DAMS => Ok(27),
+        Team::RussianTime => Ok(28),
+        Team::MPMotorsport => Ok(29),
+        Team::Pertamina => Ok(30),
+        Team::McLaren1990 => Ok(31),
+        Team::Trident => Ok(32),
+        Team::BWTArden => Ok(33),
+        Team::McLaren1976 => Ok(34),
+        Team::Lotus1972 => Ok(35),
+        Team::Ferrari1979 => Ok(36),
+        Team::McLaren1982 => Ok(37),
+        Team::Williams2003 => Ok(38),
+        Team::Brawn2009 => Ok(39),
+        Team::Lotus1978 => Ok(40),
+        Team::ArtGP2019 => Ok(42),
+        Team::Campos2019 => Ok(43),
+        Team::Carlin2019 => Ok(44),
+        Team::SauberJuniorCharouz2019 => Ok(45),
+        Team::Dams2019 => Ok(46),
+        Team::UniVirtuosi2019 => Ok(47),
+        Team::MPMotorsport2019 => Ok(48),
+        Team::Prema2019 => Ok(49),
+        Team::Trident2019 => Ok(50),
+        Team::Arden2019 => Ok(51),
+        Team::AlphaTauri => Ok(6),
+        Team::McLaren2008 => Ok(20),
+        Team::F1GenericCar => Ok(41),
+        Team::Benetton1994 => Ok(53),
+        Team::Benetton1995 => Ok(54),
+        Team::Ferrari2000 => Ok(55),
+        Team::Jordan1991 => Ok(56),
+        Team::MyTeam => Ok(255),
+        Team::Unknown(raw) => Ok(*raw),
+        _ => Err(TelemetryError::Unencodable { field: "team" }),
     }
 }
 
-pub fn parse_nationality(value: u8) -> Result<Nationality, Error> {
+pub fn parse_nationality(value: u8, decoder: &mut Decoder) -> Result<Nationality, TelemetryError> {
     match value {
         1 => Ok(Nationality::American),
         2 => Ok(Nationality::Argentinean),
@@ -361,6 +575,101 @@ pub fn parse_nationality(value: u8) -> Result<Nationality, Error> {
         87 => Ok(Nationality::Barbadian),
         88 => Ok(Nationality::Vietnamese),
         0 | 255 => Ok(Nationality::Invalid),
-        _ => Err(Error::new(ErrorKind::InvalidData, "Invalid nationality")),
+        _ => decoder.unknown("nationality", value, Nationality::Unknown(value)),
+    }
+}
+
+pub fn encode_nationality(value: &Nationality) -> Result<u8, TelemetryError> {
+    match value {
+        Nationality::American => Ok(1),
+        Nationality::Argentinean => Ok(2),
+        Nationality::Australian => Ok(3),
+        Nationality::Austrian => Ok(4),
+        Nationality::Azerbaijani => Ok(5),
+        Nationality::Bahraini => Ok(6),
+        Nationality::Barbadian => Ok(87),
+        Nationality::Belgian => Ok(7),
+        Nationality::Bolivian => Ok(8),
+        Nationality::Brazilian => Ok(9),
+        Nationality::British => Ok(10),
+        Nationality::Bulgarian => Ok(11),
+        Nationality::Cameroonian => Ok(12),
+        Nationality::Canadian => Ok(13),
+        Nationality::Chilean => Ok(14),
+        Nationality::Chinese => Ok(15),
+        Nationality::Colombian => Ok(16),
+        Nationality::CostaRican => Ok(17),
+        Nationality::Croatian => Ok(18),
+        Nationality::Cypriot => Ok(19),
+        Nationality::Czech => Ok(20),
+        Nationality::Danish => Ok(21),
+        Nationality::Dutch => Ok(22),
+        Nationality::Ecuadorian => Ok(23),
+        Nationality::Emirian => Ok(25),
+        Nationality::English => Ok(24),
+        Nationality::Estonian => Ok(26),
+        Nationality::Finnish => Ok(27),
+        Nationality::French => Ok(28),
+        Nationality::German => Ok(29),
+        Nationality::Ghanaian => Ok(30),
+        Nationality::Greek => Ok(31),
+        Nationality::Guatemalan => Ok(32),
+        Nationality::Honduran => Ok(33),
+        Nationality::HongKonger => Ok(34),
+        Nationality::Hungarian => Ok(35),
+        Nationality::Icelander => Ok(36),
+        Nationality::Indian => Ok(37),
+        Nationality::Indonesian => Ok(38),
+        Nationality::Irish => Ok(39),
+        Nationality::Israeli => Ok(40),
+        Nationality::Italian => Ok(41),
+        Nationality::Jamaican => Ok(42),
+        Nationality::Japanese => Ok(43),
+        Nationality::Jordanian => Ok(44),
+        Nationality::Kuwaiti => Ok(45),
+        Nationality::Latvian => Ok(46),
+        Nationality::Lebanese => Ok(47),
+        Nationality::Lithuanian => Ok(48),
+        Nationality::Luxembourger => Ok(49),
+        Nationality::Malaysian => Ok(50),
+        Nationality::Maltese => Ok(51),
+        Nationality::Mexican => Ok(52),
+        Nationality::Monegasque => Ok(53),
+        Nationality::NewZealander => Ok(54),
+        Nationality::Nicaraguan => Ok(55),
+        Nationality::NorthKorean => Ok(56),
+        Nationality::NorthernIrish => Ok(57),
+        Nationality::Norwegian => Ok(58),
+        Nationality::Omani => Ok(59),
+        Nationality::Pakistani => Ok(60),
+        Nationality::Panamanian => Ok(61),
+        Nationality::Paraguayan => Ok(62),
+        Nationality::Peruvian => Ok(63),
+        Nationality::Polish => Ok(64),
+        Nationality::Portuguese => Ok(65),
+        Nationality::Qatari => Ok(66),
+        Nationality::Romanian => Ok(67),
+        Nationality::Russian => Ok(68),
+        Nationality::Salvadoran => Ok(69),
+        Nationality::Saudi => Ok(70),
+        Nationality::Scottish => Ok(71),
+        Nationality::Serbian => Ok(72),
+        Nationality::Singaporean => Ok(73),
+        Nationality::Slovakian => Ok(74),
+        Nationality::Slovenian => Ok(75),
+        Nationality::SouthAfrican => Ok(77),
+        Nationality::SouthKorean => Ok(76),
+        Nationality::Spanish => Ok(78),
+        Nationality::Swedish => Ok(79),
+        Nationality::Swiss => Ok(80),
+        Nationality::Thai => Ok(81),
+        Nationality::Turkish => Ok(82),
+        Nationality::Ukrainian => Ok(84),
+        Nationality::Uruguayan => Ok(83),
+        Nationality::Venezuelan => Ok(85),
+        Nationality::Vietnamese => Ok(88),
+        Nationality::Welsh => Ok(86),
+        Nationality::Invalid => Ok(0),
+        Nationality::Unknown(raw) => Ok(*raw),
     }
 }
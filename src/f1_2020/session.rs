@@ -1,25 +1,29 @@
-use crate::f1_2020::header::PacketHeader;
-use async_std::io::{Cursor, Error, ErrorKind};
-use byteorder_async::{LittleEndian, ReaderToByteOrder};
+use crate::decoder::Decoder;
+use crate::error::TelemetryError;
+use crate::f1_2020::header::{write_header, PacketHeader};
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 use derivative::Derivative;
+use std::io::{Cursor, Write};
 
 const SESSION_MIN_SIZE: usize = 251;
 const MARSHAL_ZONE_MAX: usize = 21;
+const MARSHAL_ZONE_SIZE: usize = 5;
 const WEATHER_FORECAST_SAMPLE_MAX: usize = 20;
+const WEATHER_FORECAST_SAMPLE_SIZE: usize = 5;
 
 #[derive(Debug, Eq, PartialOrd, PartialEq, Clone)]
 pub enum ZoneFlag {
-    Unknown = -1,
-    None = 0,
-    Green = 1,
-    Blue = 2,
-    Yellow = 3,
-    Red = 4,
+    Unknown(i8),
+    None,   // 0
+    Green,  // 1
+    Blue,   // 2
+    Yellow, // 3
+    Red,    // 4
 }
 
 #[derive(Debug, Eq, PartialOrd, PartialEq, Clone)]
 pub enum SessionType {
-    Unknown, // 0
+    Unknown(u8),
     P1,
     P2,
     P3,
@@ -31,7 +35,13 @@ pub enum SessionType {
     OSQ,
     R,
     R2,
+    R3,
     TimeTrial,
+    SprintShootout1,
+    SprintShootout2,
+    SprintShootout3,
+    ShortSprintShootout,
+    OneShotSprintShootout,
 }
 
 #[derive(Debug, Eq, PartialOrd, PartialEq, Clone)]
@@ -42,6 +52,7 @@ pub enum Weather {
     LightRain,
     HeavyRain,
     Storm,
+    Unknown(u8),
 }
 
 #[derive(Debug, Eq, PartialOrd, PartialEq, Clone)]
@@ -50,6 +61,13 @@ pub enum Formula {
     F1Classic,
     F2,
     F1Generic,
+    Beta,
+    Supercars,
+    Esports,
+    F2Season2021,
+    F1World,
+    F1Elimination,
+    Unknown(u8),
 }
 
 #[derive(Debug, Eq, PartialOrd, PartialEq, Clone)]
@@ -57,12 +75,15 @@ pub enum SafetyCar {
     None, // 0,
     Full,
     Virtual,
+    FormationLap,
+    Unknown(u8),
 }
 
 #[derive(Debug, Eq, PartialOrd, PartialEq, Clone)]
 pub enum NetworkGame {
     Offline, // 0
     Online,
+    Unknown(u8),
 }
 
 #[derive(Debug, Eq, PartialOrd, PartialEq, Clone)]
@@ -94,7 +115,13 @@ pub enum Track {
     SuzukaShort,
     Hanoi,
     Zandvoort,
-    Unknown,
+    Imola,
+    Portimao,
+    Jeddah,
+    Miami,
+    LasVegas,
+    Losail,
+    Unknown(i8),
 }
 
 #[derive(Debug, PartialEq, Clone, Derivative)]
@@ -175,46 +202,51 @@ pub struct PacketSessionData {
     pub weather_forecast_sample: Vec<WeatherForecastSample>,
 }
 
-pub async fn parse_session(
-    cursor: &mut Cursor<Vec<u8>>,
+pub fn parse_session(
+    cursor: &mut Cursor<&[u8]>,
     header: PacketHeader,
     size: usize,
-) -> Result<PacketSessionData, Error> {
+    decoder: &mut Decoder,
+) -> Result<PacketSessionData, TelemetryError> {
     ensure_session_size(size)?;
 
-    let weather = parse_weather(cursor.byte_order().read_u8().await?)?;
-
-    let track_temperature = cursor.byte_order().read_i8().await?;
-    let air_temperature = cursor.byte_order().read_i8().await?;
-    let total_laps = cursor.byte_order().read_u8().await?;
-    let track_length = cursor.byte_order().read_u16::<LittleEndian>().await?;
-    let session_type = parse_session_type(cursor.byte_order().read_u8().await?)?;
-    let track_id = parse_track(cursor.byte_order().read_i8().await?)?;
-    let formula = parse_formula(cursor.byte_order().read_u8().await?)?;
-    let session_time_left = cursor.byte_order().read_u16::<LittleEndian>().await?;
-    let session_duration = cursor.byte_order().read_u16::<LittleEndian>().await?;
-    let pit_speed_limit = cursor.byte_order().read_u8().await?;
-    let game_paused = cursor.byte_order().read_u8().await?;
-    let is_spectating = cursor.byte_order().read_u8().await?;
-    let spectator_car_index = cursor.byte_order().read_u8().await?;
-    let sli_pro_native_support = cursor.byte_order().read_u8().await?;
-
-    let num_marshal_zones = cursor.byte_order().read_u8().await?;
+    let weather = parse_weather(cursor.read_u8()?, decoder)?;
+
+    let track_temperature = cursor.read_i8()?;
+    let air_temperature = cursor.read_i8()?;
+    let total_laps = cursor.read_u8()?;
+    let track_length = cursor.read_u16::<LittleEndian>()?;
+    let session_type = parse_session_type(cursor.read_u8()?, decoder)?;
+    let track_id = parse_track(cursor.read_i8()?, decoder)?;
+    let formula = parse_formula(cursor.read_u8()?, decoder)?;
+    let session_time_left = cursor.read_u16::<LittleEndian>()?;
+    let session_duration = cursor.read_u16::<LittleEndian>()?;
+    let pit_speed_limit = cursor.read_u8()?;
+    let game_paused = cursor.read_u8()?;
+    let is_spectating = cursor.read_u8()?;
+    let spectator_car_index = cursor.read_u8()?;
+    let sli_pro_native_support = cursor.read_u8()?;
+
+    // The marshal zone array is fixed size on the wire, only the first
+    // `num_marshal_zones` entries are meaningful.
+    let num_marshal_zones = cursor.read_u8()?;
+    let marshal_zone_start = cursor.position();
     let mut marshal_zone = Vec::with_capacity(MARSHAL_ZONE_MAX);
-    for _ in 0..num_marshal_zones {
-        let zone_start = cursor.byte_order().read_f32::<LittleEndian>().await?;
-        let zone_flag = parse_flag(cursor.byte_order().read_i8().await?)?;
+    for _ in 0..(num_marshal_zones as usize).min(MARSHAL_ZONE_MAX) {
+        let zone_start = cursor.read_f32::<LittleEndian>()?;
+        let zone_flag = parse_flag(cursor.read_i8()?, decoder)?;
         let zone = parse_marshal_zone(zone_start, zone_flag)?;
         marshal_zone.push(zone);
     }
+    cursor.set_position(marshal_zone_start + (MARSHAL_ZONE_MAX * MARSHAL_ZONE_SIZE) as u64);
 
-    let safety_car_status = parse_safety_car(cursor.byte_order().read_u8().await?)?;
-    let network_game = parse_network_game(cursor.byte_order().read_u8().await?)?;
+    let safety_car_status = parse_safety_car(cursor.read_u8()?, decoder)?;
+    let network_game = parse_network_game(cursor.read_u8()?, decoder)?;
 
-    let num_weather_forecast_samples = cursor.byte_order().read_u8().await?;
+    let num_weather_forecast_samples = cursor.read_u8()?;
     let mut weather_forecast_sample = Vec::with_capacity(WEATHER_FORECAST_SAMPLE_MAX);
     for _ in 0..WEATHER_FORECAST_SAMPLE_MAX {
-        let weather_forecast = parse_weather_forecast_sample(cursor).await?;
+        let weather_forecast = parse_weather_forecast_sample(cursor, decoder)?;
         weather_forecast_sample.push(weather_forecast);
     }
 
@@ -244,18 +276,79 @@ pub async fn parse_session(
     })
 }
 
-fn ensure_session_size(size: usize) -> Result<(), Error> {
+pub fn write_session<W: Write>(
+    writer: &mut W,
+    packet: &PacketSessionData,
+) -> Result<(), TelemetryError> {
+    if packet.marshal_zone.len() > MARSHAL_ZONE_MAX {
+        return Err(TelemetryError::Unencodable {
+            field: "marshal_zone",
+        });
+    }
+    if packet.weather_forecast_sample.len() > WEATHER_FORECAST_SAMPLE_MAX {
+        return Err(TelemetryError::Unencodable {
+            field: "weather_forecast_sample",
+        });
+    }
+
+    write_header(writer, &packet.header)?;
+    writer.write_u8(encode_weather(&packet.weather)?)?;
+    writer.write_i8(packet.track_temperature)?;
+    writer.write_i8(packet.air_temperature)?;
+    writer.write_u8(packet.total_laps)?;
+    writer.write_u16::<LittleEndian>(packet.track_length)?;
+    writer.write_u8(encode_session_type(&packet.session_type)?)?;
+    writer.write_i8(encode_track(&packet.track_id)?)?;
+    writer.write_u8(encode_formula(&packet.formula)?)?;
+    writer.write_u16::<LittleEndian>(packet.session_time_left)?;
+    writer.write_u16::<LittleEndian>(packet.session_duration)?;
+    writer.write_u8(packet.pit_speed_limit)?;
+    writer.write_u8(packet.game_paused)?;
+    writer.write_u8(packet.is_spectating)?;
+    writer.write_u8(packet.spectator_car_index)?;
+    writer.write_u8(packet.sli_pro_native_support)?;
+
+    // Unused entries of the fixed size arrays are sent zeroed.
+    writer.write_u8(packet.num_marshal_zones)?;
+    for zone in &packet.marshal_zone {
+        writer.write_f32::<LittleEndian>(zone.zone_start)?;
+        writer.write_i8(encode_flag(&zone.zone_flag)?)?;
+    }
+    for _ in packet.marshal_zone.len()..MARSHAL_ZONE_MAX {
+        writer.write_all(&[0; MARSHAL_ZONE_SIZE])?;
+    }
+
+    writer.write_u8(encode_safety_car(&packet.safety_car_status)?)?;
+    writer.write_u8(encode_network_game(&packet.network_game)?)?;
+
+    writer.write_u8(packet.num_weather_forecast_samples)?;
+    for sample in &packet.weather_forecast_sample {
+        writer.write_u8(encode_session_type(&sample.session_type)?)?;
+        writer.write_u8(sample.time_offset)?;
+        writer.write_u8(encode_weather(&sample.weather)?)?;
+        writer.write_i8(sample.track_temperature)?;
+        writer.write_i8(sample.air_temperature)?;
+    }
+    for _ in packet.weather_forecast_sample.len()..WEATHER_FORECAST_SAMPLE_MAX {
+        writer.write_all(&[0; WEATHER_FORECAST_SAMPLE_SIZE])?;
+    }
+
+    Ok(())
+}
+
+fn ensure_session_size(size: usize) -> Result<(), TelemetryError> {
     if size == SESSION_MIN_SIZE {
         return Ok(());
     }
 
-    Err(Error::new(
-        ErrorKind::InvalidData,
-        "Session size is too small",
-    ))
+    Err(TelemetryError::SizeMismatch {
+        packet: "Session",
+        expected: SESSION_MIN_SIZE,
+        got: size,
+    })
 }
 
-fn parse_weather(value: u8) -> Result<Weather, Error> {
+pub fn parse_weather(value: u8, decoder: &mut Decoder) -> Result<Weather, TelemetryError> {
     match value {
         0 => Ok(Weather::Clear),
         1 => Ok(Weather::LightCloud),
@@ -263,13 +356,25 @@ fn parse_weather(value: u8) -> Result<Weather, Error> {
         3 => Ok(Weather::LightRain),
         4 => Ok(Weather::HeavyRain),
         5 => Ok(Weather::Storm),
-        _ => Err(Error::new(ErrorKind::InvalidData, "Invalid weather")),
+        _ => decoder.unknown("weather", value, Weather::Unknown(value)),
     }
 }
 
-fn parse_session_type(value: u8) -> Result<SessionType, Error> {
+pub fn encode_weather(value: &Weather) -> Result<u8, TelemetryError> {
     match value {
-        0 => Ok(SessionType::Unknown),
+        Weather::Clear => Ok(0),
+        Weather::LightCloud => Ok(1),
+        Weather::Overcast => Ok(2),
+        Weather::LightRain => Ok(3),
+        Weather::HeavyRain => Ok(4),
+        Weather::Storm => Ok(5),
+        Weather::Unknown(raw) => Ok(*raw),
+    }
+}
+
+pub fn parse_session_type(value: u8, decoder: &mut Decoder) -> Result<SessionType, TelemetryError> {
+    match value {
+        0 => Ok(SessionType::Unknown(value)),
         1 => Ok(SessionType::P1),
         2 => Ok(SessionType::P2),
         3 => Ok(SessionType::P3),
@@ -278,17 +383,38 @@ fn parse_session_type(value: u8) -> Result<SessionType, Error> {
         6 => Ok(SessionType::Q2),
         7 => Ok(SessionType::Q3),
         8 => Ok(SessionType::ShortQ),
-        9 => Ok(SessionType::ShortQ),
+        9 => Ok(SessionType::OSQ),
         10 => Ok(SessionType::R),
         11 => Ok(SessionType::R2),
         12 => Ok(SessionType::TimeTrial),
-        _ => Err(Error::new(ErrorKind::InvalidData, "Invalid session type")),
+        _ => decoder.unknown("session_type", value, SessionType::Unknown(value)),
+    }
+}
+
+pub fn encode_session_type(value: &SessionType) -> Result<u8, TelemetryError> {
+    match value {
+        SessionType::P1 => Ok(1),
+        SessionType::P2 => Ok(2),
+        SessionType::P3 => Ok(3),
+        SessionType::ShortP => Ok(4),
+        SessionType::Q1 => Ok(5),
+        SessionType::Q2 => Ok(6),
+        SessionType::Q3 => Ok(7),
+        SessionType::ShortQ => Ok(8),
+        SessionType::OSQ => Ok(9),
+        SessionType::R => Ok(10),
+        SessionType::R2 => Ok(11),
+        SessionType::TimeTrial => Ok(12),
+        SessionType::Unknown(raw) => Ok(*raw),
+        _ => Err(TelemetryError::Unencodable {
+            field: "session_type",
+        }),
     }
 }
 
-fn parse_track(value: i8) -> Result<Track, Error> {
+pub fn parse_track(value: i8, decoder: &mut Decoder) -> Result<Track, TelemetryError> {
     match value {
-        -1 => Ok(Track::Unknown),
+        -1 => Ok(Track::Unknown(value)),
         0 => Ok(Track::Melbourne),
         1 => Ok(Track::PaulRicard),
         2 => Ok(Track::Shanghai),
@@ -316,64 +442,141 @@ fn parse_track(value: i8) -> Result<Track, Error> {
         24 => Ok(Track::SuzukaShort),
         25 => Ok(Track::Hanoi),
         26 => Ok(Track::Zandvoort),
-        _ => Err(Error::new(ErrorKind::InvalidData, "Invalid track")),
+        _ => decoder.unknown("track", value, Track::Unknown(value)),
+    }
+}
+
+pub fn encode_track(value: &Track) -> Result<i8, TelemetryError> {
+    match value {
+        Track::Melbourne => Ok(0),
+        Track::PaulRicard => Ok(1),
+        Track::Shanghai => Ok(2),
+        Track::Sakhir => Ok(3),
+        Track::Catalunya => Ok(4),
+        Track::Monaco => Ok(5),
+        Track::Montreal => Ok(6),
+        Track::Silverstone => Ok(7),
+        Track::Hockenheim => Ok(8),
+        Track::Hungaroring => Ok(9),
+        Track::Spa => Ok(10),
+        Track::Monza => Ok(11),
+        Track::Singapore => Ok(12),
+        Track::Suzuka => Ok(13),
+        Track::AbuDhabi => Ok(14),
+        Track::Texas => Ok(15),
+        Track::Brazil => Ok(16),
+        Track::Austria => Ok(17),
+        Track::Sochi => Ok(18),
+        Track::Mexico => Ok(19),
+        Track::Baku => Ok(20),
+        Track::SakhirShort => Ok(21),
+        Track::SilverstoneShort => Ok(22),
+        Track::TexasShort => Ok(23),
+        Track::SuzukaShort => Ok(24),
+        Track::Hanoi => Ok(25),
+        Track::Zandvoort => Ok(26),
+        Track::Unknown(raw) => Ok(*raw),
+        _ => Err(TelemetryError::Unencodable { field: "track" }),
     }
 }
 
-fn parse_formula(value: u8) -> Result<Formula, Error> {
+pub fn parse_formula(value: u8, decoder: &mut Decoder) -> Result<Formula, TelemetryError> {
     match value {
         0 => Ok(Formula::F1Modern),
         1 => Ok(Formula::F1Classic),
         2 => Ok(Formula::F2),
         3 => Ok(Formula::F1Generic),
-        _ => Err(Error::new(ErrorKind::InvalidData, "Invalid formula type")),
+        _ => decoder.unknown("formula", value, Formula::Unknown(value)),
     }
 }
 
-pub fn parse_flag(value: i8) -> Result<ZoneFlag, Error> {
+pub fn encode_formula(value: &Formula) -> Result<u8, TelemetryError> {
     match value {
-        -1 => Ok(ZoneFlag::Unknown),
+        Formula::F1Modern => Ok(0),
+        Formula::F1Classic => Ok(1),
+        Formula::F2 => Ok(2),
+        Formula::F1Generic => Ok(3),
+        Formula::Unknown(raw) => Ok(*raw),
+        _ => Err(TelemetryError::Unencodable { field: "formula" }),
+    }
+}
+
+pub fn parse_flag(value: i8, decoder: &mut Decoder) -> Result<ZoneFlag, TelemetryError> {
+    match value {
+        -1 => Ok(ZoneFlag::Unknown(value)),
         0 => Ok(ZoneFlag::None),
         1 => Ok(ZoneFlag::Green),
         2 => Ok(ZoneFlag::Blue),
         3 => Ok(ZoneFlag::Yellow),
         4 => Ok(ZoneFlag::Red),
-        _ => Err(Error::new(ErrorKind::InvalidData, "Invalid zone flag")),
+        _ => decoder.unknown("flag", value, ZoneFlag::Unknown(value)),
+    }
+}
+
+pub fn encode_flag(value: &ZoneFlag) -> Result<i8, TelemetryError> {
+    match value {
+        ZoneFlag::None => Ok(0),
+        ZoneFlag::Green => Ok(1),
+        ZoneFlag::Blue => Ok(2),
+        ZoneFlag::Yellow => Ok(3),
+        ZoneFlag::Red => Ok(4),
+        ZoneFlag::Unknown(raw) => Ok(*raw),
     }
 }
 
-fn parse_marshal_zone(zone_start: f32, zone_flag: ZoneFlag) -> Result<MarshalZone, Error> {
+fn parse_marshal_zone(zone_start: f32, zone_flag: ZoneFlag) -> Result<MarshalZone, TelemetryError> {
     Ok(MarshalZone {
         zone_start,
         zone_flag,
     })
 }
 
-fn parse_safety_car(value: u8) -> Result<SafetyCar, Error> {
+pub fn parse_safety_car(value: u8, decoder: &mut Decoder) -> Result<SafetyCar, TelemetryError> {
     match value {
         0 => Ok(SafetyCar::None),
         1 => Ok(SafetyCar::Full),
         2 => Ok(SafetyCar::Virtual),
-        _ => Err(Error::new(ErrorKind::InvalidData, "Invalid safety car")),
+        _ => decoder.unknown("safety_car", value, SafetyCar::Unknown(value)),
     }
 }
 
-fn parse_network_game(value: u8) -> Result<NetworkGame, Error> {
+pub fn encode_safety_car(value: &SafetyCar) -> Result<u8, TelemetryError> {
+    match value {
+        SafetyCar::None => Ok(0),
+        SafetyCar::Full => Ok(1),
+        SafetyCar::Virtual => Ok(2),
+        SafetyCar::Unknown(raw) => Ok(*raw),
+        _ => Err(TelemetryError::Unencodable {
+            field: "safety_car",
+        }),
+    }
+}
+
+pub fn parse_network_game(value: u8, decoder: &mut Decoder) -> Result<NetworkGame, TelemetryError> {
     match value {
         0 => Ok(NetworkGame::Offline),
         1 => Ok(NetworkGame::Online),
-        _ => Err(Error::new(ErrorKind::InvalidData, "Invalid network game")),
+        _ => decoder.unknown("network_game", value, NetworkGame::Unknown(value)),
+    }
+}
+
+pub fn encode_network_game(value: &NetworkGame) -> Result<u8, TelemetryError> {
+    match value {
+        NetworkGame::Offline => Ok(0),
+        NetworkGame::Online => Ok(1),
+        NetworkGame::Unknown(raw) => Ok(*raw),
     }
 }
 
-async fn parse_weather_forecast_sample(
-    cursor: &mut Cursor<Vec<u8>>,
-) -> Result<WeatherForecastSample, Error> {
-    let session_type = parse_session_type(cursor.byte_order().read_u8().await?)?;
-    let time_offset = cursor.byte_order().read_u8().await?;
-    let weather = parse_weather(cursor.byte_order().read_u8().await?)?;
-    let track_temperature = cursor.byte_order().read_i8().await?;
-    let air_temperature = cursor.byte_order().read_i8().await?;
+fn parse_weather_forecast_sample(
+    cursor: &mut Cursor<&[u8]>,
+    decoder: &mut Decoder,
+) -> Result<WeatherForecastSample, TelemetryError> {
+    let session_type = parse_session_type(cursor.read_u8()?, decoder)?;
+    let time_offset = cursor.read_u8()?;
+    let weather = parse_weather(cursor.read_u8()?, decoder)?;
+    let track_temperature = cursor.read_i8()?;
+    let air_temperature = cursor.read_i8()?;
 
     Ok(WeatherForecastSample {
         session_type,
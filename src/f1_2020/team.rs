@@ -61,7 +61,7 @@ pub enum Team {
     Ferrari2000,
     Jordan1991,
     MyTeam,
-    Unknown,
+    Unknown(u8),
 }
 
 impl Team {
@@ -0,0 +1,15 @@
+pub mod car;
+pub mod car_damage;
+pub mod car_setup;
+pub mod car_status;
+pub mod car_telemetry;
+pub mod event;
+pub mod final_classification;
+pub mod header;
+pub mod lap;
+pub mod lobby_info;
+pub mod motion;
+pub mod packet;
+pub mod participants;
+pub mod session;
+pub mod session_history;
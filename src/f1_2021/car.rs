@@ -0,0 +1,171 @@
+use crate::f1_2020::car::{
+    ActualTyreCompound, AntiLockBrakes, DRSStatus, ERSDeploymentMode, FuelMix, MFDPanel,
+    SurfaceType, TractionControl, TyrePressure, VisualTyreCompound,
+};
+use crate::f1_2020::motion::Wheel;
+use crate::f1_2020::session::ZoneFlag;
+use crate::f1_2021::header::PacketHeader;
+
+pub const TOTAL_CARS: usize = 22;
+pub const CAR_SETUP_2021_SIZE: usize = 1102;
+pub const CAR_SETUP_2023_SIZE: usize = 1107;
+pub const CAR_SETUP_2024_SIZE: usize = 1133;
+pub const CAR_STATUS_2021_SIZE: usize = 1058;
+pub const CAR_STATUS_2023_SIZE: usize = 1239;
+pub const CAR_TELEMETRY_2021_SIZE: usize = 1347;
+pub const CAR_TELEMETRY_2023_SIZE: usize = 1352;
+pub const CAR_DAMAGE_2021_SIZE: usize = 882;
+pub const CAR_DAMAGE_2022_SIZE: usize = 948;
+pub const CAR_DAMAGE_2023_SIZE: usize = 953;
+
+use derivative::Derivative;
+
+#[derive(Debug, PartialEq, Clone, Derivative)]
+#[derivative(Eq)]
+pub struct CarSetupData {
+    pub front_wing: u8,
+    pub rear_wing: u8,
+    pub on_throttle: u8,
+    pub off_throttle: u8,
+    pub front_camber: f32,
+    pub rear_camber: f32,
+    pub front_toe: f32,
+    pub rear_toe: f32,
+    pub front_suspension: u8,
+    pub rear_suspension: u8,
+    pub front_anti_roll_bar: u8,
+    pub rear_anti_roll_bar: u8,
+    pub front_suspension_height: u8,
+    pub rear_suspension_height: u8,
+    pub brake_pressure: u8,
+    pub brake_bias: u8,
+    /// Engine braking (percentage), sent from F1 24 onwards
+    pub engine_braking: Option<u8>,
+    pub rear_tyre_pressure: TyrePressure,
+    pub front_tyre_pressure: TyrePressure,
+    pub ballast: u8,
+    pub fuel_load: f32,
+}
+
+#[derive(Debug, PartialEq, Clone, Derivative)]
+#[derivative(Eq)]
+pub struct PacketCarSetupData {
+    pub header: PacketHeader,
+    pub car_setup_data: Vec<CarSetupData>,
+    /// Value of front wing after next pit stop - player only,
+    /// sent from F1 24 onwards
+    pub next_front_wing_value: Option<f32>,
+}
+
+#[derive(Debug, PartialEq, Clone, Derivative)]
+#[derivative(Eq)]
+pub struct CarTelemetryData {
+    pub speed: u16,
+    pub throttle: f32,
+    pub steer: f32,
+    pub brake: f32,
+    pub clutch: u8,
+    pub gear: i8,
+    pub engine_rpm: u16,
+    pub drs: bool,
+    pub rev_lights_percent: u8,
+    /// Rev lights (bit 0 = leftmost LED, bit 14 = rightmost LED)
+    pub rev_lights_bit_value: u16,
+    pub brakes_temperature: Wheel<u16>,
+    pub tyres_surface_temperature: Wheel<u8>,
+    pub tyres_inner_temperature: Wheel<u8>,
+    pub engine_temperature: u16,
+    pub tyre_pressures: Wheel<f32>,
+    pub surface_types: Wheel<SurfaceType>,
+}
+
+#[derive(Debug, PartialEq, Clone, Derivative)]
+#[derivative(Eq)]
+pub struct PacketCarTelemetryData {
+    pub header: PacketHeader,
+    pub car_telemetry_data: Vec<CarTelemetryData>,
+    pub mfd_panel_index: MFDPanel,
+    pub mfd_panel_index_secondary_player: MFDPanel,
+    pub suggested_gear: i8,
+}
+
+#[derive(Debug, PartialEq, Clone, Derivative)]
+#[derivative(Eq)]
+pub struct CarStatusData {
+    pub traction_control: TractionControl,
+    pub anti_lock_brakes: AntiLockBrakes,
+    pub fuel_mix: FuelMix,
+    pub front_brake_bias: u8,
+    pub pit_limiter: bool,
+    pub fuel_in_tank: f32,
+    pub fuel_capacity: f32,
+    pub fuel_remaining_laps: f32,
+    pub max_rpm: u16,
+    pub idle_rpm: u16,
+    pub max_gears: u8,
+    pub drs_allowed: DRSStatus,
+    /// 0 = DRS not available, non-zero - DRS will be available
+    /// in [X] metres
+    pub drs_activation_distance: u16,
+    pub actual_tyre_compound: ActualTyreCompound,
+    pub visual_tyre_compound: VisualTyreCompound,
+    pub tyres_age_laps: u8,
+    pub vehicle_fia_flags: ZoneFlag,
+    /// Engine power output of ICE (W), sent from F1 23 onwards
+    pub engine_power_ice: Option<f32>,
+    /// Engine power output of MGU-K (W), sent from F1 23 onwards
+    pub engine_power_mguk: Option<f32>,
+    pub ers_store_energy: f32,
+    pub ers_deploy_mode: ERSDeploymentMode,
+    pub ers_harvested_this_lap_mguk: f32,
+    pub ers_harvested_this_lap_mguh: f32,
+    pub ers_deployed_this_lap: f32,
+    /// Whether the car is paused in a network game
+    pub network_paused: bool,
+}
+
+#[derive(Debug, PartialEq, Clone, Derivative)]
+#[derivative(Eq)]
+pub struct PacketCarStatusData {
+    pub header: PacketHeader,
+    pub car_status_data: Vec<CarStatusData>,
+}
+
+#[derive(Debug, PartialEq, Clone, Derivative)]
+#[derivative(Eq)]
+pub struct CarDamageData {
+    /// Tyre wear (percentage)
+    pub tyres_wear: Wheel<f32>,
+    /// Tyre damage (percentage)
+    pub tyres_damage: Wheel<u8>,
+    /// Brakes damage (percentage)
+    pub brakes_damage: Wheel<u8>,
+    pub front_left_wing_damage: u8,
+    pub front_right_wing_damage: u8,
+    pub rear_wing_damage: u8,
+    pub floor_damage: u8,
+    pub diffuser_damage: u8,
+    pub sidepod_damage: u8,
+    pub drs_fault: bool,
+    /// Indicator for ERS fault, sent from F1 22 onwards
+    pub ers_fault: Option<bool>,
+    pub gear_box_damage: u8,
+    pub engine_damage: u8,
+    pub engine_mguh_wear: u8,
+    pub engine_es_wear: u8,
+    pub engine_ce_wear: u8,
+    pub engine_ice_wear: u8,
+    pub engine_mguk_wear: u8,
+    pub engine_tc_wear: u8,
+    /// Engine blown, sent from F1 22 onwards
+    pub engine_blown: Option<bool>,
+    /// Engine seized, sent from F1 22 onwards
+    pub engine_seized: Option<bool>,
+}
+
+#[derive(Debug, PartialEq, Clone, Derivative)]
+#[derivative(Eq)]
+pub struct PacketCarDamageData {
+    pub header: PacketHeader,
+    pub car_damage_data: Vec<CarDamageData>,
+}
@@ -0,0 +1,118 @@
+use crate::error::TelemetryError;
+use crate::f1_2020::motion::Wheel;
+use crate::f1_2021::car::{
+    CarDamageData, PacketCarDamageData, CAR_DAMAGE_2021_SIZE, CAR_DAMAGE_2022_SIZE,
+    CAR_DAMAGE_2023_SIZE, TOTAL_CARS,
+};
+use crate::f1_2021::header::PacketHeader;
+use byteorder::{LittleEndian, ReadBytesExt};
+use std::io::Cursor;
+
+pub fn parse_car_damage_data(
+    cursor: &mut Cursor<&[u8]>,
+    header: PacketHeader,
+    size: usize,
+) -> Result<PacketCarDamageData, TelemetryError> {
+    ensure_car_damage_size(header.packet_format, size)?;
+
+    let mut car_damage_data = Vec::with_capacity(TOTAL_CARS);
+    for _ in 0..TOTAL_CARS {
+        let cdd = parse_car_damage(cursor, header.packet_format)?;
+        car_damage_data.push(cdd);
+    }
+
+    Ok(PacketCarDamageData {
+        header,
+        car_damage_data,
+    })
+}
+
+fn parse_car_damage(
+    cursor: &mut Cursor<&[u8]>,
+    packet_format: u16,
+) -> Result<CarDamageData, TelemetryError> {
+    let tyres_wear = Wheel {
+        rear_left: cursor.read_f32::<LittleEndian>()?,
+        rear_right: cursor.read_f32::<LittleEndian>()?,
+        front_left: cursor.read_f32::<LittleEndian>()?,
+        front_right: cursor.read_f32::<LittleEndian>()?,
+    };
+    let tyres_damage = Wheel {
+        rear_left: cursor.read_u8()?,
+        rear_right: cursor.read_u8()?,
+        front_left: cursor.read_u8()?,
+        front_right: cursor.read_u8()?,
+    };
+    let brakes_damage = Wheel {
+        rear_left: cursor.read_u8()?,
+        rear_right: cursor.read_u8()?,
+        front_left: cursor.read_u8()?,
+        front_right: cursor.read_u8()?,
+    };
+    let front_left_wing_damage = cursor.read_u8()?;
+    let front_right_wing_damage = cursor.read_u8()?;
+    let rear_wing_damage = cursor.read_u8()?;
+    let floor_damage = cursor.read_u8()?;
+    let diffuser_damage = cursor.read_u8()?;
+    let sidepod_damage = cursor.read_u8()?;
+    let drs_fault = cursor.read_u8()? == 1;
+    let ers_fault = if packet_format >= 2022 {
+        Some(cursor.read_u8()? == 1)
+    } else {
+        None
+    };
+    let gear_box_damage = cursor.read_u8()?;
+    let engine_damage = cursor.read_u8()?;
+    let engine_mguh_wear = cursor.read_u8()?;
+    let engine_es_wear = cursor.read_u8()?;
+    let engine_ce_wear = cursor.read_u8()?;
+    let engine_ice_wear = cursor.read_u8()?;
+    let engine_mguk_wear = cursor.read_u8()?;
+    let engine_tc_wear = cursor.read_u8()?;
+    let (engine_blown, engine_seized) = if packet_format >= 2022 {
+        (Some(cursor.read_u8()? == 1), Some(cursor.read_u8()? == 1))
+    } else {
+        (None, None)
+    };
+
+    Ok(CarDamageData {
+        tyres_wear,
+        tyres_damage,
+        brakes_damage,
+        front_left_wing_damage,
+        front_right_wing_damage,
+        rear_wing_damage,
+        floor_damage,
+        diffuser_damage,
+        sidepod_damage,
+        drs_fault,
+        ers_fault,
+        gear_box_damage,
+        engine_damage,
+        engine_mguh_wear,
+        engine_es_wear,
+        engine_ce_wear,
+        engine_ice_wear,
+        engine_mguk_wear,
+        engine_tc_wear,
+        engine_blown,
+        engine_seized,
+    })
+}
+
+fn ensure_car_damage_size(packet_format: u16, size: usize) -> Result<(), TelemetryError> {
+    let expected = match packet_format {
+        2021 => CAR_DAMAGE_2021_SIZE,
+        2022 => CAR_DAMAGE_2022_SIZE,
+        _ => CAR_DAMAGE_2023_SIZE,
+    };
+    if size == expected {
+        return Ok(());
+    }
+
+    Err(TelemetryError::SizeMismatch {
+        packet: "Car damage",
+        expected,
+        got: size,
+    })
+}
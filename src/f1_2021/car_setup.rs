@@ -0,0 +1,113 @@
+use crate::error::TelemetryError;
+use crate::f1_2020::car::TyrePressure;
+use crate::f1_2021::car::{
+    CarSetupData, PacketCarSetupData, CAR_SETUP_2021_SIZE, CAR_SETUP_2023_SIZE,
+    CAR_SETUP_2024_SIZE, TOTAL_CARS,
+};
+use crate::f1_2021::header::PacketHeader;
+use byteorder::{LittleEndian, ReadBytesExt};
+use std::io::Cursor;
+
+pub fn parse_car_setup_data(
+    cursor: &mut Cursor<&[u8]>,
+    header: PacketHeader,
+    size: usize,
+) -> Result<PacketCarSetupData, TelemetryError> {
+    ensure_car_setup_size(header.packet_format, size)?;
+
+    let mut car_setup_data = Vec::with_capacity(TOTAL_CARS);
+    for _ in 0..TOTAL_CARS {
+        let csd = parse_car_setup(cursor, header.packet_format)?;
+        car_setup_data.push(csd);
+    }
+
+    let next_front_wing_value = if header.packet_format >= 2024 {
+        Some(cursor.read_f32::<LittleEndian>()?)
+    } else {
+        None
+    };
+
+    Ok(PacketCarSetupData {
+        header,
+        car_setup_data,
+        next_front_wing_value,
+    })
+}
+
+pub fn parse_car_setup(
+    cursor: &mut Cursor<&[u8]>,
+    packet_format: u16,
+) -> Result<CarSetupData, TelemetryError> {
+    let front_wing = cursor.read_u8()?;
+    let rear_wing = cursor.read_u8()?;
+    let on_throttle = cursor.read_u8()?;
+    let off_throttle = cursor.read_u8()?;
+    let front_camber = cursor.read_f32::<LittleEndian>()?;
+    let rear_camber = cursor.read_f32::<LittleEndian>()?;
+    let front_toe = cursor.read_f32::<LittleEndian>()?;
+    let rear_toe = cursor.read_f32::<LittleEndian>()?;
+    let front_suspension = cursor.read_u8()?;
+    let rear_suspension = cursor.read_u8()?;
+    let front_anti_roll_bar = cursor.read_u8()?;
+    let rear_anti_roll_bar = cursor.read_u8()?;
+    let front_suspension_height = cursor.read_u8()?;
+    let rear_suspension_height = cursor.read_u8()?;
+    let brake_pressure = cursor.read_u8()?;
+    let brake_bias = cursor.read_u8()?;
+    let engine_braking = if packet_format >= 2024 {
+        Some(cursor.read_u8()?)
+    } else {
+        None
+    };
+    let rear_tyre_pressure = TyrePressure {
+        left: cursor.read_f32::<LittleEndian>()?,
+        right: cursor.read_f32::<LittleEndian>()?,
+    };
+    let front_tyre_pressure = TyrePressure {
+        left: cursor.read_f32::<LittleEndian>()?,
+        right: cursor.read_f32::<LittleEndian>()?,
+    };
+    let ballast = cursor.read_u8()?;
+    let fuel_load = cursor.read_f32::<LittleEndian>()?;
+
+    Ok(CarSetupData {
+        front_wing,
+        rear_wing,
+        on_throttle,
+        off_throttle,
+        front_camber,
+        rear_camber,
+        front_toe,
+        rear_toe,
+        front_suspension,
+        rear_suspension,
+        front_anti_roll_bar,
+        rear_anti_roll_bar,
+        front_suspension_height,
+        rear_suspension_height,
+        brake_pressure,
+        brake_bias,
+        engine_braking,
+        rear_tyre_pressure,
+        front_tyre_pressure,
+        ballast,
+        fuel_load,
+    })
+}
+
+fn ensure_car_setup_size(packet_format: u16, size: usize) -> Result<(), TelemetryError> {
+    let expected = match packet_format {
+        2021 | 2022 => CAR_SETUP_2021_SIZE,
+        2023 => CAR_SETUP_2023_SIZE,
+        _ => CAR_SETUP_2024_SIZE,
+    };
+    if size == expected {
+        return Ok(());
+    }
+
+    Err(TelemetryError::SizeMismatch {
+        packet: "Car setup",
+        expected,
+        got: size,
+    })
+}
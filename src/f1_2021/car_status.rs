@@ -0,0 +1,158 @@
+use crate::decoder::Decoder;
+use crate::error::TelemetryError;
+use crate::f1_2020::car::{ActualTyreCompound, ERSDeploymentMode, VisualTyreCompound};
+use crate::f1_2020::car_status::{
+    parse_actual_tyre_compound as parse_actual_tyre_compound_2020, parse_anti_lock_brakes,
+    parse_drs, parse_fuel_mix, parse_traction_control,
+    parse_visual_tyre_compound as parse_visual_tyre_compound_2020,
+};
+use crate::f1_2020::session::parse_flag;
+use crate::f1_2021::car::{
+    CarStatusData, PacketCarStatusData, CAR_STATUS_2021_SIZE, CAR_STATUS_2023_SIZE, TOTAL_CARS,
+};
+use crate::f1_2021::header::PacketHeader;
+use byteorder::{LittleEndian, ReadBytesExt};
+use std::io::Cursor;
+
+pub fn parse_car_status_data(
+    cursor: &mut Cursor<&[u8]>,
+    header: PacketHeader,
+    size: usize,
+    decoder: &mut Decoder,
+) -> Result<PacketCarStatusData, TelemetryError> {
+    ensure_car_status_size(header.packet_format, size)?;
+
+    let mut car_status_data = Vec::with_capacity(TOTAL_CARS);
+    for _ in 0..TOTAL_CARS {
+        let csd = parse_car_status(cursor, header.packet_format, decoder)?;
+        car_status_data.push(csd);
+    }
+
+    Ok(PacketCarStatusData {
+        header,
+        car_status_data,
+    })
+}
+
+fn parse_car_status(
+    cursor: &mut Cursor<&[u8]>,
+    packet_format: u16,
+    decoder: &mut Decoder,
+) -> Result<CarStatusData, TelemetryError> {
+    let traction_control = parse_traction_control(cursor.read_u8()?, decoder)?;
+    let anti_lock_brakes = parse_anti_lock_brakes(cursor.read_u8()?, decoder)?;
+    let fuel_mix = parse_fuel_mix(cursor.read_u8()?, decoder)?;
+    let front_brake_bias = cursor.read_u8()?;
+    let pit_limiter = cursor.read_u8()? == 1;
+    let fuel_in_tank = cursor.read_f32::<LittleEndian>()?;
+    let fuel_capacity = cursor.read_f32::<LittleEndian>()?;
+    let fuel_remaining_laps = cursor.read_f32::<LittleEndian>()?;
+    let max_rpm = cursor.read_u16::<LittleEndian>()?;
+    let idle_rpm = cursor.read_u16::<LittleEndian>()?;
+    let max_gears = cursor.read_u8()?;
+    let drs_allowed = parse_drs(cursor.read_i8()?, decoder)?;
+    let drs_activation_distance = cursor.read_u16::<LittleEndian>()?;
+    let actual_tyre_compound = parse_actual_tyre_compound(cursor.read_u8()?, decoder)?;
+    let visual_tyre_compound = parse_visual_tyre_compound(cursor.read_u8()?, decoder)?;
+    let tyres_age_laps = cursor.read_u8()?;
+    let vehicle_fia_flags = parse_flag(cursor.read_i8()?, decoder)?;
+    let (engine_power_ice, engine_power_mguk) = if packet_format >= 2023 {
+        (
+            Some(cursor.read_f32::<LittleEndian>()?),
+            Some(cursor.read_f32::<LittleEndian>()?),
+        )
+    } else {
+        (None, None)
+    };
+    let ers_store_energy = cursor.read_f32::<LittleEndian>()?;
+    let ers_deploy_mode = parse_ers_deployment_mode(cursor.read_u8()?, decoder)?;
+    let ers_harvested_this_lap_mguk = cursor.read_f32::<LittleEndian>()?;
+    let ers_harvested_this_lap_mguh = cursor.read_f32::<LittleEndian>()?;
+    let ers_deployed_this_lap = cursor.read_f32::<LittleEndian>()?;
+    let network_paused = cursor.read_u8()? == 1;
+
+    Ok(CarStatusData {
+        traction_control,
+        anti_lock_brakes,
+        fuel_mix,
+        front_brake_bias,
+        pit_limiter,
+        fuel_in_tank,
+        fuel_capacity,
+        fuel_remaining_laps,
+        max_rpm,
+        idle_rpm,
+        max_gears,
+        drs_allowed,
+        drs_activation_distance,
+        actual_tyre_compound,
+        visual_tyre_compound,
+        tyres_age_laps,
+        vehicle_fia_flags,
+        engine_power_ice,
+        engine_power_mguk,
+        ers_store_energy,
+        ers_deploy_mode,
+        ers_harvested_this_lap_mguk,
+        ers_harvested_this_lap_mguh,
+        ers_deployed_this_lap,
+        network_paused,
+    })
+}
+
+fn ensure_car_status_size(packet_format: u16, size: usize) -> Result<(), TelemetryError> {
+    let expected = match packet_format {
+        2021 | 2022 => CAR_STATUS_2021_SIZE,
+        _ => CAR_STATUS_2023_SIZE,
+    };
+    if size == expected {
+        return Ok(());
+    }
+
+    Err(TelemetryError::SizeMismatch {
+        packet: "Car status",
+        expected,
+        got: size,
+    })
+}
+
+pub fn parse_ers_deployment_mode(
+    value: u8,
+    decoder: &mut Decoder,
+) -> Result<ERSDeploymentMode, TelemetryError> {
+    match value {
+        0 => Ok(ERSDeploymentMode::None),
+        1 => Ok(ERSDeploymentMode::Medium),
+        2 => Ok(ERSDeploymentMode::Hotlap),
+        3 => Ok(ERSDeploymentMode::Overtake),
+        _ => decoder.unknown(
+            "ers_deployment_mode",
+            value,
+            ERSDeploymentMode::Unknown(value),
+        ),
+    }
+}
+
+pub fn parse_actual_tyre_compound(
+    value: u8,
+    decoder: &mut Decoder,
+) -> Result<ActualTyreCompound, TelemetryError> {
+    match value {
+        21 => Ok(ActualTyreCompound::C0),
+        _ => parse_actual_tyre_compound_2020(value, decoder),
+    }
+}
+
+/// F2 visual compounds moved to 19-22 from F1 2021 onwards
+pub fn parse_visual_tyre_compound(
+    value: u8,
+    decoder: &mut Decoder,
+) -> Result<VisualTyreCompound, TelemetryError> {
+    match value {
+        19 => Ok(VisualTyreCompound::F2SuperSoft),
+        20 => Ok(VisualTyreCompound::F2Soft),
+        21 => Ok(VisualTyreCompound::F2Medium),
+        22 => Ok(VisualTyreCompound::F2Hard),
+        _ => parse_visual_tyre_compound_2020(value, decoder),
+    }
+}
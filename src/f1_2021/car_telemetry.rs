@@ -0,0 +1,120 @@
+use crate::decoder::Decoder;
+use crate::error::TelemetryError;
+use crate::f1_2020::car_telemetry::{parse_mfd_panel, parse_surface_type};
+use crate::f1_2020::motion::Wheel;
+use crate::f1_2021::car::{
+    CarTelemetryData, PacketCarTelemetryData, CAR_TELEMETRY_2021_SIZE, CAR_TELEMETRY_2023_SIZE,
+    TOTAL_CARS,
+};
+use crate::f1_2021::header::PacketHeader;
+use byteorder::{LittleEndian, ReadBytesExt};
+use std::io::Cursor;
+
+pub fn parse_car_telemetry_data(
+    cursor: &mut Cursor<&[u8]>,
+    header: PacketHeader,
+    size: usize,
+    decoder: &mut Decoder,
+) -> Result<PacketCarTelemetryData, TelemetryError> {
+    ensure_car_telemetry_size(header.packet_format, size)?;
+
+    let mut car_telemetry_data = Vec::with_capacity(TOTAL_CARS);
+    for _ in 0..TOTAL_CARS {
+        let ctd = parse_car_telemetry(cursor, decoder)?;
+        car_telemetry_data.push(ctd);
+    }
+
+    let mfd_panel_index = parse_mfd_panel(cursor.read_u8()?, decoder)?;
+    let mfd_panel_index_secondary_player = parse_mfd_panel(cursor.read_u8()?, decoder)?;
+    let suggested_gear = cursor.read_i8()?;
+
+    Ok(PacketCarTelemetryData {
+        header,
+        car_telemetry_data,
+        mfd_panel_index,
+        mfd_panel_index_secondary_player,
+        suggested_gear,
+    })
+}
+
+pub fn parse_car_telemetry(
+    cursor: &mut Cursor<&[u8]>,
+    decoder: &mut Decoder,
+) -> Result<CarTelemetryData, TelemetryError> {
+    let speed = cursor.read_u16::<LittleEndian>()?;
+    let throttle = cursor.read_f32::<LittleEndian>()?;
+    let steer = cursor.read_f32::<LittleEndian>()?;
+    let brake = cursor.read_f32::<LittleEndian>()?;
+    let clutch = cursor.read_u8()?;
+    let gear = cursor.read_i8()?;
+    let engine_rpm = cursor.read_u16::<LittleEndian>()?;
+    let drs = cursor.read_u8()? == 1;
+    let rev_lights_percent = cursor.read_u8()?;
+    let rev_lights_bit_value = cursor.read_u16::<LittleEndian>()?;
+    let brakes_temperature = Wheel {
+        rear_left: cursor.read_u16::<LittleEndian>()?,
+        rear_right: cursor.read_u16::<LittleEndian>()?,
+        front_left: cursor.read_u16::<LittleEndian>()?,
+        front_right: cursor.read_u16::<LittleEndian>()?,
+    };
+    let tyres_surface_temperature = Wheel {
+        rear_left: cursor.read_u8()?,
+        rear_right: cursor.read_u8()?,
+        front_left: cursor.read_u8()?,
+        front_right: cursor.read_u8()?,
+    };
+    let tyres_inner_temperature = Wheel {
+        rear_left: cursor.read_u8()?,
+        rear_right: cursor.read_u8()?,
+        front_left: cursor.read_u8()?,
+        front_right: cursor.read_u8()?,
+    };
+    let engine_temperature = cursor.read_u16::<LittleEndian>()?;
+    let tyre_pressures = Wheel {
+        rear_left: cursor.read_f32::<LittleEndian>()?,
+        rear_right: cursor.read_f32::<LittleEndian>()?,
+        front_left: cursor.read_f32::<LittleEndian>()?,
+        front_right: cursor.read_f32::<LittleEndian>()?,
+    };
+    let surface_types = Wheel {
+        rear_left: parse_surface_type(cursor.read_u8()?, decoder)?,
+        rear_right: parse_surface_type(cursor.read_u8()?, decoder)?,
+        front_left: parse_surface_type(cursor.read_u8()?, decoder)?,
+        front_right: parse_surface_type(cursor.read_u8()?, decoder)?,
+    };
+
+    Ok(CarTelemetryData {
+        speed,
+        throttle,
+        steer,
+        brake,
+        clutch,
+        gear,
+        engine_rpm,
+        drs,
+        rev_lights_percent,
+        rev_lights_bit_value,
+        brakes_temperature,
+        tyres_surface_temperature,
+        tyres_inner_temperature,
+        engine_temperature,
+        tyre_pressures,
+        surface_types,
+    })
+}
+
+fn ensure_car_telemetry_size(packet_format: u16, size: usize) -> Result<(), TelemetryError> {
+    let expected = match packet_format {
+        2021 | 2022 => CAR_TELEMETRY_2021_SIZE,
+        _ => CAR_TELEMETRY_2023_SIZE,
+    };
+    if size == expected {
+        return Ok(());
+    }
+
+    Err(TelemetryError::SizeMismatch {
+        packet: "Car telemetry",
+        expected,
+        got: size,
+    })
+}
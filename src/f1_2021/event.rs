@@ -0,0 +1,349 @@
+use crate::decoder::Decoder;
+use crate::error::{parse_duration, TelemetryError};
+use crate::f1_2020::event::{
+    parse_infringement_type, parse_penalty_type, FastestLap, Penalty, RaceWinner, Retirement,
+    TeamMateInPits,
+};
+use crate::f1_2020::session::SafetyCar;
+use crate::f1_2021::header::PacketHeader;
+use crate::f1_2021::session::parse_safety_car;
+use byteorder::{LittleEndian, ReadBytesExt};
+use std::io::{Cursor, Read};
+use std::time::Duration;
+
+use derivative::Derivative;
+
+const EVENT_2021_SIZE: usize = 36;
+const EVENT_2022_SIZE: usize = 40;
+const EVENT_2023_SIZE: usize = 45;
+
+#[derive(Debug, PartialEq, Copy, Clone, Derivative)]
+#[derivative(Eq)]
+pub struct SpeedTrap {
+    /// Vehicle index of the vehicle triggering speed trap
+    pub vehicle_index: u8,
+    /// Top speed achieved in kilometres per hour
+    pub speed: f32,
+    /// Overall fastest speed in session
+    pub overall_fastest_in_session: bool,
+    /// Fastest speed for driver in session
+    pub driver_fastest_in_session: bool,
+    /// Vehicle index of the vehicle that is the fastest
+    /// in this session, sent from F1 22 onwards
+    pub fastest_vehicle_index_in_session: Option<u8>,
+    /// Speed of the vehicle that is the fastest
+    /// in this session, sent from F1 22 onwards
+    pub fastest_speed_in_session: Option<f32>,
+}
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub struct StartLights {
+    /// Number of lights showing
+    pub num_lights: u8,
+}
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub struct DriveThroughPenaltyServed {
+    /// Vehicle index of the vehicle serving drive through
+    pub vehicle_index: u8,
+}
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub struct StopGoPenaltyServed {
+    /// Vehicle index of the vehicle serving stop go
+    pub vehicle_index: u8,
+    /// Time spent serving stop go in seconds, sent from F1 24 onwards
+    pub stop_time: Option<Duration>,
+}
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub struct Flashback {
+    /// Frame identifier flashed back to
+    pub flashback_frame_identifier: u32,
+    /// Session time flashed back to
+    pub flashback_session_time: Duration,
+}
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub struct Buttons {
+    /// Bit flags specifying which buttons are being pressed
+    /// currently - see appendices
+    pub button_status: u32,
+}
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub struct Overtake {
+    /// Vehicle index of the vehicle overtaking
+    pub overtaking_vehicle_index: u8,
+    /// Vehicle index of the vehicle being overtaken
+    pub being_overtaken_vehicle_index: u8,
+}
+
+#[derive(Debug, Eq, PartialOrd, PartialEq, Clone, Copy)]
+pub enum SafetyCarEventType {
+    Deployed, // 0
+    Returning,
+    Returned,
+    ResumeRace,
+    Unknown(u8),
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct SafetyCarEvent {
+    /// 0 = No Safety Car, 1 = Full Safety Car
+    /// 2 = Virtual Safety Car, 3 = Formation Lap Safety Car
+    pub safety_car_type: SafetyCar,
+    /// 0 = Deployed, 1 = Returning, 2 = Returned
+    /// 3 = Resume Race
+    pub event_type: SafetyCarEventType,
+}
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub struct Collision {
+    /// Vehicle index of the first vehicle involved in the collision
+    pub vehicle_1_index: u8,
+    /// Vehicle index of the second vehicle involved in the collision
+    pub vehicle_2_index: u8,
+}
+
+#[derive(Debug, PartialEq, Clone, Derivative)]
+#[derivative(Eq)]
+pub enum Event {
+    SessionStarted,
+    SessionEnded,
+    FastestLap(FastestLap),
+    Retirement(Retirement),
+    DRSEnabled,
+    DRSDisabled,
+    TeamMateInPits(TeamMateInPits),
+    ChequeredFlag,
+    RaceWinner(RaceWinner),
+    Penalty(Penalty),
+    SpeedTrap(SpeedTrap),
+    StartLights(StartLights),
+    LightsOut,
+    DriveThroughServed(DriveThroughPenaltyServed),
+    StopGoServed(StopGoPenaltyServed),
+    Flashback(Flashback),
+    Buttons(Buttons),
+    RedFlag,
+    Overtake(Overtake),
+    SafetyCar(SafetyCarEvent),
+    Collision(Collision),
+    /// Event code the decoder does not know, in lenient mode
+    Unknown([u8; 4]),
+}
+
+#[derive(Debug, PartialEq, Clone, Derivative)]
+#[derivative(Eq)]
+pub struct PacketEventData {
+    pub header: PacketHeader,
+    pub event: Event,
+}
+
+pub fn parse_event_data(
+    cursor: &mut Cursor<&[u8]>,
+    header: PacketHeader,
+    size: usize,
+    decoder: &mut Decoder,
+) -> Result<PacketEventData, TelemetryError> {
+    ensure_event_size(header.packet_format, size)?;
+
+    let event = parse_event(cursor, header.packet_format, decoder)?;
+    Ok(PacketEventData { header, event })
+}
+
+pub fn parse_event(
+    cursor: &mut Cursor<&[u8]>,
+    packet_format: u16,
+    decoder: &mut Decoder,
+) -> Result<Event, TelemetryError> {
+    let mut event_code = [0; 4];
+    cursor.read_exact(&mut event_code)?;
+
+    match &event_code {
+        b"SSTA" => Ok(Event::SessionStarted),
+        b"SEND" => Ok(Event::SessionEnded),
+        b"FTLP" => {
+            let vehicle_index = cursor.read_u8()?;
+            let lap_time = parse_duration("lap_time", cursor.read_f32::<LittleEndian>()?)?;
+
+            Ok(Event::FastestLap(FastestLap {
+                vehicle_index,
+                lap_time,
+            }))
+        }
+        b"RTMT" => {
+            let vehicle_index = cursor.read_u8()?;
+
+            Ok(Event::Retirement(Retirement { vehicle_index }))
+        }
+        b"DRSE" => Ok(Event::DRSEnabled),
+        b"DRSD" => Ok(Event::DRSDisabled),
+        b"TMPT" => {
+            let vehicle_index = cursor.read_u8()?;
+
+            Ok(Event::TeamMateInPits(TeamMateInPits { vehicle_index }))
+        }
+        b"CHQF" => Ok(Event::ChequeredFlag),
+        b"RCWN" => {
+            let vehicle_index = cursor.read_u8()?;
+
+            Ok(Event::RaceWinner(RaceWinner { vehicle_index }))
+        }
+        b"PENA" => {
+            let penalty_type = parse_penalty_type(cursor.read_u8()?, decoder)?;
+            let infringement_type = parse_infringement_type(cursor.read_u8()?, decoder)?;
+            let vehicle_index = cursor.read_u8()?;
+            let other_vehicle_index = cursor.read_u8()?;
+            let time = Duration::from_secs(cursor.read_u8()? as u64);
+            let lap_num = cursor.read_u8()?;
+            let places_gained = cursor.read_u8()?;
+
+            Ok(Event::Penalty(Penalty {
+                vehicle_index,
+                penalty_type,
+                infringement_type,
+                other_vehicle_index,
+                time,
+                lap_num,
+                places_gained,
+            }))
+        }
+        b"SPTP" => {
+            let vehicle_index = cursor.read_u8()?;
+            let speed = cursor.read_f32::<LittleEndian>()?;
+            let overall_fastest_in_session = cursor.read_u8()? == 1;
+            let driver_fastest_in_session = cursor.read_u8()? == 1;
+            let (fastest_vehicle_index_in_session, fastest_speed_in_session) =
+                if packet_format >= 2022 {
+                    (
+                        Some(cursor.read_u8()?),
+                        Some(cursor.read_f32::<LittleEndian>()?),
+                    )
+                } else {
+                    (None, None)
+                };
+
+            Ok(Event::SpeedTrap(SpeedTrap {
+                vehicle_index,
+                speed,
+                overall_fastest_in_session,
+                driver_fastest_in_session,
+                fastest_vehicle_index_in_session,
+                fastest_speed_in_session,
+            }))
+        }
+        b"STLG" => {
+            let num_lights = cursor.read_u8()?;
+
+            Ok(Event::StartLights(StartLights { num_lights }))
+        }
+        b"LGOT" => Ok(Event::LightsOut),
+        b"DTSV" => {
+            let vehicle_index = cursor.read_u8()?;
+
+            Ok(Event::DriveThroughServed(DriveThroughPenaltyServed {
+                vehicle_index,
+            }))
+        }
+        b"SGSV" => {
+            let vehicle_index = cursor.read_u8()?;
+            let stop_time = if packet_format >= 2024 {
+                Some(parse_duration(
+                    "stop_time",
+                    cursor.read_f32::<LittleEndian>()?,
+                )?)
+            } else {
+                None
+            };
+
+            Ok(Event::StopGoServed(StopGoPenaltyServed {
+                vehicle_index,
+                stop_time,
+            }))
+        }
+        b"FLBK" => {
+            let flashback_frame_identifier = cursor.read_u32::<LittleEndian>()?;
+            let flashback_session_time =
+                parse_duration("flashback_session_time", cursor.read_f32::<LittleEndian>()?)?;
+
+            Ok(Event::Flashback(Flashback {
+                flashback_frame_identifier,
+                flashback_session_time,
+            }))
+        }
+        b"BUTN" => {
+            let button_status = cursor.read_u32::<LittleEndian>()?;
+
+            Ok(Event::Buttons(Buttons { button_status }))
+        }
+        b"RDFL" => Ok(Event::RedFlag),
+        b"OVTK" => {
+            let overtaking_vehicle_index = cursor.read_u8()?;
+            let being_overtaken_vehicle_index = cursor.read_u8()?;
+
+            Ok(Event::Overtake(Overtake {
+                overtaking_vehicle_index,
+                being_overtaken_vehicle_index,
+            }))
+        }
+        b"SCAR" => {
+            let safety_car_type = parse_safety_car(cursor.read_u8()?, decoder)?;
+            let event_type = parse_safety_car_event_type(cursor.read_u8()?, decoder)?;
+
+            Ok(Event::SafetyCar(SafetyCarEvent {
+                safety_car_type,
+                event_type,
+            }))
+        }
+        b"COLL" => {
+            let vehicle_1_index = cursor.read_u8()?;
+            let vehicle_2_index = cursor.read_u8()?;
+
+            Ok(Event::Collision(Collision {
+                vehicle_1_index,
+                vehicle_2_index,
+            }))
+        }
+        _ => decoder.unknown(
+            "event_code",
+            u32::from_le_bytes(event_code),
+            Event::Unknown(event_code),
+        ),
+    }
+}
+
+fn parse_safety_car_event_type(
+    value: u8,
+    decoder: &mut Decoder,
+) -> Result<SafetyCarEventType, TelemetryError> {
+    match value {
+        0 => Ok(SafetyCarEventType::Deployed),
+        1 => Ok(SafetyCarEventType::Returning),
+        2 => Ok(SafetyCarEventType::Returned),
+        3 => Ok(SafetyCarEventType::ResumeRace),
+        _ => decoder.unknown(
+            "safety_car_event_type",
+            value,
+            SafetyCarEventType::Unknown(value),
+        ),
+    }
+}
+
+fn ensure_event_size(packet_format: u16, size: usize) -> Result<(), TelemetryError> {
+    let expected = match packet_format {
+        2021 => EVENT_2021_SIZE,
+        2022 => EVENT_2022_SIZE,
+        _ => EVENT_2023_SIZE,
+    };
+    if size == expected {
+        return Ok(());
+    }
+
+    Err(TelemetryError::SizeMismatch {
+        packet: "Event",
+        expected,
+        got: size,
+    })
+}
@@ -0,0 +1,141 @@
+use crate::decoder::Decoder;
+use crate::error::TelemetryError;
+use byteorder::{LittleEndian, ReadBytesExt};
+use derivative::Derivative;
+use std::io::Cursor;
+use std::time::Duration;
+
+use crate::f1_2020::car::{ActualTyreCompound, VisualTyreCompound};
+use crate::f1_2020::lap::{parse_result_status, ResultStatus};
+use crate::f1_2021::car::TOTAL_CARS;
+use crate::f1_2021::car_status::{parse_actual_tyre_compound, parse_visual_tyre_compound};
+use crate::f1_2021::header::PacketHeader;
+
+const FINAL_CLASSIFICATION_2021_SIZE: usize = 839;
+const FINAL_CLASSIFICATION_2022_SIZE: usize = 1015;
+const FINAL_CLASSIFICATION_2023_SIZE: usize = 1020;
+
+#[derive(Debug, PartialEq, Clone, Derivative)]
+#[derivative(Eq)]
+pub struct FinalClassificationData {
+    pub position: u8,
+    pub num_laps: u8,
+    pub grid_position: u8,
+    pub points: u8,
+    pub num_pit_stops: u8,
+    pub result_status: ResultStatus,
+    pub best_lap_time: Duration,   // milliseconds
+    pub total_race_time: Duration, // seconds
+    pub penalties_time: u8,
+    pub num_penalties: u8,
+    pub num_tyre_stints: u8,
+    pub tyre_stints_actual: Vec<ActualTyreCompound>,
+    pub tyre_stints_visual: Vec<VisualTyreCompound>,
+    /// The lap number stints end on, sent from F1 22 onwards
+    pub tyre_stints_end_laps: Option<Vec<u8>>,
+}
+
+#[derive(Debug, PartialEq, Clone, Derivative)]
+#[derivative(Eq)]
+pub struct PacketFinalClassificationData {
+    pub header: PacketHeader,
+    pub num_cars: u8,
+    pub final_classification_data: Vec<FinalClassificationData>,
+}
+
+pub fn parse_final_classification_data(
+    cursor: &mut Cursor<&[u8]>,
+    header: PacketHeader,
+    size: usize,
+    decoder: &mut Decoder,
+) -> Result<PacketFinalClassificationData, TelemetryError> {
+    ensure_final_classification_size(header.packet_format, size)?;
+
+    let num_cars = cursor.read_u8()?;
+
+    let mut final_classification_data = Vec::with_capacity(TOTAL_CARS);
+    for _ in 0..TOTAL_CARS {
+        let data = parse_final_classification(cursor, header.packet_format, decoder)?;
+        final_classification_data.push(data);
+    }
+
+    Ok(PacketFinalClassificationData {
+        header,
+        num_cars,
+        final_classification_data,
+    })
+}
+
+pub fn parse_final_classification(
+    cursor: &mut Cursor<&[u8]>,
+    packet_format: u16,
+    decoder: &mut Decoder,
+) -> Result<FinalClassificationData, TelemetryError> {
+    let position = cursor.read_u8()?;
+    let num_laps = cursor.read_u8()?;
+    let grid_position = cursor.read_u8()?;
+    let points = cursor.read_u8()?;
+    let num_pit_stops = cursor.read_u8()?;
+    let result_status = parse_result_status(cursor, decoder)?;
+    let best_lap_time = Duration::from_millis(cursor.read_u32::<LittleEndian>()? as u64);
+    let total_race_time = Duration::from_secs_f64(cursor.read_f64::<LittleEndian>()?);
+    let penalties_time = cursor.read_u8()?;
+    let num_penalties = cursor.read_u8()?;
+    let num_tyre_stints = cursor.read_u8()?;
+
+    let mut tyre_stints_actual = Vec::with_capacity(8);
+    for _ in 0..8 {
+        let tc = parse_actual_tyre_compound(cursor.read_u8()?, decoder)?;
+        tyre_stints_actual.push(tc);
+    }
+
+    let mut tyre_stints_visual = Vec::with_capacity(8);
+    for _ in 0..8 {
+        let tc = parse_visual_tyre_compound(cursor.read_u8()?, decoder)?;
+        tyre_stints_visual.push(tc);
+    }
+
+    let tyre_stints_end_laps = if packet_format >= 2022 {
+        let mut end_laps = Vec::with_capacity(8);
+        for _ in 0..8 {
+            end_laps.push(cursor.read_u8()?);
+        }
+        Some(end_laps)
+    } else {
+        None
+    };
+
+    Ok(FinalClassificationData {
+        position,
+        num_laps,
+        grid_position,
+        points,
+        num_pit_stops,
+        result_status,
+        best_lap_time,
+        total_race_time,
+        penalties_time,
+        num_penalties,
+        num_tyre_stints,
+        tyre_stints_actual,
+        tyre_stints_visual,
+        tyre_stints_end_laps,
+    })
+}
+
+fn ensure_final_classification_size(packet_format: u16, size: usize) -> Result<(), TelemetryError> {
+    let expected = match packet_format {
+        2021 => FINAL_CLASSIFICATION_2021_SIZE,
+        2022 => FINAL_CLASSIFICATION_2022_SIZE,
+        _ => FINAL_CLASSIFICATION_2023_SIZE,
+    };
+    if size == expected {
+        return Ok(());
+    }
+
+    Err(TelemetryError::SizeMismatch {
+        packet: "Final classification",
+        expected,
+        got: size,
+    })
+}
@@ -0,0 +1,173 @@
+use crate::error::{parse_duration, TelemetryError};
+use byteorder::{LittleEndian, ReadBytesExt};
+use std::io::Cursor;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, PartialOrd)]
+pub struct PacketHeader {
+    pub packet_format: u16,
+    /// Game year - last two digits e.g. 23, sent from F1 23 onwards
+    pub game_year: Option<u8>,
+    pub major_version: u8,
+    pub minor_version: u8,
+    pub packet_version: u8,
+    pub packet_id: u8,
+    pub session_uid: u64,
+    pub session_time: Duration,
+    pub frame_identifier: u32,
+    /// Overall identifier for the frame, doesn't go back after
+    /// flashbacks, sent from F1 23 onwards
+    pub overall_frame_identifier: Option<u32>,
+    pub player_car_index: u8,
+    pub secondary_player_car_index: u8,
+}
+
+const HEADER_MIN_SIZE: usize = 24;
+const HEADER_2023_MIN_SIZE: usize = 29;
+
+pub fn parse_headers(
+    cursor: &mut Cursor<&[u8]>,
+    size: usize,
+) -> Result<PacketHeader, TelemetryError> {
+    ensure_header_size(size, HEADER_MIN_SIZE)?;
+
+    let packet_format = cursor.read_u16::<LittleEndian>()?;
+    let game_year = if packet_format >= 2023 {
+        ensure_header_size(size, HEADER_2023_MIN_SIZE)?;
+        Some(cursor.read_u8()?)
+    } else {
+        None
+    };
+    let major_version = cursor.read_u8()?;
+    let minor_version = cursor.read_u8()?;
+    let packet_version = cursor.read_u8()?;
+    let packet_id = cursor.read_u8()?;
+    let session_uid = cursor.read_u64::<LittleEndian>()?;
+    let session_time = parse_duration("session_time", cursor.read_f32::<LittleEndian>()?)?;
+    let frame_identifier = cursor.read_u32::<LittleEndian>()?;
+    let overall_frame_identifier = if packet_format >= 2023 {
+        Some(cursor.read_u32::<LittleEndian>()?)
+    } else {
+        None
+    };
+    let player_car_index = cursor.read_u8()?;
+    let secondary_player_car_index = cursor.read_u8()?;
+
+    Ok(PacketHeader {
+        packet_format,
+        game_year,
+        major_version,
+        minor_version,
+        packet_version,
+        packet_id,
+        session_uid,
+        session_time,
+        frame_identifier,
+        overall_frame_identifier,
+        player_car_index,
+        secondary_player_car_index,
+    })
+}
+
+fn ensure_header_size(size: usize, min_size: usize) -> Result<(), TelemetryError> {
+    if size < min_size {
+        return Err(TelemetryError::SizeMismatch {
+            packet: "Header",
+            expected: min_size,
+            got: size,
+        });
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::error::TelemetryError;
+    use crate::f1_2021::header::parse_headers;
+    use std::io::Cursor;
+
+    use byteorder::{LittleEndian, WriteBytesExt};
+
+    #[test]
+    fn parse_header_error() {
+        let mut buf = Vec::with_capacity(1);
+        buf.write_u16::<LittleEndian>(0).unwrap();
+
+        let mut cursor = Cursor::new(buf.as_slice());
+        let len = cursor.get_ref().len();
+
+        let result = parse_headers(&mut cursor, len);
+        assert!(matches!(
+            result,
+            Err(TelemetryError::SizeMismatch {
+                packet: "Header",
+                expected: 24,
+                got: 2
+            })
+        ));
+    }
+
+    #[test]
+    fn parse_header_success() {
+        let mut buf = Vec::with_capacity(2048);
+        buf.write_u16::<LittleEndian>(2021).unwrap();
+        buf.write_u8(1).unwrap();
+        buf.write_u8(2).unwrap();
+        buf.write_u8(3).unwrap();
+        buf.write_u8(0).unwrap();
+        buf.write_u64::<LittleEndian>(u64::MAX).unwrap();
+        buf.write_f32::<LittleEndian>(1.0).unwrap();
+        buf.write_u32::<LittleEndian>(u32::MAX).unwrap();
+        buf.write_u8(19).unwrap();
+        buf.write_u8(255).unwrap();
+
+        let mut cursor = Cursor::new(buf.as_slice());
+        let len = cursor.get_mut().len();
+        let result = parse_headers(&mut cursor, len).unwrap();
+
+        assert_eq!(2021, result.packet_format);
+        assert_eq!(1, result.major_version);
+        assert_eq!(2, result.minor_version);
+        assert_eq!(3, result.packet_version);
+        assert_eq!(0, result.packet_id);
+        assert_eq!(u64::MAX, result.session_uid);
+        assert_eq!(1, result.session_time.as_secs());
+        assert_eq!(1000, result.session_time.as_millis());
+        assert_eq!(u32::MAX, result.frame_identifier);
+        assert_eq!(19, result.player_car_index);
+        assert_eq!(255, result.secondary_player_car_index);
+        assert_eq!(None, result.game_year);
+        assert_eq!(None, result.overall_frame_identifier);
+    }
+
+    #[test]
+    fn parse_header_2023_success() {
+        let mut buf = Vec::with_capacity(2048);
+        buf.write_u16::<LittleEndian>(2023).unwrap();
+        buf.write_u8(23).unwrap();
+        buf.write_u8(1).unwrap();
+        buf.write_u8(2).unwrap();
+        buf.write_u8(3).unwrap();
+        buf.write_u8(0).unwrap();
+        buf.write_u64::<LittleEndian>(u64::MAX).unwrap();
+        buf.write_f32::<LittleEndian>(1.0).unwrap();
+        buf.write_u32::<LittleEndian>(10).unwrap();
+        buf.write_u32::<LittleEndian>(42).unwrap();
+        buf.write_u8(19).unwrap();
+        buf.write_u8(255).unwrap();
+
+        let mut cursor = Cursor::new(buf.as_slice());
+        let len = cursor.get_mut().len();
+        let result = parse_headers(&mut cursor, len).unwrap();
+
+        assert_eq!(2023, result.packet_format);
+        assert_eq!(Some(23), result.game_year);
+        assert_eq!(1, result.major_version);
+        assert_eq!(10, result.frame_identifier);
+        assert_eq!(Some(42), result.overall_frame_identifier);
+        assert_eq!(19, result.player_car_index);
+        assert_eq!(255, result.secondary_player_car_index);
+        assert_eq!(29, cursor.position());
+    }
+}
@@ -0,0 +1,249 @@
+use crate::decoder::Decoder;
+use crate::error::TelemetryError;
+use crate::f1_2020::lap::{
+    parse_driver_status, parse_pit_status, parse_result_status, DriverStatus, PitStatus,
+    ResultStatus,
+};
+use crate::f1_2021::car::TOTAL_CARS;
+use crate::f1_2021::header::PacketHeader;
+use byteorder::{LittleEndian, ReadBytesExt};
+use derivative::Derivative;
+use std::io::Cursor;
+use std::time::Duration;
+
+const LAP_DATA_2021_SIZE: usize = 970;
+const LAP_DATA_2022_SIZE: usize = 972;
+const LAP_DATA_2023_SIZE: usize = 1131;
+const LAP_DATA_2024_SIZE: usize = 1285;
+
+#[derive(Debug, PartialEq, Clone, Copy, Derivative)]
+#[derivative(Eq)]
+pub struct LapData {
+    /// Last lap time in milliseconds
+    pub last_lap_time: Duration,
+    /// Current time around the lap in milliseconds
+    pub current_lap_time: Duration,
+    /// Sector 1 time in milliseconds (minutes part added from F1 23)
+    pub sector_1_time: Duration,
+    /// Sector 2 time in milliseconds (minutes part added from F1 23)
+    pub sector_2_time: Duration,
+    /// Time delta to car in front, sent from F1 23 onwards
+    pub delta_to_car_in_front: Option<Duration>,
+    /// Time delta to race leader, sent from F1 23 onwards
+    pub delta_to_race_leader: Option<Duration>,
+    /// Distance vehicle is around current lap in metres – could
+    /// be negative if line hasn’t been crossed yet
+    pub lap_distance: f32,
+    /// Total distance travelled in session in metres – could
+    /// be negative if line hasn’t been crossed yet
+    pub total_distance: f32,
+    /// Delta in seconds for safety car
+    pub safety_car_delta: f32,
+    pub car_position: u8,
+    pub current_lap_num: u8,
+    pub pit_status: PitStatus,
+    pub num_pit_stops: u8,
+    pub sector: u8,
+    pub current_lap_invalid: bool,
+    /// Accumulated time penalties in seconds to be added
+    pub penalties: u8,
+    /// Accumulated number of warnings issued
+    pub warnings: u8,
+    /// Accumulated number of corner cutting warnings issued,
+    /// sent from F1 23 onwards
+    pub corner_cutting_warnings: Option<u8>,
+    /// Num drive through pens left to serve
+    pub num_unserved_drive_through_pens: u8,
+    /// Num stop go pens left to serve
+    pub num_unserved_stop_go_pens: u8,
+    pub grid_position: u8,
+    pub driver_status: DriverStatus,
+    pub result_status: ResultStatus,
+    /// Pit lane timing, 0 = inactive, 1 = active
+    pub pit_lane_timer_active: bool,
+    /// If active, the current time spent in the pit lane in ms
+    pub pit_lane_time_in_lane: Duration,
+    /// Time of the actual pit stop in ms
+    pub pit_stop_timer: Duration,
+    /// Whether the car should serve a penalty at this stop
+    pub pit_stop_should_serve_pen: bool,
+    /// Fastest speed through speed trap for this car in kmph,
+    /// sent from F1 24 onwards
+    pub speed_trap_fastest_speed: Option<f32>,
+    /// Lap no the fastest speed was achieved, 255 = not set,
+    /// sent from F1 24 onwards
+    pub speed_trap_fastest_lap: Option<u8>,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct PacketLapData {
+    pub header: PacketHeader,
+    pub lap_data: Vec<LapData>,
+    /// Index of Personal Best car in time trial (255 if invalid),
+    /// sent from F1 22 onwards
+    pub time_trial_pb_car_index: Option<u8>,
+    /// Index of Rival car in time trial (255 if invalid),
+    /// sent from F1 22 onwards
+    pub time_trial_rival_car_index: Option<u8>,
+}
+
+pub fn parse_lap_data(
+    cursor: &mut Cursor<&[u8]>,
+    header: PacketHeader,
+    size: usize,
+    decoder: &mut Decoder,
+) -> Result<PacketLapData, TelemetryError> {
+    ensure_lap_data_size(header.packet_format, size)?;
+
+    let mut laps = Vec::with_capacity(TOTAL_CARS);
+    for _ in 0..TOTAL_CARS {
+        let lap = parse_lap(cursor, header.packet_format, decoder)?;
+        laps.push(lap);
+    }
+
+    let (time_trial_pb_car_index, time_trial_rival_car_index) = if header.packet_format >= 2022 {
+        (Some(cursor.read_u8()?), Some(cursor.read_u8()?))
+    } else {
+        (None, None)
+    };
+
+    Ok(PacketLapData {
+        header,
+        lap_data: laps,
+        time_trial_pb_car_index,
+        time_trial_rival_car_index,
+    })
+}
+
+fn parse_lap(
+    cursor: &mut Cursor<&[u8]>,
+    packet_format: u16,
+    decoder: &mut Decoder,
+) -> Result<LapData, TelemetryError> {
+    let last_lap_time = Duration::from_millis(cursor.read_u32::<LittleEndian>()? as u64);
+    let current_lap_time = Duration::from_millis(cursor.read_u32::<LittleEndian>()? as u64);
+    let sector_1_time = parse_sector_time(cursor, packet_format)?;
+    let sector_2_time = parse_sector_time(cursor, packet_format)?;
+    let (delta_to_car_in_front, delta_to_race_leader) = if packet_format >= 2023 {
+        (
+            Some(parse_delta_time(cursor, packet_format)?),
+            Some(parse_delta_time(cursor, packet_format)?),
+        )
+    } else {
+        (None, None)
+    };
+    let lap_distance = cursor.read_f32::<LittleEndian>()?;
+    let total_distance = cursor.read_f32::<LittleEndian>()?;
+    let safety_car_delta = cursor.read_f32::<LittleEndian>()?;
+    let car_position = cursor.read_u8()?;
+    let current_lap_num = cursor.read_u8()?;
+    let pit_status = parse_pit_status(cursor, decoder)?;
+    let num_pit_stops = cursor.read_u8()?;
+    let sector = cursor.read_u8()?;
+    let current_lap_invalid = cursor.read_u8()? == 1;
+    let penalties = cursor.read_u8()?;
+    let warnings = cursor.read_u8()?;
+    let corner_cutting_warnings = if packet_format >= 2023 {
+        Some(cursor.read_u8()?)
+    } else {
+        None
+    };
+    let num_unserved_drive_through_pens = cursor.read_u8()?;
+    let num_unserved_stop_go_pens = cursor.read_u8()?;
+    let grid_position = cursor.read_u8()?;
+    let driver_status = parse_driver_status(cursor, decoder)?;
+    let result_status = parse_result_status(cursor, decoder)?;
+    let pit_lane_timer_active = cursor.read_u8()? == 1;
+    let pit_lane_time_in_lane = Duration::from_millis(cursor.read_u16::<LittleEndian>()? as u64);
+    let pit_stop_timer = Duration::from_millis(cursor.read_u16::<LittleEndian>()? as u64);
+    let pit_stop_should_serve_pen = cursor.read_u8()? == 1;
+    let (speed_trap_fastest_speed, speed_trap_fastest_lap) = if packet_format >= 2024 {
+        (
+            Some(cursor.read_f32::<LittleEndian>()?),
+            Some(cursor.read_u8()?),
+        )
+    } else {
+        (None, None)
+    };
+
+    Ok(LapData {
+        last_lap_time,
+        current_lap_time,
+        sector_1_time,
+        sector_2_time,
+        delta_to_car_in_front,
+        delta_to_race_leader,
+        lap_distance,
+        total_distance,
+        safety_car_delta,
+        car_position,
+        current_lap_num,
+        pit_status,
+        num_pit_stops,
+        sector,
+        current_lap_invalid,
+        penalties,
+        warnings,
+        corner_cutting_warnings,
+        num_unserved_drive_through_pens,
+        num_unserved_stop_go_pens,
+        grid_position,
+        driver_status,
+        result_status,
+        pit_lane_timer_active,
+        pit_lane_time_in_lane,
+        pit_stop_timer,
+        pit_stop_should_serve_pen,
+        speed_trap_fastest_speed,
+        speed_trap_fastest_lap,
+    })
+}
+
+/// Sector times are a milliseconds part, followed by a minutes part
+/// from F1 23 onwards
+pub fn parse_sector_time(
+    cursor: &mut Cursor<&[u8]>,
+    packet_format: u16,
+) -> Result<Duration, TelemetryError> {
+    let millis = cursor.read_u16::<LittleEndian>()? as u64;
+    let minutes = if packet_format >= 2023 {
+        cursor.read_u8()? as u64
+    } else {
+        0
+    };
+
+    Ok(Duration::from_millis(minutes * 60_000 + millis))
+}
+
+/// Deltas are in milliseconds, with a minutes part from F1 24 onwards
+fn parse_delta_time(
+    cursor: &mut Cursor<&[u8]>,
+    packet_format: u16,
+) -> Result<Duration, TelemetryError> {
+    let millis = cursor.read_u16::<LittleEndian>()? as u64;
+    let minutes = if packet_format >= 2024 {
+        cursor.read_u8()? as u64
+    } else {
+        0
+    };
+
+    Ok(Duration::from_millis(minutes * 60_000 + millis))
+}
+
+fn ensure_lap_data_size(packet_format: u16, size: usize) -> Result<(), TelemetryError> {
+    let expected = match packet_format {
+        2021 => LAP_DATA_2021_SIZE,
+        2022 => LAP_DATA_2022_SIZE,
+        2023 => LAP_DATA_2023_SIZE,
+        _ => LAP_DATA_2024_SIZE,
+    };
+    if size < expected {
+        return Err(TelemetryError::SizeMismatch {
+            packet: "Lap data",
+            expected,
+            got: size,
+        });
+    }
+
+    Ok(())
+}
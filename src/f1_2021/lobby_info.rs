@@ -0,0 +1,123 @@
+use crate::decoder::Decoder;
+use crate::error::TelemetryError;
+use std::io::Cursor;
+
+use crate::f1_2020::lobby_info::{parse_ready_status, ReadyStatus};
+use crate::f1_2020::nationality::Nationality;
+use crate::f1_2020::participants::{
+    parse_name, parse_nationality, parse_your_telemetry, YourTelemetry,
+};
+use crate::f1_2021::car::TOTAL_CARS;
+use crate::f1_2021::header::PacketHeader;
+use byteorder::{LittleEndian, ReadBytesExt};
+
+const LOBBY_INFO_2021_SIZE: usize = 1191;
+const LOBBY_INFO_2023_SIZE: usize = 1218;
+const LOBBY_INFO_2024_SIZE: usize = 1306;
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct LobbyInfoData {
+    pub ai_controlled: bool,
+    /// Team id - see appendix (255 if no team currently selected)
+    pub team_id: u8,
+    pub nationality: Nationality,
+    /// 1 = Steam, 3 = PlayStation, 4 = Xbox, 6 = Origin, 255 = unknown,
+    /// sent from F1 23 onwards
+    pub platform: Option<u8>,
+    pub name: String,
+    pub car_number: u8,
+    /// The player's UDP setting, sent from F1 24 onwards
+    pub your_telemetry: Option<YourTelemetry>,
+    /// The player's show online names setting, sent from F1 24 onwards
+    pub show_online_names: Option<bool>,
+    /// F1 World tech level, sent from F1 24 onwards
+    pub tech_level: Option<u16>,
+    pub ready_status: ReadyStatus,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct PacketLobbyInfoData {
+    pub header: PacketHeader,
+    pub num_players: u8,
+    pub lobby_info_data: Vec<LobbyInfoData>,
+}
+
+pub fn parse_lobby_info_data(
+    cursor: &mut Cursor<&[u8]>,
+    header: PacketHeader,
+    size: usize,
+    decoder: &mut Decoder,
+) -> Result<PacketLobbyInfoData, TelemetryError> {
+    ensure_lobby_info_size(header.packet_format, size)?;
+
+    let num_players = cursor.read_u8()?;
+
+    let mut lobby_info_data = Vec::with_capacity(TOTAL_CARS);
+    for _ in 0..TOTAL_CARS {
+        let data = parse_lobby_info(cursor, header.packet_format, decoder)?;
+        lobby_info_data.push(data);
+    }
+
+    Ok(PacketLobbyInfoData {
+        header,
+        num_players,
+        lobby_info_data,
+    })
+}
+
+pub fn parse_lobby_info(
+    cursor: &mut Cursor<&[u8]>,
+    packet_format: u16,
+    decoder: &mut Decoder,
+) -> Result<LobbyInfoData, TelemetryError> {
+    let ai_controlled = cursor.read_u8()? == 1;
+    let team_id = cursor.read_u8()?;
+    let nationality = parse_nationality(cursor.read_u8()?, decoder)?;
+    let platform = if packet_format >= 2023 {
+        Some(cursor.read_u8()?)
+    } else {
+        None
+    };
+    let name = parse_name(cursor)?;
+    let car_number = cursor.read_u8()?;
+    let (your_telemetry, show_online_names, tech_level) = if packet_format >= 2024 {
+        (
+            Some(parse_your_telemetry(cursor.read_u8()?, decoder)?),
+            Some(cursor.read_u8()? == 1),
+            Some(cursor.read_u16::<LittleEndian>()?),
+        )
+    } else {
+        (None, None, None)
+    };
+    let ready_status = parse_ready_status(cursor.read_u8()?, decoder)?;
+
+    Ok(LobbyInfoData {
+        ai_controlled,
+        team_id,
+        nationality,
+        platform,
+        name,
+        car_number,
+        your_telemetry,
+        show_online_names,
+        tech_level,
+        ready_status,
+    })
+}
+
+fn ensure_lobby_info_size(packet_format: u16, size: usize) -> Result<(), TelemetryError> {
+    let expected = match packet_format {
+        2021 | 2022 => LOBBY_INFO_2021_SIZE,
+        2023 => LOBBY_INFO_2023_SIZE,
+        _ => LOBBY_INFO_2024_SIZE,
+    };
+    if size == expected {
+        return Ok(());
+    }
+
+    Err(TelemetryError::SizeMismatch {
+        packet: "Lobby info",
+        expected,
+        got: size,
+    })
+}
@@ -0,0 +1,244 @@
+use crate::decoder::Decoder;
+use crate::error::TelemetryError;
+use crate::f1_2021::car::{
+    PacketCarDamageData, PacketCarSetupData, PacketCarStatusData, PacketCarTelemetryData,
+};
+use crate::f1_2021::car_damage::parse_car_damage_data;
+use crate::f1_2021::car_setup::parse_car_setup_data;
+use crate::f1_2021::car_status::parse_car_status_data;
+use crate::f1_2021::car_telemetry::parse_car_telemetry_data;
+use crate::f1_2021::event::{parse_event_data, PacketEventData};
+use crate::f1_2021::final_classification::{
+    parse_final_classification_data, PacketFinalClassificationData,
+};
+use crate::f1_2021::header::parse_headers;
+use crate::f1_2021::lap::{parse_lap_data, PacketLapData};
+use crate::f1_2021::lobby_info::{parse_lobby_info_data, PacketLobbyInfoData};
+use crate::f1_2021::motion::{parse_motion_data, PacketMotionData};
+use crate::f1_2021::participants::{parse_participants_data, PacketParticipantsData};
+use crate::f1_2021::session::{parse_session, PacketSessionData};
+use crate::f1_2021::session_history::{parse_session_history_data, PacketSessionHistoryData};
+use std::io::Cursor;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum PacketID {
+    Motion,
+    Session,
+    LapData,
+    Event,
+    Participants,
+    CarSetups,
+    CarTelemetry,
+    CarStatus,
+    FinalClassification,
+    LobbyInfo,
+    CarDamage,
+    SessionHistory,
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum Packet2021 {
+    Motion(PacketMotionData),
+    Session(PacketSessionData),
+    Lap(PacketLapData),
+    Event(PacketEventData),
+    Participants(PacketParticipantsData),
+    CarSetups(PacketCarSetupData),
+    CarTelemetry(PacketCarTelemetryData),
+    CarStatus(PacketCarStatusData),
+    FinalClassification(PacketFinalClassificationData),
+    LobbyInfo(PacketLobbyInfoData),
+    CarDamage(PacketCarDamageData),
+    SessionHistory(PacketSessionHistoryData),
+}
+
+pub fn parse_f12021(
+    cursor: &mut Cursor<&[u8]>,
+    size: usize,
+    decoder: &mut Decoder,
+) -> Result<Packet2021, TelemetryError> {
+    let header = parse_headers(cursor, size)?;
+    match packet_type(header.packet_id)? {
+        PacketID::Motion => {
+            let motion = parse_motion_data(cursor, header, size)?;
+            Ok(Packet2021::Motion(motion))
+        }
+        PacketID::Session => {
+            let session = parse_session(cursor, header, size, decoder)?;
+            Ok(Packet2021::Session(session))
+        }
+        PacketID::LapData => {
+            let lap = parse_lap_data(cursor, header, size, decoder)?;
+            Ok(Packet2021::Lap(lap))
+        }
+        PacketID::Event => {
+            let event = parse_event_data(cursor, header, size, decoder)?;
+            Ok(Packet2021::Event(event))
+        }
+        PacketID::Participants => {
+            let participants = parse_participants_data(cursor, header, size, decoder)?;
+            Ok(Packet2021::Participants(participants))
+        }
+        PacketID::CarSetups => {
+            let car_setups = parse_car_setup_data(cursor, header, size)?;
+            Ok(Packet2021::CarSetups(car_setups))
+        }
+        PacketID::CarTelemetry => {
+            let car_telemetry = parse_car_telemetry_data(cursor, header, size, decoder)?;
+            Ok(Packet2021::CarTelemetry(car_telemetry))
+        }
+        PacketID::CarStatus => {
+            let car_status = parse_car_status_data(cursor, header, size, decoder)?;
+            Ok(Packet2021::CarStatus(car_status))
+        }
+        PacketID::FinalClassification => {
+            let final_classification =
+                parse_final_classification_data(cursor, header, size, decoder)?;
+            Ok(Packet2021::FinalClassification(final_classification))
+        }
+        PacketID::LobbyInfo => {
+            let lobby_info = parse_lobby_info_data(cursor, header, size, decoder)?;
+            Ok(Packet2021::LobbyInfo(lobby_info))
+        }
+        PacketID::CarDamage => {
+            let car_damage = parse_car_damage_data(cursor, header, size)?;
+            Ok(Packet2021::CarDamage(car_damage))
+        }
+        PacketID::SessionHistory => {
+            let session_history = parse_session_history_data(cursor, header, size, decoder)?;
+            Ok(Packet2021::SessionHistory(session_history))
+        }
+    }
+}
+
+pub fn packet_type(packet_id: u8) -> Result<PacketID, TelemetryError> {
+    match packet_id {
+        0 => Ok(PacketID::Motion),
+        1 => Ok(PacketID::Session),
+        2 => Ok(PacketID::LapData),
+        3 => Ok(PacketID::Event),
+        4 => Ok(PacketID::Participants),
+        5 => Ok(PacketID::CarSetups),
+        6 => Ok(PacketID::CarTelemetry),
+        7 => Ok(PacketID::CarStatus),
+        8 => Ok(PacketID::FinalClassification),
+        9 => Ok(PacketID::LobbyInfo),
+        10 => Ok(PacketID::CarDamage),
+        11 => Ok(PacketID::SessionHistory),
+        _ => Err(TelemetryError::UnknownPacketId { id: packet_id }),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::decoder::{DecodeMode, Decoder};
+    use crate::error::TelemetryError;
+    use crate::f1_2021::event::Event;
+    use crate::f1_2021::packet::{parse_f12021, Packet2021};
+    use std::io::Cursor;
+
+    use byteorder::{LittleEndian, WriteBytesExt};
+
+    fn header(packet_id: u8) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(2048);
+        buf.write_u16::<LittleEndian>(2021).unwrap();
+        buf.write_u8(1).unwrap();
+        buf.write_u8(3).unwrap();
+        buf.write_u8(1).unwrap();
+        buf.write_u8(packet_id).unwrap();
+        buf.write_u64::<LittleEndian>(7).unwrap();
+        buf.write_f32::<LittleEndian>(12.5).unwrap();
+        buf.write_u32::<LittleEndian>(100).unwrap();
+        buf.write_u8(0).unwrap();
+        buf.write_u8(255).unwrap();
+        buf
+    }
+
+    #[test]
+    fn parse_unknown_event_code() {
+        let mut buf = header(3);
+        buf.extend_from_slice(b"NEWE");
+        buf.resize(36, 0);
+
+        let result = parse_f12021(
+            &mut Cursor::new(buf.as_slice()),
+            36,
+            &mut Decoder::default(),
+        );
+        assert!(matches!(
+            result,
+            Err(TelemetryError::InvalidEnumValue {
+                field: "event_code",
+                ..
+            })
+        ));
+
+        let mut decoder = Decoder::new(DecodeMode::Lenient);
+        match parse_f12021(&mut Cursor::new(buf.as_slice()), 36, &mut decoder).unwrap() {
+            Packet2021::Event(event) => assert_eq!(Event::Unknown(*b"NEWE"), event.event),
+            p => panic!("Unexpected packet {:?}", p),
+        }
+        assert_eq!(1, decoder.substitutions());
+    }
+
+    #[test]
+    fn parse_car_damage_packet() {
+        let mut buf = header(10);
+        for car in 0..22 {
+            buf.write_f32::<LittleEndian>(car as f32).unwrap();
+            buf.write_f32::<LittleEndian>(0.0).unwrap();
+            buf.write_f32::<LittleEndian>(0.0).unwrap();
+            buf.write_f32::<LittleEndian>(0.0).unwrap();
+            buf.extend_from_slice(&[0; 8]);
+            buf.extend_from_slice(&[car, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0]);
+        }
+
+        let mut cursor = Cursor::new(buf.as_slice());
+        let len = cursor.get_ref().len();
+        assert_eq!(882, len);
+        let result = parse_f12021(&mut cursor, len, &mut Decoder::default()).unwrap();
+
+        match result {
+            Packet2021::CarDamage(damage) => {
+                assert_eq!(22, damage.car_damage_data.len());
+                assert_eq!(21.0, damage.car_damage_data[21].tyres_wear.rear_left);
+                assert_eq!(21, damage.car_damage_data[21].front_left_wing_damage);
+                assert!(damage.car_damage_data[21].drs_fault);
+            }
+            p => panic!("Unexpected packet {:?}", p),
+        }
+    }
+
+    #[test]
+    fn parse_session_history_packet() {
+        let mut buf = header(11);
+        buf.extend_from_slice(&[3, 2, 1, 1, 1, 2, 1]);
+        for lap in 0..100u32 {
+            buf.write_u32::<LittleEndian>(90_000 + lap).unwrap();
+            buf.write_u16::<LittleEndian>(30_000).unwrap();
+            buf.write_u16::<LittleEndian>(30_000).unwrap();
+            buf.write_u16::<LittleEndian>(30_000).unwrap();
+            buf.write_u8(0x0f).unwrap();
+        }
+        for _ in 0..8 {
+            buf.extend_from_slice(&[255, 18, 18]);
+        }
+
+        let mut cursor = Cursor::new(buf.as_slice());
+        let len = cursor.get_ref().len();
+        assert_eq!(1155, len);
+        let result = parse_f12021(&mut cursor, len, &mut Decoder::default()).unwrap();
+
+        match result {
+            Packet2021::SessionHistory(history) => {
+                assert_eq!(3, history.car_index);
+                assert_eq!(2, history.lap_history_data.len());
+                assert_eq!(90_001, history.lap_history_data[1].lap_time.as_millis());
+                assert!(history.lap_history_data[1].lap_valid());
+                assert_eq!(1, history.tyre_stints_history_data.len());
+                assert_eq!(len as u64, cursor.position());
+            }
+            p => panic!("Unexpected packet {:?}", p),
+        }
+    }
+}
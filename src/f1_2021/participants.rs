@@ -0,0 +1,141 @@
+use crate::decoder::Decoder;
+use crate::error::TelemetryError;
+use crate::f1_2020::nationality::Nationality;
+use crate::f1_2020::participants::{
+    parse_name, parse_nationality, parse_your_telemetry, YourTelemetry,
+};
+use crate::f1_2021::car::TOTAL_CARS;
+use crate::f1_2021::header::PacketHeader;
+use byteorder::{LittleEndian, ReadBytesExt};
+use std::io::Cursor;
+
+const PARTICIPANTS_2021_SIZE: usize = 1257;
+const PARTICIPANTS_2023_SIZE: usize = 1306;
+const PARTICIPANTS_2024_SIZE: usize = 1350;
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct ParticipantData {
+    // Whether the vehicle is AI (1) or Human (0) controlled
+    pub ai_controlled: bool, // u8,
+    // Driver id - see appendix, 255 if network human
+    pub driver_id: u8,
+    // Network id – unique identifier for network players
+    pub network_id: u8,
+    // Team id - see appendix
+    pub team_id: u8,
+    // My team flag – 1 = My Team, 0 = otherwise
+    pub my_team: bool, // u8,
+    // Race number of the car
+    pub race_number: u8,
+    // Nationality of the driver
+    pub nationality: Nationality, // u8,
+    // Name of participant in UTF-8 format – null terminated
+    // Will be truncated with … (U+2026) if too long
+    pub name: String,
+    // The player's UDP setting, 0 = restricted, 1 = public
+    pub your_telemetry: YourTelemetry, // u8,
+    // The player's show online names setting, 0 = off, 1 = on,
+    // sent from F1 23 onwards
+    pub show_online_names: Option<bool>, // u8,
+    // F1 World tech level, sent from F1 24 onwards
+    pub tech_level: Option<u16>,
+    // 1 = Steam, 3 = PlayStation, 4 = Xbox, 6 = Origin, 255 = unknown,
+    // sent from F1 23 onwards
+    pub platform: Option<u8>,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct PacketParticipantsData {
+    // Header
+    pub header: PacketHeader,
+    // Number of active cars in the data – should match number of
+    // cars on HUD
+    pub num_active_cars: u8,
+    pub participants: Vec<ParticipantData>,
+}
+
+pub fn parse_participants_data(
+    cursor: &mut Cursor<&[u8]>,
+    header: PacketHeader,
+    size: usize,
+    decoder: &mut Decoder,
+) -> Result<PacketParticipantsData, TelemetryError> {
+    ensure_participants_size(header.packet_format, size)?;
+
+    let num_active_cars = cursor.read_u8()?;
+
+    let mut participants = Vec::with_capacity(TOTAL_CARS);
+    for _ in 0..TOTAL_CARS {
+        let participant = parse_participant(cursor, header.packet_format, decoder)?;
+        participants.push(participant);
+    }
+
+    Ok(PacketParticipantsData {
+        header,
+        num_active_cars,
+        participants,
+    })
+}
+
+fn ensure_participants_size(packet_format: u16, size: usize) -> Result<(), TelemetryError> {
+    let expected = match packet_format {
+        2021 | 2022 => PARTICIPANTS_2021_SIZE,
+        2023 => PARTICIPANTS_2023_SIZE,
+        _ => PARTICIPANTS_2024_SIZE,
+    };
+    if size < expected {
+        return Err(TelemetryError::SizeMismatch {
+            packet: "Participants",
+            expected,
+            got: size,
+        });
+    }
+
+    Ok(())
+}
+
+fn parse_participant(
+    cursor: &mut Cursor<&[u8]>,
+    packet_format: u16,
+    decoder: &mut Decoder,
+) -> Result<ParticipantData, TelemetryError> {
+    let ai_controlled = cursor.read_u8()? == 1;
+    let driver_id = cursor.read_u8()?;
+    let network_id = cursor.read_u8()?;
+    let team_id = cursor.read_u8()?;
+    let my_team = cursor.read_u8()? == 1;
+    let race_number = cursor.read_u8()?;
+    let nationality = parse_nationality(cursor.read_u8()?, decoder)?;
+    let name = parse_name(cursor)?;
+    let your_telemetry = parse_your_telemetry(cursor.read_u8()?, decoder)?;
+    let show_online_names = if packet_format >= 2023 {
+        Some(cursor.read_u8()? == 1)
+    } else {
+        None
+    };
+    let tech_level = if packet_format >= 2024 {
+        Some(cursor.read_u16::<LittleEndian>()?)
+    } else {
+        None
+    };
+    let platform = if packet_format >= 2023 {
+        Some(cursor.read_u8()?)
+    } else {
+        None
+    };
+
+    Ok(ParticipantData {
+        ai_controlled,
+        driver_id,
+        network_id,
+        team_id,
+        my_team,
+        race_number,
+        nationality,
+        name,
+        your_telemetry,
+        show_online_names,
+        tech_level,
+        platform,
+    })
+}
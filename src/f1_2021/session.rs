@@ -0,0 +1,623 @@
+use crate::decoder::Decoder;
+use crate::error::TelemetryError;
+use crate::f1_2020::session::{
+    parse_flag, parse_formula as parse_formula_2020, parse_network_game,
+    parse_safety_car as parse_safety_car_2020, parse_track as parse_track_2020, parse_weather,
+    Formula, MarshalZone, NetworkGame, SafetyCar, SessionType, Track, Weather,
+};
+use crate::f1_2021::header::PacketHeader;
+use byteorder::{LittleEndian, ReadBytesExt};
+use derivative::Derivative;
+use std::io::Cursor;
+
+const SESSION_2021_SIZE: usize = 625;
+const SESSION_2022_SIZE: usize = 632;
+const SESSION_2023_SIZE: usize = 644;
+const SESSION_2024_SIZE: usize = 753;
+const MARSHAL_ZONE_MAX: usize = 21;
+const MARSHAL_ZONE_SIZE: usize = 5;
+const WEATHER_FORECAST_SAMPLE_MAX: usize = 56;
+const WEATHER_FORECAST_SAMPLE_2024_MAX: usize = 64;
+const WEATHER_FORECAST_SAMPLE_SIZE: usize = 8;
+const WEEKEND_STRUCTURE_MAX: usize = 12;
+
+#[derive(Debug, Eq, PartialOrd, PartialEq, Clone)]
+pub enum TemperatureChange {
+    Up, // 0
+    Down,
+    NoChange,
+    Unknown(i8),
+}
+
+#[derive(Debug, Eq, PartialOrd, PartialEq, Clone)]
+pub enum ForecastAccuracy {
+    Perfect, // 0
+    Approximate,
+    Unknown(u8),
+}
+
+#[derive(Debug, PartialOrd, Eq, PartialEq, Clone)]
+pub struct WeatherForecastSample {
+    /// 0 = unknown, 1 = P1, 2 = P2, 3 = P3, 4 = Short P, 5 = Q1
+    /// 6 = Q2, 7 = Q3, 8 = Short Q, 9 = OSQ, 10 = R, 11 = R2
+    /// 12 = R3, 13 = Time Trial (renumbered in F1 24, see appendix)
+    pub session_type: SessionType, // u8,
+    /// Time in minutes the forecast is for
+    pub time_offset: u8,
+    /// Weather - 0 = clear, 1 = light cloud, 2 = overcast, 3 = light rain, 4 = heavy rain, 5 = storm
+    pub weather: Weather, //u8,
+    /// Track temp. in degrees celsius
+    pub track_temperature: i8,
+    /// Track temp. change – 0 = up, 1 = down, 2 = no change
+    pub track_temperature_change: TemperatureChange, // i8,
+    /// Air temp. in degrees celsius
+    pub air_temperature: i8,
+    /// Air temp. change – 0 = up, 1 = down, 2 = no change
+    pub air_temperature_change: TemperatureChange, // i8,
+    /// Rain percentage (0-100)
+    pub rain_percentage: u8,
+}
+
+#[derive(Debug, PartialEq, Clone, Derivative)]
+#[derivative(Eq)]
+pub struct PacketSessionData {
+    /// Header
+    pub header: PacketHeader,
+    /// Weather - 0 = clear, 1 = light cloud, 2 = overcast, 3 = light rain, 4 = heavy rain, 5 = storm
+    pub weather: Weather, //u8,
+    /// Track temp. in degrees celsius
+    pub track_temperature: i8,
+    /// Air temp. in degrees celsius
+    pub air_temperature: i8,
+    /// Total number of laps in this race
+    pub total_laps: u8,
+    /// Track length in metres
+    pub track_length: u16,
+    /// 0 = unknown, 1 = P1, 2 = P2, 3 = P3, 4 = Short P
+    /// 5 = Q1, 6 = Q2, 7 = Q3, 8 = Short Q, 9 = OSQ
+    /// 10 = R, 11 = R2, 12 = R3, 13 = Time Trial
+    /// (renumbered in F1 24 to add sprint shootouts, see appendix)
+    pub session_type: SessionType, //u8,
+    /// -1 for unknown, see appendix
+    pub track_id: Track, //i8,
+    /// Formula, 0 = F1 Modern, 1 = F1 Classic, 2 = F2,
+    /// 3 = F1 Generic, 4 = Beta, 5 = Supercars, 6 = Esports,
+    /// 7 = F2 2021, 8 = F1 World, 9 = F1 Elimination
+    pub formula: Formula, // u8,
+    /// Time left in session in seconds
+    pub session_time_left: u16,
+    /// Session duration in seconds
+    pub session_duration: u16,
+    /// Pit speed limit in kilometres per hour
+    pub pit_speed_limit: u8,
+    /// Whether the game is paused
+    pub game_paused: u8,
+    /// Whether the player is spectating
+    pub is_spectating: u8,
+    /// Index of the car being spectated
+    pub spectator_car_index: u8,
+    /// SLI Pro support, 0 = inactive, 1 = active
+    pub sli_pro_native_support: u8,
+    /// Number of marshal zones to follow
+    pub num_marshal_zones: u8,
+    /// List of marshal zones – max 21
+    pub marshal_zone: Vec<MarshalZone>,
+    /// 0 = no safety car, 1 = full safety car
+    /// 2 = virtual safety car, 3 = formation lap
+    pub safety_car_status: SafetyCar, //u8,
+    /// 0 = offline, 1 = online
+    pub network_game: NetworkGame, // u8,
+    /// Number of weather samples to follow
+    pub num_weather_forecast_samples: u8,
+    /// Array of weather forecast samples
+    pub weather_forecast_sample: Vec<WeatherForecastSample>,
+    /// 0 = Perfect, 1 = Approximate
+    pub forecast_accuracy: ForecastAccuracy, // u8,
+    /// AI Difficulty rating – 0-110
+    pub ai_difficulty: u8,
+    /// Identifier for season - persists across saves
+    pub season_link_identifier: u32,
+    /// Identifier for weekend - persists across saves
+    pub weekend_link_identifier: u32,
+    /// Identifier for session - persists across saves
+    pub session_link_identifier: u32,
+    /// Ideal lap to pit on for current strategy (player)
+    pub pit_stop_window_ideal_lap: u8,
+    /// Latest lap to pit on for current strategy (player)
+    pub pit_stop_window_latest_lap: u8,
+    /// Predicted position to rejoin at (player)
+    pub pit_stop_rejoin_position: u8,
+    /// 0 = off, 1 = on
+    pub steering_assist: u8,
+    /// 0 = off, 1 = low, 2 = medium, 3 = high
+    pub braking_assist: u8,
+    /// 1 = manual, 2 = manual & suggested gear, 3 = auto
+    pub gearbox_assist: u8,
+    /// 0 = off, 1 = on
+    pub pit_assist: u8,
+    /// 0 = off, 1 = on
+    pub pit_release_assist: u8,
+    /// 0 = off, 1 = on
+    pub ers_assist: u8,
+    /// 0 = off, 1 = on
+    pub drs_assist: u8,
+    /// 0 = off, 1 = corners only, 2 = full
+    pub dynamic_racing_line: u8,
+    /// 0 = 2D, 1 = 3D
+    pub dynamic_racing_line_type: u8,
+    /// Game mode id - see appendix, sent from F1 22 onwards
+    pub game_mode: Option<u8>,
+    /// Ruleset - see appendix, sent from F1 22 onwards
+    pub rule_set: Option<u8>,
+    /// Local time of day - minutes since midnight, sent from F1 22 onwards
+    pub time_of_day: Option<u32>,
+    /// 0 = None, 2 = Very Short, 3 = Short, 4 = Medium
+    /// 5 = Medium Long, 6 = Long, 7 = Full, sent from F1 22 onwards
+    pub session_length: Option<u8>,
+    /// 0 = MPH, 1 = KPH, sent from F1 23 onwards
+    pub speed_units_lead_player: Option<u8>,
+    /// 0 = Celsius, 1 = Fahrenheit, sent from F1 23 onwards
+    pub temperature_units_lead_player: Option<u8>,
+    /// 0 = MPH, 1 = KPH, sent from F1 23 onwards
+    pub speed_units_secondary_player: Option<u8>,
+    /// 0 = Celsius, 1 = Fahrenheit, sent from F1 23 onwards
+    pub temperature_units_secondary_player: Option<u8>,
+    /// Number of safety cars called during session, sent from F1 23 onwards
+    pub num_safety_car_periods: Option<u8>,
+    /// Number of virtual safety cars called, sent from F1 23 onwards
+    pub num_virtual_safety_car_periods: Option<u8>,
+    /// Number of red flags called during session, sent from F1 23 onwards
+    pub num_red_flag_periods: Option<u8>,
+    /// 0 = Off, 1 = On, sent from F1 24 onwards
+    pub equal_car_performance: Option<u8>,
+    /// 0 = None, 1 = Flashbacks, 2 = Auto-recovery
+    pub recovery_mode: Option<u8>,
+    /// 0 = Low, 1 = Medium, 2 = High, 3 = Unlimited
+    pub flashback_limit: Option<u8>,
+    /// 0 = Simplified, 1 = Realistic
+    pub surface_type: Option<u8>,
+    /// 0 = Easy, 1 = Hard
+    pub low_fuel_mode: Option<u8>,
+    /// 0 = Manual, 1 = Assisted
+    pub race_starts: Option<u8>,
+    /// 0 = Surface only, 1 = Surface & Carcass
+    pub tyre_temperature: Option<u8>,
+    /// 0 = On, 1 = Off
+    pub pit_lane_tyre_sim: Option<u8>,
+    /// 0 = Off, 1 = Reduced, 2 = Standard, 3 = Simulation
+    pub car_damage: Option<u8>,
+    /// 0 = Reduced, 1 = Standard, 2 = Simulation
+    pub car_damage_rate: Option<u8>,
+    /// 0 = Off, 1 = Player-to-Player Off, 2 = On
+    pub collisions: Option<u8>,
+    /// 0 = Disabled, 1 = Enabled
+    pub collisions_off_for_first_lap_only: Option<u8>,
+    /// 0 = On, 1 = Off (Multiplayer)
+    pub mp_unsafe_pit_release: Option<u8>,
+    /// 0 = Disabled, 1 = Enabled (Multiplayer)
+    pub mp_off_for_griefing: Option<u8>,
+    /// 0 = Regular, 1 = Strict
+    pub corner_cutting_stringency: Option<u8>,
+    /// 0 = Off, 1 = On
+    pub parc_ferme_rules: Option<u8>,
+    /// 0 = Automatic, 1 = Broadcast, 2 = Immersive
+    pub pit_stop_experience: Option<u8>,
+    /// Safety car setting, 0 = Off, 1 = Reduced, 2 = Standard, 3 = Increased
+    pub safety_car: Option<u8>,
+    /// 0 = Broadcast, 1 = Immersive
+    pub safety_car_experience: Option<u8>,
+    /// 0 = Off, 1 = On
+    pub formation_lap: Option<u8>,
+    /// 0 = Broadcast, 1 = Immersive
+    pub formation_lap_experience: Option<u8>,
+    /// 0 = Off, 1 = Reduced, 2 = Standard, 3 = Increased
+    pub red_flags: Option<u8>,
+    /// 0 = Off, 1 = On
+    pub affects_licence_level_solo: Option<u8>,
+    /// 0 = Off, 1 = On
+    pub affects_licence_level_mp: Option<u8>,
+    /// Number of session in following array
+    pub num_sessions_in_weekend: Option<u8>,
+    /// List of session types to show weekend structure
+    pub weekend_structure: Option<Vec<SessionType>>,
+    /// Distance in m around track where sector 2 starts
+    pub sector_2_lap_distance_start: Option<f32>,
+    /// Distance in m around track where sector 3 starts
+    pub sector_3_lap_distance_start: Option<f32>,
+}
+
+pub fn parse_session(
+    cursor: &mut Cursor<&[u8]>,
+    header: PacketHeader,
+    size: usize,
+    decoder: &mut Decoder,
+) -> Result<PacketSessionData, TelemetryError> {
+    ensure_session_size(header.packet_format, size)?;
+    let packet_format = header.packet_format;
+
+    let weather = parse_weather(cursor.read_u8()?, decoder)?;
+    let track_temperature = cursor.read_i8()?;
+    let air_temperature = cursor.read_i8()?;
+    let total_laps = cursor.read_u8()?;
+    let track_length = cursor.read_u16::<LittleEndian>()?;
+    let session_type = parse_session_type(packet_format, cursor.read_u8()?, decoder)?;
+    let track_id = parse_track(cursor.read_i8()?, decoder)?;
+    let formula = parse_formula(cursor.read_u8()?, decoder)?;
+    let session_time_left = cursor.read_u16::<LittleEndian>()?;
+    let session_duration = cursor.read_u16::<LittleEndian>()?;
+    let pit_speed_limit = cursor.read_u8()?;
+    let game_paused = cursor.read_u8()?;
+    let is_spectating = cursor.read_u8()?;
+    let spectator_car_index = cursor.read_u8()?;
+    let sli_pro_native_support = cursor.read_u8()?;
+
+    // The marshal zone and forecast arrays are fixed size on the wire, only
+    // the first `num_*` entries are meaningful.
+    let num_marshal_zones = cursor.read_u8()?;
+    let marshal_zone_start = cursor.position();
+    let mut marshal_zone = Vec::with_capacity(MARSHAL_ZONE_MAX);
+    for _ in 0..(num_marshal_zones as usize).min(MARSHAL_ZONE_MAX) {
+        let zone_start = cursor.read_f32::<LittleEndian>()?;
+        let zone_flag = parse_flag(cursor.read_i8()?, decoder)?;
+        marshal_zone.push(MarshalZone {
+            zone_start,
+            zone_flag,
+        });
+    }
+    cursor.set_position(marshal_zone_start + (MARSHAL_ZONE_MAX * MARSHAL_ZONE_SIZE) as u64);
+
+    let safety_car_status = parse_safety_car(cursor.read_u8()?, decoder)?;
+    let network_game = parse_network_game(cursor.read_u8()?, decoder)?;
+
+    let weather_forecast_sample_max = if packet_format >= 2024 {
+        WEATHER_FORECAST_SAMPLE_2024_MAX
+    } else {
+        WEATHER_FORECAST_SAMPLE_MAX
+    };
+    let num_weather_forecast_samples = cursor.read_u8()?;
+    let weather_forecast_start = cursor.position();
+    let mut weather_forecast_sample = Vec::with_capacity(weather_forecast_sample_max);
+    for _ in 0..(num_weather_forecast_samples as usize).min(weather_forecast_sample_max) {
+        let weather_forecast = parse_weather_forecast_sample(cursor, packet_format, decoder)?;
+        weather_forecast_sample.push(weather_forecast);
+    }
+    cursor.set_position(
+        weather_forecast_start
+            + (weather_forecast_sample_max * WEATHER_FORECAST_SAMPLE_SIZE) as u64,
+    );
+
+    let forecast_accuracy = parse_forecast_accuracy(cursor.read_u8()?, decoder)?;
+    let ai_difficulty = cursor.read_u8()?;
+    let season_link_identifier = cursor.read_u32::<LittleEndian>()?;
+    let weekend_link_identifier = cursor.read_u32::<LittleEndian>()?;
+    let session_link_identifier = cursor.read_u32::<LittleEndian>()?;
+    let pit_stop_window_ideal_lap = cursor.read_u8()?;
+    let pit_stop_window_latest_lap = cursor.read_u8()?;
+    let pit_stop_rejoin_position = cursor.read_u8()?;
+    let steering_assist = cursor.read_u8()?;
+    let braking_assist = cursor.read_u8()?;
+    let gearbox_assist = cursor.read_u8()?;
+    let pit_assist = cursor.read_u8()?;
+    let pit_release_assist = cursor.read_u8()?;
+    let ers_assist = cursor.read_u8()?;
+    let drs_assist = cursor.read_u8()?;
+    let dynamic_racing_line = cursor.read_u8()?;
+    let dynamic_racing_line_type = cursor.read_u8()?;
+
+    let since_2022 = packet_format >= 2022;
+    let game_mode = parse_optional_u8(cursor, since_2022)?;
+    let rule_set = parse_optional_u8(cursor, since_2022)?;
+    let time_of_day = if since_2022 {
+        Some(cursor.read_u32::<LittleEndian>()?)
+    } else {
+        None
+    };
+    let session_length = parse_optional_u8(cursor, since_2022)?;
+
+    let since_2023 = packet_format >= 2023;
+    let speed_units_lead_player = parse_optional_u8(cursor, since_2023)?;
+    let temperature_units_lead_player = parse_optional_u8(cursor, since_2023)?;
+    let speed_units_secondary_player = parse_optional_u8(cursor, since_2023)?;
+    let temperature_units_secondary_player = parse_optional_u8(cursor, since_2023)?;
+    let num_safety_car_periods = parse_optional_u8(cursor, since_2023)?;
+    let num_virtual_safety_car_periods = parse_optional_u8(cursor, since_2023)?;
+    let num_red_flag_periods = parse_optional_u8(cursor, since_2023)?;
+
+    let since_2024 = packet_format >= 2024;
+    let equal_car_performance = parse_optional_u8(cursor, since_2024)?;
+    let recovery_mode = parse_optional_u8(cursor, since_2024)?;
+    let flashback_limit = parse_optional_u8(cursor, since_2024)?;
+    let surface_type = parse_optional_u8(cursor, since_2024)?;
+    let low_fuel_mode = parse_optional_u8(cursor, since_2024)?;
+    let race_starts = parse_optional_u8(cursor, since_2024)?;
+    let tyre_temperature = parse_optional_u8(cursor, since_2024)?;
+    let pit_lane_tyre_sim = parse_optional_u8(cursor, since_2024)?;
+    let car_damage = parse_optional_u8(cursor, since_2024)?;
+    let car_damage_rate = parse_optional_u8(cursor, since_2024)?;
+    let collisions = parse_optional_u8(cursor, since_2024)?;
+    let collisions_off_for_first_lap_only = parse_optional_u8(cursor, since_2024)?;
+    let mp_unsafe_pit_release = parse_optional_u8(cursor, since_2024)?;
+    let mp_off_for_griefing = parse_optional_u8(cursor, since_2024)?;
+    let corner_cutting_stringency = parse_optional_u8(cursor, since_2024)?;
+    let parc_ferme_rules = parse_optional_u8(cursor, since_2024)?;
+    let pit_stop_experience = parse_optional_u8(cursor, since_2024)?;
+    let safety_car = parse_optional_u8(cursor, since_2024)?;
+    let safety_car_experience = parse_optional_u8(cursor, since_2024)?;
+    let formation_lap = parse_optional_u8(cursor, since_2024)?;
+    let formation_lap_experience = parse_optional_u8(cursor, since_2024)?;
+    let red_flags = parse_optional_u8(cursor, since_2024)?;
+    let affects_licence_level_solo = parse_optional_u8(cursor, since_2024)?;
+    let affects_licence_level_mp = parse_optional_u8(cursor, since_2024)?;
+    let num_sessions_in_weekend = parse_optional_u8(cursor, since_2024)?;
+    let weekend_structure = match num_sessions_in_weekend {
+        Some(num_sessions) => {
+            let weekend_structure_start = cursor.position();
+            let mut sessions = Vec::with_capacity(WEEKEND_STRUCTURE_MAX);
+            for _ in 0..(num_sessions as usize).min(WEEKEND_STRUCTURE_MAX) {
+                let session = parse_session_type(packet_format, cursor.read_u8()?, decoder)?;
+                sessions.push(session);
+            }
+            cursor.set_position(weekend_structure_start + WEEKEND_STRUCTURE_MAX as u64);
+            Some(sessions)
+        }
+        None => None,
+    };
+    let (sector_2_lap_distance_start, sector_3_lap_distance_start) = if since_2024 {
+        (
+            Some(cursor.read_f32::<LittleEndian>()?),
+            Some(cursor.read_f32::<LittleEndian>()?),
+        )
+    } else {
+        (None, None)
+    };
+
+    Ok(PacketSessionData {
+        header,
+        weather,
+        track_temperature,
+        air_temperature,
+        total_laps,
+        track_length,
+        session_type,
+        track_id,
+        formula,
+        session_time_left,
+        session_duration,
+        pit_speed_limit,
+        game_paused,
+        is_spectating,
+        spectator_car_index,
+        sli_pro_native_support,
+        num_marshal_zones,
+        marshal_zone,
+        safety_car_status,
+        network_game,
+        num_weather_forecast_samples,
+        weather_forecast_sample,
+        forecast_accuracy,
+        ai_difficulty,
+        season_link_identifier,
+        weekend_link_identifier,
+        session_link_identifier,
+        pit_stop_window_ideal_lap,
+        pit_stop_window_latest_lap,
+        pit_stop_rejoin_position,
+        steering_assist,
+        braking_assist,
+        gearbox_assist,
+        pit_assist,
+        pit_release_assist,
+        ers_assist,
+        drs_assist,
+        dynamic_racing_line,
+        dynamic_racing_line_type,
+        game_mode,
+        rule_set,
+        time_of_day,
+        session_length,
+        speed_units_lead_player,
+        temperature_units_lead_player,
+        speed_units_secondary_player,
+        temperature_units_secondary_player,
+        num_safety_car_periods,
+        num_virtual_safety_car_periods,
+        num_red_flag_periods,
+        equal_car_performance,
+        recovery_mode,
+        flashback_limit,
+        surface_type,
+        low_fuel_mode,
+        race_starts,
+        tyre_temperature,
+        pit_lane_tyre_sim,
+        car_damage,
+        car_damage_rate,
+        collisions,
+        collisions_off_for_first_lap_only,
+        mp_unsafe_pit_release,
+        mp_off_for_griefing,
+        corner_cutting_stringency,
+        parc_ferme_rules,
+        pit_stop_experience,
+        safety_car,
+        safety_car_experience,
+        formation_lap,
+        formation_lap_experience,
+        red_flags,
+        affects_licence_level_solo,
+        affects_licence_level_mp,
+        num_sessions_in_weekend,
+        weekend_structure,
+        sector_2_lap_distance_start,
+        sector_3_lap_distance_start,
+    })
+}
+
+fn parse_optional_u8(
+    cursor: &mut Cursor<&[u8]>,
+    present: bool,
+) -> Result<Option<u8>, TelemetryError> {
+    if present {
+        Ok(Some(cursor.read_u8()?))
+    } else {
+        Ok(None)
+    }
+}
+
+fn ensure_session_size(packet_format: u16, size: usize) -> Result<(), TelemetryError> {
+    let expected = match packet_format {
+        2021 => SESSION_2021_SIZE,
+        2022 => SESSION_2022_SIZE,
+        2023 => SESSION_2023_SIZE,
+        _ => SESSION_2024_SIZE,
+    };
+    if size == expected {
+        return Ok(());
+    }
+
+    Err(TelemetryError::SizeMismatch {
+        packet: "Session",
+        expected,
+        got: size,
+    })
+}
+
+pub fn parse_session_type(
+    packet_format: u16,
+    value: u8,
+    decoder: &mut Decoder,
+) -> Result<SessionType, TelemetryError> {
+    if packet_format >= 2024 {
+        return parse_session_type_2024(value, decoder);
+    }
+
+    match value {
+        0 => Ok(SessionType::Unknown(value)),
+        1 => Ok(SessionType::P1),
+        2 => Ok(SessionType::P2),
+        3 => Ok(SessionType::P3),
+        4 => Ok(SessionType::ShortP),
+        5 => Ok(SessionType::Q1),
+        6 => Ok(SessionType::Q2),
+        7 => Ok(SessionType::Q3),
+        8 => Ok(SessionType::ShortQ),
+        9 => Ok(SessionType::OSQ),
+        10 => Ok(SessionType::R),
+        11 => Ok(SessionType::R2),
+        12 => Ok(SessionType::R3),
+        13 => Ok(SessionType::TimeTrial),
+        _ => decoder.unknown("session_type", value, SessionType::Unknown(value)),
+    }
+}
+
+fn parse_session_type_2024(
+    value: u8,
+    decoder: &mut Decoder,
+) -> Result<SessionType, TelemetryError> {
+    match value {
+        0 => Ok(SessionType::Unknown(value)),
+        1 => Ok(SessionType::P1),
+        2 => Ok(SessionType::P2),
+        3 => Ok(SessionType::P3),
+        4 => Ok(SessionType::ShortP),
+        5 => Ok(SessionType::Q1),
+        6 => Ok(SessionType::Q2),
+        7 => Ok(SessionType::Q3),
+        8 => Ok(SessionType::ShortQ),
+        9 => Ok(SessionType::OSQ),
+        10 => Ok(SessionType::SprintShootout1),
+        11 => Ok(SessionType::SprintShootout2),
+        12 => Ok(SessionType::SprintShootout3),
+        13 => Ok(SessionType::ShortSprintShootout),
+        14 => Ok(SessionType::OneShotSprintShootout),
+        15 => Ok(SessionType::R),
+        16 => Ok(SessionType::R2),
+        17 => Ok(SessionType::R3),
+        18 => Ok(SessionType::TimeTrial),
+        _ => decoder.unknown("session_type", value, SessionType::Unknown(value)),
+    }
+}
+
+pub fn parse_formula(value: u8, decoder: &mut Decoder) -> Result<Formula, TelemetryError> {
+    match value {
+        4 => Ok(Formula::Beta),
+        5 => Ok(Formula::Supercars),
+        6 => Ok(Formula::Esports),
+        7 => Ok(Formula::F2Season2021),
+        8 => Ok(Formula::F1World),
+        9 => Ok(Formula::F1Elimination),
+        _ => parse_formula_2020(value, decoder),
+    }
+}
+
+pub fn parse_track(value: i8, decoder: &mut Decoder) -> Result<Track, TelemetryError> {
+    match value {
+        27 => Ok(Track::Imola),
+        28 => Ok(Track::Portimao),
+        29 => Ok(Track::Jeddah),
+        30 => Ok(Track::Miami),
+        31 => Ok(Track::LasVegas),
+        32 => Ok(Track::Losail),
+        _ => parse_track_2020(value, decoder),
+    }
+}
+
+pub fn parse_safety_car(value: u8, decoder: &mut Decoder) -> Result<SafetyCar, TelemetryError> {
+    match value {
+        3 => Ok(SafetyCar::FormationLap),
+        _ => parse_safety_car_2020(value, decoder),
+    }
+}
+
+pub fn parse_temperature_change(
+    value: i8,
+    decoder: &mut Decoder,
+) -> Result<TemperatureChange, TelemetryError> {
+    match value {
+        0 => Ok(TemperatureChange::Up),
+        1 => Ok(TemperatureChange::Down),
+        2 => Ok(TemperatureChange::NoChange),
+        _ => decoder.unknown(
+            "temperature_change",
+            value,
+            TemperatureChange::Unknown(value),
+        ),
+    }
+}
+
+fn parse_forecast_accuracy(
+    value: u8,
+    decoder: &mut Decoder,
+) -> Result<ForecastAccuracy, TelemetryError> {
+    match value {
+        0 => Ok(ForecastAccuracy::Perfect),
+        1 => Ok(ForecastAccuracy::Approximate),
+        _ => decoder.unknown("forecast_accuracy", value, ForecastAccuracy::Unknown(value)),
+    }
+}
+
+fn parse_weather_forecast_sample(
+    cursor: &mut Cursor<&[u8]>,
+    packet_format: u16,
+    decoder: &mut Decoder,
+) -> Result<WeatherForecastSample, TelemetryError> {
+    let session_type = parse_session_type(packet_format, cursor.read_u8()?, decoder)?;
+    let time_offset = cursor.read_u8()?;
+    let weather = parse_weather(cursor.read_u8()?, decoder)?;
+    let track_temperature = cursor.read_i8()?;
+    let track_temperature_change = parse_temperature_change(cursor.read_i8()?, decoder)?;
+    let air_temperature = cursor.read_i8()?;
+    let air_temperature_change = parse_temperature_change(cursor.read_i8()?, decoder)?;
+    let rain_percentage = cursor.read_u8()?;
+
+    Ok(WeatherForecastSample {
+        session_type,
+        time_offset,
+        weather,
+        track_temperature,
+        track_temperature_change,
+        air_temperature,
+        air_temperature_change,
+        rain_percentage,
+    })
+}
@@ -0,0 +1,184 @@
+use crate::decoder::Decoder;
+use crate::error::TelemetryError;
+use byteorder::{LittleEndian, ReadBytesExt};
+use std::io::Cursor;
+use std::time::Duration;
+
+use crate::f1_2020::car::{ActualTyreCompound, VisualTyreCompound};
+use crate::f1_2021::car_status::{parse_actual_tyre_compound, parse_visual_tyre_compound};
+use crate::f1_2021::header::PacketHeader;
+use crate::f1_2021::lap::parse_sector_time;
+
+const SESSION_HISTORY_2021_SIZE: usize = 1155;
+const SESSION_HISTORY_2023_SIZE: usize = 1460;
+const LAP_HISTORY_MAX: usize = 100;
+const LAP_HISTORY_2021_SIZE: usize = 11;
+const LAP_HISTORY_2023_SIZE: usize = 14;
+const TYRE_STINT_HISTORY_MAX: usize = 8;
+const TYRE_STINT_HISTORY_SIZE: usize = 3;
+
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub struct LapHistoryData {
+    /// Lap time in milliseconds
+    pub lap_time: Duration,
+    /// Sector 1 time in milliseconds (minutes part added from F1 23)
+    pub sector_1_time: Duration,
+    /// Sector 2 time in milliseconds (minutes part added from F1 23)
+    pub sector_2_time: Duration,
+    /// Sector 3 time in milliseconds (minutes part added from F1 23)
+    pub sector_3_time: Duration,
+    /// 0x01 bit set-lap valid, 0x02 bit set-sector 1 valid
+    /// 0x04 bit set-sector 2 valid, 0x08 bit set-sector 3 valid
+    pub lap_valid_bit_flags: u8,
+}
+
+impl LapHistoryData {
+    pub fn lap_valid(&self) -> bool {
+        self.lap_valid_bit_flags & 0x01 != 0
+    }
+
+    pub fn sector_1_valid(&self) -> bool {
+        self.lap_valid_bit_flags & 0x02 != 0
+    }
+
+    pub fn sector_2_valid(&self) -> bool {
+        self.lap_valid_bit_flags & 0x04 != 0
+    }
+
+    pub fn sector_3_valid(&self) -> bool {
+        self.lap_valid_bit_flags & 0x08 != 0
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub struct TyreStintHistoryData {
+    /// Lap the tyre usage ends on (255 of current tyre)
+    pub end_lap: u8,
+    pub tyre_actual_compound: ActualTyreCompound,
+    pub tyre_visual_compound: VisualTyreCompound,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct PacketSessionHistoryData {
+    pub header: PacketHeader,
+    /// Index of the car this lap data relates to
+    pub car_index: u8,
+    /// Num laps in the data (including current partial lap)
+    pub num_laps: u8,
+    /// Number of tyre stints in the data
+    pub num_tyre_stints: u8,
+    /// Lap the best lap time was achieved on
+    pub best_lap_time_lap_num: u8,
+    /// Lap the best Sector 1 time was achieved on
+    pub best_sector_1_lap_num: u8,
+    /// Lap the best Sector 2 time was achieved on
+    pub best_sector_2_lap_num: u8,
+    /// Lap the best Sector 3 time was achieved on
+    pub best_sector_3_lap_num: u8,
+    /// Only the first `num_laps` entries of the wire array
+    pub lap_history_data: Vec<LapHistoryData>,
+    /// Only the first `num_tyre_stints` entries of the wire array
+    pub tyre_stints_history_data: Vec<TyreStintHistoryData>,
+}
+
+pub fn parse_session_history_data(
+    cursor: &mut Cursor<&[u8]>,
+    header: PacketHeader,
+    size: usize,
+    decoder: &mut Decoder,
+) -> Result<PacketSessionHistoryData, TelemetryError> {
+    ensure_session_history_size(header.packet_format, size)?;
+    let lap_history_size = if header.packet_format >= 2023 {
+        LAP_HISTORY_2023_SIZE
+    } else {
+        LAP_HISTORY_2021_SIZE
+    };
+
+    let car_index = cursor.read_u8()?;
+    let num_laps = cursor.read_u8()?;
+    let num_tyre_stints = cursor.read_u8()?;
+    let best_lap_time_lap_num = cursor.read_u8()?;
+    let best_sector_1_lap_num = cursor.read_u8()?;
+    let best_sector_2_lap_num = cursor.read_u8()?;
+    let best_sector_3_lap_num = cursor.read_u8()?;
+
+    let lap_history_start = cursor.position();
+    let mut lap_history_data = Vec::with_capacity(num_laps as usize);
+    for _ in 0..(num_laps as usize).min(LAP_HISTORY_MAX) {
+        let lap = parse_lap_history(cursor, header.packet_format)?;
+        lap_history_data.push(lap);
+    }
+    cursor.set_position(lap_history_start + (LAP_HISTORY_MAX * lap_history_size) as u64);
+
+    let tyre_stint_start = cursor.position();
+    let mut tyre_stints_history_data = Vec::with_capacity(num_tyre_stints as usize);
+    for _ in 0..(num_tyre_stints as usize).min(TYRE_STINT_HISTORY_MAX) {
+        let stint = parse_tyre_stint_history(cursor, decoder)?;
+        tyre_stints_history_data.push(stint);
+    }
+    cursor
+        .set_position(tyre_stint_start + (TYRE_STINT_HISTORY_MAX * TYRE_STINT_HISTORY_SIZE) as u64);
+
+    Ok(PacketSessionHistoryData {
+        header,
+        car_index,
+        num_laps,
+        num_tyre_stints,
+        best_lap_time_lap_num,
+        best_sector_1_lap_num,
+        best_sector_2_lap_num,
+        best_sector_3_lap_num,
+        lap_history_data,
+        tyre_stints_history_data,
+    })
+}
+
+fn parse_lap_history(
+    cursor: &mut Cursor<&[u8]>,
+    packet_format: u16,
+) -> Result<LapHistoryData, TelemetryError> {
+    let lap_time = Duration::from_millis(cursor.read_u32::<LittleEndian>()? as u64);
+    let sector_1_time = parse_sector_time(cursor, packet_format)?;
+    let sector_2_time = parse_sector_time(cursor, packet_format)?;
+    let sector_3_time = parse_sector_time(cursor, packet_format)?;
+    let lap_valid_bit_flags = cursor.read_u8()?;
+
+    Ok(LapHistoryData {
+        lap_time,
+        sector_1_time,
+        sector_2_time,
+        sector_3_time,
+        lap_valid_bit_flags,
+    })
+}
+
+fn parse_tyre_stint_history(
+    cursor: &mut Cursor<&[u8]>,
+    decoder: &mut Decoder,
+) -> Result<TyreStintHistoryData, TelemetryError> {
+    let end_lap = cursor.read_u8()?;
+    let tyre_actual_compound = parse_actual_tyre_compound(cursor.read_u8()?, decoder)?;
+    let tyre_visual_compound = parse_visual_tyre_compound(cursor.read_u8()?, decoder)?;
+
+    Ok(TyreStintHistoryData {
+        end_lap,
+        tyre_actual_compound,
+        tyre_visual_compound,
+    })
+}
+
+fn ensure_session_history_size(packet_format: u16, size: usize) -> Result<(), TelemetryError> {
+    let expected = match packet_format {
+        2021 | 2022 => SESSION_HISTORY_2021_SIZE,
+        _ => SESSION_HISTORY_2023_SIZE,
+    };
+    if size == expected {
+        return Ok(());
+    }
+
+    Err(TelemetryError::SizeMismatch {
+        packet: "Session history",
+        expected,
+        got: size,
+    })
+}
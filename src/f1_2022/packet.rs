@@ -0,0 +1,181 @@
+use crate::decoder::Decoder;
+use crate::error::TelemetryError;
+use crate::f1_2021::car::{
+    PacketCarDamageData, PacketCarSetupData, PacketCarStatusData, PacketCarTelemetryData,
+};
+use crate::f1_2021::car_damage::parse_car_damage_data;
+use crate::f1_2021::car_setup::parse_car_setup_data;
+use crate::f1_2021::car_status::parse_car_status_data;
+use crate::f1_2021::car_telemetry::parse_car_telemetry_data;
+use crate::f1_2021::event::{parse_event_data, PacketEventData};
+use crate::f1_2021::final_classification::{
+    parse_final_classification_data, PacketFinalClassificationData,
+};
+use crate::f1_2021::header::parse_headers;
+use crate::f1_2021::lap::{parse_lap_data, PacketLapData};
+use crate::f1_2021::lobby_info::{parse_lobby_info_data, PacketLobbyInfoData};
+use crate::f1_2021::motion::{parse_motion_data, PacketMotionData};
+use crate::f1_2021::participants::{parse_participants_data, PacketParticipantsData};
+use crate::f1_2021::session::{parse_session, PacketSessionData};
+use crate::f1_2021::session_history::{parse_session_history_data, PacketSessionHistoryData};
+use std::io::Cursor;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum PacketID {
+    Motion,
+    Session,
+    LapData,
+    Event,
+    Participants,
+    CarSetups,
+    CarTelemetry,
+    CarStatus,
+    FinalClassification,
+    LobbyInfo,
+    CarDamage,
+    SessionHistory,
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum Packet2022 {
+    Motion(PacketMotionData),
+    Session(PacketSessionData),
+    Lap(PacketLapData),
+    Event(PacketEventData),
+    Participants(PacketParticipantsData),
+    CarSetups(PacketCarSetupData),
+    CarTelemetry(PacketCarTelemetryData),
+    CarStatus(PacketCarStatusData),
+    FinalClassification(PacketFinalClassificationData),
+    LobbyInfo(PacketLobbyInfoData),
+    CarDamage(PacketCarDamageData),
+    SessionHistory(PacketSessionHistoryData),
+}
+
+pub fn parse_f12022(
+    cursor: &mut Cursor<&[u8]>,
+    size: usize,
+    decoder: &mut Decoder,
+) -> Result<Packet2022, TelemetryError> {
+    let header = parse_headers(cursor, size)?;
+    match packet_type(header.packet_id)? {
+        PacketID::Motion => {
+            let motion = parse_motion_data(cursor, header, size)?;
+            Ok(Packet2022::Motion(motion))
+        }
+        PacketID::Session => {
+            let session = parse_session(cursor, header, size, decoder)?;
+            Ok(Packet2022::Session(session))
+        }
+        PacketID::LapData => {
+            let lap = parse_lap_data(cursor, header, size, decoder)?;
+            Ok(Packet2022::Lap(lap))
+        }
+        PacketID::Event => {
+            let event = parse_event_data(cursor, header, size, decoder)?;
+            Ok(Packet2022::Event(event))
+        }
+        PacketID::Participants => {
+            let participants = parse_participants_data(cursor, header, size, decoder)?;
+            Ok(Packet2022::Participants(participants))
+        }
+        PacketID::CarSetups => {
+            let car_setups = parse_car_setup_data(cursor, header, size)?;
+            Ok(Packet2022::CarSetups(car_setups))
+        }
+        PacketID::CarTelemetry => {
+            let car_telemetry = parse_car_telemetry_data(cursor, header, size, decoder)?;
+            Ok(Packet2022::CarTelemetry(car_telemetry))
+        }
+        PacketID::CarStatus => {
+            let car_status = parse_car_status_data(cursor, header, size, decoder)?;
+            Ok(Packet2022::CarStatus(car_status))
+        }
+        PacketID::FinalClassification => {
+            let final_classification =
+                parse_final_classification_data(cursor, header, size, decoder)?;
+            Ok(Packet2022::FinalClassification(final_classification))
+        }
+        PacketID::LobbyInfo => {
+            let lobby_info = parse_lobby_info_data(cursor, header, size, decoder)?;
+            Ok(Packet2022::LobbyInfo(lobby_info))
+        }
+        PacketID::CarDamage => {
+            let car_damage = parse_car_damage_data(cursor, header, size)?;
+            Ok(Packet2022::CarDamage(car_damage))
+        }
+        PacketID::SessionHistory => {
+            let session_history = parse_session_history_data(cursor, header, size, decoder)?;
+            Ok(Packet2022::SessionHistory(session_history))
+        }
+    }
+}
+
+pub fn packet_type(packet_id: u8) -> Result<PacketID, TelemetryError> {
+    match packet_id {
+        0 => Ok(PacketID::Motion),
+        1 => Ok(PacketID::Session),
+        2 => Ok(PacketID::LapData),
+        3 => Ok(PacketID::Event),
+        4 => Ok(PacketID::Participants),
+        5 => Ok(PacketID::CarSetups),
+        6 => Ok(PacketID::CarTelemetry),
+        7 => Ok(PacketID::CarStatus),
+        8 => Ok(PacketID::FinalClassification),
+        9 => Ok(PacketID::LobbyInfo),
+        10 => Ok(PacketID::CarDamage),
+        11 => Ok(PacketID::SessionHistory),
+        _ => Err(TelemetryError::UnknownPacketId { id: packet_id }),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::decoder::Decoder;
+    use crate::f1_2021::event::Event;
+    use crate::f1_2022::packet::{parse_f12022, Packet2022};
+    use std::io::Cursor;
+
+    use byteorder::{LittleEndian, WriteBytesExt};
+
+    #[test]
+    fn parse_speed_trap_event_packet() {
+        let mut buf = Vec::with_capacity(2048);
+        buf.write_u16::<LittleEndian>(2022).unwrap();
+        buf.write_u8(1).unwrap();
+        buf.write_u8(3).unwrap();
+        buf.write_u8(1).unwrap();
+        buf.write_u8(3).unwrap();
+        buf.write_u64::<LittleEndian>(7).unwrap();
+        buf.write_f32::<LittleEndian>(12.5).unwrap();
+        buf.write_u32::<LittleEndian>(100).unwrap();
+        buf.write_u8(0).unwrap();
+        buf.write_u8(255).unwrap();
+        buf.extend_from_slice(b"SPTP");
+        buf.write_u8(4).unwrap();
+        buf.write_f32::<LittleEndian>(320.5).unwrap();
+        buf.write_u8(0).unwrap();
+        buf.write_u8(1).unwrap();
+        buf.write_u8(9).unwrap();
+        buf.write_f32::<LittleEndian>(331.0).unwrap();
+
+        let mut cursor = Cursor::new(buf.as_slice());
+        let len = cursor.get_ref().len();
+        assert_eq!(40, len);
+        let result = parse_f12022(&mut cursor, len, &mut Decoder::default()).unwrap();
+
+        match result {
+            Packet2022::Event(event) => match event.event {
+                Event::SpeedTrap(speed_trap) => {
+                    assert_eq!(4, speed_trap.vehicle_index);
+                    assert!(!speed_trap.overall_fastest_in_session);
+                    assert!(speed_trap.driver_fastest_in_session);
+                    assert_eq!(Some(9), speed_trap.fastest_vehicle_index_in_session);
+                    assert_eq!(Some(331.0), speed_trap.fastest_speed_in_session);
+                }
+                e => panic!("Unexpected event {:?}", e),
+            },
+            p => panic!("Unexpected packet {:?}", p),
+        }
+    }
+}
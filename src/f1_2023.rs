@@ -0,0 +1,4 @@
+pub mod motion;
+pub mod motion_ex;
+pub mod packet;
+pub mod tyre_sets;
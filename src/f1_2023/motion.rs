@@ -0,0 +1,49 @@
+use crate::error::TelemetryError;
+use crate::f1_2020::motion::{parse_car_motion, CarMotionData};
+use crate::f1_2021::car::TOTAL_CARS;
+use crate::f1_2021::header::PacketHeader;
+use std::io::Cursor;
+
+use derivative::Derivative;
+
+const MOTION_MIN_SIZE: usize = 1349;
+
+/// From F1 23 the motion packet only carries per car data, the player
+/// car extras moved to the Motion Ex packet
+#[derive(Debug, PartialEq, Clone, Derivative)]
+#[derivative(Eq)]
+pub struct PacketMotionData {
+    pub header: PacketHeader,
+    pub motion_data: Vec<CarMotionData>,
+}
+
+pub fn parse_motion_data(
+    cursor: &mut Cursor<&[u8]>,
+    header: PacketHeader,
+    size: usize,
+) -> Result<PacketMotionData, TelemetryError> {
+    ensure_motion_size(size)?;
+
+    let mut car_motion_data = Vec::with_capacity(TOTAL_CARS);
+    for _ in 0..TOTAL_CARS {
+        let car_motion = parse_car_motion(cursor)?;
+        car_motion_data.push(car_motion);
+    }
+
+    Ok(PacketMotionData {
+        header,
+        motion_data: car_motion_data,
+    })
+}
+
+fn ensure_motion_size(size: usize) -> Result<(), TelemetryError> {
+    if size < MOTION_MIN_SIZE {
+        return Err(TelemetryError::SizeMismatch {
+            packet: "Motion",
+            expected: MOTION_MIN_SIZE,
+            got: size,
+        });
+    }
+
+    Ok(())
+}
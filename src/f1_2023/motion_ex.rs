@@ -0,0 +1,152 @@
+use crate::error::TelemetryError;
+use crate::f1_2020::motion::Wheel;
+use crate::f1_2021::header::PacketHeader;
+use byteorder::{LittleEndian, ReadBytesExt};
+use std::io::Cursor;
+
+use derivative::Derivative;
+
+const MOTION_EX_2023_SIZE: usize = 217;
+const MOTION_EX_2024_SIZE: usize = 237;
+
+/// Extended motion data for the player car only
+#[derive(Debug, PartialEq, Clone, Derivative)]
+#[derivative(Eq)]
+pub struct PacketMotionExData {
+    pub header: PacketHeader,
+    pub suspension_position: Wheel<f32>,
+    pub suspension_velocity: Wheel<f32>,
+    pub suspension_acceleration: Wheel<f32>,
+    pub wheel_speed: Wheel<f32>,
+    pub wheel_slip_ratio: Wheel<f32>,
+    pub wheel_slip_angle: Wheel<f32>,
+    pub wheel_lat_force: Wheel<f32>,
+    pub wheel_long_force: Wheel<f32>,
+    /// Height of centre of gravity above ground
+    pub height_of_cog_above_ground: f32,
+    pub local_velocity_x: f32,
+    pub local_velocity_y: f32,
+    pub local_velocity_z: f32,
+    pub angular_velocity_x: f32,
+    pub angular_velocity_y: f32,
+    pub angular_velocity_z: f32,
+    pub angular_acceleration_x: f32,
+    pub angular_acceleration_y: f32,
+    pub angular_acceleration_z: f32,
+    /// Current front wheels angle in radians
+    pub front_wheels_angle: f32,
+    /// Vertical forces for each wheel
+    pub wheel_vert_force: Wheel<f32>,
+    /// Front plank edge height above road surface, sent from F1 24 onwards
+    pub front_aero_height: Option<f32>,
+    /// Rear plank edge height above road surface, sent from F1 24 onwards
+    pub rear_aero_height: Option<f32>,
+    /// Roll angle of the front suspension, sent from F1 24 onwards
+    pub front_roll_angle: Option<f32>,
+    /// Roll angle of the rear suspension, sent from F1 24 onwards
+    pub rear_roll_angle: Option<f32>,
+    /// Yaw angle of the chassis relative to the direction of motion - radians,
+    /// sent from F1 24 onwards
+    pub chassis_yaw: Option<f32>,
+}
+
+pub fn parse_motion_ex_data(
+    cursor: &mut Cursor<&[u8]>,
+    header: PacketHeader,
+    size: usize,
+) -> Result<PacketMotionExData, TelemetryError> {
+    ensure_motion_ex_size(header.packet_format, size)?;
+
+    let suspension_position = parse_wheel(cursor)?;
+    let suspension_velocity = parse_wheel(cursor)?;
+    let suspension_acceleration = parse_wheel(cursor)?;
+    let wheel_speed = parse_wheel(cursor)?;
+    let wheel_slip_ratio = parse_wheel(cursor)?;
+    let wheel_slip_angle = parse_wheel(cursor)?;
+    let wheel_lat_force = parse_wheel(cursor)?;
+    let wheel_long_force = parse_wheel(cursor)?;
+    let height_of_cog_above_ground = cursor.read_f32::<LittleEndian>()?;
+    let local_velocity_x = cursor.read_f32::<LittleEndian>()?;
+    let local_velocity_y = cursor.read_f32::<LittleEndian>()?;
+    let local_velocity_z = cursor.read_f32::<LittleEndian>()?;
+    let angular_velocity_x = cursor.read_f32::<LittleEndian>()?;
+    let angular_velocity_y = cursor.read_f32::<LittleEndian>()?;
+    let angular_velocity_z = cursor.read_f32::<LittleEndian>()?;
+    let angular_acceleration_x = cursor.read_f32::<LittleEndian>()?;
+    let angular_acceleration_y = cursor.read_f32::<LittleEndian>()?;
+    let angular_acceleration_z = cursor.read_f32::<LittleEndian>()?;
+    let front_wheels_angle = cursor.read_f32::<LittleEndian>()?;
+    let wheel_vert_force = parse_wheel(cursor)?;
+
+    let since_2024 = header.packet_format >= 2024;
+    let front_aero_height = parse_optional_f32(cursor, since_2024)?;
+    let rear_aero_height = parse_optional_f32(cursor, since_2024)?;
+    let front_roll_angle = parse_optional_f32(cursor, since_2024)?;
+    let rear_roll_angle = parse_optional_f32(cursor, since_2024)?;
+    let chassis_yaw = parse_optional_f32(cursor, since_2024)?;
+
+    Ok(PacketMotionExData {
+        header,
+        suspension_position,
+        suspension_velocity,
+        suspension_acceleration,
+        wheel_speed,
+        wheel_slip_ratio,
+        wheel_slip_angle,
+        wheel_lat_force,
+        wheel_long_force,
+        height_of_cog_above_ground,
+        local_velocity_x,
+        local_velocity_y,
+        local_velocity_z,
+        angular_velocity_x,
+        angular_velocity_y,
+        angular_velocity_z,
+        angular_acceleration_x,
+        angular_acceleration_y,
+        angular_acceleration_z,
+        front_wheels_angle,
+        wheel_vert_force,
+        front_aero_height,
+        rear_aero_height,
+        front_roll_angle,
+        rear_roll_angle,
+        chassis_yaw,
+    })
+}
+
+fn parse_wheel(cursor: &mut Cursor<&[u8]>) -> Result<Wheel<f32>, TelemetryError> {
+    Ok(Wheel {
+        rear_left: cursor.read_f32::<LittleEndian>()?,
+        rear_right: cursor.read_f32::<LittleEndian>()?,
+        front_left: cursor.read_f32::<LittleEndian>()?,
+        front_right: cursor.read_f32::<LittleEndian>()?,
+    })
+}
+
+fn parse_optional_f32(
+    cursor: &mut Cursor<&[u8]>,
+    present: bool,
+) -> Result<Option<f32>, TelemetryError> {
+    if present {
+        Ok(Some(cursor.read_f32::<LittleEndian>()?))
+    } else {
+        Ok(None)
+    }
+}
+
+fn ensure_motion_ex_size(packet_format: u16, size: usize) -> Result<(), TelemetryError> {
+    let expected = match packet_format {
+        2023 => MOTION_EX_2023_SIZE,
+        _ => MOTION_EX_2024_SIZE,
+    };
+    if size == expected {
+        return Ok(());
+    }
+
+    Err(TelemetryError::SizeMismatch {
+        packet: "Motion ex",
+        expected,
+        got: size,
+    })
+}
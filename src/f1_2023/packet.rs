@@ -0,0 +1,260 @@
+use crate::decoder::Decoder;
+use crate::error::TelemetryError;
+use crate::f1_2021::car::{
+    PacketCarDamageData, PacketCarSetupData, PacketCarStatusData, PacketCarTelemetryData,
+};
+use crate::f1_2021::car_damage::parse_car_damage_data;
+use crate::f1_2021::car_setup::parse_car_setup_data;
+use crate::f1_2021::car_status::parse_car_status_data;
+use crate::f1_2021::car_telemetry::parse_car_telemetry_data;
+use crate::f1_2021::event::{parse_event_data, PacketEventData};
+use crate::f1_2021::final_classification::{
+    parse_final_classification_data, PacketFinalClassificationData,
+};
+use crate::f1_2021::header::parse_headers;
+use crate::f1_2021::lap::{parse_lap_data, PacketLapData};
+use crate::f1_2021::lobby_info::{parse_lobby_info_data, PacketLobbyInfoData};
+use crate::f1_2021::participants::{parse_participants_data, PacketParticipantsData};
+use crate::f1_2021::session::{parse_session, PacketSessionData};
+use crate::f1_2021::session_history::{parse_session_history_data, PacketSessionHistoryData};
+use crate::f1_2023::motion::{parse_motion_data, PacketMotionData};
+use crate::f1_2023::motion_ex::{parse_motion_ex_data, PacketMotionExData};
+use crate::f1_2023::tyre_sets::{parse_tyre_sets_data, PacketTyreSetsData};
+use std::io::Cursor;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum PacketID {
+    Motion,
+    Session,
+    LapData,
+    Event,
+    Participants,
+    CarSetups,
+    CarTelemetry,
+    CarStatus,
+    FinalClassification,
+    LobbyInfo,
+    CarDamage,
+    SessionHistory,
+    TyreSets,
+    MotionEx,
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum Packet2023 {
+    Motion(PacketMotionData),
+    Session(PacketSessionData),
+    Lap(PacketLapData),
+    Event(PacketEventData),
+    Participants(PacketParticipantsData),
+    CarSetups(PacketCarSetupData),
+    CarTelemetry(PacketCarTelemetryData),
+    CarStatus(PacketCarStatusData),
+    FinalClassification(PacketFinalClassificationData),
+    LobbyInfo(PacketLobbyInfoData),
+    CarDamage(PacketCarDamageData),
+    SessionHistory(PacketSessionHistoryData),
+    TyreSets(PacketTyreSetsData),
+    MotionEx(PacketMotionExData),
+}
+
+pub fn parse_f12023(
+    cursor: &mut Cursor<&[u8]>,
+    size: usize,
+    decoder: &mut Decoder,
+) -> Result<Packet2023, TelemetryError> {
+    let header = parse_headers(cursor, size)?;
+    match packet_type(header.packet_id)? {
+        PacketID::Motion => {
+            let motion = parse_motion_data(cursor, header, size)?;
+            Ok(Packet2023::Motion(motion))
+        }
+        PacketID::Session => {
+            let session = parse_session(cursor, header, size, decoder)?;
+            Ok(Packet2023::Session(session))
+        }
+        PacketID::LapData => {
+            let lap = parse_lap_data(cursor, header, size, decoder)?;
+            Ok(Packet2023::Lap(lap))
+        }
+        PacketID::Event => {
+            let event = parse_event_data(cursor, header, size, decoder)?;
+            Ok(Packet2023::Event(event))
+        }
+        PacketID::Participants => {
+            let participants = parse_participants_data(cursor, header, size, decoder)?;
+            Ok(Packet2023::Participants(participants))
+        }
+        PacketID::CarSetups => {
+            let car_setups = parse_car_setup_data(cursor, header, size)?;
+            Ok(Packet2023::CarSetups(car_setups))
+        }
+        PacketID::CarTelemetry => {
+            let car_telemetry = parse_car_telemetry_data(cursor, header, size, decoder)?;
+            Ok(Packet2023::CarTelemetry(car_telemetry))
+        }
+        PacketID::CarStatus => {
+            let car_status = parse_car_status_data(cursor, header, size, decoder)?;
+            Ok(Packet2023::CarStatus(car_status))
+        }
+        PacketID::FinalClassification => {
+            let final_classification =
+                parse_final_classification_data(cursor, header, size, decoder)?;
+            Ok(Packet2023::FinalClassification(final_classification))
+        }
+        PacketID::LobbyInfo => {
+            let lobby_info = parse_lobby_info_data(cursor, header, size, decoder)?;
+            Ok(Packet2023::LobbyInfo(lobby_info))
+        }
+        PacketID::CarDamage => {
+            let car_damage = parse_car_damage_data(cursor, header, size)?;
+            Ok(Packet2023::CarDamage(car_damage))
+        }
+        PacketID::SessionHistory => {
+            let session_history = parse_session_history_data(cursor, header, size, decoder)?;
+            Ok(Packet2023::SessionHistory(session_history))
+        }
+        PacketID::TyreSets => {
+            let tyre_sets = parse_tyre_sets_data(cursor, header, size, decoder)?;
+            Ok(Packet2023::TyreSets(tyre_sets))
+        }
+        PacketID::MotionEx => {
+            let motion_ex = parse_motion_ex_data(cursor, header, size)?;
+            Ok(Packet2023::MotionEx(motion_ex))
+        }
+    }
+}
+
+pub fn packet_type(packet_id: u8) -> Result<PacketID, TelemetryError> {
+    match packet_id {
+        0 => Ok(PacketID::Motion),
+        1 => Ok(PacketID::Session),
+        2 => Ok(PacketID::LapData),
+        3 => Ok(PacketID::Event),
+        4 => Ok(PacketID::Participants),
+        5 => Ok(PacketID::CarSetups),
+        6 => Ok(PacketID::CarTelemetry),
+        7 => Ok(PacketID::CarStatus),
+        8 => Ok(PacketID::FinalClassification),
+        9 => Ok(PacketID::LobbyInfo),
+        10 => Ok(PacketID::CarDamage),
+        11 => Ok(PacketID::SessionHistory),
+        12 => Ok(PacketID::TyreSets),
+        13 => Ok(PacketID::MotionEx),
+        _ => Err(TelemetryError::UnknownPacketId { id: packet_id }),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::decoder::Decoder;
+    use crate::f1_2020::car::{ActualTyreCompound, VisualTyreCompound};
+    use crate::f1_2020::session::SessionType;
+    use crate::f1_2023::packet::{parse_f12023, Packet2023};
+    use std::io::Cursor;
+
+    use byteorder::{LittleEndian, WriteBytesExt};
+
+    fn header(packet_id: u8) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(2048);
+        buf.write_u16::<LittleEndian>(2023).unwrap();
+        buf.write_u8(23).unwrap();
+        buf.write_u8(1).unwrap();
+        buf.write_u8(3).unwrap();
+        buf.write_u8(1).unwrap();
+        buf.write_u8(packet_id).unwrap();
+        buf.write_u64::<LittleEndian>(7).unwrap();
+        buf.write_f32::<LittleEndian>(12.5).unwrap();
+        buf.write_u32::<LittleEndian>(100).unwrap();
+        buf.write_u32::<LittleEndian>(120).unwrap();
+        buf.write_u8(0).unwrap();
+        buf.write_u8(255).unwrap();
+        buf
+    }
+
+    #[test]
+    fn parse_lap_data_packet() {
+        let mut buf = header(2);
+        for car in 0..22u8 {
+            buf.write_u32::<LittleEndian>(91_000).unwrap();
+            buf.write_u32::<LittleEndian>(10_000).unwrap();
+            buf.write_u16::<LittleEndian>(5_000).unwrap();
+            buf.write_u8(1).unwrap();
+            buf.write_u16::<LittleEndian>(30_000).unwrap();
+            buf.write_u8(0).unwrap();
+            buf.write_u16::<LittleEndian>(1_500).unwrap();
+            buf.write_u16::<LittleEndian>(12_000).unwrap();
+            buf.write_f32::<LittleEndian>(100.0).unwrap();
+            buf.write_f32::<LittleEndian>(5100.0).unwrap();
+            buf.write_f32::<LittleEndian>(0.0).unwrap();
+            buf.extend_from_slice(&[car + 1, 2, 0, 0, 0, 0, 0, 3, 2, 0, 0, car + 1, 4, 2, 0]);
+            buf.write_u16::<LittleEndian>(0).unwrap();
+            buf.write_u16::<LittleEndian>(0).unwrap();
+            buf.write_u8(0).unwrap();
+        }
+        buf.write_u8(255).unwrap();
+        buf.write_u8(255).unwrap();
+
+        let mut cursor = Cursor::new(buf.as_slice());
+        let len = cursor.get_ref().len();
+        assert_eq!(1131, len);
+        let result = parse_f12023(&mut cursor, len, &mut Decoder::default()).unwrap();
+
+        match result {
+            Packet2023::Lap(lap) => {
+                assert_eq!(Some(120), lap.header.overall_frame_identifier);
+                assert_eq!(22, lap.lap_data.len());
+                let data = lap.lap_data[21];
+                assert_eq!(65_000, data.sector_1_time.as_millis());
+                assert_eq!(30_000, data.sector_2_time.as_millis());
+                assert_eq!(
+                    Some(1_500),
+                    data.delta_to_car_in_front.map(|d| d.as_millis())
+                );
+                assert_eq!(
+                    Some(12_000),
+                    data.delta_to_race_leader.map(|d| d.as_millis())
+                );
+                assert_eq!(22, data.car_position);
+                assert_eq!(3, data.warnings);
+                assert_eq!(Some(2), data.corner_cutting_warnings);
+                assert_eq!(None, data.speed_trap_fastest_speed);
+                assert_eq!(Some(255), lap.time_trial_pb_car_index);
+            }
+            p => panic!("Unexpected packet {:?}", p),
+        }
+    }
+
+    #[test]
+    fn parse_tyre_sets_packet() {
+        let mut buf = header(12);
+        buf.write_u8(5).unwrap();
+        for set in 0..20u8 {
+            buf.extend_from_slice(&[18, 16, set, 1, 10, 20, 22]);
+            buf.write_i16::<LittleEndian>(-250).unwrap();
+            buf.write_u8((set == 3) as u8).unwrap();
+        }
+        buf.write_u8(3).unwrap();
+
+        let mut cursor = Cursor::new(buf.as_slice());
+        let len = cursor.get_ref().len();
+        assert_eq!(231, len);
+        let result = parse_f12023(&mut cursor, len, &mut Decoder::default()).unwrap();
+
+        match result {
+            Packet2023::TyreSets(tyre_sets) => {
+                assert_eq!(5, tyre_sets.car_index);
+                assert_eq!(20, tyre_sets.tyre_set_data.len());
+                assert_eq!(3, tyre_sets.fitted_index);
+                let fitted = &tyre_sets.tyre_set_data[3];
+                assert_eq!(ActualTyreCompound::C3, fitted.actual_tyre_compound);
+                assert_eq!(VisualTyreCompound::Soft, fitted.visual_tyre_compound);
+                assert_eq!(3, fitted.wear);
+                assert_eq!(SessionType::R, fitted.recommended_session);
+                assert_eq!(-250, fitted.lap_delta_time);
+                assert!(fitted.fitted);
+            }
+            p => panic!("Unexpected packet {:?}", p),
+        }
+    }
+}
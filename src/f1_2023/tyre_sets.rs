@@ -0,0 +1,109 @@
+use crate::decoder::Decoder;
+use crate::error::TelemetryError;
+use crate::f1_2020::car::{ActualTyreCompound, VisualTyreCompound};
+use crate::f1_2020::session::SessionType;
+use crate::f1_2021::car_status::{parse_actual_tyre_compound, parse_visual_tyre_compound};
+use crate::f1_2021::header::PacketHeader;
+use crate::f1_2021::session::parse_session_type;
+use byteorder::{LittleEndian, ReadBytesExt};
+use std::io::Cursor;
+
+const TYRE_SETS_MIN_SIZE: usize = 231;
+const TOTAL_TYRE_SETS: usize = 20;
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct TyreSetData {
+    pub actual_tyre_compound: ActualTyreCompound,
+    pub visual_tyre_compound: VisualTyreCompound,
+    /// Tyre wear (percentage)
+    pub wear: u8,
+    /// Whether this set is currently available
+    pub available: bool,
+    /// Recommended session for tyre set
+    pub recommended_session: SessionType,
+    /// Laps left in this tyre set
+    pub life_span: u8,
+    /// Max number of laps recommended for this compound
+    pub usable_life: u8,
+    /// Lap delta time in milliseconds compared to fitted set
+    pub lap_delta_time: i16,
+    /// Whether the set is fitted or not
+    pub fitted: bool,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct PacketTyreSetsData {
+    pub header: PacketHeader,
+    /// Index of the car this data relates to
+    pub car_index: u8,
+    /// 13 (dry) + 7 (wet)
+    pub tyre_set_data: Vec<TyreSetData>,
+    /// Index into array of fitted tyre
+    pub fitted_index: u8,
+}
+
+pub fn parse_tyre_sets_data(
+    cursor: &mut Cursor<&[u8]>,
+    header: PacketHeader,
+    size: usize,
+    decoder: &mut Decoder,
+) -> Result<PacketTyreSetsData, TelemetryError> {
+    ensure_tyre_sets_size(size)?;
+
+    let car_index = cursor.read_u8()?;
+
+    let mut tyre_set_data = Vec::with_capacity(TOTAL_TYRE_SETS);
+    for _ in 0..TOTAL_TYRE_SETS {
+        let tyre_set = parse_tyre_set(cursor, header.packet_format, decoder)?;
+        tyre_set_data.push(tyre_set);
+    }
+
+    let fitted_index = cursor.read_u8()?;
+
+    Ok(PacketTyreSetsData {
+        header,
+        car_index,
+        tyre_set_data,
+        fitted_index,
+    })
+}
+
+fn parse_tyre_set(
+    cursor: &mut Cursor<&[u8]>,
+    packet_format: u16,
+    decoder: &mut Decoder,
+) -> Result<TyreSetData, TelemetryError> {
+    let actual_tyre_compound = parse_actual_tyre_compound(cursor.read_u8()?, decoder)?;
+    let visual_tyre_compound = parse_visual_tyre_compound(cursor.read_u8()?, decoder)?;
+    let wear = cursor.read_u8()?;
+    let available = cursor.read_u8()? == 1;
+    let recommended_session = parse_session_type(packet_format, cursor.read_u8()?, decoder)?;
+    let life_span = cursor.read_u8()?;
+    let usable_life = cursor.read_u8()?;
+    let lap_delta_time = cursor.read_i16::<LittleEndian>()?;
+    let fitted = cursor.read_u8()? == 1;
+
+    Ok(TyreSetData {
+        actual_tyre_compound,
+        visual_tyre_compound,
+        wear,
+        available,
+        recommended_session,
+        life_span,
+        usable_life,
+        lap_delta_time,
+        fitted,
+    })
+}
+
+fn ensure_tyre_sets_size(size: usize) -> Result<(), TelemetryError> {
+    if size == TYRE_SETS_MIN_SIZE {
+        return Ok(());
+    }
+
+    Err(TelemetryError::SizeMismatch {
+        packet: "Tyre sets",
+        expected: TYRE_SETS_MIN_SIZE,
+        got: size,
+    })
+}
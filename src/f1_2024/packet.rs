@@ -0,0 +1,263 @@
+use crate::decoder::Decoder;
+use crate::error::TelemetryError;
+use crate::f1_2021::car::{
+    PacketCarDamageData, PacketCarSetupData, PacketCarStatusData, PacketCarTelemetryData,
+};
+use crate::f1_2021::car_damage::parse_car_damage_data;
+use crate::f1_2021::car_setup::parse_car_setup_data;
+use crate::f1_2021::car_status::parse_car_status_data;
+use crate::f1_2021::car_telemetry::parse_car_telemetry_data;
+use crate::f1_2021::event::{parse_event_data, PacketEventData};
+use crate::f1_2021::final_classification::{
+    parse_final_classification_data, PacketFinalClassificationData,
+};
+use crate::f1_2021::header::parse_headers;
+use crate::f1_2021::lap::{parse_lap_data, PacketLapData};
+use crate::f1_2021::lobby_info::{parse_lobby_info_data, PacketLobbyInfoData};
+use crate::f1_2021::participants::{parse_participants_data, PacketParticipantsData};
+use crate::f1_2021::session::{parse_session, PacketSessionData};
+use crate::f1_2021::session_history::{parse_session_history_data, PacketSessionHistoryData};
+use crate::f1_2023::motion::{parse_motion_data, PacketMotionData};
+use crate::f1_2023::motion_ex::{parse_motion_ex_data, PacketMotionExData};
+use crate::f1_2023::tyre_sets::{parse_tyre_sets_data, PacketTyreSetsData};
+use crate::f1_2024::time_trial::{parse_time_trial_data, PacketTimeTrialData};
+use std::io::Cursor;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum PacketID {
+    Motion,
+    Session,
+    LapData,
+    Event,
+    Participants,
+    CarSetups,
+    CarTelemetry,
+    CarStatus,
+    FinalClassification,
+    LobbyInfo,
+    CarDamage,
+    SessionHistory,
+    TyreSets,
+    MotionEx,
+    TimeTrial,
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum Packet2024 {
+    Motion(PacketMotionData),
+    Session(PacketSessionData),
+    Lap(PacketLapData),
+    Event(PacketEventData),
+    Participants(PacketParticipantsData),
+    CarSetups(PacketCarSetupData),
+    CarTelemetry(PacketCarTelemetryData),
+    CarStatus(PacketCarStatusData),
+    FinalClassification(PacketFinalClassificationData),
+    LobbyInfo(PacketLobbyInfoData),
+    CarDamage(PacketCarDamageData),
+    SessionHistory(PacketSessionHistoryData),
+    TyreSets(PacketTyreSetsData),
+    MotionEx(PacketMotionExData),
+    TimeTrial(PacketTimeTrialData),
+}
+
+pub fn parse_f12024(
+    cursor: &mut Cursor<&[u8]>,
+    size: usize,
+    decoder: &mut Decoder,
+) -> Result<Packet2024, TelemetryError> {
+    let header = parse_headers(cursor, size)?;
+    match packet_type(header.packet_id)? {
+        PacketID::Motion => {
+            let motion = parse_motion_data(cursor, header, size)?;
+            Ok(Packet2024::Motion(motion))
+        }
+        PacketID::Session => {
+            let session = parse_session(cursor, header, size, decoder)?;
+            Ok(Packet2024::Session(session))
+        }
+        PacketID::LapData => {
+            let lap = parse_lap_data(cursor, header, size, decoder)?;
+            Ok(Packet2024::Lap(lap))
+        }
+        PacketID::Event => {
+            let event = parse_event_data(cursor, header, size, decoder)?;
+            Ok(Packet2024::Event(event))
+        }
+        PacketID::Participants => {
+            let participants = parse_participants_data(cursor, header, size, decoder)?;
+            Ok(Packet2024::Participants(participants))
+        }
+        PacketID::CarSetups => {
+            let car_setups = parse_car_setup_data(cursor, header, size)?;
+            Ok(Packet2024::CarSetups(car_setups))
+        }
+        PacketID::CarTelemetry => {
+            let car_telemetry = parse_car_telemetry_data(cursor, header, size, decoder)?;
+            Ok(Packet2024::CarTelemetry(car_telemetry))
+        }
+        PacketID::CarStatus => {
+            let car_status = parse_car_status_data(cursor, header, size, decoder)?;
+            Ok(Packet2024::CarStatus(car_status))
+        }
+        PacketID::FinalClassification => {
+            let final_classification =
+                parse_final_classification_data(cursor, header, size, decoder)?;
+            Ok(Packet2024::FinalClassification(final_classification))
+        }
+        PacketID::LobbyInfo => {
+            let lobby_info = parse_lobby_info_data(cursor, header, size, decoder)?;
+            Ok(Packet2024::LobbyInfo(lobby_info))
+        }
+        PacketID::CarDamage => {
+            let car_damage = parse_car_damage_data(cursor, header, size)?;
+            Ok(Packet2024::CarDamage(car_damage))
+        }
+        PacketID::SessionHistory => {
+            let session_history = parse_session_history_data(cursor, header, size, decoder)?;
+            Ok(Packet2024::SessionHistory(session_history))
+        }
+        PacketID::TyreSets => {
+            let tyre_sets = parse_tyre_sets_data(cursor, header, size, decoder)?;
+            Ok(Packet2024::TyreSets(tyre_sets))
+        }
+        PacketID::MotionEx => {
+            let motion_ex = parse_motion_ex_data(cursor, header, size)?;
+            Ok(Packet2024::MotionEx(motion_ex))
+        }
+        PacketID::TimeTrial => {
+            let time_trial = parse_time_trial_data(cursor, header, size)?;
+            Ok(Packet2024::TimeTrial(time_trial))
+        }
+    }
+}
+
+pub fn packet_type(packet_id: u8) -> Result<PacketID, TelemetryError> {
+    match packet_id {
+        0 => Ok(PacketID::Motion),
+        1 => Ok(PacketID::Session),
+        2 => Ok(PacketID::LapData),
+        3 => Ok(PacketID::Event),
+        4 => Ok(PacketID::Participants),
+        5 => Ok(PacketID::CarSetups),
+        6 => Ok(PacketID::CarTelemetry),
+        7 => Ok(PacketID::CarStatus),
+        8 => Ok(PacketID::FinalClassification),
+        9 => Ok(PacketID::LobbyInfo),
+        10 => Ok(PacketID::CarDamage),
+        11 => Ok(PacketID::SessionHistory),
+        12 => Ok(PacketID::TyreSets),
+        13 => Ok(PacketID::MotionEx),
+        14 => Ok(PacketID::TimeTrial),
+        // 15 (Lap Positions) is only sent by F1 25
+        _ => Err(TelemetryError::UnknownPacketId { id: packet_id }),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::decoder::Decoder;
+    use crate::f1_2020::session::SessionType;
+    use crate::f1_2024::packet::{parse_f12024, Packet2024};
+    use std::io::Cursor;
+
+    use byteorder::{LittleEndian, WriteBytesExt};
+
+    fn header(packet_id: u8) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(2048);
+        buf.write_u16::<LittleEndian>(2024).unwrap();
+        buf.write_u8(24).unwrap();
+        buf.write_u8(1).unwrap();
+        buf.write_u8(3).unwrap();
+        buf.write_u8(1).unwrap();
+        buf.write_u8(packet_id).unwrap();
+        buf.write_u64::<LittleEndian>(7).unwrap();
+        buf.write_f32::<LittleEndian>(12.5).unwrap();
+        buf.write_u32::<LittleEndian>(100).unwrap();
+        buf.write_u32::<LittleEndian>(120).unwrap();
+        buf.write_u8(0).unwrap();
+        buf.write_u8(255).unwrap();
+        buf
+    }
+
+    #[test]
+    fn parse_session_packet() {
+        let mut buf = header(1);
+        buf.resize(753, 0);
+        // 15 = race in the F1 24 session type numbering
+        buf[35] = 15;
+        buf[732] = 3;
+        buf[733..736].copy_from_slice(&[1, 10, 15]);
+        buf[745..749].copy_from_slice(&2100.5f32.to_le_bytes());
+        buf[749..753].copy_from_slice(&4200.0f32.to_le_bytes());
+
+        let mut cursor = Cursor::new(buf.as_slice());
+        let len = cursor.get_ref().len();
+        let result = parse_f12024(&mut cursor, len, &mut Decoder::default()).unwrap();
+
+        match result {
+            Packet2024::Session(session) => {
+                assert_eq!(SessionType::R, session.session_type);
+                assert_eq!(Some(0), session.game_mode);
+                assert_eq!(Some(0), session.num_red_flag_periods);
+                assert_eq!(Some(3), session.num_sessions_in_weekend);
+                assert_eq!(
+                    Some(vec![
+                        SessionType::P1,
+                        SessionType::SprintShootout1,
+                        SessionType::R
+                    ]),
+                    session.weekend_structure
+                );
+                assert_eq!(Some(2100.5), session.sector_2_lap_distance_start);
+                assert_eq!(Some(4200.0), session.sector_3_lap_distance_start);
+                assert_eq!(len as u64, cursor.position());
+            }
+            p => panic!("Unexpected packet {:?}", p),
+        }
+    }
+
+    #[test]
+    fn parse_time_trial_packet() {
+        let mut buf = header(14);
+        for car in 0..3u8 {
+            buf.write_u8(car).unwrap();
+            buf.write_u8(2).unwrap();
+            buf.write_u32::<LittleEndian>(80_000 + car as u32).unwrap();
+            buf.write_u32::<LittleEndian>(25_000).unwrap();
+            buf.write_u32::<LittleEndian>(30_000).unwrap();
+            buf.write_u32::<LittleEndian>(25_000 + car as u32).unwrap();
+            buf.extend_from_slice(&[2, 3, 1, 0, 1, 1]);
+        }
+
+        let mut cursor = Cursor::new(buf.as_slice());
+        let len = cursor.get_ref().len();
+        assert_eq!(101, len);
+        let result = parse_f12024(&mut cursor, len, &mut Decoder::default()).unwrap();
+
+        match result {
+            Packet2024::TimeTrial(time_trial) => {
+                assert_eq!(0, time_trial.player_session_best_data_set.car_index);
+                assert_eq!(1, time_trial.personal_best_data_set.car_index);
+                let rival = time_trial.rival_data_set;
+                assert_eq!(2, rival.car_index);
+                assert_eq!(80_002, rival.lap_time.as_millis());
+                assert_eq!(25_002, rival.sector_3_time.as_millis());
+                assert!(rival.anti_lock_brakes);
+                assert!(rival.custom_setup);
+                assert!(rival.valid);
+            }
+            p => panic!("Unexpected packet {:?}", p),
+        }
+    }
+
+    #[test]
+    fn reject_lap_positions_packet() {
+        let mut buf = header(15);
+        buf.resize(1131, 0);
+
+        let mut cursor = Cursor::new(buf.as_slice());
+        let len = cursor.get_ref().len();
+        assert!(parse_f12024(&mut cursor, len, &mut Decoder::default()).is_err());
+    }
+}
@@ -0,0 +1,109 @@
+use crate::error::TelemetryError;
+use crate::f1_2021::header::PacketHeader;
+use byteorder::{LittleEndian, ReadBytesExt};
+use std::io::Cursor;
+use std::time::Duration;
+
+const TIME_TRIAL_MIN_SIZE: usize = 101;
+
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub struct TimeTrialDataSet {
+    /// Index of the car this data relates to
+    pub car_index: u8,
+    /// Team id - see appendix
+    pub team_id: u8,
+    /// Lap time in milliseconds
+    pub lap_time: Duration,
+    /// Sector 1 time in milliseconds
+    pub sector_1_time: Duration,
+    /// Sector 2 time in milliseconds
+    pub sector_2_time: Duration,
+    /// Sector 3 time in milliseconds
+    pub sector_3_time: Duration,
+    /// 0 = off, 1 = medium, 2 = full
+    pub traction_control: u8,
+    /// 1 = manual, 2 = manual & suggested gear, 3 = auto
+    pub gearbox_assist: u8,
+    /// 0 (off) - 1 (on)
+    pub anti_lock_brakes: bool,
+    /// 0 = Realistic, 1 = Equal
+    pub equal_car_performance: u8,
+    /// 0 = No, 1 = Yes
+    pub custom_setup: bool,
+    /// 0 = invalid, 1 = valid
+    pub valid: bool,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct PacketTimeTrialData {
+    pub header: PacketHeader,
+    /// Player session best data set
+    pub player_session_best_data_set: TimeTrialDataSet,
+    /// Personal best data set
+    pub personal_best_data_set: TimeTrialDataSet,
+    /// Rival data set
+    pub rival_data_set: TimeTrialDataSet,
+}
+
+pub fn parse_time_trial_data(
+    cursor: &mut Cursor<&[u8]>,
+    header: PacketHeader,
+    size: usize,
+) -> Result<PacketTimeTrialData, TelemetryError> {
+    ensure_time_trial_size(size)?;
+
+    let player_session_best_data_set = parse_time_trial_data_set(cursor)?;
+    let personal_best_data_set = parse_time_trial_data_set(cursor)?;
+    let rival_data_set = parse_time_trial_data_set(cursor)?;
+
+    Ok(PacketTimeTrialData {
+        header,
+        player_session_best_data_set,
+        personal_best_data_set,
+        rival_data_set,
+    })
+}
+
+fn parse_time_trial_data_set(
+    cursor: &mut Cursor<&[u8]>,
+) -> Result<TimeTrialDataSet, TelemetryError> {
+    let car_index = cursor.read_u8()?;
+    let team_id = cursor.read_u8()?;
+    let lap_time = Duration::from_millis(cursor.read_u32::<LittleEndian>()? as u64);
+    let sector_1_time = Duration::from_millis(cursor.read_u32::<LittleEndian>()? as u64);
+    let sector_2_time = Duration::from_millis(cursor.read_u32::<LittleEndian>()? as u64);
+    let sector_3_time = Duration::from_millis(cursor.read_u32::<LittleEndian>()? as u64);
+    let traction_control = cursor.read_u8()?;
+    let gearbox_assist = cursor.read_u8()?;
+    let anti_lock_brakes = cursor.read_u8()? == 1;
+    let equal_car_performance = cursor.read_u8()?;
+    let custom_setup = cursor.read_u8()? == 1;
+    let valid = cursor.read_u8()? == 1;
+
+    Ok(TimeTrialDataSet {
+        car_index,
+        team_id,
+        lap_time,
+        sector_1_time,
+        sector_2_time,
+        sector_3_time,
+        traction_control,
+        gearbox_assist,
+        anti_lock_brakes,
+        equal_car_performance,
+        custom_setup,
+        valid,
+    })
+}
+
+fn ensure_time_trial_size(size: usize) -> Result<(), TelemetryError> {
+    if size == TIME_TRIAL_MIN_SIZE {
+        return Ok(());
+    }
+
+    Err(TelemetryError::SizeMismatch {
+        packet: "Time trial",
+        expected: TIME_TRIAL_MIN_SIZE,
+        got: size,
+    })
+}
@@ -0,0 +1,280 @@
+use crate::error::TelemetryError;
+use crate::f1_2020::motion::Wheel;
+use byteorder::{LittleEndian, ReadBytesExt};
+use std::io::Cursor;
+
+use derivative::Derivative;
+
+/// Size of the packet sent with `extradata="0"`: 38 floats.
+pub const LEGACY_MIN_SIZE: usize = 152;
+/// Size of the packet sent with `extradata="3"`: 66 floats.
+pub const LEGACY_EXTRA_SIZE: usize = 264;
+
+#[derive(Debug, PartialEq, Clone, Derivative)]
+#[derivative(Eq)]
+pub struct PacketLegacyData {
+    pub time: f32,
+    pub lap_time: f32,
+    pub lap_distance: f32,
+    pub total_distance: f32,
+    pub world_position_x: f32,
+    pub world_position_y: f32,
+    pub world_position_z: f32,
+    pub speed: f32,
+    pub world_velocity_x: f32,
+    pub world_velocity_y: f32,
+    pub world_velocity_z: f32,
+    pub world_right_dir_x: f32,
+    pub world_right_dir_y: f32,
+    pub world_right_dir_z: f32,
+    pub world_forward_dir_x: f32,
+    pub world_forward_dir_y: f32,
+    pub world_forward_dir_z: f32,
+    pub suspension_position: Wheel<f32>,
+    pub suspension_velocity: Wheel<f32>,
+    pub wheel_speed: Wheel<f32>,
+    pub throttle: f32,
+    pub steer: f32,
+    pub brake: f32,
+    pub clutch: f32,
+    pub gear: f32,
+    pub g_force_lateral: f32,
+    pub g_force_longitudinal: f32,
+    pub lap: f32,
+    pub engine_rate: f32,
+    pub extra_data: Option<LegacyExtraData>,
+}
+
+/// Fields only sent when the game is configured with `extradata="3"`.
+#[derive(Debug, PartialEq, Clone, Derivative)]
+#[derivative(Eq)]
+pub struct LegacyExtraData {
+    pub sli_pro_native_support: f32,
+    pub car_position: f32,
+    pub kers_level: f32,
+    pub kers_max_level: f32,
+    pub drs: f32,
+    pub traction_control: f32,
+    pub anti_lock_brakes: f32,
+    pub fuel_in_tank: f32,
+    pub fuel_capacity: f32,
+    pub in_pits: f32,
+    pub sector: f32,
+    pub sector1_time: f32,
+    pub sector2_time: f32,
+    pub brakes_temperature: Wheel<f32>,
+    pub tyre_pressures: Wheel<f32>,
+    pub team_info: f32,
+    pub total_laps: f32,
+    pub track_size: f32,
+    pub last_lap_time: f32,
+    pub max_rpm: f32,
+    pub idle_rpm: f32,
+    pub max_gears: f32,
+}
+
+pub fn parse_legacy(
+    cursor: &mut Cursor<&[u8]>,
+    size: usize,
+) -> Result<PacketLegacyData, TelemetryError> {
+    ensure_legacy_size(size)?;
+
+    let time = read_float(cursor)?;
+    let lap_time = read_float(cursor)?;
+    let lap_distance = read_float(cursor)?;
+    let total_distance = read_float(cursor)?;
+    let world_position_x = read_float(cursor)?;
+    let world_position_y = read_float(cursor)?;
+    let world_position_z = read_float(cursor)?;
+    let speed = read_float(cursor)?;
+    let world_velocity_x = read_float(cursor)?;
+    let world_velocity_y = read_float(cursor)?;
+    let world_velocity_z = read_float(cursor)?;
+    let world_right_dir_x = read_float(cursor)?;
+    let world_right_dir_y = read_float(cursor)?;
+    let world_right_dir_z = read_float(cursor)?;
+    let world_forward_dir_x = read_float(cursor)?;
+    let world_forward_dir_y = read_float(cursor)?;
+    let world_forward_dir_z = read_float(cursor)?;
+    let suspension_position = parse_wheel(cursor)?;
+    let suspension_velocity = parse_wheel(cursor)?;
+    let wheel_speed = parse_wheel(cursor)?;
+    let throttle = read_float(cursor)?;
+    let steer = read_float(cursor)?;
+    let brake = read_float(cursor)?;
+    let clutch = read_float(cursor)?;
+    let gear = read_float(cursor)?;
+    let g_force_lateral = read_float(cursor)?;
+    let g_force_longitudinal = read_float(cursor)?;
+    let lap = read_float(cursor)?;
+    let engine_rate = read_float(cursor)?;
+
+    let extra_data = if size >= LEGACY_EXTRA_SIZE {
+        Some(parse_extra_data(cursor)?)
+    } else {
+        None
+    };
+
+    Ok(PacketLegacyData {
+        time,
+        lap_time,
+        lap_distance,
+        total_distance,
+        world_position_x,
+        world_position_y,
+        world_position_z,
+        speed,
+        world_velocity_x,
+        world_velocity_y,
+        world_velocity_z,
+        world_right_dir_x,
+        world_right_dir_y,
+        world_right_dir_z,
+        world_forward_dir_x,
+        world_forward_dir_y,
+        world_forward_dir_z,
+        suspension_position,
+        suspension_velocity,
+        wheel_speed,
+        throttle,
+        steer,
+        brake,
+        clutch,
+        gear,
+        g_force_lateral,
+        g_force_longitudinal,
+        lap,
+        engine_rate,
+        extra_data,
+    })
+}
+
+fn parse_extra_data(cursor: &mut Cursor<&[u8]>) -> Result<LegacyExtraData, TelemetryError> {
+    let sli_pro_native_support = read_float(cursor)?;
+    let car_position = read_float(cursor)?;
+    let kers_level = read_float(cursor)?;
+    let kers_max_level = read_float(cursor)?;
+    let drs = read_float(cursor)?;
+    let traction_control = read_float(cursor)?;
+    let anti_lock_brakes = read_float(cursor)?;
+    let fuel_in_tank = read_float(cursor)?;
+    let fuel_capacity = read_float(cursor)?;
+    let in_pits = read_float(cursor)?;
+    let sector = read_float(cursor)?;
+    let sector1_time = read_float(cursor)?;
+    let sector2_time = read_float(cursor)?;
+    let brakes_temperature = parse_wheel(cursor)?;
+    let tyre_pressures = parse_wheel(cursor)?;
+    let team_info = read_float(cursor)?;
+    let total_laps = read_float(cursor)?;
+    let track_size = read_float(cursor)?;
+    let last_lap_time = read_float(cursor)?;
+    let max_rpm = read_float(cursor)?;
+    let idle_rpm = read_float(cursor)?;
+    let max_gears = read_float(cursor)?;
+
+    Ok(LegacyExtraData {
+        sli_pro_native_support,
+        car_position,
+        kers_level,
+        kers_max_level,
+        drs,
+        traction_control,
+        anti_lock_brakes,
+        fuel_in_tank,
+        fuel_capacity,
+        in_pits,
+        sector,
+        sector1_time,
+        sector2_time,
+        brakes_temperature,
+        tyre_pressures,
+        team_info,
+        total_laps,
+        track_size,
+        last_lap_time,
+        max_rpm,
+        idle_rpm,
+        max_gears,
+    })
+}
+
+fn parse_wheel(cursor: &mut Cursor<&[u8]>) -> Result<Wheel<f32>, TelemetryError> {
+    Ok(Wheel {
+        rear_left: read_float(cursor)?,
+        rear_right: read_float(cursor)?,
+        front_left: read_float(cursor)?,
+        front_right: read_float(cursor)?,
+    })
+}
+
+fn read_float(cursor: &mut Cursor<&[u8]>) -> Result<f32, TelemetryError> {
+    Ok(cursor.read_f32::<LittleEndian>()?)
+}
+
+/// F1 2016/2017 append their own data after the 66 shared floats, so anything
+/// from `LEGACY_EXTRA_SIZE` upwards decodes the shared part and ignores the rest.
+fn ensure_legacy_size(size: usize) -> Result<(), TelemetryError> {
+    if size == LEGACY_MIN_SIZE || size >= LEGACY_EXTRA_SIZE {
+        return Ok(());
+    }
+
+    Err(TelemetryError::SizeMismatch {
+        packet: "Legacy",
+        expected: LEGACY_MIN_SIZE,
+        got: size,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::legacy::packet::{parse_legacy, LEGACY_EXTRA_SIZE, LEGACY_MIN_SIZE};
+    use std::io::Cursor;
+
+    use byteorder::{LittleEndian, WriteBytesExt};
+
+    fn floats(count: usize) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(count * 4);
+        for i in 0..count {
+            buf.write_f32::<LittleEndian>(i as f32).unwrap();
+        }
+        buf
+    }
+
+    #[test]
+    fn parse_legacy_without_extra_data() {
+        let buf = floats(38);
+        let mut cursor = Cursor::new(buf.as_slice());
+        let result = parse_legacy(&mut cursor, LEGACY_MIN_SIZE).unwrap();
+
+        assert_eq!(7.0, result.speed);
+        assert_eq!(17.0, result.suspension_position.rear_left);
+        assert_eq!(28.0, result.wheel_speed.front_right);
+        assert_eq!(33.0, result.gear);
+        assert_eq!(37.0, result.engine_rate);
+        assert_eq!(None, result.extra_data);
+    }
+
+    #[test]
+    fn parse_legacy_with_extra_data() {
+        let buf = floats(66);
+        let mut cursor = Cursor::new(buf.as_slice());
+        let result = parse_legacy(&mut cursor, LEGACY_EXTRA_SIZE).unwrap();
+
+        let extra = result.extra_data.unwrap();
+        assert_eq!(39.0, extra.car_position);
+        assert_eq!(45.0, extra.fuel_in_tank);
+        assert_eq!(51.0, extra.brakes_temperature.rear_left);
+        assert_eq!(58.0, extra.tyre_pressures.front_right);
+        assert_eq!(65.0, extra.max_gears);
+    }
+
+    #[test]
+    fn parse_legacy_invalid_size() {
+        let buf = floats(50);
+        let mut cursor = Cursor::new(buf.as_slice());
+        let result = parse_legacy(&mut cursor, 200);
+
+        assert!(result.is_err());
+    }
+}
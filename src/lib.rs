@@ -2,7 +2,8 @@
 //!
 //! # Example
 //!
-//! ```rust
+#![cfg_attr(feature = "async-std", doc = "```no_run")]
+#![cfg_attr(not(feature = "async-std"), doc = "```ignore")]
 //! use f1_telemetry_client::{Telemetry, packet::Packet};
 //! use async_std::task;
 //!
@@ -29,85 +30,126 @@
 //! }
 //! ```
 
-use async_std::io::{Cursor, Error};
-use async_std::net::{IpAddr, SocketAddr, UdpSocket};
+use byteorder::{LittleEndian, ReadBytesExt};
+use std::io::Cursor;
 
-use byteorder_async::{LittleEndian, ReaderToByteOrder};
-use std::str::FromStr;
+pub use crate::decoder::{DecodeMode, Decoder};
+pub use crate::error::TelemetryError;
 
+#[cfg(feature = "async-std")]
+pub use crate::async_std_client::Telemetry;
+#[cfg(all(feature = "tokio", not(feature = "async-std")))]
+pub use crate::tokio_client::Telemetry;
+
+pub mod analysis;
+pub mod archive;
+#[cfg(feature = "async-std")]
+pub mod async_std_client;
+pub mod blocking;
+pub mod capture;
+mod client;
+pub mod decoder;
+pub mod error;
+pub mod f1_2018;
+pub mod f1_2019;
 pub mod f1_2020;
+pub mod f1_2021;
+pub mod f1_2022;
+pub mod f1_2023;
+pub mod f1_2024;
+pub mod legacy;
 pub mod packet;
+pub mod pcap;
+pub mod replay;
+#[cfg(feature = "tokio")]
+pub mod tokio_client;
+
+/// Wire format the game is configured to send.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Protocol {
+    /// Header based format used from F1 2018 onwards, detected from `packet_format`.
+    Standard,
+    /// Float array "extradata" format of F1 2016/2017 and DiRT Rally.
+    Legacy,
+}
 
-pub struct Telemetry(UdpSocket);
+/// Decodes a single datagram in the header based format, detecting the game
+/// from its `packet_format`.
+pub fn parse(bytes: &[u8]) -> Result<packet::Packet, TelemetryError> {
+    parse_with(bytes, Protocol::Standard, &mut Decoder::default())
+}
 
-impl Telemetry {
-    pub async fn new(ip: &str, port: u16) -> Result<Self, Error> {
-        let ip = IpAddr::from_str(ip).expect("Invalid ip address");
-        let socket_addrs = SocketAddr::new(ip, port);
-        let socket = UdpSocket::bind(socket_addrs).await?;
+/// Decodes a single datagram sent in `protocol`, using `decoder` for values
+/// outside of a field's enumeration.
+pub fn parse_with(
+    bytes: &[u8],
+    protocol: Protocol,
+    decoder: &mut Decoder,
+) -> Result<packet::Packet, TelemetryError> {
+    let size = bytes.len();
+    let mut cursor = Cursor::new(bytes);
+    let cursor = &mut cursor;
 
-        Ok(Telemetry(socket))
+    if protocol == Protocol::Legacy {
+        let result = legacy::packet::parse_legacy(cursor, size)?;
+        return Ok(packet::Packet::Legacy(result));
     }
 
-    pub async fn next(&self) -> Result<packet::Packet, Error> {
-        let mut buf = vec![0; 2048];
-        let (size, _) = self.0.recv_from(&mut buf).await?;
-        let mut cursor = Cursor::new(buf);
-
-        let packet_format = cursor
-            .clone()
-            .byte_order()
-            .read_u16::<LittleEndian>()
-            .await?;
-        match packet_format {
-            2020 => {
-                let result = f1_2020::packet::parse_f12020(&mut cursor, size).await?;
-                Ok(packet::Packet::F12020(result))
-            }
-            2019 => unimplemented!(),
-            2018 => unimplemented!(),
-            _ => unimplemented!(),
+    let packet_format = cursor.clone().read_u16::<LittleEndian>()?;
+    match packet_format {
+        2024 => {
+            let result = f1_2024::packet::parse_f12024(cursor, size, decoder)?;
+            Ok(packet::Packet::F12024(result))
+        }
+        2023 => {
+            let result = f1_2023::packet::parse_f12023(cursor, size, decoder)?;
+            Ok(packet::Packet::F12023(result))
         }
+        2022 => {
+            let result = f1_2022::packet::parse_f12022(cursor, size, decoder)?;
+            Ok(packet::Packet::F12022(result))
+        }
+        2021 => {
+            let result = f1_2021::packet::parse_f12021(cursor, size, decoder)?;
+            Ok(packet::Packet::F12021(result))
+        }
+        2020 => {
+            let result = f1_2020::packet::parse_f12020(cursor, size, decoder)?;
+            Ok(packet::Packet::F12020(result))
+        }
+        2019 => {
+            let result = f1_2019::packet::parse_f12019(cursor, size, decoder)?;
+            Ok(packet::Packet::F12019(result))
+        }
+        2018 => {
+            let result = f1_2018::packet::parse_f12018(cursor, size, decoder)?;
+            Ok(packet::Packet::F12018(result))
+        }
+        format => Err(TelemetryError::UnsupportedFormat { format }),
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use async_std::net::UdpSocket;
-    use async_std::task::spawn;
-    use byteorder_async::WriteBytesExt;
+    use byteorder::WriteBytesExt;
 
-    fn send() {
-        let handle = spawn(async {
-            let socket = UdpSocket::bind("127.0.0.1:8080").await.unwrap();
-            let mut send_buf = Vec::with_capacity(2048);
-            send_buf.write_u16::<LittleEndian>(2020).unwrap();
-            send_buf.write_u8(1).unwrap();
-            send_buf.write_u8(2).unwrap();
-            send_buf.write_u8(3).unwrap();
-            send_buf.write_u8(0).unwrap();
-            send_buf
-                .write_u64::<LittleEndian>(u64::max_value())
-                .unwrap();
-            send_buf.write_f32::<LittleEndian>(1.0).unwrap();
-            send_buf
-                .write_u32::<LittleEndian>(u32::max_value())
-                .unwrap();
-            send_buf.write_u8(19).unwrap();
-            send_buf.write_u8(255).unwrap();
-            socket.send_to(&*send_buf, "127.0.0.1:20777").await.unwrap();
-        });
+    #[test]
+    fn test_parse_unsupported_format() {
+        let mut buf = Vec::with_capacity(24);
+        buf.write_u16::<LittleEndian>(2017).unwrap();
+        buf.resize(24, 0);
 
-        drop(handle);
+        let result = parse(&buf);
+        assert!(matches!(
+            result,
+            Err(TelemetryError::UnsupportedFormat { format: 2017 })
+        ));
     }
 
-    #[async_std::test]
-    async fn test_telemetry_next() {
-        send();
-
-        let client = Telemetry::new("127.0.0.1", 20777).await.unwrap();
-        let result = client.next().await.map_err(|e| e.kind());
-        assert_eq!(result, Err(async_std::io::ErrorKind::InvalidData));
+    #[test]
+    fn test_parse_truncated_datagram() {
+        let result = parse(&[0xe4]);
+        assert!(matches!(result, Err(TelemetryError::Io(_))));
     }
 }
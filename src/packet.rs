@@ -1,9 +1,21 @@
+use crate::f1_2018::packet::Packet2018;
+use crate::f1_2019::packet::Packet2019;
 use crate::f1_2020::packet::Packet2020;
+use crate::f1_2021::packet::Packet2021;
+use crate::f1_2022::packet::Packet2022;
+use crate::f1_2023::packet::Packet2023;
+use crate::f1_2024::packet::Packet2024;
+use crate::legacy::packet::PacketLegacyData;
 
 #[derive(Debug, PartialEq)]
 pub enum Packet {
+    F12024(Packet2024),
+    F12023(Packet2023),
+    F12022(Packet2022),
+    F12021(Packet2021),
     F12020(Packet2020),
-    F12019,
-    F12018,
+    F12019(Packet2019),
+    F12018(Packet2018),
+    Legacy(PacketLegacyData),
     NONE,
 }
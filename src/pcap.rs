@@ -0,0 +1,907 @@
+//! Reading UDP datagrams out of pcap and pcapng captures, e.g. taken with
+//! Wireshark or tcpdump, and exporting recordings as pcapng.
+//!
+//! Only unfragmented UDP over IPv4 or IPv6 is extracted. Supported link types
+//! are Ethernet (with VLAN tags), BSD loopback, raw IP and Linux cooked
+//! captures.
+
+use crate::capture::CaptureReader;
+use crate::decoder::Decoder;
+use crate::f1_2020::packet::{parse_f12020, Packet2020};
+use crate::TelemetryError;
+use byteorder::{BigEndian, ByteOrder, LittleEndian, ReadBytesExt, WriteBytesExt};
+use std::convert::TryFrom;
+use std::fs::File;
+use std::io::{self, BufReader, Cursor, Read, Write};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const PCAP_MAGIC_MICROS: u32 = 0xa1b2_c3d4;
+const PCAP_MAGIC_NANOS: u32 = 0xa1b2_3c4d;
+const PCAPNG_SECTION_HEADER: u32 = 0x0a0d_0d0a;
+const PCAPNG_BYTE_ORDER_MAGIC: u32 = 0x1a2b_3c4d;
+const PCAPNG_INTERFACE_DESCRIPTION: u32 = 1;
+const PCAPNG_SIMPLE_PACKET: u32 = 3;
+const PCAPNG_ENHANCED_PACKET: u32 = 6;
+
+const LINKTYPE_NULL: u16 = 0;
+const LINKTYPE_ETHERNET: u16 = 1;
+const LINKTYPE_RAW: u16 = 101;
+const LINKTYPE_LINUX_SLL: u16 = 113;
+const LINKTYPE_IPV4: u16 = 228;
+const LINKTYPE_IPV6: u16 = 229;
+const LINKTYPE_LINUX_SLL2: u16 = 276;
+
+const IP_PROTOCOL_UDP: u8 = 17;
+
+/// Longest frame a pcap record is read for, enough for any IP datagram.
+const MAX_CAPTURED_LENGTH: usize = 65535;
+/// Longest pcapng block read, a frame of `MAX_CAPTURED_LENGTH` with room
+/// for options.
+const MAX_BLOCK_LENGTH: usize = 1 << 20;
+
+/// A UDP datagram taken from a capture.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Datagram {
+    pub timestamp: SystemTime,
+    pub source: SocketAddr,
+    pub destination: SocketAddr,
+    pub payload: Vec<u8>,
+}
+
+impl Datagram {
+    /// Decodes the payload as an F1 2020 packet.
+    pub fn parse_f12020(&self, decoder: &mut Decoder) -> Result<Packet2020, TelemetryError> {
+        let mut cursor = Cursor::new(self.payload.as_slice());
+        let format = cursor.clone().read_u16::<LittleEndian>()?;
+        if format != 2020 {
+            return Err(TelemetryError::UnsupportedFormat { format });
+        }
+
+        parse_f12020(&mut cursor, self.payload.len(), decoder)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Format {
+    Pcap {
+        nanos: bool,
+        link_type: u16,
+        /// Longest frame a record may hold
+        snap_length: usize,
+    },
+    Pcapng,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Interface {
+    link_type: u16,
+    /// Length of one timestamp unit
+    resolution: Resolution,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Resolution {
+    Decimal(u8),
+    Binary(u8),
+}
+
+impl Resolution {
+    /// Reads an if_tsresol option, rejecting units a `u64` cannot count
+    /// seconds in.
+    fn from_option(value: u8) -> Result<Self, TelemetryError> {
+        match value {
+            v if v & 0x80 == 0 && v <= 19 => Ok(Resolution::Decimal(v)),
+            v if v & 0x80 != 0 && v & 0x7f <= 63 => Ok(Resolution::Binary(v & 0x7f)),
+            _ => Err(TelemetryError::InvalidCapture {
+                reason: "unsupported pcapng timestamp resolution",
+            }),
+        }
+    }
+
+    fn timestamp(self, units: u64) -> Result<SystemTime, TelemetryError> {
+        let out_of_range = || TelemetryError::InvalidCapture {
+            reason: "capture timestamp out of range",
+        };
+        let since_epoch = match self {
+            Resolution::Decimal(exponent) => {
+                let exponent = exponent as u32;
+                let per_second = 10u64.checked_pow(exponent).ok_or_else(out_of_range)?;
+                let nanos = units % per_second;
+                let nanos = if exponent <= 9 {
+                    nanos * 10u64.pow(9 - exponent)
+                } else {
+                    nanos / 10u64.pow(exponent - 9)
+                };
+                Duration::new(units / per_second, nanos as u32)
+            }
+            Resolution::Binary(exponent) => {
+                Duration::try_from_secs_f64(units as f64 / 2f64.powi(exponent as i32))
+                    .map_err(|_| out_of_range())?
+            }
+        };
+
+        UNIX_EPOCH.checked_add(since_epoch).ok_or_else(out_of_range)
+    }
+}
+
+/// Iterates over the UDP datagrams sent to one port in a pcap or pcapng capture.
+pub struct PcapReader<R> {
+    reader: R,
+    port: u16,
+    format: Format,
+    big_endian: bool,
+    interfaces: Vec<Interface>,
+    skipped: usize,
+}
+
+impl PcapReader<BufReader<File>> {
+    pub fn open<P: AsRef<Path>>(path: P, port: u16) -> Result<Self, TelemetryError> {
+        PcapReader::new(BufReader::new(File::open(path)?), port)
+    }
+}
+
+impl<R: Read> PcapReader<R> {
+    /// Reads the file header, telling pcap and pcapng apart by their magic.
+    pub fn new(mut reader: R, port: u16) -> Result<Self, TelemetryError> {
+        let mut magic = [0; 4];
+        reader.read_exact(&mut magic)?;
+
+        let mut pcap = PcapReader {
+            reader,
+            port,
+            format: Format::Pcapng,
+            big_endian: false,
+            interfaces: Vec::new(),
+            skipped: 0,
+        };
+
+        if LittleEndian::read_u32(&magic) == PCAPNG_SECTION_HEADER {
+            pcap.read_section_header()?;
+            return Ok(pcap);
+        }
+
+        let nanos = match (LittleEndian::read_u32(&magic), BigEndian::read_u32(&magic)) {
+            (PCAP_MAGIC_MICROS, _) => false,
+            (PCAP_MAGIC_NANOS, _) => true,
+            (_, PCAP_MAGIC_MICROS) => {
+                pcap.big_endian = true;
+                false
+            }
+            (_, PCAP_MAGIC_NANOS) => {
+                pcap.big_endian = true;
+                true
+            }
+            _ => {
+                return Err(TelemetryError::InvalidCapture {
+                    reason: "not a pcap or pcapng file",
+                })
+            }
+        };
+
+        let mut header = [0; 20];
+        pcap.reader.read_exact(&mut header)?;
+        let snap_length = match pcap.u32(&header[12..]) as usize {
+            0 => MAX_CAPTURED_LENGTH,
+            snap_length => snap_length.min(MAX_CAPTURED_LENGTH),
+        };
+        pcap.format = Format::Pcap {
+            nanos,
+            link_type: pcap.u32(&header[16..]) as u16,
+            snap_length,
+        };
+
+        Ok(pcap)
+    }
+
+    /// Number of datagrams to the port left out because they were fragmented
+    /// or cut short by the capture's snapshot length.
+    pub fn skipped(&self) -> usize {
+        self.skipped
+    }
+
+    /// Returns the next datagram sent to the port, `None` at the end of the file.
+    pub fn next_datagram(&mut self) -> Result<Option<Datagram>, TelemetryError> {
+        loop {
+            let frame = match self.format {
+                Format::Pcap {
+                    nanos,
+                    link_type,
+                    snap_length,
+                } => self.next_pcap_frame(nanos, link_type, snap_length)?,
+                Format::Pcapng => self.next_pcapng_frame()?,
+            };
+            let (timestamp, link_type, data) = match frame {
+                Some(Some(frame)) => frame,
+                Some(None) => continue,
+                None => return Ok(None),
+            };
+
+            match extract_udp(link_type, &data) {
+                Some(Udp {
+                    source,
+                    destination,
+                    payload: Some(payload),
+                }) if destination.port() == self.port => {
+                    return Ok(Some(Datagram {
+                        timestamp,
+                        source,
+                        destination,
+                        payload: payload.to_vec(),
+                    }))
+                }
+                Some(Udp {
+                    destination,
+                    payload: None,
+                    ..
+                }) if destination.port() == self.port => self.skipped += 1,
+                _ => {}
+            }
+        }
+    }
+
+    /// Reads a record, `None` at the end of the file.
+    #[allow(clippy::type_complexity)]
+    fn next_pcap_frame(
+        &mut self,
+        nanos: bool,
+        link_type: u16,
+        snap_length: usize,
+    ) -> Result<Option<Option<(SystemTime, u16, Vec<u8>)>>, TelemetryError> {
+        let mut header = [0; 16];
+        if !self.read_or_eof(&mut header)? {
+            return Ok(None);
+        }
+
+        let seconds = self.u32(&header[0..]) as u64;
+        let fraction = self.u32(&header[4..]) as u64;
+        let captured = self.u32(&header[8..]) as usize;
+        if captured > snap_length {
+            return Err(TelemetryError::InvalidCapture {
+                reason: "pcap record longer than the snapshot length",
+            });
+        }
+        let mut data = vec![0; captured];
+        self.reader.read_exact(&mut data)?;
+
+        let resolution = Resolution::Decimal(if nanos { 9 } else { 6 });
+        let per_second = if nanos { 1_000_000_000 } else { 1_000_000 };
+        let timestamp = resolution.timestamp(seconds * per_second + fraction)?;
+
+        Ok(Some(Some((timestamp, link_type, data))))
+    }
+
+    /// Reads a block, `Some(None)` for blocks that do not hold a packet.
+    #[allow(clippy::type_complexity)]
+    fn next_pcapng_frame(
+        &mut self,
+    ) -> Result<Option<Option<(SystemTime, u16, Vec<u8>)>>, TelemetryError> {
+        let mut block_type = [0; 4];
+        if !self.read_or_eof(&mut block_type)? {
+            return Ok(None);
+        }
+        if LittleEndian::read_u32(&block_type) == PCAPNG_SECTION_HEADER {
+            self.read_section_header()?;
+            return Ok(Some(None));
+        }
+        let block_type = self.u32(&block_type);
+
+        let mut length = [0; 4];
+        self.reader.read_exact(&mut length)?;
+        let length = self.u32(&length) as usize;
+        if length < 12 || length % 4 != 0 || length > MAX_BLOCK_LENGTH {
+            return Err(TelemetryError::InvalidCapture {
+                reason: "invalid pcapng block length",
+            });
+        }
+        let mut body = vec![0; length - 8];
+        self.reader.read_exact(&mut body)?;
+        body.truncate(length - 12);
+
+        match block_type {
+            PCAPNG_INTERFACE_DESCRIPTION if body.len() >= 8 => {
+                let link_type = self.u16(&body[0..]);
+                let mut resolution = Resolution::Decimal(6);
+                for (code, value) in self.options(&body[8..]) {
+                    if code == 9 && !value.is_empty() {
+                        resolution = Resolution::from_option(value[0])?;
+                    }
+                }
+                self.interfaces.push(Interface {
+                    link_type,
+                    resolution,
+                });
+                Ok(Some(None))
+            }
+            PCAPNG_ENHANCED_PACKET if body.len() >= 20 => {
+                let interface = self.interface(self.u32(&body[0..]) as usize)?;
+                let units = (self.u32(&body[4..]) as u64) << 32 | self.u32(&body[8..]) as u64;
+                let captured = (self.u32(&body[12..]) as usize).min(body.len() - 20);
+                let data = body[20..20 + captured].to_vec();
+
+                Ok(Some(Some((
+                    interface.resolution.timestamp(units)?,
+                    interface.link_type,
+                    data,
+                ))))
+            }
+            PCAPNG_SIMPLE_PACKET if body.len() >= 4 => {
+                let interface = self.interface(0)?;
+                let captured = (self.u32(&body[0..]) as usize).min(body.len() - 4);
+                let data = body[4..4 + captured].to_vec();
+
+                // Simple packets carry no timestamp
+                Ok(Some(Some((UNIX_EPOCH, interface.link_type, data))))
+            }
+            _ => Ok(Some(None)),
+        }
+    }
+
+    /// Reads the rest of a section header block, which sets the byte order
+    /// of the blocks that follow.
+    fn read_section_header(&mut self) -> Result<(), TelemetryError> {
+        let mut fields = [0; 8];
+        self.reader.read_exact(&mut fields)?;
+        self.big_endian = match LittleEndian::read_u32(&fields[4..]) {
+            PCAPNG_BYTE_ORDER_MAGIC => false,
+            _ if BigEndian::read_u32(&fields[4..]) == PCAPNG_BYTE_ORDER_MAGIC => true,
+            _ => {
+                return Err(TelemetryError::InvalidCapture {
+                    reason: "invalid pcapng byte order magic",
+                })
+            }
+        };
+
+        let length = self.u32(&fields[0..]) as usize;
+        if length < 28 || length % 4 != 0 || length > MAX_BLOCK_LENGTH {
+            return Err(TelemetryError::InvalidCapture {
+                reason: "invalid pcapng block length",
+            });
+        }
+        let mut rest = vec![0; length - 12];
+        self.reader.read_exact(&mut rest)?;
+        self.interfaces.clear();
+
+        Ok(())
+    }
+
+    fn interface(&self, id: usize) -> Result<Interface, TelemetryError> {
+        self.interfaces
+            .get(id)
+            .copied()
+            .ok_or(TelemetryError::InvalidCapture {
+                reason: "packet for an undescribed pcapng interface",
+            })
+    }
+
+    fn options<'a>(&self, mut options: &'a [u8]) -> Vec<(u16, &'a [u8])> {
+        let mut parsed = Vec::new();
+        while options.len() >= 4 {
+            let code = self.u16(&options[0..]);
+            let length = self.u16(&options[2..]) as usize;
+            if code == 0 || options.len() < 4 + length {
+                break;
+            }
+            parsed.push((code, &options[4..4 + length]));
+            options = &options[(4 + (length + 3) / 4 * 4).min(options.len())..];
+        }
+        parsed
+    }
+
+    /// Fills `buf`, returning `false` at a clean end of the file.
+    fn read_or_eof(&mut self, buf: &mut [u8]) -> Result<bool, TelemetryError> {
+        let mut filled = 0;
+        while filled < buf.len() {
+            match self.reader.read(&mut buf[filled..]) {
+                Ok(0) if filled == 0 => return Ok(false),
+                Ok(0) => return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into()),
+                Ok(n) => filled += n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        Ok(true)
+    }
+
+    fn u16(&self, bytes: &[u8]) -> u16 {
+        if self.big_endian {
+            BigEndian::read_u16(bytes)
+        } else {
+            LittleEndian::read_u16(bytes)
+        }
+    }
+
+    fn u32(&self, bytes: &[u8]) -> u32 {
+        if self.big_endian {
+            BigEndian::read_u32(bytes)
+        } else {
+            LittleEndian::read_u32(bytes)
+        }
+    }
+}
+
+impl<R: Read> Iterator for PcapReader<R> {
+    type Item = Result<Datagram, TelemetryError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_datagram().transpose()
+    }
+}
+
+struct Udp<'a> {
+    source: SocketAddr,
+    destination: SocketAddr,
+    /// `None` when the payload is fragmented or not captured in full
+    payload: Option<&'a [u8]>,
+}
+
+fn extract_udp(link_type: u16, frame: &[u8]) -> Option<Udp<'_>> {
+    let packet = match link_type {
+        LINKTYPE_ETHERNET => {
+            let mut offset = 12;
+            let mut ether_type = BigEndian::read_u16(frame.get(offset..offset + 2)?);
+            // 802.1Q and 802.1ad tags
+            while ether_type == 0x8100 || ether_type == 0x88a8 {
+                offset += 4;
+                ether_type = BigEndian::read_u16(frame.get(offset..offset + 2)?);
+            }
+            frame.get(offset + 2..)?
+        }
+        LINKTYPE_NULL => frame.get(4..)?,
+        LINKTYPE_RAW | LINKTYPE_IPV4 | LINKTYPE_IPV6 => frame,
+        LINKTYPE_LINUX_SLL => frame.get(16..)?,
+        LINKTYPE_LINUX_SLL2 => frame.get(20..)?,
+        _ => return None,
+    };
+
+    match packet.first()? >> 4 {
+        4 => extract_udp_ipv4(packet),
+        6 => extract_udp_ipv6(packet),
+        _ => None,
+    }
+}
+
+fn extract_udp_ipv4(packet: &[u8]) -> Option<Udp<'_>> {
+    let header_length = (packet.first()? & 0x0f) as usize * 4;
+    if packet.len() < 20 || packet[9] != IP_PROTOCOL_UDP {
+        return None;
+    }
+    let total_length = BigEndian::read_u16(&packet[2..]) as usize;
+    let source = Ipv4Addr::from(<[u8; 4]>::try_from(&packet[12..16]).ok()?);
+    let destination = Ipv4Addr::from(<[u8; 4]>::try_from(&packet[16..20]).ok()?);
+
+    // More fragments flag or a fragment offset
+    let fragmented = BigEndian::read_u16(&packet[6..]) & 0x3fff != 0;
+    let udp = packet.get(header_length..total_length.min(packet.len()))?;
+
+    extract_payload(IpAddr::V4(source), IpAddr::V4(destination), udp, fragmented)
+}
+
+fn extract_udp_ipv6(packet: &[u8]) -> Option<Udp<'_>> {
+    if packet.len() < 40 {
+        return None;
+    }
+    let payload_length = BigEndian::read_u16(&packet[4..]) as usize;
+    let source = Ipv6Addr::from(<[u8; 16]>::try_from(&packet[8..24]).ok()?);
+    let destination = Ipv6Addr::from(<[u8; 16]>::try_from(&packet[24..40]).ok()?);
+    let end = (40 + payload_length).min(packet.len());
+
+    let mut next_header = packet[6];
+    let mut offset = 40;
+    let mut fragmented = false;
+    loop {
+        match next_header {
+            IP_PROTOCOL_UDP => break,
+            // Hop-by-hop, routing and destination options
+            0 | 43 | 60 => {
+                let extension = packet.get(offset..offset + 2)?;
+                next_header = extension[0];
+                offset += (extension[1] as usize + 1) * 8;
+            }
+            44 => {
+                let extension = packet.get(offset..offset + 8)?;
+                next_header = extension[0];
+                fragmented |= BigEndian::read_u16(&extension[2..]) & 0xfff9 != 0;
+                offset += 8;
+            }
+            _ => return None,
+        }
+    }
+
+    let udp = packet.get(offset..end)?;
+    extract_payload(IpAddr::V6(source), IpAddr::V6(destination), udp, fragmented)
+}
+
+fn extract_payload<'a>(
+    source: IpAddr,
+    destination: IpAddr,
+    udp: &'a [u8],
+    fragmented: bool,
+) -> Option<Udp<'a>> {
+    let ports = udp.get(..4)?;
+    let payload = match udp.get(4..6) {
+        Some(length) if !fragmented => {
+            let length = BigEndian::read_u16(length) as usize;
+            udp.get(8..length.max(8))
+        }
+        _ => None,
+    };
+
+    Some(Udp {
+        source: SocketAddr::new(source, BigEndian::read_u16(&ports[0..])),
+        destination: SocketAddr::new(destination, BigEndian::read_u16(&ports[2..])),
+        payload,
+    })
+}
+
+/// Writes datagrams as raw IP packets to a pcapng file that Wireshark opens.
+pub struct PcapngWriter<W: Write> {
+    writer: W,
+}
+
+impl PcapngWriter<File> {
+    pub fn create<P: AsRef<Path>>(path: P) -> Result<Self, TelemetryError> {
+        PcapngWriter::new(File::create(path)?)
+    }
+}
+
+impl<W: Write> PcapngWriter<W> {
+    /// Writes the section header and a single interface with nanosecond
+    /// timestamps.
+    pub fn new(mut writer: W) -> Result<Self, TelemetryError> {
+        let mut section = Vec::with_capacity(16);
+        section.write_u32::<LittleEndian>(PCAPNG_BYTE_ORDER_MAGIC)?;
+        section.write_u16::<LittleEndian>(1)?;
+        section.write_u16::<LittleEndian>(0)?;
+        // Section length not specified
+        section.write_i64::<LittleEndian>(-1)?;
+        write_block(&mut writer, PCAPNG_SECTION_HEADER, &section)?;
+
+        let mut interface = Vec::with_capacity(20);
+        interface.write_u16::<LittleEndian>(LINKTYPE_RAW)?;
+        interface.write_u16::<LittleEndian>(0)?;
+        interface.write_u32::<LittleEndian>(0)?;
+        // if_tsresol of 10^-9 followed by opt_endofopt
+        interface.write_all(&[9, 0, 1, 0, 9, 0, 0, 0, 0, 0, 0, 0])?;
+        write_block(&mut writer, PCAPNG_INTERFACE_DESCRIPTION, &interface)?;
+
+        Ok(PcapngWriter { writer })
+    }
+
+    pub fn write_datagram(&mut self, datagram: &Datagram) -> Result<(), TelemetryError> {
+        let packet = ip_packet(datagram)?;
+        let nanos = datagram
+            .timestamp
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos();
+        let nanos = u64::try_from(nanos).unwrap_or(u64::MAX);
+
+        let mut body = Vec::with_capacity(20 + packet.len() + 3);
+        body.write_u32::<LittleEndian>(0)?;
+        body.write_u32::<LittleEndian>((nanos >> 32) as u32)?;
+        body.write_u32::<LittleEndian>(nanos as u32)?;
+        body.write_u32::<LittleEndian>(packet.len() as u32)?;
+        body.write_u32::<LittleEndian>(packet.len() as u32)?;
+        body.write_all(&packet)?;
+        body.resize((body.len() + 3) / 4 * 4, 0);
+        write_block(&mut self.writer, PCAPNG_ENHANCED_PACKET, &body)
+    }
+
+    pub fn flush(&mut self) -> Result<(), TelemetryError> {
+        self.writer.flush()?;
+        Ok(())
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+/// Converts a recording to pcapng. Captures do not store where datagrams were
+/// sent, so all of them are exported as sent to `destination`. Returns the
+/// number of datagrams written.
+pub fn export_capture<R: Read, W: Write>(
+    capture: CaptureReader<R>,
+    destination: SocketAddr,
+    writer: &mut PcapngWriter<W>,
+) -> Result<usize, TelemetryError> {
+    let start_time = capture.start_time();
+    let mut written = 0;
+    for record in capture {
+        let record = record?;
+        writer.write_datagram(&Datagram {
+            timestamp: start_time + record.offset,
+            source: record.source,
+            destination,
+            payload: record.datagram,
+        })?;
+        written += 1;
+    }
+    writer.flush()?;
+
+    Ok(written)
+}
+
+fn write_block<W: Write>(
+    writer: &mut W,
+    block_type: u32,
+    body: &[u8],
+) -> Result<(), TelemetryError> {
+    let length = (body.len() + 12) as u32;
+    let mut block = Vec::with_capacity(length as usize);
+    block.write_u32::<LittleEndian>(block_type)?;
+    block.write_u32::<LittleEndian>(length)?;
+    block.write_all(body)?;
+    block.write_u32::<LittleEndian>(length)?;
+    writer.write_all(&block)?;
+
+    Ok(())
+}
+
+fn ip_packet(datagram: &Datagram) -> Result<Vec<u8>, TelemetryError> {
+    let udp_length = u16::try_from(datagram.payload.len() + 8)
+        .ok()
+        .filter(|length| *length as usize + 40 <= u16::MAX as usize)
+        .ok_or(TelemetryError::Unencodable { field: "payload" })?;
+
+    let mut udp = Vec::with_capacity(udp_length as usize);
+    udp.write_u16::<BigEndian>(datagram.source.port())?;
+    udp.write_u16::<BigEndian>(datagram.destination.port())?;
+    udp.write_u16::<BigEndian>(udp_length)?;
+    udp.write_u16::<BigEndian>(0)?;
+    udp.write_all(&datagram.payload)?;
+
+    let mut packet = Vec::with_capacity(40 + udp.len());
+    let mut pseudo_header = Vec::with_capacity(40);
+    match (datagram.source.ip(), datagram.destination.ip()) {
+        (IpAddr::V4(source), IpAddr::V4(destination)) => {
+            packet.write_all(&[0x45, 0])?;
+            packet.write_u16::<BigEndian>(20 + udp_length)?;
+            // Identification, don't fragment, TTL and protocol
+            packet.write_all(&[0, 0, 0x40, 0, 64, IP_PROTOCOL_UDP, 0, 0])?;
+            packet.write_all(&source.octets())?;
+            packet.write_all(&destination.octets())?;
+            let checksum = internet_checksum(&[&packet]);
+            BigEndian::write_u16(&mut packet[10..], checksum);
+
+            pseudo_header.write_all(&source.octets())?;
+            pseudo_header.write_all(&destination.octets())?;
+            pseudo_header.write_all(&[0, IP_PROTOCOL_UDP])?;
+            pseudo_header.write_u16::<BigEndian>(udp_length)?;
+        }
+        (IpAddr::V6(source), IpAddr::V6(destination)) => {
+            packet.write_all(&[0x60, 0, 0, 0])?;
+            packet.write_u16::<BigEndian>(udp_length)?;
+            packet.write_all(&[IP_PROTOCOL_UDP, 64])?;
+            packet.write_all(&source.octets())?;
+            packet.write_all(&destination.octets())?;
+
+            pseudo_header.write_all(&source.octets())?;
+            pseudo_header.write_all(&destination.octets())?;
+            pseudo_header.write_u32::<BigEndian>(udp_length as u32)?;
+            pseudo_header.write_all(&[0, 0, 0, IP_PROTOCOL_UDP])?;
+        }
+        _ => {
+            return Err(TelemetryError::Unencodable {
+                field: "destination",
+            })
+        }
+    }
+
+    let checksum = match internet_checksum(&[&pseudo_header, &udp]) {
+        0 => 0xffff,
+        checksum => checksum,
+    };
+    BigEndian::write_u16(&mut udp[6..], checksum);
+    packet.write_all(&udp)?;
+
+    Ok(packet)
+}
+
+/// One's complement checksum of RFC 1071 over the concatenated parts, each
+/// of which but the last has an even length.
+fn internet_checksum(parts: &[&[u8]]) -> u16 {
+    let mut sum: u32 = 0;
+    for part in parts {
+        for word in part.chunks(2) {
+            let word = match word {
+                [high, low] => u16::from_be_bytes([*high, *low]),
+                [high] => u16::from_be_bytes([*high, 0]),
+                _ => 0,
+            };
+            sum += word as u32;
+        }
+    }
+    while sum > 0xffff {
+        sum = (sum & 0xffff) + (sum >> 16);
+    }
+
+    !(sum as u16)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::capture::{CaptureReader, Record, Recorder};
+    use crate::decoder::Decoder;
+    use crate::f1_2020::event::{Event, PacketEventData};
+    use crate::f1_2020::header::PacketHeader;
+    use crate::f1_2020::packet::{encode_f12020, Packet2020};
+    use crate::pcap::{export_capture, ip_packet, Datagram, PcapReader, PcapngWriter};
+    use crate::TelemetryError;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    fn datagram(source: &str, destination: &str, payload: &[u8]) -> Datagram {
+        Datagram {
+            timestamp: UNIX_EPOCH + Duration::new(1_600_000_000, 123_456_789),
+            source: source.parse().unwrap(),
+            destination: destination.parse().unwrap(),
+            payload: payload.to_vec(),
+        }
+    }
+
+    #[test]
+    fn pcapng_round_trip() {
+        let datagrams = vec![
+            datagram("192.168.1.2:50000", "192.168.1.3:20777", b"first"),
+            datagram("192.168.1.2:50000", "192.168.1.3:20778", b"other port"),
+            datagram("[::1]:50000", "[::1]:20777", b"second!"),
+        ];
+
+        let mut writer = PcapngWriter::new(Vec::new()).unwrap();
+        for datagram in &datagrams {
+            writer.write_datagram(datagram).unwrap();
+        }
+        let bytes = writer.into_inner();
+
+        let read: Vec<Datagram> = PcapReader::new(bytes.as_slice(), 20777)
+            .unwrap()
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(vec![datagrams[0].clone(), datagrams[2].clone()], read);
+    }
+
+    #[test]
+    fn reads_big_endian_ethernet_pcap() {
+        let mut sent = datagram("10.0.0.1:40000", "10.0.0.2:20777", b"payload");
+        sent.timestamp = UNIX_EPOCH + Duration::new(1_600_000_000, 250_000_000);
+        let fragment = {
+            let mut packet = ip_packet(&sent).unwrap();
+            // More fragments flag
+            packet[6] |= 0x20;
+            packet
+        };
+
+        let mut file = vec![0xa1, 0xb2, 0xc3, 0xd4, 0, 2, 0, 4];
+        file.extend_from_slice(&[0; 8]);
+        file.extend_from_slice(&65535u32.to_be_bytes());
+        file.extend_from_slice(&1u32.to_be_bytes());
+        for packet in [ip_packet(&sent).unwrap(), fragment] {
+            let mut frame = vec![0; 12];
+            // VLAN tag followed by IPv4
+            frame.extend_from_slice(&[0x81, 0x00, 0, 1, 0x08, 0x00]);
+            frame.extend_from_slice(&packet);
+
+            file.extend_from_slice(&1_600_000_000u32.to_be_bytes());
+            file.extend_from_slice(&250_000u32.to_be_bytes());
+            file.extend_from_slice(&(frame.len() as u32).to_be_bytes());
+            file.extend_from_slice(&(frame.len() as u32).to_be_bytes());
+            file.extend_from_slice(&frame);
+        }
+
+        let mut reader = PcapReader::new(file.as_slice(), 20777).unwrap();
+        assert_eq!(Some(sent), reader.next_datagram().unwrap());
+        assert_eq!(None, reader.next_datagram().unwrap());
+        assert_eq!(1, reader.skipped());
+    }
+
+    #[test]
+    fn exports_recordings_for_the_2020_parser() {
+        let packet = Packet2020::Event(PacketEventData {
+            header: PacketHeader {
+                packet_format: 2020,
+                major_version: 1,
+                minor_version: 0,
+                packet_version: 1,
+                packet_id: 3,
+                session_uid: 1,
+                session_time: Duration::from_secs(1),
+                frame_identifier: 7,
+                player_car_index: 0,
+                secondary_player_car_index: 255,
+            },
+            event: Event::SessionStarted,
+        });
+
+        let path = std::env::temp_dir().join(format!(
+            "f1-telemetry-{}-pcap-export.capture",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&path);
+        Recorder::create(&path)
+            .unwrap()
+            .write_record(&Record {
+                offset: Duration::from_millis(5),
+                source: "127.0.0.1:50000".parse().unwrap(),
+                datagram: encode_f12020(&packet).unwrap(),
+            })
+            .unwrap();
+        let reader = CaptureReader::open(&path).unwrap();
+        let start_time = reader.start_time();
+
+        let mut writer = PcapngWriter::new(Vec::new()).unwrap();
+        let destination = "127.0.0.1:20777".parse().unwrap();
+        assert_eq!(1, export_capture(reader, destination, &mut writer).unwrap());
+
+        let bytes = writer.into_inner();
+        let datagram = PcapReader::new(bytes.as_slice(), 20777)
+            .unwrap()
+            .next_datagram()
+            .unwrap()
+            .unwrap();
+        assert_eq!(start_time + Duration::from_millis(5), datagram.timestamp);
+        match datagram.parse_f12020(&mut Decoder::default()).unwrap() {
+            Packet2020::Event(event) => assert_eq!(7, event.header.frame_identifier),
+            p => panic!("Unexpected packet {:?}", p.packet_id()),
+        }
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn rejects_records_longer_than_the_snapshot_length() {
+        let mut file = vec![0xd4, 0xc3, 0xb2, 0xa1, 2, 0, 4, 0];
+        file.extend_from_slice(&[0; 8]);
+        file.extend_from_slice(&1500u32.to_le_bytes());
+        file.extend_from_slice(&1u32.to_le_bytes());
+        file.extend_from_slice(&[0; 8]);
+        file.extend_from_slice(&u32::MAX.to_le_bytes());
+        file.extend_from_slice(&u32::MAX.to_le_bytes());
+
+        let mut reader = PcapReader::new(file.as_slice(), 20777).unwrap();
+        assert!(matches!(
+            reader.next_datagram(),
+            Err(TelemetryError::InvalidCapture { .. })
+        ));
+    }
+
+    #[test]
+    fn rejects_unusable_timestamps_without_panicking() {
+        let mut writer = PcapngWriter::new(Vec::new()).unwrap();
+        writer
+            .write_datagram(&datagram("10.0.0.1:40000", "10.0.0.2:20777", b"x"))
+            .unwrap();
+        let bytes = writer.into_inner();
+        // if_tsresol value in the interface block and the packet's timestamp
+        let (tsresol, units) = (48, 72..80);
+        assert_eq!(9, bytes[tsresol]);
+
+        for resolution in 0..=u8::MAX {
+            for high in [0, 1, 0x7fff_ffff, u32::MAX] {
+                let mut file = bytes.clone();
+                file[tsresol] = resolution;
+                file[units.clone()].copy_from_slice(&[high.to_le_bytes(), [0xff; 4]].concat());
+
+                let read = PcapReader::new(file.as_slice(), 20777)
+                    .unwrap()
+                    .next_datagram();
+                let supported = resolution <= 19 || (0x80..=0xbf).contains(&resolution);
+                match read {
+                    Ok(Some(_)) => assert!(supported, "{}", resolution),
+                    Err(TelemetryError::InvalidCapture { .. }) => {}
+                    other => panic!("{} {:#x}: {:?}", resolution, high, other),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn rejects_other_files() {
+        assert!(matches!(
+            PcapReader::new(&b"F1TC\x01\x00"[..], 20777),
+            Err(TelemetryError::InvalidCapture { .. })
+        ));
+    }
+}
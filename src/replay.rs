@@ -0,0 +1,399 @@
+use crate::capture::CaptureReader;
+use crate::TelemetryError;
+use std::io::{Error, ErrorKind};
+use std::net::{SocketAddr, ToSocketAddrs, UdpSocket};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+
+/// How fast a capture is replayed relative to the time it was recorded in.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Speed {
+    /// Scales the recorded gaps between datagrams, `2.0` plays twice as fast.
+    Multiplier(f64),
+    /// Sends datagrams back to back without waiting.
+    Max,
+}
+
+#[derive(Debug)]
+struct State {
+    speed: Speed,
+    looping: bool,
+    paused: bool,
+    stopped: bool,
+    /// Bumped on every change the schedule has to be re-anchored for.
+    generation: u64,
+}
+
+/// Handle to pause, resume, retime or stop a replay from another thread.
+#[derive(Debug, Clone)]
+pub struct PlayerControl {
+    shared: Arc<(Mutex<State>, Condvar)>,
+}
+
+impl PlayerControl {
+    /// Sets the replay speed, taking effect from the next datagram. Fails with
+    /// `TelemetryError::Io` of kind `InvalidInput` for a multiplier that is
+    /// not a positive, finite number.
+    pub fn set_speed(&self, speed: Speed) -> Result<(), TelemetryError> {
+        if let Speed::Multiplier(multiplier) = speed {
+            if !(multiplier.is_finite() && multiplier > 0.0) {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    format!("invalid replay speed {}", multiplier),
+                )
+                .into());
+            }
+        }
+
+        self.update(|state| state.speed = speed);
+        Ok(())
+    }
+
+    pub fn speed(&self) -> Speed {
+        self.lock().speed
+    }
+
+    /// Whether the replay starts over from the first datagram at the end of
+    /// the capture.
+    pub fn set_loop(&self, looping: bool) {
+        self.update(|state| state.looping = looping);
+    }
+
+    pub fn pause(&self) {
+        self.update(|state| state.paused = true);
+    }
+
+    /// Continues a paused replay, keeping the recorded gaps from here on.
+    pub fn resume(&self) {
+        self.update(|state| state.paused = false);
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.lock().paused
+    }
+
+    /// Ends the replay, `Player::play_to` returns after the datagram in flight.
+    pub fn stop(&self) {
+        self.update(|state| state.stopped = true);
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.shared.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn update<F: FnOnce(&mut State)>(&self, change: F) {
+        let mut state = self.lock();
+        change(&mut state);
+        state.generation += 1;
+        self.shared.1.notify_all();
+    }
+
+    /// Waits until `deadline`, or indefinitely while paused. Returns `None`
+    /// once stopped, else the generation at the time waiting ended.
+    fn wait(&self, deadline: Option<Instant>) -> Option<u64> {
+        let mut state = self.lock();
+        let generation = state.generation;
+        loop {
+            if state.stopped {
+                return None;
+            }
+            if state.paused {
+                state = self
+                    .shared
+                    .1
+                    .wait(state)
+                    .unwrap_or_else(PoisonError::into_inner);
+                continue;
+            }
+            if state.generation != generation {
+                return Some(state.generation);
+            }
+
+            let timeout = match deadline {
+                Some(deadline) => deadline.saturating_duration_since(Instant::now()),
+                None => return Some(state.generation),
+            };
+            if timeout == Duration::ZERO {
+                return Some(state.generation);
+            }
+            state = self
+                .shared
+                .1
+                .wait_timeout(state, timeout)
+                .unwrap_or_else(PoisonError::into_inner)
+                .0;
+        }
+    }
+}
+
+/// Re-emits the datagrams of a capture file over UDP.
+pub struct Player {
+    path: PathBuf,
+    control: PlayerControl,
+}
+
+impl Player {
+    /// Prepares a real-time replay of the capture at `path`, checking its header.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, TelemetryError> {
+        let path = path.as_ref().to_path_buf();
+        CaptureReader::open(&path)?;
+
+        Ok(Player {
+            path,
+            control: PlayerControl {
+                shared: Arc::new((
+                    Mutex::new(State {
+                        speed: Speed::Multiplier(1.0),
+                        looping: false,
+                        paused: false,
+                        stopped: false,
+                        generation: 0,
+                    }),
+                    Condvar::new(),
+                )),
+            },
+        })
+    }
+
+    pub fn control(&self) -> PlayerControl {
+        self.control.clone()
+    }
+
+    /// Sends the capture's datagrams to `target` from an ephemeral port,
+    /// blocking until the end of the capture or until stopped. Returns the
+    /// number of datagrams sent.
+    pub fn play_to<A: ToSocketAddrs>(&self, target: A) -> Result<usize, TelemetryError> {
+        let target = target
+            .to_socket_addrs()?
+            .next()
+            .ok_or_else(|| Error::new(ErrorKind::InvalidInput, "no target address"))?;
+        let bind = if target.is_ipv4() {
+            "0.0.0.0:0"
+        } else {
+            "[::]:0"
+        };
+        let socket = UdpSocket::bind(bind)?;
+
+        let mut sent = 0;
+        while self.play_once(&socket, target, &mut sent)? && self.control.lock().looping {}
+
+        Ok(sent)
+    }
+
+    /// Plays the capture from the start, returning `false` when stopped or
+    /// when the capture has no datagrams to send again.
+    fn play_once(
+        &self,
+        socket: &UdpSocket,
+        target: SocketAddr,
+        sent: &mut usize,
+    ) -> Result<bool, TelemetryError> {
+        // Datagrams are due at their recorded offset, scaled, from an anchor
+        // that moves to the pending datagram whenever the replay is paused
+        // or retimed.
+        let mut anchor: Option<(Instant, Duration)> = None;
+        let mut generation = self.control.lock().generation;
+        let before = *sent;
+
+        for record in CaptureReader::open(&self.path)? {
+            let record = record?;
+
+            loop {
+                let (anchor_time, anchor_offset) =
+                    *anchor.get_or_insert((Instant::now(), record.offset));
+                let deadline = match self.control.speed() {
+                    Speed::Multiplier(multiplier) => Some(
+                        anchor_time
+                            + record
+                                .offset
+                                .saturating_sub(anchor_offset)
+                                .div_f64(multiplier),
+                    ),
+                    Speed::Max => None,
+                };
+
+                match self.control.wait(deadline) {
+                    None => return Ok(false),
+                    Some(current) if current == generation => break,
+                    Some(current) => {
+                        generation = current;
+                        anchor = Some((Instant::now(), record.offset));
+                    }
+                }
+            }
+
+            socket.send_to(&record.datagram, target)?;
+            *sent += 1;
+        }
+
+        Ok(*sent > before)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::blocking::Telemetry;
+    use crate::capture::{Record, Recorder};
+    use crate::f1_2020::event::{Event, PacketEventData};
+    use crate::f1_2020::header::PacketHeader;
+    use crate::f1_2020::packet::{encode_f12020, Packet2020};
+    use crate::packet::Packet;
+    use crate::replay::{Player, Speed};
+    use crate::TelemetryError;
+    use std::net::UdpSocket;
+    use std::path::PathBuf;
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    /// Writes a capture of `count` datagrams recorded `gap` apart.
+    fn capture(name: &str, count: u32, gap: Duration) -> PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "f1-telemetry-{}-{}.capture",
+            std::process::id(),
+            name
+        ));
+        let _ = std::fs::remove_file(&path);
+
+        let mut recorder = Recorder::create(&path).unwrap();
+        for frame_identifier in 0..count {
+            let packet = Packet2020::Event(PacketEventData {
+                header: PacketHeader {
+                    packet_format: 2020,
+                    major_version: 1,
+                    minor_version: 0,
+                    packet_version: 1,
+                    packet_id: 3,
+                    session_uid: 1,
+                    session_time: gap * frame_identifier,
+                    frame_identifier,
+                    player_car_index: 0,
+                    secondary_player_car_index: 255,
+                },
+                event: Event::SessionStarted,
+            });
+            recorder
+                .write_record(&Record {
+                    offset: gap * frame_identifier,
+                    source: "127.0.0.1:20777".parse().unwrap(),
+                    datagram: encode_f12020(&packet).unwrap(),
+                })
+                .unwrap();
+        }
+
+        path
+    }
+
+    fn receiver() -> UdpSocket {
+        let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+        socket
+            .set_read_timeout(Some(Duration::from_millis(500)))
+            .unwrap();
+        socket
+    }
+
+    #[test]
+    fn replays_into_telemetry() {
+        let path = capture("replay-telemetry", 3, Duration::from_secs(60));
+        let client = Telemetry::new("127.0.0.1", 0).unwrap();
+        client
+            .set_read_timeout(Some(Duration::from_millis(500)))
+            .unwrap();
+
+        let player = Player::open(&path).unwrap();
+        player.control().set_speed(Speed::Max).unwrap();
+        assert_eq!(3, player.play_to(client.local_addr().unwrap()).unwrap());
+
+        let frames: Vec<u32> = client
+            .iter()
+            .map(|packet| match packet {
+                Ok(Packet::F12020(packet)) => packet.header().frame_identifier,
+                r => panic!("Unexpected result {:?}", r),
+            })
+            .collect();
+        assert_eq!(vec![0, 1, 2], frames);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn honours_scaled_timing() {
+        let path = capture("replay-timing", 3, Duration::from_millis(100));
+        let socket = receiver();
+
+        let player = Player::open(&path).unwrap();
+        player.control().set_speed(Speed::Multiplier(2.0)).unwrap();
+        let started = Instant::now();
+        assert_eq!(3, player.play_to(socket.local_addr().unwrap()).unwrap());
+
+        // 200ms recorded at twice the speed
+        let elapsed = started.elapsed();
+        assert!(elapsed >= Duration::from_millis(95), "{:?}", elapsed);
+        assert!(elapsed < Duration::from_millis(195), "{:?}", elapsed);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn pauses_loops_and_stops() {
+        let path = capture("replay-control", 2, Duration::from_millis(1));
+        let socket = receiver();
+        let target = socket.local_addr().unwrap();
+
+        let player = Player::open(&path).unwrap();
+        let control = player.control();
+        control.set_loop(true);
+        control.pause();
+        let handle = thread::spawn(move || player.play_to(target));
+
+        let mut buf = [0; 64];
+        socket
+            .set_read_timeout(Some(Duration::from_millis(100)))
+            .unwrap();
+        assert!(socket.recv(&mut buf).is_err());
+
+        control.resume();
+        socket
+            .set_read_timeout(Some(Duration::from_millis(500)))
+            .unwrap();
+        for _ in 0..5 {
+            socket.recv(&mut buf).unwrap();
+        }
+        control.stop();
+
+        let sent = handle.join().unwrap().unwrap();
+        assert!(sent >= 5, "{}", sent);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn stops_looping_an_empty_capture() {
+        let path = capture("replay-empty", 0, Duration::ZERO);
+        let socket = receiver();
+        let target = socket.local_addr().unwrap();
+
+        let player = Player::open(&path).unwrap();
+        player.control().set_loop(true);
+        let (tx, rx) = std::sync::mpsc::channel();
+        thread::spawn(move || tx.send(player.play_to(target).unwrap()).unwrap());
+
+        assert_eq!(rx.recv_timeout(Duration::from_secs(1)).unwrap(), 0);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn rejects_invalid_speed() {
+        let path = capture("replay-speed", 1, Duration::ZERO);
+        let control = Player::open(&path).unwrap().control();
+
+        assert!(matches!(
+            control.set_speed(Speed::Multiplier(0.0)),
+            Err(TelemetryError::Io(_))
+        ));
+        assert_eq!(Speed::Multiplier(1.0), control.speed());
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
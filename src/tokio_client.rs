@@ -0,0 +1,128 @@
+use crate::capture::Recorder;
+use crate::client::{decode, record, socket_addr};
+use crate::{packet, DecodeMode, Protocol, TelemetryError};
+use futures::stream::{self, Stream};
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use tokio::net::UdpSocket;
+
+/// Telemetry client receiving on a `tokio::net::UdpSocket`.
+pub struct Telemetry {
+    socket: UdpSocket,
+    protocol: Protocol,
+    mode: DecodeMode,
+    substitutions: AtomicUsize,
+    recorder: Option<Mutex<Recorder>>,
+}
+
+impl Telemetry {
+    pub async fn new(ip: &str, port: u16) -> Result<Self, TelemetryError> {
+        Telemetry::with_protocol(ip, port, Protocol::Standard).await
+    }
+
+    pub async fn with_protocol(
+        ip: &str,
+        port: u16,
+        protocol: Protocol,
+    ) -> Result<Self, TelemetryError> {
+        let socket = UdpSocket::bind(socket_addr(ip, port)?).await?;
+
+        Ok(Telemetry {
+            socket,
+            protocol,
+            mode: DecodeMode::Strict,
+            substitutions: AtomicUsize::new(0),
+            recorder: None,
+        })
+    }
+
+    /// Address the socket is bound to, which tells the port picked for port 0.
+    pub fn local_addr(&self) -> Result<SocketAddr, TelemetryError> {
+        Ok(self.socket.local_addr()?)
+    }
+
+    /// Sets how values outside of a field's enumeration are decoded.
+    pub fn set_decode_mode(&mut self, mode: DecodeMode) {
+        self.mode = mode;
+    }
+
+    /// Total number of values decoded as `Unknown` in lenient mode.
+    pub fn substitutions(&self) -> usize {
+        self.substitutions.load(Ordering::Relaxed)
+    }
+
+    /// Writes every datagram received from now on to `recorder`, before it
+    /// is decoded.
+    pub fn set_recorder(&mut self, recorder: Recorder) {
+        self.recorder = Some(Mutex::new(recorder));
+    }
+
+    pub async fn next(&self) -> Result<packet::Packet, TelemetryError> {
+        let mut buf = [0; 2048];
+        let (size, source) = self.socket.recv_from(&mut buf).await?;
+
+        record(&self.recorder, source, &buf[..size])?;
+        decode(&buf[..size], self.protocol, self.mode, &self.substitutions)
+    }
+
+    /// Returns an endless stream of received packets, yielding the same
+    /// results as repeated calls to `next`.
+    pub fn stream(&self) -> impl Stream<Item = Result<packet::Packet, TelemetryError>> + '_ {
+        stream::unfold(self, |telemetry| async move {
+            Some((telemetry.next().await, telemetry))
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::tokio_client::Telemetry;
+    use crate::TelemetryError;
+    use byteorder::{LittleEndian, WriteBytesExt};
+    use futures::StreamExt;
+    use tokio::net::UdpSocket;
+
+    #[tokio::test]
+    async fn test_telemetry_next() {
+        let client = Telemetry::new("127.0.0.1", 0).await.unwrap();
+        let target = client.local_addr().unwrap();
+
+        let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let mut send_buf = Vec::with_capacity(2048);
+        send_buf.write_u16::<LittleEndian>(2017).unwrap();
+        send_buf.resize(24, 0);
+        socket.send_to(&send_buf, target).await.unwrap();
+
+        let result = client.next().await;
+        assert!(matches!(
+            result,
+            Err(TelemetryError::UnsupportedFormat { format: 2017 })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_telemetry_stream() {
+        let client = Telemetry::new("127.0.0.1", 0).await.unwrap();
+        let target = client.local_addr().unwrap();
+
+        let socket = std::net::UdpSocket::bind("127.0.0.1:0").unwrap();
+        for format in [2016u16, 2017] {
+            let mut send_buf = Vec::with_capacity(24);
+            send_buf.write_u16::<LittleEndian>(format).unwrap();
+            send_buf.resize(24, 0);
+            socket.send_to(&send_buf, target).unwrap();
+        }
+
+        let formats: Vec<u16> = client
+            .stream()
+            .take(2)
+            .map(|result| match result {
+                Err(TelemetryError::UnsupportedFormat { format }) => format,
+                r => panic!("Unexpected result {:?}", r),
+            })
+            .collect()
+            .await;
+        assert_eq!(vec![2016, 2017], formats);
+    }
+}